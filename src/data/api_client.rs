@@ -3,13 +3,47 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::data::demo_backend::DemoBackend;
 
 /// Configuration du client API
 pub struct APIClient {
-    base_url: String,
+    backend: Box<dyn ClientBackend>,
     auth_token: Option<String>,
 }
 
+/// Transport des requêtes du client API (HTTP réel ou données de démonstration)
+pub trait ClientBackend: Send + Sync {
+    fn get(&self, endpoint: &str, token: Option<&str>) -> Result<String, APIError>;
+    fn post(&self, endpoint: &str, body: &str, token: Option<&str>) -> Result<String, APIError>;
+    fn delete(&self, endpoint: &str, token: Option<&str>) -> Result<(), APIError>;
+
+    /// Vérifie rapidement que le serveur répond ; les transports sans réseau répondent toujours
+    fn ping(&self, _timeout: Duration) -> Result<(), APIError> {
+        Ok(())
+    }
+
+    /// Indique si les réponses proviennent de données fictives
+    fn is_demo(&self) -> bool {
+        false
+    }
+}
+
+/// Méthode et chemin d'une requête vers l'API (ex: `GET /api/vms`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct APIRequest {
+    pub method: &'static str,
+    pub endpoint: String,
+}
+
+/// Indique si le mode démonstration est demandé (RMDB_DEMO=1)
+pub fn demo_mode_enabled() -> bool {
+    std::env::var("RMDB_DEMO")
+        .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
+        .unwrap_or(false)
+}
+
 /// Structure pour une VM
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VM {
@@ -153,120 +187,284 @@ pub struct VMListResponse {
 /// Erreur API
 #[derive(Debug)]
 pub enum APIError {
-    NetworkError(String),
-    AuthError(String),
+    /// Serveur injoignable (connexion refusée, DNS...)
+    Network(String),
+    /// Pas de réponse dans le délai imparti
+    Timeout(String),
+    /// Authentification absente ou refusée (401, 403)
+    Unauthorized(String),
+    /// Ressource inexistante (404)
     NotFound(String),
-    ServerError(String),
-    ParseError(String),
+    /// Autre statut HTTP en erreur, avec un extrait du corps de la réponse
+    Http { status: u16, body: String },
+    /// Réponse illisible ou JSON inattendu
+    Decode(String),
+    /// Erreur d'une requête envoyée par `APIClient`, avec sa méthode et son chemin
+    Request { request: APIRequest, error: Box<APIError> },
 }
 
-impl APIClient {
-    /// Crée un nouveau client API
+/// Longueur maximale de l'extrait de corps conservé dans `APIError::Http`
+const HTTP_BODY_EXCERPT_LEN: usize = 200;
+
+/// Délai maximal d'une requête HTTP
+const HTTP_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Délai maximal de `APIClient::ping`, court pour ne pas faire attendre un écran hors ligne
+const PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+impl APIError {
+    /// Rattache l'erreur à la requête qui l'a provoquée
+    pub fn for_request(self, method: &'static str, endpoint: &str) -> Self {
+        match self {
+            APIError::Request { .. } => self,
+            error => APIError::Request { request: APIRequest { method, endpoint: endpoint.to_string() }, error: Box::new(error) },
+        }
+    }
+
+    /// Requête en cause, si l'erreur vient d'un appel de `APIClient`
+    pub fn request(&self) -> Option<&APIRequest> {
+        match self {
+            APIError::Request { request, .. } => Some(request),
+            _ => None,
+        }
+    }
+
+    /// Erreur elle-même, sans la requête : c'est elle qu'on filtre par variante
+    pub fn cause(&self) -> &APIError {
+        match self {
+            APIError::Request { error, .. } => error.cause(),
+            error => error,
+        }
+    }
+
+    /// Erreur susceptible de disparaître en réessayant (serveur arrêté, redémarrage en cours)
+    pub fn is_transient(&self) -> bool {
+        match self {
+            APIError::Network(_) | APIError::Timeout(_) => true,
+            APIError::Request { error, .. } => error.is_transient(),
+            APIError::Http { status, .. } => *status >= 500,
+            _ => false,
+        }
+    }
+
+    /// Nom de la variante, pour les rapports de bug
+    pub fn kind(&self) -> &'static str {
+        match self {
+            APIError::Network(_) => "Network",
+            APIError::Timeout(_) => "Timeout",
+            APIError::Unauthorized(_) => "Unauthorized",
+            APIError::NotFound(_) => "NotFound",
+            APIError::Http { .. } => "Http",
+            APIError::Decode(_) => "Decode",
+            APIError::Request { error, .. } => error.kind(),
+        }
+    }
+
+    /// Statut HTTP, quand la variante le fixe
+    pub fn status(&self) -> Option<u16> {
+        match self {
+            APIError::Http { status, .. } => Some(*status),
+            APIError::NotFound(_) => Some(404),
+            APIError::Request { error, .. } => error.status(),
+            _ => None,
+        }
+    }
+
+    /// Message ou extrait du corps de la réponse, sans le préfixe de la variante
+    pub fn detail(&self) -> &str {
+        match self {
+            APIError::Network(msg)
+            | APIError::Timeout(msg)
+            | APIError::Unauthorized(msg)
+            | APIError::NotFound(msg)
+            | APIError::Decode(msg) => msg,
+            APIError::Http { body, .. } => body,
+            APIError::Request { error, .. } => error.detail(),
+        }
+    }
+
+    /// Classe une réponse HTTP en erreur selon son statut
+    pub fn from_status(status: u16, body: &str) -> Self {
+        let body = body_excerpt(body);
+        match status {
+            401 | 403 => APIError::Unauthorized(if body.is_empty() { format!("HTTP {}", status) } else { body }),
+            404 => APIError::NotFound(if body.is_empty() { "HTTP 404".to_string() } else { body }),
+            _ => APIError::Http { status, body },
+        }
+    }
+}
+
+/// Rapport d'erreur à coller dans un ticket : version du TUI, requête, variante, statut et détail
+///
+/// `base_url` est l'URL de l'API en vigueur ; la requête est celle portée par l'erreur.
+pub fn error_report(context: &str, error: &APIError, base_url: &str) -> String {
+    let request = match error.request() {
+        Some(request) => format!("{} {}{}", request.method, base_url.trim_end_matches('/'), request.endpoint),
+        None => format!("inconnue ({})", base_url),
+    };
+    let status = error.status().map(|status| status.to_string()).unwrap_or_else(|| "-".to_string());
+    [
+        format!("RMDB TUI {} — erreur API", env!("CARGO_PKG_VERSION")),
+        format!("Date : {}", chrono::Local::now().format("%Y-%m-%d %H:%M:%S %z")),
+        format!("Contexte : {}", context),
+        format!("Requête : {}", request),
+        format!("Erreur : {}", error.kind()),
+        format!("Statut HTTP : {}", status),
+        format!("Détail : {}", error.detail()),
+    ]
+    .join("\n")
+}
+
+/// Début du corps d'une réponse, sur une ligne, pour l'affichage et le débogage
+fn body_excerpt(body: &str) -> String {
+    let flat = body.split_whitespace().collect::<Vec<_>>().join(" ");
+    match flat.char_indices().nth(HTTP_BODY_EXCERPT_LEN) {
+        Some((index, _)) => format!("{}…", &flat[..index]),
+        None => flat,
+    }
+}
+
+/// Convertit le résultat d'une requête ureq en corps de réponse ou en `APIError`
+fn read_response(result: Result<ureq::Response, ureq::Error>) -> Result<String, APIError> {
+    match result {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| APIError::Decode(format!("Failed to read response: {}", e))),
+        Err(ureq::Error::Status(code, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            Err(APIError::from_status(code, &body))
+        }
+        Err(ureq::Error::Transport(transport)) => {
+            let timed_out = std::error::Error::source(&transport)
+                .and_then(|source| source.downcast_ref::<std::io::Error>())
+                .is_some_and(|io| matches!(io.kind(), std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock));
+            if timed_out {
+                Err(APIError::Timeout(transport.to_string()))
+            } else {
+                Err(APIError::Network(transport.to_string()))
+            }
+        }
+    }
+}
+
+/// Transport HTTP vers le serveur RMDB
+pub struct HttpBackend {
+    base_url: String,
+}
+
+impl HttpBackend {
     pub fn new(base_url: String) -> Self {
         Self {
             base_url: base_url.trim_end_matches('/').to_string(),
-            auth_token: None,
         }
     }
+}
 
-    /// Configure le token d'authentification
-    pub fn with_auth_token(mut self, token: String) -> Self {
-        self.auth_token = Some(token);
-        self
-    }
-
+impl ClientBackend for HttpBackend {
     /// Effectue une requête GET
-    fn get(&self, endpoint: &str) -> Result<String, APIError> {
+    fn get(&self, endpoint: &str, token: Option<&str>) -> Result<String, APIError> {
         let url = format!("{}{}", self.base_url, endpoint);
         
         // Utiliser ureq pour les requêtes HTTP
-        let mut request = ureq::get(&url);
+        let mut request = ureq::get(&url).timeout(HTTP_TIMEOUT);
         
-        if let Some(ref token) = self.auth_token {
+        if let Some(token) = token {
             request = request.set("Authorization", &format!("Bearer {}", token));
         }
 
-        match request.call() {
-            Ok(response) => {
-                response.into_string()
-                    .map_err(|e| APIError::ParseError(format!("Failed to read response: {}", e)))
-            }
-            Err(ureq::Error::Status(code, response)) => {
-                let error_msg = response.into_string().unwrap_or_else(|_| format!("HTTP {}", code));
-                if code == 404 {
-                    Err(APIError::NotFound(error_msg))
-                } else if code == 401 || code == 403 {
-                    Err(APIError::AuthError(error_msg))
-                } else {
-                    Err(APIError::ServerError(error_msg))
-                }
-            }
-            Err(e) => {
-                Err(APIError::NetworkError(format!("Network error: {}", e)))
-            }
-        }
+        read_response(request.call())
     }
 
     /// Effectue une requête POST
-    fn post(&self, endpoint: &str, body: &str) -> Result<String, APIError> {
+    fn post(&self, endpoint: &str, body: &str, token: Option<&str>) -> Result<String, APIError> {
         let url = format!("{}{}", self.base_url, endpoint);
         
         let mut request = ureq::post(&url)
+            .timeout(HTTP_TIMEOUT)
             .set("Content-Type", "application/json");
         
-        if let Some(ref token) = self.auth_token {
+        if let Some(token) = token {
             request = request.set("Authorization", &format!("Bearer {}", token));
         }
 
-        match request.send_string(body) {
-            Ok(response) => {
-                response.into_string()
-                    .map_err(|e| APIError::ParseError(format!("Failed to read response: {}", e)))
-            }
-            Err(ureq::Error::Status(code, response)) => {
-                let error_msg = response.into_string().unwrap_or_else(|_| format!("HTTP {}", code));
-                if code == 404 {
-                    Err(APIError::NotFound(error_msg))
-                } else if code == 401 || code == 403 {
-                    Err(APIError::AuthError(error_msg))
-                } else {
-                    Err(APIError::ServerError(error_msg))
-                }
-            }
-            Err(e) => {
-                Err(APIError::NetworkError(format!("Network error: {}", e)))
-            }
-        }
+        read_response(request.send_string(body))
     }
 
     /// Effectue une requête DELETE
-    fn delete(&self, endpoint: &str) -> Result<(), APIError> {
+    fn delete(&self, endpoint: &str, token: Option<&str>) -> Result<(), APIError> {
         let url = format!("{}{}", self.base_url, endpoint);
         
-        let mut request = ureq::delete(&url);
+        let mut request = ureq::delete(&url).timeout(HTTP_TIMEOUT);
         
-        if let Some(ref token) = self.auth_token {
+        if let Some(token) = token {
             request = request.set("Authorization", &format!("Bearer {}", token));
         }
 
-        match request.call() {
+        read_response(request.call()).map(|_| ())
+    }
+
+    /// Toute réponse HTTP, même une erreur, prouve que le serveur est joignable
+    fn ping(&self, timeout: Duration) -> Result<(), APIError> {
+        let url = format!("{}/", self.base_url);
+        match read_response(ureq::get(&url).timeout(timeout).call()) {
             Ok(_) => Ok(()),
-            Err(ureq::Error::Status(code, response)) => {
-                let error_msg = response.into_string().unwrap_or_else(|_| format!("HTTP {}", code));
-                if code == 404 {
-                    Err(APIError::NotFound(error_msg))
-                } else if code == 401 || code == 403 {
-                    Err(APIError::AuthError(error_msg))
-                } else {
-                    Err(APIError::ServerError(error_msg))
-                }
-            }
-            Err(e) => {
-                Err(APIError::NetworkError(format!("Network error: {}", e)))
-            }
+            Err(e @ (APIError::Network(_) | APIError::Timeout(_))) => Err(e),
+            Err(_) => Ok(()),
+        }
+    }
+}
+
+impl APIClient {
+    /// Crée un nouveau client API
+    ///
+    /// Avec RMDB_DEMO=1, le client répond avec des données fictives sans contacter le serveur.
+    pub fn new(base_url: String) -> Self {
+        if demo_mode_enabled() {
+            Self::with_backend(Box::new(DemoBackend))
+        } else {
+            Self::with_backend(Box::new(HttpBackend::new(base_url)))
+        }
+    }
+
+    /// Crée un client API avec un transport donné
+    pub fn with_backend(backend: Box<dyn ClientBackend>) -> Self {
+        Self {
+            backend,
+            auth_token: None,
         }
     }
 
+    /// Indique si le client fonctionne en mode démonstration
+    pub fn is_demo(&self) -> bool {
+        self.backend.is_demo()
+    }
+
+    /// Configure le token d'authentification
+    pub fn with_auth_token(mut self, token: String) -> Self {
+        self.auth_token = Some(token);
+        self
+    }
+
+    /// Vérifie que le serveur est joignable, avec un délai court
+    pub fn ping(&self) -> Result<(), APIError> {
+        self.backend.ping(PING_TIMEOUT)
+    }
+
+    /// Effectue une requête GET
+    fn get(&self, endpoint: &str) -> Result<String, APIError> {
+        self.backend.get(endpoint, self.auth_token.as_deref()).map_err(|e| e.for_request("GET", endpoint))
+    }
+
+    /// Effectue une requête POST
+    fn post(&self, endpoint: &str, body: &str) -> Result<String, APIError> {
+        self.backend.post(endpoint, body, self.auth_token.as_deref()).map_err(|e| e.for_request("POST", endpoint))
+    }
+
+    /// Effectue une requête DELETE
+    fn delete(&self, endpoint: &str) -> Result<(), APIError> {
+        self.backend.delete(endpoint, self.auth_token.as_deref()).map_err(|e| e.for_request("DELETE", endpoint))
+    }
+
     /// Récupère la liste des VMs
     pub fn get_vms(&self, category: Option<&str>) -> Result<Vec<VM>, APIError> {
         let endpoint = if let Some(cat) = category {
@@ -277,7 +475,7 @@ impl APIClient {
 
         let response = self.get(&endpoint)?;
         let vm_response: VMListResponse = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse VM list: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse VM list: {}", e)))?;
 
         Ok(vm_response.vms)
     }
@@ -288,7 +486,7 @@ impl APIClient {
         let response = self.get(&endpoint)?;
         
         let vm: VM = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse VM: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse VM: {}", e)))?;
 
         Ok(vm)
     }
@@ -305,7 +503,7 @@ impl APIClient {
 
         let response = self.post("/api/vms", &body.to_string())?;
         let vm: VM = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse created VM: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse created VM: {}", e)))?;
 
         Ok(vm)
     }
@@ -323,7 +521,7 @@ impl APIClient {
         let response = self.post(&endpoint, &body)?;
         
         let vm: VM = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse updated VM: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse updated VM: {}", e)))?;
 
         Ok(vm)
     }
@@ -349,7 +547,7 @@ impl APIClient {
     pub fn get_vm_categories(&self) -> Result<Vec<VMCategory>, APIError> {
         let response = self.get("/api/vms/categories")?;
         let categories: Vec<VMCategory> = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse categories: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse categories: {}", e)))?;
 
         Ok(categories)
     }
@@ -358,7 +556,7 @@ impl APIClient {
     pub fn get_dhcp_leases(&self) -> Result<Vec<DHCPLease>, APIError> {
         let response = self.get("/api/dhcp/leases")?;
         let leases_response: DHCPLeasesResponse = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse DHCP leases: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse DHCP leases: {}", e)))?;
 
         Ok(leases_response.leases)
     }
@@ -367,7 +565,7 @@ impl APIClient {
     pub fn get_connected_clients(&self) -> Result<Vec<ConnectedClient>, APIError> {
         let response = self.get("/api/clients/connected")?;
         let clients_response: ConnectedClientsResponse = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse connected clients: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse connected clients: {}", e)))?;
 
         Ok(clients_response.clients)
     }
@@ -376,7 +574,7 @@ impl APIClient {
     pub fn get_system_metrics(&self) -> Result<SystemMetrics, APIError> {
         let response = self.get("/api/system/metrics")?;
         let metrics: SystemMetrics = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse system metrics: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse system metrics: {}", e)))?;
 
         Ok(metrics)
     }
@@ -391,11 +589,22 @@ impl APIClient {
     pub fn get_ipxe_entries(&self) -> Result<Vec<IPXEEntry>, APIError> {
         let response = self.get("/api/ipxe/entries")?;
         let entries: Vec<IPXEEntry> = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse iPXE entries: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse iPXE entries: {}", e)))?;
 
         Ok(entries)
     }
 
+    /// Menu iPXE que produirait une régénération, sans l'appliquer
+    ///
+    /// `Ok(None)` si le serveur ne propose pas d'aperçu (404 sur `/api/ipxe/preview`).
+    pub fn preview_ipxe_menu(&self) -> Result<Option<String>, APIError> {
+        match self.get("/api/ipxe/preview") {
+            Ok(response) => Ok(Some(response)),
+            Err(e) if matches!(e.cause(), APIError::NotFound(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Génère le menu iPXE
     pub fn generate_ipxe_menu(&self) -> Result<String, APIError> {
         let response = self.post("/api/ipxe/generate", "{}")?;
@@ -411,13 +620,13 @@ impl APIClient {
 
         let response = self.post("/api/login", &body.to_string())?;
         let login_response: serde_json::Value = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse login response: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse login response: {}", e)))?;
 
         if let Some(token) = login_response.get("token").and_then(|t| t.as_str()) {
             self.auth_token = Some(token.to_string());
             Ok(token.to_string())
         } else {
-            Err(APIError::AuthError("No token in login response".to_string()))
+            Err(APIError::Unauthorized("No token in login response".to_string()))
         }
     }
 
@@ -425,7 +634,7 @@ impl APIClient {
     pub fn get_overlays(&self) -> Result<Vec<VMOverlay>, APIError> {
         let response = self.get("/api/overlays")?;
         let overlays_response: OverlaysResponse = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse overlays: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse overlays: {}", e)))?;
 
         Ok(overlays_response.overlays)
     }
@@ -435,7 +644,7 @@ impl APIClient {
         let endpoint = format!("/api/overlays/mac/{}", mac);
         let response = self.get(&endpoint)?;
         let overlay: VMOverlay = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse overlay: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse overlay: {}", e)))?;
 
         Ok(overlay)
     }
@@ -448,7 +657,7 @@ impl APIClient {
         });
         let response = self.post("/api/overlays", &body.to_string())?;
         let overlay: VMOverlay = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse created overlay: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse created overlay: {}", e)))?;
 
         Ok(overlay)
     }
@@ -463,7 +672,7 @@ impl APIClient {
     pub fn get_config(&self) -> Result<serde_json::Value, APIError> {
         let response = self.get("/api/config")?;
         let config: serde_json::Value = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse config: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse config: {}", e)))?;
 
         Ok(config)
     }
@@ -473,7 +682,7 @@ impl APIClient {
         let body = config.to_string();
         let response = self.post("/api/config", &body)?;
         let updated_config: serde_json::Value = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse updated config: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse updated config: {}", e)))?;
 
         Ok(updated_config)
     }
@@ -485,7 +694,7 @@ impl APIClient {
         });
         let response = self.post("/api/repair", &body.to_string())?;
         let result: RepairResult = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse repair result: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse repair result: {}", e)))?;
 
         Ok(result)
     }
@@ -494,7 +703,7 @@ impl APIClient {
     pub fn get_repair_problems(&self) -> Result<Vec<RepairProblem>, APIError> {
         let response = self.get("/api/repair/problems")?;
         let problems: Vec<RepairProblem> = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse repair problems: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse repair problems: {}", e)))?;
 
         Ok(problems)
     }
@@ -506,7 +715,7 @@ impl APIClient {
         });
         let response = self.post("/api/test", &body.to_string())?;
         let result: TestResult = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse test result: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse test result: {}", e)))?;
 
         Ok(result)
     }
@@ -515,7 +724,7 @@ impl APIClient {
     pub fn get_security_metrics(&self) -> Result<SecurityMetrics, APIError> {
         let response = self.get("/api/security/metrics")?;
         let metrics: SecurityMetrics = serde_json::from_str(&response)
-            .map_err(|e| APIError::ParseError(format!("Failed to parse security metrics: {}", e)))?;
+            .map_err(|e| APIError::Decode(format!("Failed to parse security metrics: {}", e)))?;
 
         Ok(metrics)
     }
@@ -559,12 +768,61 @@ pub struct SecurityMetrics {
 impl std::fmt::Display for APIError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            APIError::NetworkError(msg) => write!(f, "Network error: {}", msg),
-            APIError::AuthError(msg) => write!(f, "Authentication error: {}", msg),
+            APIError::Network(msg) => write!(f, "Network error: {}", msg),
+            APIError::Timeout(msg) => write!(f, "Timeout: {}", msg),
+            APIError::Unauthorized(msg) => write!(f, "Unauthorized: {}", msg),
             APIError::NotFound(msg) => write!(f, "Not found: {}", msg),
-            APIError::ServerError(msg) => write!(f, "Server error: {}", msg),
-            APIError::ParseError(msg) => write!(f, "Parse error: {}", msg),
+            APIError::Http { status, body } if body.is_empty() => write!(f, "HTTP {}", status),
+            APIError::Http { status, body } => write!(f, "HTTP {}: {}", status, body),
+            APIError::Decode(msg) => write!(f, "Decode error: {}", msg),
+            APIError::Request { error, .. } => error.fmt(f),
         }
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_error_from_status() {
+        assert!(matches!(APIError::from_status(401, ""), APIError::Unauthorized(_)));
+        assert!(matches!(APIError::from_status(404, "no such vm"), APIError::NotFound(ref m) if m == "no such vm"));
+        let error = APIError::from_status(503, "{\"error\":\n  \"maintenance\"}");
+        assert!(matches!(error, APIError::Http { status: 503, ref body } if body == "{\"error\": \"maintenance\"}"));
+        assert!(error.is_transient());
+        assert!(!APIError::from_status(400, "bad request").is_transient());
+
+        let long = "x".repeat(500);
+        match APIError::from_status(500, &long) {
+            APIError::Http { body, .. } => assert_eq!(body.chars().count(), HTTP_BODY_EXCERPT_LEN + 1),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_error_report() {
+        let error = APIError::from_status(500, "disk full").for_request("POST", "/api/vms");
+        assert!(error.is_transient());
+        let report = error_report("Création de la VM", &error, "http://pxe:8080/");
+        assert!(report.contains("Requête : POST http://pxe:8080/api/vms"));
+        assert!(report.contains("Erreur : Http"));
+        assert!(report.contains("Statut HTTP : 500"));
+        assert!(report.ends_with("Détail : disk full"));
+
+        let report = error_report("Ping", &APIError::Network("refused".to_string()), "http://pxe:8080");
+        assert!(report.contains("Requête : inconnue (http://pxe:8080)"));
+        assert!(report.contains("Statut HTTP : -"));
+    }
+
+    #[test]
+    fn test_ping_unreachable_server() {
+        // Port fermé sur la boucle locale : refus immédiat, sans attendre le délai
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+        let client = APIClient::with_backend(Box::new(HttpBackend::new(format!("http://127.0.0.1:{}", port))));
+        assert!(matches!(client.ping(), Err(APIError::Network(_))));
+        assert!(APIClient::with_backend(Box::new(DemoBackend)).ping().is_ok());
+    }
+}
@@ -0,0 +1,121 @@
+use crate::data::deploy_cache::cache_dir;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+
+/// Journal de diagnostic de l'interface (`~/.cache/rmdb/tui.log`)
+pub fn diagnostics_log_path() -> PathBuf {
+    cache_dir().join("tui.log")
+}
+
+/// Ajoute un avertissement au journal de diagnostic, au format des journaux de déploiement
+///
+/// L'interface occupe le terminal : rien n'est écrit sur stderr, et une erreur d'écriture est ignorée.
+pub fn log_warning(message: &str) {
+    let _ = fs::create_dir_all(cache_dir());
+    if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(diagnostics_log_path()) {
+        let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+        let _ = writeln!(file, "[{}] [WARN] {}", timestamp, message);
+    }
+}
+
+/// Écrit un fichier d'un bloc : fichier temporaire voisin puis renommage
+///
+/// Un arrêt brutal laisse l'ancien contenu ou le nouveau, jamais un fichier tronqué.
+pub fn atomic_write(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    write_with_mode(path, bytes, 0o666)
+}
+
+/// Comme `atomic_write`, le fichier n'étant lisible que par l'utilisateur (0600)
+pub fn atomic_write_private(path: &Path, bytes: &[u8]) -> io::Result<()> {
+    write_with_mode(path, bytes, 0o600)
+}
+
+/// `mode` est celui du fichier temporaire créé (masqué par l'umask), conservé au renommage
+fn write_with_mode(path: &Path, bytes: &[u8], mode: u32) -> io::Result<()> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    let file_name = path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    let tmp = path.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+
+    let result = (|| {
+        let mut file = OpenOptions::new().write(true).create(true).truncate(true).mode(mode).open(&tmp)?;
+        if mode & 0o077 == 0 {
+            // Un fichier temporaire resté d'un essai précédent garde ses droits : les resserrer
+            file.set_permissions(fs::Permissions::from_mode(mode))?;
+        }
+        file.write_all(bytes)?;
+        file.sync_all()?;
+        fs::rename(&tmp, path)
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp);
+    }
+    result
+}
+
+/// Sérialise en JSON lisible et écrit avec `atomic_write`
+pub fn save_json<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(value).map_err(io::Error::other)?;
+    atomic_write(path, json.as_bytes())
+}
+
+/// Comme `save_json`, pour un fichier pouvant contenir des secrets (`atomic_write_private`)
+pub fn save_json_private<T: Serialize>(path: &Path, value: &T) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(value).map_err(io::Error::other)?;
+    atomic_write_private(path, json.as_bytes())
+}
+
+/// Lit un fichier JSON : `Ok(None)` s'il est absent, `Err` s'il est illisible ou mal formé
+pub fn read_json<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, String> {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(format!("{}: {}", path.display(), e)),
+    };
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Comme `read_json`, mais un fichier corrompu vaut « pas de cache » et est signalé dans le journal
+pub fn load_json<T: DeserializeOwned>(path: &Path) -> Option<T> {
+    match read_json(path) {
+        Ok(value) => value,
+        Err(message) => {
+            log_warning(&format!("Fichier ignoré (illisible ou corrompu), valeurs par défaut utilisées: {}", message));
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_atomic_write_and_corrupted_read() {
+        let dir = std::env::temp_dir().join(format!("rmdb-cache-test-{}", std::process::id()));
+        let path = dir.join("cache.json");
+
+        let value: HashMap<String, u32> = [("cpu".to_string(), 4)].into_iter().collect();
+        save_json(&path, &value).unwrap();
+        assert_eq!(read_json::<HashMap<String, u32>>(&path), Ok(Some(value.clone())));
+        // Aucun fichier temporaire laissé à côté
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+        save_json_private(&path, &value).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+
+        fs::write(&path, "{\"cpu\": 4").unwrap();
+        assert!(read_json::<HashMap<String, u32>>(&path).is_err());
+        assert_eq!(read_json::<HashMap<String, u32>>(&dir.join("absent.json")), Ok(None));
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}
@@ -1,5 +1,16 @@
 use std::process::Command;
 
+/// Outil attendu par l'interface, et paquet qui le fournit
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolRequirement {
+    pub tool: &'static str,
+    /// Fonctions de l'interface qui en dépendent
+    pub purpose: &'static str,
+    /// Nom générique du paquet (voir `DistributionInfo::package_name`) ; `None` si non installable
+    pub package: Option<&'static str>,
+    pub present: bool,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct Capabilities {
     pub has_systemctl: bool,
@@ -26,9 +37,13 @@ pub struct Capabilities {
     pub has_rpm: bool,
     pub has_dpkg: bool,
     pub has_sudo: bool,
+    pub has_curl: bool,
+    pub has_lxc: bool,
     // RMDB/IPXE specific
     pub has_rmdbd: bool,
     pub has_go: bool,
+    /// L'interface tourne déjà en root (ex: `sudo rmdb`) : sudo est inutile
+    pub running_as_root: bool,
 }
 
 impl Capabilities {
@@ -66,9 +81,50 @@ impl Capabilities {
             has_rpm: has("rpm"),
             has_dpkg: has("dpkg"),
             has_sudo: has("sudo"),
+            has_curl: has("curl"),
+            has_lxc: has("lxc-create"),
             has_rmdbd: has("rmdbd"),
             has_go: has("go"),
+            running_as_root: unsafe { libc::geteuid() } == 0,
         }
     }
+
+    /// Vrai si l'uid effectif est 0
+    pub fn is_root(&self) -> bool {
+        self.running_as_root
+    }
+
+    /// Outils attendus, présents ou non
+    pub fn requirements(&self) -> Vec<ToolRequirement> {
+        let tool = |tool, purpose, package, present| ToolRequirement { tool, purpose, package, present };
+        vec![
+            // Inutile quand l'interface tourne déjà en root
+            tool("sudo", "Mode Admin", Some("sudo"), self.has_sudo || self.running_as_root),
+            tool("lxc", "Déploiement et gestion des containers", Some("lxc"), self.has_lxc),
+            tool("systemctl/rc-service", "Gestion des services", None, self.has_systemctl || self.has_rc_service),
+            tool("curl", "Vérification des miroirs, santé de l'API", Some("curl"), self.has_curl),
+            tool("ip", "Interfaces et ponts réseau", Some("iproute2"), self.has_ip),
+            tool("ss", "Ports en écoute", Some("iproute2"), self.has_ss),
+            tool("go", "Compilation de rmdbd sur l'hôte", Some("golang"), self.has_go),
+        ]
+    }
+
+    /// Outils attendus mais absents
+    pub fn missing_tools(&self) -> Vec<ToolRequirement> {
+        self.requirements().into_iter().filter(|r| !r.present).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_tools() {
+        let caps = Capabilities { has_rc_service: true, has_curl: true, has_ip: true, running_as_root: true, ..Default::default() };
+        let missing: Vec<&str> = caps.missing_tools().iter().map(|r| r.tool).collect();
+        assert_eq!(missing, ["lxc", "ss", "go"]);
+        assert_eq!(caps.requirements().len(), 7);
+    }
 }
 
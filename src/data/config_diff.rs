@@ -0,0 +1,140 @@
+use serde_json::Value;
+
+/// Nature d'une différence entre deux configurations
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiffKind {
+    /// Clé présente uniquement dans la seconde configuration
+    Added,
+    /// Clé présente uniquement dans la première configuration
+    Removed,
+    /// Clé présente des deux côtés avec une valeur différente
+    Changed,
+}
+
+/// Une différence entre deux configurations, identifiée par un chemin pointé (ex: `dhcp.range_start`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    pub key: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// Compare deux valeurs JSON ; les objets imbriqués sont parcourus récursivement
+pub fn json_diff(a: &Value, b: &Value) -> Vec<DiffLine> {
+    let mut lines = Vec::new();
+    diff_into(&mut lines, "", a, b);
+    lines
+}
+
+fn diff_into(lines: &mut Vec<DiffLine>, prefix: &str, a: &Value, b: &Value) {
+    match (a, b) {
+        (Value::Object(map_a), Value::Object(map_b)) => {
+            let mut keys: Vec<&String> = map_a.keys().chain(map_b.keys()).collect();
+            keys.sort();
+            keys.dedup();
+
+            for key in keys {
+                let path = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                match (map_a.get(key), map_b.get(key)) {
+                    (Some(va), Some(vb)) => diff_into(lines, &path, va, vb),
+                    (Some(va), None) => lines.push(DiffLine {
+                        kind: DiffKind::Removed,
+                        key: path,
+                        old: Some(va.to_string()),
+                        new: None,
+                    }),
+                    (None, Some(vb)) => lines.push(DiffLine {
+                        kind: DiffKind::Added,
+                        key: path,
+                        old: None,
+                        new: Some(vb.to_string()),
+                    }),
+                    (None, None) => {}
+                }
+            }
+        }
+        _ if a != b => lines.push(DiffLine {
+            kind: DiffKind::Changed,
+            key: if prefix.is_empty() { "(racine)".to_string() } else { prefix.to_string() },
+            old: Some(a.to_string()),
+            new: Some(b.to_string()),
+        }),
+        _ => {}
+    }
+}
+
+/// Ligne d'une comparaison de textes (menu iPXE...)
+#[derive(Debug, Clone, PartialEq)]
+pub enum LineChange {
+    Same(String),
+    Added(String),
+    Removed(String),
+}
+
+/// Compare deux textes ligne à ligne (plus longue sous-séquence commune)
+///
+/// Quadratique en nombre de lignes : prévu pour des fichiers de quelques centaines de lignes.
+pub fn line_diff(old: &str, new: &str) -> Vec<LineChange> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    // lcs[i][j] : longueur de la sous-séquence commune de a[i..] et b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] { lcs[i + 1][j + 1] + 1 } else { lcs[i + 1][j].max(lcs[i][j + 1]) };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut changes = Vec::new();
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            changes.push(LineChange::Same(a[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if j < b.len() && (i == a.len() || lcs[i][j + 1] >= lcs[i + 1][j]) {
+            changes.push(LineChange::Added(b[j].to_string()));
+            j += 1;
+        } else {
+            changes.push(LineChange::Removed(a[i].to_string()));
+            i += 1;
+        }
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_json_diff_nested() {
+        let a = json!({"port": 8080, "dhcp": {"start": "10.0.0.10", "lease": 3600}, "old": true});
+        let b = json!({"port": 8080, "dhcp": {"start": "10.0.0.20", "lease": 3600}, "new": 1});
+        let diff = json_diff(&a, &b);
+        assert_eq!(diff.len(), 3);
+        assert_eq!(diff[0].kind, DiffKind::Changed);
+        assert_eq!(diff[0].key, "dhcp.start");
+        assert_eq!(diff[1].kind, DiffKind::Added);
+        assert_eq!(diff[1].key, "new");
+        assert_eq!(diff[2].kind, DiffKind::Removed);
+        assert_eq!(diff[2].key, "old");
+    }
+
+    #[test]
+    fn test_line_diff() {
+        let diff = line_diff("#!ipxe\nitem a A\nitem b B\n", "#!ipxe\nitem b B\nitem c C\n");
+        assert_eq!(
+            diff,
+            [
+                LineChange::Same("#!ipxe".to_string()),
+                LineChange::Removed("item a A".to_string()),
+                LineChange::Same("item b B".to_string()),
+                LineChange::Added("item c C".to_string()),
+            ]
+        );
+        assert!(line_diff("a\nb", "a\nb").iter().all(|c| matches!(c, LineChange::Same(_))));
+    }
+}
@@ -0,0 +1,284 @@
+//! Transport de démonstration du client API (RMDB_DEMO=1)
+//! Répond avec des données fictives fixes, sans serveur RMDB.
+//! Les écritures (création, suppression, mise à jour) réussissent mais ne sont pas conservées.
+
+use serde_json::{json, Value};
+
+use crate::data::api_client::{APIError, ClientBackend};
+
+/// Token retourné par la connexion en mode démonstration
+const DEMO_TOKEN: &str = "demo-token";
+
+/// Transport retournant des données fictives
+pub struct DemoBackend;
+
+impl ClientBackend for DemoBackend {
+    fn get(&self, endpoint: &str, _token: Option<&str>) -> Result<String, APIError> {
+        let (path, query) = split_query(endpoint);
+
+        let value = match path {
+            "/api/vms" => {
+                let vms: Vec<Value> = demo_vms()
+                    .into_iter()
+                    .filter(|vm| match query_param(query, "category") {
+                        Some(category) => vm["category"] == category,
+                        None => true,
+                    })
+                    .collect();
+                json!({ "count": vms.len(), "vms": vms })
+            }
+            "/api/vms/categories" => json!([
+                { "name": "linux", "description": "Distributions Linux" },
+                { "name": "windows", "description": "Postes Windows" },
+                { "name": "tools", "description": "Outils de maintenance" },
+            ]),
+            "/api/dhcp/leases" => {
+                let leases = demo_leases();
+                json!({ "count": leases.len(), "leases": leases })
+            }
+            "/api/clients/connected" => json!({
+                "count": 2,
+                "clients": [
+                    { "mac": "52:54:00:12:34:01", "ip": "192.168.100.101", "hostname": "poste-01",
+                      "connected_at": "2024-05-14T08:02:11Z", "last_seen": "2024-05-14T11:47:52Z" },
+                    { "mac": "52:54:00:12:34:02", "ip": "192.168.100.102", "hostname": "poste-02",
+                      "connected_at": "2024-05-14T08:05:40Z", "last_seen": "2024-05-14T11:47:30Z" },
+                ],
+            }),
+            "/api/system/metrics" => json!({
+                "cpu": { "usage_percent": 12.5, "cores": 4, "load_average": [0.42, 0.37, 0.30] },
+                "memory": { "total": 8_589_934_592u64, "used": 2_147_483_648u64,
+                            "free": 6_442_450_944u64, "usage_percent": 25.0 },
+                "disk": { "total": 274_877_906_944u64, "used": 68_719_476_736u64,
+                          "free": 206_158_430_208u64, "usage_percent": 25.0 },
+                "network": { "interfaces": [
+                    { "name": "eth0", "rx_bytes": 1_073_741_824u64, "tx_bytes": 4_294_967_296u64,
+                      "rx_packets": 812_345, "tx_packets": 2_934_112 },
+                ]},
+            }),
+            "/api/ipxe/menu" => return Ok(demo_ipxe_menu()),
+            "/api/ipxe/preview" => return Ok(demo_ipxe_menu()),
+            "/api/ipxe/entries" => json!(demo_ipxe_entries()),
+            "/api/overlays" => {
+                let overlays = demo_overlays();
+                json!({ "count": overlays.len(), "overlays": overlays })
+            }
+            "/api/config" => demo_config(),
+            "/api/repair/problems" => json!([
+                { "id": "tftp-perms", "severity": "warning", "category": "tftp",
+                  "description": "Permissions trop larges sur /srv/tftp" },
+            ]),
+            "/api/security/metrics" => json!({
+                "threats_detected": 3, "active_threats": 0, "blocked_ips": 2, "failed_logins": 5,
+            }),
+            _ => {
+                if let Some(mac) = path.strip_prefix("/api/overlays/mac/") {
+                    find_by(demo_overlays(), "mac_address", mac)?
+                } else if let Some(id) = path.strip_prefix("/api/vms/") {
+                    find_by(demo_vms(), "id", id)?
+                } else {
+                    return Err(not_found(path));
+                }
+            }
+        };
+
+        Ok(value.to_string())
+    }
+
+    fn post(&self, endpoint: &str, body: &str, _token: Option<&str>) -> Result<String, APIError> {
+        let (path, _) = split_query(endpoint);
+        let body: Value = serde_json::from_str(body).unwrap_or_else(|_| json!({}));
+
+        let value = match path {
+            "/api/login" => json!({ "token": DEMO_TOKEN }),
+            "/api/ipxe/generate" => return Ok(demo_ipxe_menu()),
+            "/api/vms" => merge(demo_vms().remove(0), json!({ "id": "vm-demo-new" }), &body),
+            "/api/overlays" => merge(demo_overlays().remove(0), json!({ "id": "ov-demo-new" }), &body),
+            "/api/config" => body,
+            "/api/repair" => json!({
+                "success": true,
+                "message": "Réparation simulée (mode démo)",
+                "details": body.get("type").and_then(|t| t.as_str()).map(|t| format!("Type: {}", t)),
+            }),
+            "/api/test" => json!({
+                "success": true,
+                "message": "Test simulé (mode démo)",
+                "details": body.get("type").and_then(|t| t.as_str()).map(|t| format!("Type: {}", t)),
+                "duration_ms": 42,
+            }),
+            _ => {
+                let Some(rest) = path.strip_prefix("/api/vms/") else {
+                    return Err(not_found(path));
+                };
+                if rest.ends_with("/compress") || rest.ends_with("/decompress") {
+                    json!({})
+                } else {
+                    merge(find_by(demo_vms(), "id", rest)?, json!({}), &body)
+                }
+            }
+        };
+
+        Ok(value.to_string())
+    }
+
+    fn delete(&self, _endpoint: &str, _token: Option<&str>) -> Result<(), APIError> {
+        Ok(())
+    }
+
+    fn is_demo(&self) -> bool {
+        true
+    }
+}
+
+/// Sépare le chemin et la chaîne de requête d'un endpoint
+fn split_query(endpoint: &str) -> (&str, &str) {
+    endpoint.split_once('?').unwrap_or((endpoint, ""))
+}
+
+/// Extrait un paramètre d'une chaîne de requête (sans décodage)
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+fn not_found(path: &str) -> APIError {
+    APIError::NotFound(format!("{} (mode démo)", path))
+}
+
+/// Cherche un élément de fixture par la valeur d'un champ
+fn find_by(items: Vec<Value>, field: &str, value: &str) -> Result<Value, APIError> {
+    items
+        .into_iter()
+        .find(|item| item[field].as_str().is_some_and(|v| v.eq_ignore_ascii_case(value)))
+        .ok_or_else(|| not_found(value))
+}
+
+/// Applique les champs fournis puis ceux de la requête sur un élément de fixture
+fn merge(mut base: Value, defaults: Value, body: &Value) -> Value {
+    for fields in [&defaults, body] {
+        if let (Some(target), Some(source)) = (base.as_object_mut(), fields.as_object()) {
+            for (key, value) in source {
+                target.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    base
+}
+
+fn demo_vms() -> Vec<Value> {
+    vec![
+        json!({
+            "id": "vm-debian12", "name": "debian-12", "description": "Debian 12 poste bureautique",
+            "category": "linux", "format": "qcow2", "disk_path": "/srv/rmdb/vms/debian-12.qcow2",
+            "size": 8_589_934_592u64, "compressed": false,
+            "created_at": "2024-03-02T09:15:00Z", "updated_at": "2024-05-10T16:40:00Z",
+            "metadata": { "os": "debian", "version": "12" },
+        }),
+        json!({
+            "id": "vm-win11", "name": "windows-11", "description": "Windows 11 salle informatique",
+            "category": "windows", "format": "qcow2", "disk_path": "/srv/rmdb/vms/windows-11.qcow2",
+            "size": 42_949_672_960u64, "compressed": true,
+            "created_at": "2024-02-18T14:00:00Z", "updated_at": "2024-04-29T08:20:00Z",
+            "metadata": { "os": "windows", "version": "11" },
+        }),
+        json!({
+            "id": "vm-clonezilla", "name": "clonezilla", "description": "Sauvegarde et restauration de disques",
+            "category": "tools", "format": "raw", "disk_path": "/srv/rmdb/vms/clonezilla.img",
+            "size": 536_870_912u64, "compressed": false,
+            "created_at": "2024-01-07T11:30:00Z", "updated_at": "2024-01-07T11:30:00Z",
+            "metadata": {},
+        }),
+    ]
+}
+
+fn demo_overlays() -> Vec<Value> {
+    vec![
+        json!({
+            "id": "ov-01", "vm_id": "vm-debian12", "mac_address": "52:54:00:12:34:01",
+            "overlay_path": "/srv/rmdb/overlays/52-54-00-12-34-01.qcow2", "size": 314_572_800u64,
+            "created_at": "2024-05-13T08:02:30Z", "updated_at": "2024-05-14T11:45:00Z",
+        }),
+        json!({
+            "id": "ov-02", "vm_id": "vm-win11", "mac_address": "52:54:00:12:34:02",
+            "overlay_path": "/srv/rmdb/overlays/52-54-00-12-34-02.qcow2", "size": 1_258_291_200u64,
+            "created_at": "2024-05-13T08:06:00Z", "updated_at": "2024-05-14T11:40:00Z",
+        }),
+        json!({
+            "id": "ov-03", "vm_id": "vm-supprimee", "mac_address": "52:54:00:12:34:09",
+            "overlay_path": "/srv/rmdb/overlays/52-54-00-12-34-09.qcow2", "size": 104_857_600u64,
+            "created_at": "2024-03-20T10:00:00Z", "updated_at": "2024-03-21T17:12:00Z",
+        }),
+    ]
+}
+
+fn demo_leases() -> Vec<Value> {
+    vec![
+        json!({ "mac": "52:54:00:12:34:01", "ip": "192.168.100.101", "hostname": "poste-01",
+                "expires_at": "2024-05-14T20:02:11Z", "state": "active" }),
+        json!({ "mac": "52:54:00:12:34:02", "ip": "192.168.100.102", "hostname": "poste-02",
+                "expires_at": "2024-05-14T20:05:40Z", "state": "active" }),
+        json!({ "mac": "52:54:00:12:34:03", "ip": "192.168.100.103", "hostname": null,
+                "expires_at": "2024-05-13T18:00:00Z", "state": "expired" }),
+    ]
+}
+
+fn demo_ipxe_entries() -> Vec<Value> {
+    vec![
+        json!({ "id": "debian-12", "name": "Debian 12", "description": "Poste bureautique",
+                "menu_type": "vm", "boot_target": "vm-debian12", "enabled": true }),
+        json!({ "id": "windows-11", "name": "Windows 11", "description": "Salle informatique",
+                "menu_type": "vm", "boot_target": "vm-win11", "enabled": true }),
+        json!({ "id": "clonezilla", "name": "Clonezilla", "description": null,
+                "menu_type": "tool", "boot_target": "vm-clonezilla", "enabled": false }),
+    ]
+}
+
+fn demo_ipxe_menu() -> String {
+    "#!ipxe\n\
+     menu RMDB (démo)\n\
+     item debian-12 Debian 12\n\
+     item windows-11 Windows 11\n\
+     choose target && goto ${target}\n"
+        .to_string()
+}
+
+fn demo_config() -> Value {
+    json!({
+        "server": { "listen": "0.0.0.0", "port": 8080 },
+        "dhcp": { "enabled": true, "interface": "lxcbr0",
+                  "range_start": "192.168.100.100", "range_end": "192.168.100.200" },
+        "tftp": { "enabled": true, "root": "/srv/tftp" },
+        "storage": { "vms_path": "/srv/rmdb/vms", "overlays_path": "/srv/rmdb/overlays" },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::api_client::APIClient;
+
+    #[test]
+    fn test_demo_fixtures_deserialize() {
+        let client = APIClient::with_backend(Box::new(DemoBackend));
+        assert!(client.is_demo());
+        assert_eq!(client.get_vms(None).unwrap().len(), 3);
+        assert_eq!(client.get_vms(Some("linux")).unwrap().len(), 1);
+        assert_eq!(client.get_vm("vm-win11").unwrap().name, "windows-11");
+        assert!(client.get_vm("inconnue").is_err());
+        assert!(client.get_overlay_by_mac("52:54:00:12:34:02").is_ok());
+        assert!(client.get_overlays().is_ok());
+        assert!(client.get_ipxe_entries().is_ok());
+        assert!(client.preview_ipxe_menu().unwrap().is_some());
+        assert!(client.get_dhcp_leases().is_ok());
+        assert!(client.get_connected_clients().is_ok());
+        assert!(client.get_system_metrics().is_ok());
+        assert!(client.get_security_metrics().is_ok());
+        assert!(client.get_repair_problems().is_ok());
+        assert!(client.get_vm_categories().is_ok());
+        assert!(client.get_config().is_ok());
+        assert_eq!(client.create_overlay("vm-debian12", "aa:bb:cc:dd:ee:ff").unwrap().mac_address, "aa:bb:cc:dd:ee:ff");
+    }
+}
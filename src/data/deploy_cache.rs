@@ -0,0 +1,38 @@
+use crate::data::cache_file::{load_json, save_json};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::PathBuf;
+
+/// Métadonnées du dernier déploiement d'un container, conservées entre les sessions
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeployRecord {
+    pub container: String,
+    /// Date de fin du déploiement (RFC 3339)
+    pub finished_at: String,
+    pub duration_secs: u64,
+    /// Taille du template Alpine dans le cache LXC, si elle a pu être mesurée
+    pub template_size_bytes: Option<u64>,
+    pub rmdb_installed: bool,
+}
+
+/// Répertoire de cache de RMDB (`$XDG_CACHE_HOME/rmdb` ou `~/.cache/rmdb`)
+pub fn cache_dir() -> PathBuf {
+    match std::env::var("XDG_CACHE_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir).join("rmdb"),
+        _ => PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(".cache/rmdb"),
+    }
+}
+
+fn last_deploy_path() -> PathBuf {
+    cache_dir().join("last_deploy.json")
+}
+
+/// Charge le dernier déploiement enregistré ; `None` si absent ou illisible
+pub fn load_last_deploy() -> Option<DeployRecord> {
+    load_json(&last_deploy_path())
+}
+
+/// Enregistre le dernier déploiement (remplace le précédent)
+pub fn save_last_deploy(record: &DeployRecord) -> io::Result<()> {
+    save_json(&last_deploy_path(), record)
+}
@@ -0,0 +1,129 @@
+use crate::data::cache_file::{load_json, save_json};
+use crate::data::deploy_cache::cache_dir;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::PathBuf;
+
+/// Nombre d'entrées conservées ; les plus anciennes sont oubliées
+const MAX_ENTRIES: usize = 200;
+
+/// Opération de déploiement ou d'installation
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeployKind {
+    LxcDeploy,
+    ContainerReinstall,
+    HostInstall,
+    ContainerInstall,
+}
+
+impl DeployKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            DeployKind::LxcDeploy => "Déploiement LXC",
+            DeployKind::ContainerReinstall => "Réinstallation container",
+            DeployKind::HostInstall => "Installation hôte",
+            DeployKind::ContainerInstall => "Installation container",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DeployResult {
+    Success,
+    Failed,
+    Cancelled,
+}
+
+impl DeployResult {
+    pub fn label(self) -> &'static str {
+        match self {
+            DeployResult::Success => "succès",
+            DeployResult::Failed => "échec",
+            DeployResult::Cancelled => "annulé",
+        }
+    }
+}
+
+/// Une tentative de déploiement ou d'installation
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HistoryEntry {
+    /// Début de la tentative (RFC 3339)
+    pub started_at: String,
+    pub kind: DeployKind,
+    /// Container ou hôte visé
+    pub target: String,
+    pub result: DeployResult,
+    pub duration_secs: u64,
+    /// Journal `DeploymentLogger` de la tentative, s'il y en a un
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub log_path: Option<PathBuf>,
+}
+
+/// Historique des déploiements (`~/.cache/rmdb/deploy_history.json`)
+pub struct DeployHistory {
+    path: PathBuf,
+}
+
+impl DeployHistory {
+    pub fn new() -> Self {
+        Self::at(cache_dir().join("deploy_history.json"))
+    }
+
+    pub fn at(path: PathBuf) -> Self {
+        Self { path }
+    }
+
+    /// Ajoute une tentative à l'historique
+    pub fn record(&self, entry: HistoryEntry) -> io::Result<()> {
+        let mut entries: Vec<HistoryEntry> = load_json(&self.path).unwrap_or_default();
+        entries.push(entry);
+        let excess = entries.len().saturating_sub(MAX_ENTRIES);
+        entries.drain(..excess);
+        save_json(&self.path, &entries)
+    }
+
+    /// Tentatives enregistrées, de la plus récente à la plus ancienne
+    pub fn list(&self) -> Vec<HistoryEntry> {
+        let mut entries: Vec<HistoryEntry> = load_json(&self.path).unwrap_or_default();
+        entries.reverse();
+        entries
+    }
+}
+
+impl Default for DeployHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_list() {
+        let dir = std::env::temp_dir().join(format!("rmdb-history-test-{}", std::process::id()));
+        let history = DeployHistory::at(dir.join("deploy_history.json"));
+        assert!(history.list().is_empty());
+
+        let entry = |target: &str, result| HistoryEntry {
+            started_at: "2024-05-14T08:00:00+02:00".to_string(),
+            kind: DeployKind::LxcDeploy,
+            target: target.to_string(),
+            result,
+            duration_secs: 42,
+            log_path: None,
+        };
+        history.record(entry("rmdb", DeployResult::Failed)).unwrap();
+        history.record(entry("rmdb", DeployResult::Success)).unwrap();
+
+        let entries = history.list();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].result, DeployResult::Success);
+        assert_eq!(entries[1].result, DeployResult::Failed);
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Attentes et nombres de tentatives du déploiement, de l'ajout et de la réinstallation des containers
+///
+/// Clé `deploy_timings` de `tui.json`, durées en millisecondes ; les champs absents gardent
+/// la valeur par défaut. À réduire sur une machine rapide, à allonger sur un hôte chargé.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct DeployTimings {
+    /// Attente après le démarrage d'un container, avant de le vérifier
+    pub container_start_ms: u64,
+    /// Attente que le container créé soit prêt, avant de contrôler son état
+    pub container_ready_ms: u64,
+    /// Attente après une création, un arrêt ou une suppression, le temps que LXC et le disque soient à jour
+    pub settle_ms: u64,
+    /// Courte attente avant une nouvelle vérification (templates installés, nettoyage, détection finale)
+    pub recheck_ms: u64,
+    /// Détections des templates LXC après leur installation
+    pub template_detect_attempts: u32,
+    /// Délai entre deux détections des templates
+    pub template_detect_interval_ms: u64,
+    /// Vérifications de l'existence du container après `lxc-create`
+    pub verify_attempts: u32,
+    /// Délai avant la première vérification, multiplié par le numéro de tentative ensuite
+    pub verify_interval_ms: u64,
+}
+
+impl Default for DeployTimings {
+    fn default() -> Self {
+        Self {
+            container_start_ms: 2000,
+            container_ready_ms: 3000,
+            settle_ms: 2000,
+            recheck_ms: 1000,
+            template_detect_attempts: 3,
+            template_detect_interval_ms: 1000,
+            verify_attempts: 5,
+            verify_interval_ms: 500,
+        }
+    }
+}
+
+impl DeployTimings {
+    pub fn container_start(&self) -> Duration {
+        Duration::from_millis(self.container_start_ms)
+    }
+
+    pub fn container_ready(&self) -> Duration {
+        Duration::from_millis(self.container_ready_ms)
+    }
+
+    pub fn settle(&self) -> Duration {
+        Duration::from_millis(self.settle_ms)
+    }
+
+    pub fn recheck(&self) -> Duration {
+        Duration::from_millis(self.recheck_ms)
+    }
+
+    /// Au moins une détection, même si la configuration indique 0
+    pub fn template_detect_attempts(&self) -> u32 {
+        self.template_detect_attempts.max(1)
+    }
+
+    pub fn template_detect_interval(&self) -> Duration {
+        Duration::from_millis(self.template_detect_interval_ms)
+    }
+
+    /// Au moins une vérification, même si la configuration indique 0
+    pub fn verify_attempts(&self) -> u32 {
+        self.verify_attempts.max(1)
+    }
+
+    /// Délai avant la tentative `attempt` (0 : première vérification), croissant d'une tentative à l'autre
+    pub fn verify_delay(&self, attempt: u32) -> Duration {
+        Duration::from_millis(self.verify_interval_ms.saturating_mul(attempt.max(1) as u64))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_partial_timings_keep_defaults() {
+        let timings: DeployTimings = serde_json::from_str(r#"{"container_start_ms": 8000, "verify_attempts": 0}"#).unwrap();
+        assert_eq!(timings.container_start(), Duration::from_secs(8));
+        assert_eq!(timings.container_ready(), Duration::from_secs(3));
+        assert_eq!(timings.settle(), Duration::from_secs(2));
+        assert_eq!(timings.recheck(), Duration::from_secs(1));
+        assert_eq!(timings.template_detect_attempts(), 3);
+        assert_eq!(timings.verify_attempts(), 1);
+        assert_eq!(timings.verify_delay(0), Duration::from_millis(500));
+        assert_eq!(timings.verify_delay(3), Duration::from_millis(1500));
+    }
+}
@@ -0,0 +1,191 @@
+use crate::data::api_client::DHCPLease;
+use crate::data::distribution::LinuxDistribution;
+use crate::pres::executor::CommandExecutor;
+use std::path::{Path, PathBuf};
+
+/// Format d'un fichier de leases DHCP
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LeaseFormat {
+    /// ISC dhcpd : blocs `lease <ip> { ... }`
+    Isc,
+    /// dnsmasq : une ligne `<expiration> <mac> <ip> <nom> <client-id>` par lease
+    Dnsmasq,
+}
+
+impl LeaseFormat {
+    /// Déduit le format d'après le nom du fichier
+    pub fn from_path(path: &Path) -> Self {
+        if path.to_string_lossy().contains("dnsmasq") {
+            LeaseFormat::Dnsmasq
+        } else {
+            LeaseFormat::Isc
+        }
+    }
+
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            LeaseFormat::Isc => "ISC dhcpd",
+            LeaseFormat::Dnsmasq => "dnsmasq",
+        }
+    }
+}
+
+/// Emplacements connus des fichiers de leases, par ordre de préférence selon la distribution
+fn candidate_lease_paths(distro: &LinuxDistribution) -> Vec<&'static str> {
+    let preferred: &[&str] = match distro {
+        LinuxDistribution::Debian | LinuxDistribution::Ubuntu | LinuxDistribution::Alpine => {
+            &["/var/lib/dhcp/dhcpd.leases", "/var/lib/misc/dnsmasq.leases"]
+        }
+        LinuxDistribution::Fedora | LinuxDistribution::RHEL | LinuxDistribution::CentOS => {
+            &["/var/lib/dhcpd/dhcpd.leases", "/var/lib/dnsmasq/dnsmasq.leases"]
+        }
+        LinuxDistribution::OpenSUSE => &["/var/lib/dhcp/db/dhcpd.leases", "/var/lib/misc/dnsmasq.leases"],
+        LinuxDistribution::Arch => &["/var/lib/dhcp/dhcpd.leases", "/var/lib/misc/dnsmasq.leases"],
+        LinuxDistribution::Unknown => &[],
+    };
+    let all = [
+        "/var/lib/dhcp/dhcpd.leases",
+        "/var/lib/dhcpd/dhcpd.leases",
+        "/var/lib/dhcp/db/dhcpd.leases",
+        "/var/db/dhcpd.leases",
+        "/var/lib/misc/dnsmasq.leases",
+        "/var/lib/dnsmasq/dnsmasq.leases",
+    ];
+
+    let mut paths: Vec<&'static str> = preferred.to_vec();
+    for path in all {
+        if !paths.contains(&path) {
+            paths.push(path);
+        }
+    }
+    paths
+}
+
+/// Cherche le fichier de leases DHCP (ISC dhcpd ou dnsmasq) présent sur le système
+pub fn find_dhcp_leases_path(distro: &LinuxDistribution, executor: &CommandExecutor) -> Option<PathBuf> {
+    candidate_lease_paths(distro).into_iter().find_map(|path| {
+        let cmd = format!("test -f {} && echo 'present' || echo 'missing'", path);
+        executor
+            .run_shell(&cmd, false)
+            .ok()
+            .filter(|o| o.stdout.contains("present"))
+            .map(|_| PathBuf::from(path))
+    })
+}
+
+/// Analyse un fichier de leases DHCP (format ISC dhcpd ou dnsmasq)
+///
+/// Pour ISC dhcpd, le fichier est en ajout seul : la dernière entrée d'une IP l'emporte.
+pub fn parse_leases(content: &str) -> Vec<DHCPLease> {
+    if content.contains("lease ") && content.contains('{') {
+        parse_isc_leases(content)
+    } else {
+        parse_dnsmasq_leases(content)
+    }
+}
+
+/// Analyse un fichier de leases dont le format est connu
+pub fn parse_leases_as(content: &str, format: LeaseFormat) -> Vec<DHCPLease> {
+    match format {
+        LeaseFormat::Isc => parse_isc_leases(content),
+        LeaseFormat::Dnsmasq => parse_dnsmasq_leases(content),
+    }
+}
+
+fn parse_isc_leases(content: &str) -> Vec<DHCPLease> {
+    let mut leases: Vec<DHCPLease> = Vec::new();
+    let mut current: Option<DHCPLease> = None;
+
+    for raw_line in content.lines() {
+        let line = raw_line.trim().trim_end_matches(';');
+
+        if let Some(rest) = line.strip_prefix("lease ") {
+            let ip = rest.trim_end_matches('{').trim().to_string();
+            current = Some(DHCPLease {
+                mac: String::new(),
+                ip,
+                hostname: None,
+                expires_at: None,
+                state: String::new(),
+            });
+            continue;
+        }
+
+        let lease = match current.as_mut() {
+            Some(lease) => lease,
+            None => continue,
+        };
+
+        if line == "}" {
+            let lease = current.take().unwrap();
+            leases.retain(|l| l.ip != lease.ip);
+            leases.push(lease);
+        } else if let Some(mac) = line.strip_prefix("hardware ethernet ") {
+            lease.mac = mac.trim().to_lowercase();
+        } else if let Some(name) = line.strip_prefix("client-hostname ") {
+            lease.hostname = Some(name.trim().trim_matches('"').to_string());
+        } else if let Some(ends) = line.strip_prefix("ends ") {
+            // Format: "ends <jour> AAAA/MM/JJ HH:MM:SS"
+            let date = ends.split_whitespace().skip(1).collect::<Vec<_>>().join(" ");
+            lease.expires_at = if date.is_empty() { None } else { Some(date) };
+        } else if let Some(state) = line.strip_prefix("binding state ") {
+            lease.state = state.trim().to_string();
+        }
+    }
+
+    leases
+}
+
+fn parse_dnsmasq_leases(content: &str) -> Vec<DHCPLease> {
+    // Format: <expiration epoch> <mac> <ip> <hostname> <client-id>
+    content
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 3 {
+                return None;
+            }
+            let expires_at = fields[0].parse::<i64>().ok()
+                .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                .map(|dt| dt.format("%Y/%m/%d %H:%M:%S").to_string());
+            Some(DHCPLease {
+                mac: fields[1].to_lowercase(),
+                ip: fields[2].to_string(),
+                hostname: fields.get(3).filter(|h| **h != "*").map(|h| h.to_string()),
+                expires_at,
+                state: "active".to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_isc_leases_keeps_last_entry() {
+        let content = "lease 10.0.0.5 {\n  starts 1 2024/01/01 10:00:00;\n  ends 1 2024/01/01 22:00:00;\n  binding state free;\n  hardware ethernet AA:BB:CC:DD:EE:FF;\n}\nlease 10.0.0.5 {\n  binding state active;\n  hardware ethernet aa:bb:cc:dd:ee:ff;\n  client-hostname \"pxe01\";\n}\n";
+        let leases = parse_leases(content);
+        assert_eq!(leases.len(), 1);
+        assert_eq!(leases[0].state, "active");
+        assert_eq!(leases[0].mac, "aa:bb:cc:dd:ee:ff");
+        assert_eq!(leases[0].hostname.as_deref(), Some("pxe01"));
+    }
+
+    #[test]
+    fn test_candidate_lease_paths_prefers_distro() {
+        let paths = candidate_lease_paths(&LinuxDistribution::RHEL);
+        assert_eq!(paths[0], "/var/lib/dhcpd/dhcpd.leases");
+        assert_eq!(paths.len(), 6);
+        assert_eq!(LeaseFormat::from_path(Path::new(paths[1])), LeaseFormat::Dnsmasq);
+    }
+
+    #[test]
+    fn test_parse_dnsmasq_leases() {
+        let leases = parse_leases("1700000000 aa:bb:cc:dd:ee:ff 10.0.0.7 pxe02 *\n");
+        assert_eq!(leases.len(), 1);
+        assert_eq!(leases[0].ip, "10.0.0.7");
+        assert_eq!(leases[0].hostname.as_deref(), Some("pxe02"));
+    }
+}
@@ -0,0 +1,85 @@
+use crate::pres::executor::Executor;
+use std::path::Path;
+
+/// État d'un fichier disque vu depuis l'hôte
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiskPathStatus {
+    Readable,
+    /// Présent mais illisible pour l'utilisateur courant
+    Unreadable,
+    /// Chemin d'un répertoire, pas d'un fichier
+    Directory,
+    Missing,
+}
+
+/// Vérifie un chemin de disque avec `test` (exécuté par l'executor, donc sur l'hôte)
+pub fn check_disk_path(executor: &dyn Executor, path: &str) -> Option<DiskPathStatus> {
+    let quoted = format!("'{}'", path.replace('\'', r"'\''"));
+    let cmd = format!(
+        "if [ -d {p} ]; then echo dir; elif [ -r {p} ]; then echo ok; elif [ -e {p} ]; then echo unreadable; else echo missing; fi",
+        p = quoted
+    );
+    let output = executor.run_shell(&cmd, false).ok()?;
+    match output.stdout.trim() {
+        "ok" => Some(DiskPathStatus::Readable),
+        "unreadable" => Some(DiskPathStatus::Unreadable),
+        "dir" => Some(DiskPathStatus::Directory),
+        "missing" => Some(DiskPathStatus::Missing),
+        _ => None,
+    }
+}
+
+/// Format d'image attendu d'après l'extension (`disk.qcow2` → `qcow2`, `disk.img` → `raw`)
+pub fn format_from_extension(path: &str) -> Option<&'static str> {
+    let extension = Path::new(path).extension()?.to_str()?.to_ascii_lowercase();
+    match extension.as_str() {
+        "qcow2" | "qcow" => Some("qcow2"),
+        "raw" | "img" => Some("raw"),
+        "vmdk" => Some("vmdk"),
+        "vhd" | "vpc" => Some("vhd"),
+        "vhdx" => Some("vhdx"),
+        _ => None,
+    }
+}
+
+/// Avertissements sur un disque de VM avant sa création (vide si tout est cohérent)
+///
+/// `status` vaut `None` quand l'existence n'a pas pu être vérifiée (serveur distant).
+pub fn validate_disk_path(path: &str, format: &str, status: Option<DiskPathStatus>) -> Vec<String> {
+    let mut warnings = Vec::new();
+    if !path.starts_with('/') {
+        warnings.push(format!("Chemin relatif : le serveur le résoudra depuis son propre répertoire ({})", path));
+    }
+    match status {
+        Some(DiskPathStatus::Readable) | None => {}
+        Some(DiskPathStatus::Unreadable) => warnings.push(format!("{} existe mais n'est pas lisible", path)),
+        Some(DiskPathStatus::Directory) => warnings.push(format!("{} est un répertoire, pas un fichier disque", path)),
+        Some(DiskPathStatus::Missing) => warnings.push(format!("{} n'existe pas (encore ?)", path)),
+    }
+    if let Some(expected) = format_from_extension(path) {
+        if !expected.eq_ignore_ascii_case(format.trim()) {
+            warnings.push(format!("Le format '{}' ne correspond pas à l'extension (attendu: {})", format, expected));
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pres::executor::MockExecutor;
+
+    #[test]
+    fn test_validate_disk_path() {
+        assert!(validate_disk_path("/var/lib/rmdb/debian.qcow2", "qcow2", Some(DiskPathStatus::Readable)).is_empty());
+        assert!(validate_disk_path("/srv/disk.img", "RAW", None).is_empty());
+
+        let warnings = validate_disk_path("/srv/win.vmdk", "qcow2", Some(DiskPathStatus::Missing));
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[1].contains("attendu: vmdk"));
+        assert_eq!(validate_disk_path("disk.bin", "raw", None).len(), 1);
+
+        let executor = MockExecutor::new().on("[ -d '/srv/it'\\''s.qcow2' ]", "missing\n");
+        assert_eq!(check_disk_path(&executor, "/srv/it's.qcow2"), Some(DiskPathStatus::Missing));
+    }
+}
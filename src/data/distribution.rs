@@ -206,10 +206,59 @@ impl DistributionInfo {
                 "lxc-templates".to_string() // Même nom sur RHEL/Fedora
             }
             ("lxc-templates", _) => "lxc-templates".to_string(),
+            ("iproute2", LinuxDistribution::Fedora | LinuxDistribution::RHEL | LinuxDistribution::CentOS) => "iproute".to_string(),
+            ("golang", LinuxDistribution::Arch | LinuxDistribution::Alpine | LinuxDistribution::OpenSUSE) => "go".to_string(),
             _ => base_package.to_string(),
         }
     }
 
+    /// Paquets à installer pour disposer de LXC et de ses templates
+    ///
+    /// Sur RHEL/CentOS, le template `download` est fourni par `lxc` ; EPEL doit être activé
+    /// avant (voir `lxc_install_commands`). Les distributions inconnues reçoivent la liste
+    /// générique (voir `has_known_lxc_packages`).
+    pub fn lxc_packages(&self) -> Vec<&'static str> {
+        match self.distro {
+            LinuxDistribution::Debian | LinuxDistribution::Ubuntu => vec!["lxc", "lxc-templates", "uidmap"],
+            LinuxDistribution::Fedora => vec!["lxc", "lxc-templates"],
+            LinuxDistribution::RHEL | LinuxDistribution::CentOS => vec!["lxc", "lxc-libs"],
+            LinuxDistribution::Arch | LinuxDistribution::OpenSUSE => vec!["lxc"],
+            LinuxDistribution::Alpine => vec!["lxc", "lxc-templates", "lxc-download"],
+            LinuxDistribution::Unknown => vec!["lxc", "lxc-templates"],
+        }
+    }
+
+    /// Commandes installant `packages` (paquets LXC), dans l'ordre
+    ///
+    /// Sur RHEL/CentOS, LXC provient d'EPEL : `epel-release` est installé par une commande
+    /// séparée, le dépôt n'étant connu du gestionnaire de paquets qu'une fois celle-ci terminée.
+    pub fn lxc_install_commands(&self, packages: &[&str]) -> Vec<String> {
+        let mut commands = Vec::new();
+        if matches!(self.distro, LinuxDistribution::RHEL | LinuxDistribution::CentOS) {
+            commands.push(self.install_command(&["epel-release"]));
+        }
+        commands.push(self.install_command(packages));
+        commands
+    }
+
+    /// Paquets fournissant les templates LXC, sous-ensemble de `lxc_packages`
+    pub fn lxc_template_packages(&self) -> Vec<&'static str> {
+        match self.distro {
+            LinuxDistribution::Debian | LinuxDistribution::Ubuntu | LinuxDistribution::Fedora => vec!["lxc-templates"],
+            LinuxDistribution::Alpine => vec!["lxc-templates", "lxc-download"],
+            // Templates livrés avec le paquet lxc lui-même
+            LinuxDistribution::RHEL | LinuxDistribution::CentOS | LinuxDistribution::Arch | LinuxDistribution::OpenSUSE => {
+                vec!["lxc"]
+            }
+            LinuxDistribution::Unknown => vec!["lxc-templates"],
+        }
+    }
+
+    /// Indique si la liste de paquets LXC est connue pour cette distribution
+    pub fn has_known_lxc_packages(&self) -> bool {
+        self.distro != LinuxDistribution::Unknown
+    }
+
     pub fn lxc_config_path(&self) -> String {
         // Sur RHEL/CentOS, LXC peut utiliser des chemins différents
         match self.distro {
@@ -247,6 +296,30 @@ impl DistributionInfo {
         }
     }
 
+    /// Nom court et version majeure.mineure (`Debian 12`, `Ubuntu 24.04`), pour les barres d'état
+    pub fn short_label(&self) -> String {
+        let name = match self.distro {
+            LinuxDistribution::Debian => "Debian",
+            LinuxDistribution::Ubuntu => "Ubuntu",
+            LinuxDistribution::Fedora => "Fedora",
+            LinuxDistribution::RHEL => "RHEL",
+            LinuxDistribution::CentOS => "CentOS",
+            LinuxDistribution::Arch => "Arch",
+            LinuxDistribution::OpenSUSE => "openSUSE",
+            LinuxDistribution::Alpine => "Alpine",
+            LinuxDistribution::Unknown => "Linux",
+        };
+        // `3.20.1` → `3.20`, `bookworm/sid` → `bookworm`
+        let version = self.version.as_deref().map(|version| {
+            let version = version.split(['/', ' ']).next().unwrap_or_default();
+            version.split('.').take(2).collect::<Vec<_>>().join(".").chars().take(8).collect::<String>()
+        });
+        match version {
+            Some(version) if !version.is_empty() => format!("{} {}", name, version),
+            _ => name.to_string(),
+        }
+    }
+
     pub fn needs_root_for_lxc(&self) -> bool {
         // Sur RHEL/CentOS, LXC nécessite souvent root ou des mappings UID
         matches!(self.distro, LinuxDistribution::RHEL | LinuxDistribution::CentOS)
@@ -269,3 +342,41 @@ impl std::fmt::Display for LinuxDistribution {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lxc_packages_per_distro() {
+        let info = |distro| DistributionInfo { distro, version: None, package_manager: PackageManager::Apt };
+
+        assert!(!info(LinuxDistribution::RHEL).lxc_packages().contains(&"lxc-templates"));
+        let rhel = DistributionInfo { distro: LinuxDistribution::RHEL, version: None, package_manager: PackageManager::Dnf };
+        assert_eq!(
+            rhel.lxc_install_commands(&rhel.lxc_packages()),
+            ["dnf install -y epel-release", "dnf install -y lxc lxc-libs"]
+        );
+        assert_eq!(info(LinuxDistribution::Debian).lxc_install_commands(&["lxc"]), ["apt-get install -y lxc"]);
+        assert_eq!(info(LinuxDistribution::Unknown).lxc_packages(), vec!["lxc", "lxc-templates"]);
+        assert!(!info(LinuxDistribution::Unknown).has_known_lxc_packages());
+
+        // Les paquets de templates font partie de l'installation complète
+        for distro in [LinuxDistribution::Debian, LinuxDistribution::Fedora, LinuxDistribution::Arch, LinuxDistribution::Alpine] {
+            let info = info(distro);
+            for package in info.lxc_template_packages() {
+                assert!(info.lxc_packages().contains(&package));
+            }
+        }
+    }
+
+    #[test]
+    fn test_short_label() {
+        let info = |distro, version: Option<&str>| DistributionInfo { distro, version: version.map(String::from), package_manager: PackageManager::Apt };
+
+        assert_eq!(info(LinuxDistribution::Alpine, Some("3.20.1")).short_label(), "Alpine 3.20");
+        assert_eq!(info(LinuxDistribution::Debian, Some("bookworm/sid")).short_label(), "Debian bookworm");
+        assert_eq!(info(LinuxDistribution::OpenSUSE, Some("15.5")).short_label(), "openSUSE 15.5");
+        assert_eq!(info(LinuxDistribution::Arch, None).short_label(), "Arch");
+        assert_eq!(info(LinuxDistribution::Unknown, Some("")).short_label(), "Linux");
+    }
+}
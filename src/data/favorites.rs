@@ -0,0 +1,98 @@
+use crate::data::cache_file::{load_json, save_json};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::PathBuf;
+
+/// Nombre d'actions récentes conservées
+pub const RECENT_MAX: usize = 8;
+
+/// Actions épinglées et dernières actions lancées, identifiées par leur libellé de palette
+/// (ex: `Configuration › Éditer Configuration`)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct Favorites {
+    #[serde(default)]
+    pub pinned: Vec<String>,
+    #[serde(default)]
+    pub recent: Vec<String>,
+}
+
+/// Répertoire de configuration de RMDB (`$XDG_CONFIG_HOME/rmdb` ou `~/.config/rmdb`)
+pub fn config_dir() -> PathBuf {
+    match std::env::var("XDG_CONFIG_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir).join("rmdb"),
+        _ => PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(".config/rmdb"),
+    }
+}
+
+fn favorites_path() -> PathBuf {
+    config_dir().join("favorites.json")
+}
+
+impl Favorites {
+    /// Charge les favoris enregistrés ; liste vide si absents ou illisibles
+    pub fn load() -> Self {
+        load_json(&favorites_path()).unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        save_json(&favorites_path(), self)
+    }
+
+    /// Place l'action en tête des récents
+    pub fn record(&mut self, label: &str) {
+        self.recent.retain(|l| l != label);
+        self.recent.insert(0, label.to_string());
+        self.recent.truncate(RECENT_MAX);
+    }
+
+    pub fn is_pinned(&self, label: &str) -> bool {
+        self.pinned.iter().any(|l| l == label)
+    }
+
+    /// Épingle ou désépingle l'action ; vrai si elle est maintenant épinglée
+    pub fn toggle_pin(&mut self, label: &str) -> bool {
+        if self.is_pinned(label) {
+            self.pinned.retain(|l| l != label);
+            false
+        } else {
+            self.pinned.push(label.to_string());
+            true
+        }
+    }
+
+    /// Libellés à afficher : épinglés d'abord, puis récents non épinglés
+    pub fn entries(&self) -> Vec<&str> {
+        self.pinned
+            .iter()
+            .chain(self.recent.iter().filter(|l| !self.is_pinned(l)))
+            .map(String::as_str)
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pinned.is_empty() && self.recent.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_and_pin() {
+        let mut favorites = Favorites::default();
+        for i in 0..RECENT_MAX + 2 {
+            favorites.record(&format!("action {}", i));
+        }
+        favorites.record("action 3");
+        assert_eq!(favorites.recent.len(), RECENT_MAX);
+        assert_eq!(favorites.recent[0], "action 3");
+
+        assert!(favorites.toggle_pin("action 5"));
+        let entries = favorites.entries();
+        assert_eq!(entries[0], "action 5");
+        assert_eq!(entries.iter().filter(|l| **l == "action 5").count(), 1);
+        assert!(!favorites.toggle_pin("action 5"));
+        assert!(favorites.pinned.is_empty());
+    }
+}
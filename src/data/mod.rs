@@ -1,4 +1,18 @@
 pub mod capabilities;
 pub mod distribution;
 pub mod api_client;
+pub mod demo_backend;
+pub mod config_diff;
+pub mod dhcp_leases;
 
+pub mod net;
+pub mod deploy_cache;
+pub mod deploy_history;
+pub mod deploy_timings;
+pub mod cache_file;
+pub mod favorites;
+pub mod tui_config;
+pub mod profiles;
+pub mod overlay_cleanup;
+pub mod system_info;
+pub mod disk_image;
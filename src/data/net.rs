@@ -0,0 +1,116 @@
+use std::io;
+use std::net::UdpSocket;
+use std::process::{Command, Stdio};
+
+/// Port UDP utilisé par convention pour le wake-on-lan
+const WOL_PORT: u16 = 9;
+
+/// Vérifie si une IP répond à un ping (un seul paquet, délai d'une seconde)
+pub fn ping(ip: &str) -> bool {
+    Command::new("ping")
+        .args(["-c", "1", "-W", "1", ip])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+/// Envoie un paquet magique wake-on-lan en broadcast pour réveiller la machine `mac`
+pub fn wake_on_lan(mac: &str) -> io::Result<()> {
+    let mac_bytes = parse_mac(mac)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, format!("Adresse MAC invalide: {}", mac)))?;
+
+    // Paquet magique : 6 octets 0xFF suivis de 16 répétitions de l'adresse MAC
+    let mut packet = vec![0xFFu8; 6];
+    for _ in 0..16 {
+        packet.extend_from_slice(&mac_bytes);
+    }
+
+    let socket = UdpSocket::bind("0.0.0.0:0")?;
+    socket.set_broadcast(true)?;
+    socket.send_to(&packet, ("255.255.255.255", WOL_PORT))?;
+    Ok(())
+}
+
+/// Ponts réseau du système (`ip -o link show type bridge`), ex: `br0`, `lxcbr0`
+pub fn list_bridges() -> Vec<String> {
+    Command::new("ip")
+        .args(["-o", "link", "show", "type", "bridge"])
+        .output()
+        .map(|out| parse_ip_link_names(&String::from_utf8_lossy(&out.stdout)))
+        .unwrap_or_default()
+}
+
+/// Noms d'interfaces de la sortie `ip -o link` (`3: br0: <BROADCAST,...> ...`)
+fn parse_ip_link_names(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split(": ").nth(1))
+        .map(|name| name.split('@').next().unwrap_or(name).trim().to_string())
+        .filter(|name| is_valid_interface_name(name))
+        .collect()
+}
+
+/// Nom d'interface Linux valide (15 caractères au plus, sans espace ni `/`)
+pub fn is_valid_interface_name(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= 15
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+}
+
+/// Indique si une URL désigne la machine locale (`localhost`, `127.x.x.x`, `::1`)
+pub fn is_local_url(url: &str) -> bool {
+    let rest = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+    let authority = rest.split('/').next().unwrap_or(rest);
+    let authority = authority.rsplit('@').next().unwrap_or(authority);
+    let host = match authority.strip_prefix('[') {
+        Some(ipv6) => ipv6.split(']').next().unwrap_or(ipv6),
+        None => authority.split(':').next().unwrap_or(authority),
+    };
+    host.eq_ignore_ascii_case("localhost") || host.starts_with("127.") || host == "::1"
+}
+
+/// Analyse une adresse MAC au format `aa:bb:cc:dd:ee:ff` ou `aa-bb-cc-dd-ee-ff`
+fn parse_mac(mac: &str) -> Option<[u8; 6]> {
+    let parts: Vec<&str> = mac.trim().split([':', '-']).collect();
+    if parts.len() != 6 {
+        return None;
+    }
+    let mut bytes = [0u8; 6];
+    for (byte, part) in bytes.iter_mut().zip(parts) {
+        *byte = u8::from_str_radix(part, 16).ok()?;
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mac() {
+        assert_eq!(parse_mac("AA:bb:cc:00:11:22"), Some([0xaa, 0xbb, 0xcc, 0x00, 0x11, 0x22]));
+        assert_eq!(parse_mac("aa-bb-cc-00-11-22"), Some([0xaa, 0xbb, 0xcc, 0x00, 0x11, 0x22]));
+        assert_eq!(parse_mac("aa:bb:cc"), None);
+        assert_eq!(parse_mac("zz:bb:cc:00:11:22"), None);
+    }
+
+    #[test]
+    fn test_parse_ip_link_names() {
+        let output = "3: lxcbr0: <BROADCAST,MULTICAST,UP,LOWER_UP> mtu 1500 qdisc noqueue state UP\n\
+                      7: br0: <BROADCAST,MULTICAST> mtu 1500 qdisc noop state DOWN\n";
+        assert_eq!(parse_ip_link_names(output), vec!["lxcbr0", "br0"]);
+        assert!(parse_ip_link_names("").is_empty());
+        assert!(!is_valid_interface_name("br0; rm -rf /"));
+        assert!(!is_valid_interface_name("un-nom-beaucoup-trop-long"));
+    }
+
+    #[test]
+    fn test_is_local_url() {
+        assert!(is_local_url("http://localhost:8080"));
+        assert!(is_local_url("http://127.0.0.1:8080/api"));
+        assert!(is_local_url("http://[::1]:8080"));
+        assert!(!is_local_url("https://pxe.example:8443"));
+    }
+}
@@ -0,0 +1,128 @@
+use crate::data::api_client::{DHCPLease, VMOverlay, VM};
+use chrono::{DateTime, NaiveDateTime, Utc};
+
+/// Raison pour laquelle un overlay est considéré comme orphelin
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OrphanReason {
+    /// La VM de base n'existe plus
+    MissingVm,
+    /// Aucune lease DHCP actuelle pour l'adresse MAC du client
+    NoRecentLease,
+}
+
+impl OrphanReason {
+    pub fn label(&self) -> &'static str {
+        match self {
+            OrphanReason::MissingVm => "VM supprimée",
+            OrphanReason::NoRecentLease => "aucune lease récente",
+        }
+    }
+}
+
+/// Fin d'une lease : RFC 3339 (API) ou `AAAA/MM/JJ HH:MM:SS` en UTC (fichiers de leases)
+fn lease_end(lease: &DHCPLease) -> Option<DateTime<Utc>> {
+    let raw = lease.expires_at.as_deref()?.trim();
+    DateTime::parse_from_rfc3339(raw)
+        .map(|end| end.with_timezone(&Utc))
+        .or_else(|_| NaiveDateTime::parse_from_str(raw, "%Y/%m/%d %H:%M:%S").map(|end| end.and_utc()))
+        .ok()
+}
+
+/// Lease encore valable à `now` ; sans fin lisible (lease permanente), elle est gardée
+fn is_recent(lease: &DHCPLease, now: DateTime<Utc>) -> bool {
+    lease_end(lease).is_none_or(|end| end > now)
+}
+
+/// Détermine si un overlay est orphelin
+///
+/// Le critère des leases n'est appliqué que si la liste des leases n'est pas vide,
+/// pour ne pas tout considérer comme orphelin quand le serveur DHCP ne répond pas.
+/// Une lease dont la fin est passée à `now` ne compte pas.
+pub fn orphan_reason(overlay: &VMOverlay, vms: &[VM], leases: &[DHCPLease], now: DateTime<Utc>) -> Option<OrphanReason> {
+    if !vms.iter().any(|vm| vm.id == overlay.vm_id) {
+        return Some(OrphanReason::MissingVm);
+    }
+    let has_recent_lease = leases
+        .iter()
+        .any(|l| l.mac.eq_ignore_ascii_case(&overlay.mac_address) && is_recent(l, now));
+    if !leases.is_empty() && !has_recent_lease {
+        return Some(OrphanReason::NoRecentLease);
+    }
+    None
+}
+
+/// Retourne les overlays dont la VM n'existe plus ou dont la MAC n'a pas de lease récente
+pub fn find_orphan_overlays<'a>(overlays: &'a [VMOverlay], vms: &[VM], leases: &[DHCPLease], now: DateTime<Utc>) -> Vec<&'a VMOverlay> {
+    overlays
+        .iter()
+        .filter(|overlay| orphan_reason(overlay, vms, leases, now).is_some())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn overlay(id: &str, vm_id: &str, mac: &str) -> VMOverlay {
+        VMOverlay {
+            id: id.to_string(),
+            vm_id: vm_id.to_string(),
+            mac_address: mac.to_string(),
+            overlay_path: format!("/var/lib/rmdb/overlays/{}.qcow2", id),
+            size: 0,
+            created_at: String::new(),
+            updated_at: String::new(),
+        }
+    }
+
+    fn vm(id: &str) -> VM {
+        VM {
+            id: id.to_string(),
+            name: id.to_string(),
+            description: String::new(),
+            category: String::new(),
+            format: "qcow2".to_string(),
+            disk_path: String::new(),
+            size: 0,
+            compressed: false,
+            created_at: String::new(),
+            updated_at: String::new(),
+            metadata: Default::default(),
+        }
+    }
+
+    fn lease(mac: &str) -> DHCPLease {
+        DHCPLease {
+            mac: mac.to_string(),
+            ip: "10.0.0.10".to_string(),
+            hostname: None,
+            expires_at: None,
+            state: "active".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_find_orphan_overlays() {
+        let overlays = vec![
+            overlay("o1", "vm1", "AA:BB:CC:DD:EE:01"),
+            overlay("o2", "gone", "aa:bb:cc:dd:ee:02"),
+            overlay("o3", "vm1", "aa:bb:cc:dd:ee:03"),
+        ];
+        let vms = vec![vm("vm1")];
+        let now = DateTime::parse_from_rfc3339("2024-05-14T20:00:00Z").unwrap().with_timezone(&Utc);
+        let mut expired = lease("aa:bb:cc:dd:ee:03");
+        expired.expires_at = Some("2024/05/13 18:00:00".to_string());
+        let mut current = lease("aa:bb:cc:dd:ee:01");
+        current.expires_at = Some("2024-05-14T20:02:11Z".to_string());
+        let leases = vec![current, lease("aa:bb:cc:dd:ee:02"), expired];
+
+        let orphans: Vec<&str> = find_orphan_overlays(&overlays, &vms, &leases, now).iter().map(|o| o.id.as_str()).collect();
+        assert_eq!(orphans, vec!["o2", "o3"]);
+        assert_eq!(orphan_reason(&overlays[1], &vms, &leases, now), Some(OrphanReason::MissingVm));
+        assert_eq!(orphan_reason(&overlays[2], &vms, &leases, now), Some(OrphanReason::NoRecentLease));
+
+        // Sans leases, seul le critère de la VM s'applique
+        let orphans = find_orphan_overlays(&overlays, &vms, &[], now);
+        assert_eq!(orphans.len(), 1);
+    }
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+
+/// Profil de connexion à un serveur RMDB
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApiProfile {
+    pub name: String,
+    pub url: String,
+    /// Token envoyé en `Authorization: Bearer` (stocké en clair dans tui.json)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+/// Profils de connexion enregistrés et profil actif
+///
+/// Sans profil actif, l'URL vient de `api_url` (tui.json, RMDB_API_URL, `--api-url`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct Profiles {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub active: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub entries: Vec<ApiProfile>,
+}
+
+impl Profiles {
+    pub fn is_empty(&self) -> bool {
+        self.active.is_none() && self.entries.is_empty()
+    }
+
+    /// Profil actif ; un nom actif sans profil correspondant est ignoré
+    pub fn active(&self) -> Option<&ApiProfile> {
+        let name = self.active.as_deref()?;
+        self.entries.iter().find(|p| p.name == name)
+    }
+
+    pub fn active_index(&self) -> Option<usize> {
+        let name = self.active.as_deref()?;
+        self.entries.iter().position(|p| p.name == name)
+    }
+
+    /// Ajoute un profil, ou remplace celui de même nom
+    pub fn add(&mut self, profile: ApiProfile) {
+        match self.entries.iter_mut().find(|p| p.name == profile.name) {
+            Some(existing) => *existing = profile,
+            None => self.entries.push(profile),
+        }
+    }
+
+    /// Supprime un profil ; s'il était actif, plus aucun profil ne l'est
+    pub fn remove(&mut self, name: &str) {
+        self.entries.retain(|p| p.name != name);
+        if self.active.as_deref() == Some(name) {
+            self.active = None;
+        }
+    }
+
+    /// Active un profil existant (`None` : revenir à `api_url`)
+    pub fn select(&mut self, name: Option<&str>) -> bool {
+        match name {
+            Some(name) if !self.entries.iter().any(|p| p.name == name) => false,
+            _ => {
+                self.active = name.map(str::to_string);
+                true
+            }
+        }
+    }
+}
+
+/// Vérifie une URL de profil (schéma http ou https, hôte non vide)
+pub fn validate_profile_url(url: &str) -> Result<(), String> {
+    let rest = url
+        .strip_prefix("http://")
+        .or_else(|| url.strip_prefix("https://"))
+        .ok_or_else(|| format!("URL sans http:// ni https:// : {}", url))?;
+    if rest.trim_end_matches('/').is_empty() || rest.contains(char::is_whitespace) {
+        return Err(format!("URL invalide: {}", url));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles_add_select_remove() {
+        let mut profiles = Profiles::default();
+        let profile = |name: &str, url: &str| ApiProfile { name: name.to_string(), url: url.to_string(), token: None };
+        profiles.add(profile("labo", "http://labo:8080"));
+        profiles.add(profile("prod", "https://pxe.example:8443"));
+        profiles.add(profile("labo", "http://labo2:8080"));
+        assert_eq!(profiles.entries.len(), 2);
+
+        assert!(!profiles.select(Some("absent")));
+        assert!(profiles.select(Some("labo")));
+        assert_eq!(profiles.active().unwrap().url, "http://labo2:8080");
+        assert_eq!(profiles.active_index(), Some(0));
+
+        profiles.remove("labo");
+        assert!(profiles.active().is_none());
+        assert!(profiles.active.is_none());
+
+        assert!(validate_profile_url("https://pxe.example:8443").is_ok());
+        assert!(validate_profile_url("pxe:8080").is_err());
+        assert!(validate_profile_url("http://").is_err());
+    }
+}
@@ -0,0 +1,238 @@
+use crate::pres::executor::Executor;
+use crate::pres::utils::format_size;
+use serde::Serialize;
+use std::fmt;
+
+/// Informations de l'hôte, analysées à partir de `hostname`, `/proc/uptime`, `nproc` et `free -b`
+///
+/// Les champs restent à `None` quand la commande correspondante échoue ou que sa sortie
+/// n'est pas reconnue.
+#[derive(Debug, Clone, Default, Serialize, PartialEq)]
+pub struct SystemInfo {
+    pub hostname: Option<String>,
+    pub uptime_secs: Option<u64>,
+    pub cpu_count: Option<u32>,
+    /// Mémoire en octets
+    pub mem_total: Option<u64>,
+    pub mem_used: Option<u64>,
+    pub mem_free: Option<u64>,
+}
+
+/// Mémoire lue dans la ligne `Mem:` de `free`
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MemoryUsage {
+    pub total: Option<u64>,
+    pub used: Option<u64>,
+    pub free: Option<u64>,
+}
+
+impl SystemInfo {
+    pub fn collect(executor: &dyn Executor) -> Self {
+        let stdout = |cmd: &str| executor.run_shell(cmd, false).ok().filter(|o| o.exit_code == Some(0)).map(|o| o.stdout);
+
+        let memory = stdout("free -b").map(|out| parse_free(&out)).unwrap_or_default();
+        Self {
+            hostname: stdout("hostname")
+                .map(|out| out.trim().to_string())
+                .filter(|name| !name.is_empty()),
+            uptime_secs: stdout("cat /proc/uptime").and_then(|out| parse_uptime(&out)),
+            cpu_count: stdout("nproc").and_then(|out| out.trim().parse().ok()),
+            mem_total: memory.total,
+            mem_used: memory.used,
+            mem_free: memory.free,
+        }
+    }
+
+    /// Lignes `(libellé, valeur)` pour l'affichage
+    pub fn rows(&self) -> Vec<(&'static str, String)> {
+        let na = || "N/A".to_string();
+        let size = |bytes: Option<u64>| bytes.map(format_size).unwrap_or_else(na);
+        vec![
+            ("Hostname", self.hostname.clone().unwrap_or_else(na)),
+            ("Uptime", self.uptime_secs.map(format_uptime).unwrap_or_else(na)),
+            ("CPU", self.cpu_count.map(|n| n.to_string()).unwrap_or_else(na)),
+            (
+                "Mémoire",
+                format!("{} utilisés / {} (libre: {})", size(self.mem_used), size(self.mem_total), size(self.mem_free)),
+            ),
+        ]
+    }
+}
+
+impl fmt::Display for SystemInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (label, value) in self.rows() {
+            writeln!(f, "{:<10}{}", format!("{}:", label), value)?;
+        }
+        Ok(())
+    }
+}
+
+/// Espace estimé pour créer le container Alpine et y installer RMDB (`/var/lib/lxc`)
+pub const CONTAINER_SPACE_ESTIMATE: u64 = 1 << 30;
+/// Espace estimé pour compiler et installer rmdbd sur l'hôte (`/usr/local`)
+pub const HOST_BUILD_SPACE_ESTIMATE: u64 = 512 << 20;
+/// Espace estimé pour les données initiales de RMDB sur l'hôte (`/var/lib/rmdb`)
+pub const HOST_DATA_SPACE_ESTIMATE: u64 = 256 << 20;
+
+/// Espace disponible sur un point de montage, comparé au besoin estimé d'une opération
+#[derive(Debug, Clone, PartialEq)]
+pub struct FreeSpace {
+    pub mount: String,
+    pub available: u64,
+    pub required: u64,
+}
+
+impl FreeSpace {
+    pub fn is_low(&self) -> bool {
+        self.available < self.required
+    }
+}
+
+/// Espace libre pour chaque besoin `(chemin, octets)`, regroupé par point de montage
+///
+/// Un chemin absent est mesuré sur son premier parent existant ; les besoins d'un même
+/// point de montage s'additionnent. Un chemin dont `df` échoue est ignoré.
+pub fn check_free_space(executor: &dyn Executor, needs: &[(&str, u64)]) -> Vec<FreeSpace> {
+    let mut spaces: Vec<FreeSpace> = Vec::new();
+    for &(path, required) in needs {
+        let cmd = format!(
+            "p='{}'; while [ ! -e \"$p\" ] && [ \"$p\" != / ]; do p=$(dirname \"$p\"); done; df -k --output=avail,target \"$p\"",
+            path.replace('\'', "")
+        );
+        let Some((available, mount)) = executor.run_shell(&cmd, false).ok().and_then(|o| parse_df(&o.stdout)) else {
+            continue;
+        };
+        match spaces.iter_mut().find(|space| space.mount == mount) {
+            Some(space) => space.required += required,
+            None => spaces.push(FreeSpace { mount, available, required }),
+        }
+    }
+    spaces
+}
+
+/// Espace disponible (octets) et point de montage, depuis `df -k --output=avail,target`
+pub fn parse_df(output: &str) -> Option<(u64, String)> {
+    let line = output.lines().skip(1).filter(|l| !l.trim().is_empty()).last()?;
+    // Un seul découpage : le point de montage, en dernier, peut contenir des espaces
+    let (available_kib, mount) = line.trim_start().split_once(char::is_whitespace)?;
+    let available_kib: u64 = available_kib.parse().ok()?;
+    Some((available_kib * 1024, mount.trim().to_string()))
+}
+
+/// Analyse la sortie de `free` (`-b`, `-k`, `-h`...)
+///
+/// Les colonnes sont repérées par l'en-tête (`total`, `used`, `free`) plutôt que par position,
+/// car procps ancien (`buffers cached`), procps récent (`buff/cache available`) et BusyBox
+/// n'alignent pas les mêmes colonnes. La ligne de données peut être `Mem:` ou `Mém.:` selon la locale.
+pub fn parse_free(output: &str) -> MemoryUsage {
+    let mut lines = output.lines().filter(|l| !l.trim().is_empty());
+    let Some(header) = lines.next() else {
+        return MemoryUsage::default();
+    };
+    let columns: Vec<String> = header.split_whitespace().map(|c| c.to_lowercase()).collect();
+    let Some(row) = lines.find(|l| l.trim_start().to_lowercase().starts_with("m")) else {
+        return MemoryUsage::default();
+    };
+    // Premier champ : libellé de la ligne
+    let values: Vec<&str> = row.split_whitespace().skip(1).collect();
+    let column = |names: &[&str]| {
+        columns
+            .iter()
+            .position(|c| names.contains(&c.as_str()))
+            .and_then(|i| values.get(i))
+            .and_then(|v| parse_size(v))
+    };
+    MemoryUsage {
+        total: column(&["total"]),
+        used: column(&["used", "utilisé", "utilisée"]),
+        free: column(&["free", "libre"]),
+    }
+}
+
+/// Taille brute (`8053063680`) ou lisible (`7,5Gi`, `512M`, `980K`) en octets
+pub fn parse_size(text: &str) -> Option<u64> {
+    let text = text.trim().replace(',', ".");
+    let split = text.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(text.len());
+    let (number, unit) = text.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let multiplier: u64 = match unit.trim_end_matches(['i', 'B', 'o']) {
+        "" => 1,
+        "K" | "k" => 1 << 10,
+        "M" => 1 << 20,
+        "G" => 1 << 30,
+        "T" => 1 << 40,
+        _ => return None,
+    };
+    Some((number * multiplier as f64).round() as u64)
+}
+
+/// Secondes de fonctionnement, premier champ de `/proc/uptime`
+pub fn parse_uptime(output: &str) -> Option<u64> {
+    output.split_whitespace().next()?.parse::<f64>().ok().map(|secs| secs as u64)
+}
+
+/// Durée de fonctionnement lisible, ex: `3 j 04 h 12 min`
+pub fn format_uptime(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86_400, secs % 86_400 / 3600, secs % 3600 / 60);
+    if days > 0 {
+        format!("{} j {:02} h {:02} min", days, hours, minutes)
+    } else {
+        format!("{} h {:02} min", hours, minutes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pres::executor::MockExecutor;
+
+    #[test]
+    fn test_parse_free_layouts() {
+        let procps = "               total        used        free      shared  buff/cache   available\n\
+                      Mem:      8053063680  2147483648  4294967296   104857600  1610612736  5637144576\n\
+                      Swap:     2147479552           0  2147479552\n";
+        let old = "             total       used       free     shared    buffers     cached\n\
+                   Mem:          7,5Gi       6Gi       1,5Gi        0B       120M       2G\n\
+                   -/+ buffers/cache:      3G       4G\n";
+        let busybox_no_shared = "        total   used   free\nMem:    1024    512    512\n";
+
+        let info = parse_free(procps);
+        assert_eq!(info.total, Some(8053063680));
+        assert_eq!(info.used, Some(2147483648));
+        assert_eq!(info.free, Some(4294967296));
+        assert_eq!(parse_free(old).free, Some(1610612736));
+        assert_eq!(parse_free(busybox_no_shared).used, Some(512));
+        assert_eq!(parse_free(""), MemoryUsage::default());
+    }
+
+    #[test]
+    fn test_check_free_space() {
+        let df = " Avail Mounted on\n524288 /\n";
+        assert_eq!(parse_df(df), Some((512 << 20, "/".to_string())));
+        assert_eq!(parse_df(" Avail Mounted on\n  2048 /mnt/disque externe\n"), Some((2 << 20, "/mnt/disque externe".to_string())));
+        assert_eq!(parse_df(" Avail Mounted on\n"), None);
+
+        let executor = MockExecutor::new().on("df -k --output=avail,target", df);
+        let spaces = check_free_space(&executor, &[("/usr/local", HOST_BUILD_SPACE_ESTIMATE), ("/var/lib/rmdb", HOST_DATA_SPACE_ESTIMATE)]);
+        assert_eq!(spaces.len(), 1);
+        assert_eq!(spaces[0].required, HOST_BUILD_SPACE_ESTIMATE + HOST_DATA_SPACE_ESTIMATE);
+        assert!(spaces[0].is_low());
+    }
+
+    #[test]
+    fn test_collect_with_mock() {
+        let executor = MockExecutor::new()
+            .on("hostname", "pxe-01\n")
+            .on("/proc/uptime", "273600.52 1000.00\n")
+            .on("nproc", "4\n")
+            .on("free", "  total used free\nMem: 2048 1024 1024\n");
+        let info = SystemInfo::collect(&executor);
+        assert_eq!(info.hostname.as_deref(), Some("pxe-01"));
+        assert_eq!(info.uptime_secs, Some(273600));
+        assert_eq!(info.cpu_count, Some(4));
+        assert_eq!(info.mem_total, Some(2048));
+        assert!(info.to_string().contains("3 j 04 h 00 min"));
+        assert!(serde_json::to_string(&info).unwrap().contains("\"cpu_count\":4"));
+    }
+}
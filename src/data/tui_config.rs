@@ -0,0 +1,228 @@
+use crate::data::cache_file::{load_json, save_json_private};
+use crate::data::deploy_timings::DeployTimings;
+use crate::data::favorites::config_dir;
+use crate::data::profiles::Profiles;
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::path::PathBuf;
+
+/// URL de l'API RMDB sans configuration
+pub const DEFAULT_API_URL: &str = "http://localhost:8080";
+/// Container visé par les raccourcis RMDB sans configuration
+pub const DEFAULT_CONTAINER: &str = "rmdb";
+
+/// Préférences de l'interface terminal
+///
+/// Ordre de priorité : options de la ligne de commande, puis variables d'environnement,
+/// puis `~/.config/rmdb/tui.json`. Les champs absents gardent la valeur par défaut.
+/// Une URL passée par RMDB_API_URL ou `--api-url` désactive le profil actif pour la session.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct TuiConfig {
+    /// URL de l'API RMDB (RMDB_API_URL, `--api-url`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
+    /// Container visé par les raccourcis (RMDB_CONTAINER)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub container: Option<String>,
+    /// Miroir Alpine du déploiement LXC (RMDB_ALPINE_MIRROR)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub alpine_mirror: Option<String>,
+    /// Répertoire `rmdb_source` essayé avant les emplacements habituels (RMDB_SOURCE_DIR)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_dir: Option<String>,
+    /// Niveau minimal des journaux de déploiement (RMDB_LOG_LEVEL)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub log_level: Option<String>,
+    /// Seuil de la disposition compacte, ex: `60x20` (RMDB_MIN_SIZE)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_size: Option<String>,
+    /// Touches vim (RMDB_VIM_KEYS)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vim_keys: Option<bool>,
+    /// Console de diagnostic (RMDB_EXPERT)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expert: Option<bool>,
+    /// Sans pauses décoratives (RMDB_NO_ANIM, `--no-anim`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_anim: Option<bool>,
+    /// Rendu ASCII forcé (`--ascii`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ascii: Option<bool>,
+    /// Éditeur externe de la configuration, prioritaire sur VISUAL/EDITOR (RMDB_EDITOR)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub editor: Option<String>,
+    /// `sudo -k` en quittant le mode Admin (RMDB_SUDO_RESET)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sudo_reset_on_exit: Option<bool>,
+    /// Débit maximal proposé pour le téléchargement du template LXC, en Ko/s (RMDB_RATE_LIMIT)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<u32>,
+    /// Attentes et tentatives du déploiement LXC, en millisecondes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deploy_timings: Option<DeployTimings>,
+    /// Mode d'utilisation de l'installation sur l'hôte (`desktop`, `web`, `tui`), choisi par « Changer le mode d'utilisation »
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub installation_mode: Option<String>,
+    /// Profils de connexion ; le profil actif remplace `api_url`
+    #[serde(skip_serializing_if = "Profiles::is_empty")]
+    pub profiles: Profiles,
+}
+
+/// Fichier des préférences (`$XDG_CONFIG_HOME/rmdb/tui.json` ou `~/.config/rmdb/tui.json`)
+pub fn config_path() -> PathBuf {
+    config_dir().join("tui.json")
+}
+
+impl TuiConfig {
+    /// Fichier de préférences, complété par les variables d'environnement
+    pub fn load() -> Self {
+        Self::load_file().apply_env(|name| std::env::var(name).ok())
+    }
+
+    /// Contenu du fichier seul ; configuration vide s'il est absent ou corrompu (signalé dans `tui.log`)
+    pub fn load_file() -> Self {
+        load_json(&config_path()).unwrap_or_default()
+    }
+
+    /// Écrit le fichier en 0600 : il peut contenir les tokens des profils
+    pub fn save(&self) -> io::Result<PathBuf> {
+        let path = config_path();
+        save_json_private(&path, self)?;
+        Ok(path)
+    }
+
+    /// Remplace les valeurs par celles des variables d'environnement définies
+    pub fn apply_env(mut self, var: impl Fn(&str) -> Option<String>) -> Self {
+        let text = |name: &str| var(name).map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        let flag = |name: &str| var(name).map(|v| v == "1" || v.eq_ignore_ascii_case("true"));
+
+        if let Some(value) = text("RMDB_API_URL") {
+            self.api_url = Some(value);
+            self.profiles.active = None;
+        }
+        if let Some(value) = text("RMDB_CONTAINER") {
+            self.container = Some(value);
+        }
+        if let Some(value) = text("RMDB_ALPINE_MIRROR") {
+            self.alpine_mirror = Some(value);
+        }
+        if let Some(value) = text("RMDB_SOURCE_DIR") {
+            self.source_dir = Some(value);
+        }
+        if let Some(value) = text("RMDB_LOG_LEVEL") {
+            self.log_level = Some(value);
+        }
+        if let Some(value) = text("RMDB_MIN_SIZE") {
+            self.min_size = Some(value);
+        }
+        if let Some(value) = text("RMDB_EDITOR") {
+            self.editor = Some(value);
+        }
+        if let Some(value) = text("RMDB_RATE_LIMIT").and_then(|v| v.parse().ok()) {
+            self.rate_limit = Some(value);
+        }
+        if let Some(value) = flag("RMDB_VIM_KEYS") {
+            self.vim_keys = Some(value);
+        }
+        if let Some(value) = flag("RMDB_EXPERT") {
+            self.expert = Some(value);
+        }
+        if let Some(value) = flag("RMDB_NO_ANIM") {
+            self.no_anim = Some(value);
+        }
+        if let Some(value) = flag("RMDB_SUDO_RESET") {
+            self.sudo_reset_on_exit = Some(value);
+        }
+        self
+    }
+
+    /// Applique et retire de `args` les options `--ascii`, `--no-anim` et `--api-url <url>`
+    pub fn apply_args(mut self, args: &mut Vec<String>) -> Self {
+        let mut remaining = Vec::with_capacity(args.len());
+        let mut iter = args.drain(..);
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--ascii" => self.ascii = Some(true),
+                "--no-anim" => self.no_anim = Some(true),
+                "--api-url" => {
+                    if let Some(url) = iter.next() {
+                        self.api_url = Some(url);
+                        self.profiles.active = None;
+                    }
+                }
+                _ => match arg.strip_prefix("--api-url=") {
+                    Some(url) => {
+                        self.api_url = Some(url.to_string());
+                        self.profiles.active = None;
+                    }
+                    None => remaining.push(arg),
+                },
+            }
+        }
+        drop(iter);
+        *args = remaining;
+        self
+    }
+
+    /// URL de l'API : profil actif, sinon `api_url`, sinon `DEFAULT_API_URL`
+    pub fn api_url(&self) -> String {
+        self.profiles
+            .active()
+            .map(|profile| profile.url.clone())
+            .or_else(|| self.api_url.clone())
+            .unwrap_or_else(|| DEFAULT_API_URL.to_string())
+    }
+
+    /// Token du profil actif
+    pub fn api_token(&self) -> Option<String> {
+        self.profiles.active().and_then(|profile| profile.token.clone())
+    }
+
+    pub fn container(&self) -> String {
+        self.container.clone().unwrap_or_else(|| DEFAULT_CONTAINER.to_string())
+    }
+
+    pub fn deploy_timings(&self) -> DeployTimings {
+        self.deploy_timings.unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_and_args_override_file() {
+        let file: TuiConfig = serde_json::from_str(r#"{"api_url": "http://pxe:8080", "container": "labo", "vim_keys": true}"#).unwrap();
+        let env = |name: &str| match name {
+            "RMDB_API_URL" => Some("http://env:9090".to_string()),
+            "RMDB_VIM_KEYS" => Some("0".to_string()),
+            "RMDB_CONTAINER" => Some("  ".to_string()),
+            "RMDB_RATE_LIMIT" => Some("512".to_string()),
+            _ => None,
+        };
+        let config = file.apply_env(env);
+        assert_eq!(config.api_url(), "http://env:9090");
+        assert_eq!(config.container(), "labo");
+        assert_eq!(config.vim_keys, Some(false));
+        assert_eq!(config.rate_limit, Some(512));
+
+        let mut args: Vec<String> = ["rmdb", "--api-url", "http://cli:1", "--ascii", "--gui"].iter().map(|s| s.to_string()).collect();
+        let config = config.apply_args(&mut args);
+        assert_eq!(config.api_url(), "http://cli:1");
+        assert_eq!(config.ascii, Some(true));
+        assert_eq!(args, ["rmdb", "--gui"]);
+
+        assert_eq!(TuiConfig::default().api_url(), DEFAULT_API_URL);
+
+        let with_profile: TuiConfig = serde_json::from_str(
+            r#"{"api_url": "http://pxe:8080", "profiles": {"active": "prod", "entries": [{"name": "prod", "url": "https://prod:8443", "token": "t"}]}}"#,
+        ).unwrap();
+        assert_eq!(with_profile.api_url(), "https://prod:8443");
+        assert_eq!(with_profile.api_token().as_deref(), Some("t"));
+        let mut args: Vec<String> = vec!["--api-url=http://cli:1".to_string()];
+        assert_eq!(with_profile.apply_args(&mut args).api_url(), "http://cli:1");
+        assert_eq!(serde_json::to_string(&TuiConfig::default()).unwrap(), "{}");
+    }
+}
@@ -1,5 +1,81 @@
-use crate::pres::executor::{CommandExecutor, CommandOutput, ExecError};
-use crate::data::distribution::DistributionInfo;
+use crate::pres::executor::{CommandExecutor, CommandOutput, ExecError, Executor};
+use crate::data::distribution::{DistributionInfo, LinuxDistribution};
+
+/// Ports réseau requis par RMDB : DNS, DHCP, TFTP, API HTTP et NBD
+pub const RMDB_REQUIRED_PORTS: &[(u16, &str)] = &[
+    (53, "udp"),
+    (53, "tcp"),
+    (67, "udp"),
+    (69, "udp"),
+    (8080, "tcp"),
+    (10809, "tcp"),
+];
+
+/// Fichier PID de rmdbd lancé sans système d'init
+pub const RMDB_PID_FILE: &str = "/run/rmdbd.pid";
+const RMDB_BINARY: &str = "/usr/local/bin/rmdbd";
+const RMDB_CONFIG_FILE: &str = "/etc/rmdbd/config.json";
+const RMDB_DIRECT_LOG: &str = "/var/log/rmdbd.log";
+
+/// Répertoires de données créés par l'installation
+pub const RMDB_DATA_DIRS: &[&str] = &[
+    "/var/lib/rmdb/www",
+    "/var/lib/rmdb/tftpboot",
+    "/var/lib/rmdb/images",
+    "/var/lib/rmdb/vms",
+    "/var/lib/rmdb/overlays",
+    "/var/lib/rmdb/backups",
+    "/var/lib/rmdb/audits",
+    "/var/lib/rmdb/checksums",
+    "/var/lib/rmdb/metrics",
+];
+const RMDB_SYSTEMD_UNIT: &str = "/etc/systemd/system/rmdbd.service";
+const RMDB_OPENRC_SCRIPT: &str = "/etc/init.d/rmdbd";
+
+/// Un point de contrôle de l'installation sur l'hôte
+#[derive(Debug, Clone)]
+pub struct IntegrityCheck {
+    pub label: &'static str,
+    pub passed: bool,
+    pub detail: String,
+}
+
+impl IntegrityCheck {
+    fn new(label: &'static str, problems: Vec<String>, ok_detail: &str) -> Self {
+        Self {
+            label,
+            passed: problems.is_empty(),
+            detail: if problems.is_empty() { ok_detail.to_string() } else { problems.join(" ; ") },
+        }
+    }
+}
+
+/// Pare-feu actif détecté sur le système hôte
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FirewallBackend {
+    Firewalld,
+    Ufw,
+    Nftables,
+}
+
+impl FirewallBackend {
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            FirewallBackend::Firewalld => "firewalld",
+            FirewallBackend::Ufw => "ufw",
+            FirewallBackend::Nftables => "nftables",
+        }
+    }
+}
+
+/// Résultat de l'ouverture des ports du pare-feu
+#[derive(Debug, Clone)]
+pub struct FirewallReport {
+    pub backend: FirewallBackend,
+    pub opened: Vec<String>,
+    pub already_open: Vec<String>,
+    pub failed: Vec<String>,
+}
 
 /// Gestion de l'installation et de la configuration de RMDB sur le système hôte
 pub struct HostDeployment {
@@ -56,8 +132,62 @@ impl HostDeployment {
         }
     }
 
+    /// Vérifie une installation existante : binaire, configuration, service et répertoires de données
+    ///
+    /// Contrairement à `check_rmdb_installed`, chaque point est rapporté séparément.
+    pub fn verify_install(&self, executor: &CommandExecutor) -> Vec<IntegrityCheck> {
+        let run = |cmd: &str| executor.run_shell(cmd, false).map(|o| o.stdout).unwrap_or_default();
+        let mut checks = Vec::new();
+
+        let binary = run(&format!("stat -c '%n %a %U' {} 2>/dev/null", RMDB_BINARY));
+        let mut problems = Vec::new();
+        match parse_stat_line(&binary) {
+            None => problems.push(format!("{} absent", RMDB_BINARY)),
+            Some((_, mode, _)) if mode & 0o111 == 0 => problems.push(format!("{} non exécutable ({:o})", RMDB_BINARY, mode)),
+            Some(_) => {}
+        }
+        checks.push(IntegrityCheck::new("Binaire rmdbd", problems, RMDB_BINARY));
+
+        let content = run(&format!("cat {} 2>/dev/null", RMDB_CONFIG_FILE));
+        let problems = match serde_json::from_str::<serde_json::Value>(&content) {
+            _ if content.trim().is_empty() => vec![format!("{} absent ou vide", RMDB_CONFIG_FILE)],
+            Ok(value) if value.is_object() => Vec::new(),
+            Ok(_) => vec![format!("{} n'est pas un objet JSON", RMDB_CONFIG_FILE)],
+            Err(e) => vec![format!("JSON invalide: {}", e)],
+        };
+        checks.push(IntegrityCheck::new("Configuration", problems, RMDB_CONFIG_FILE));
+
+        let (unit, needs_exec) = if executor.capabilities().has_systemctl {
+            (RMDB_SYSTEMD_UNIT, false)
+        } else {
+            (RMDB_OPENRC_SCRIPT, true)
+        };
+        let mut problems = Vec::new();
+        match parse_stat_line(&run(&format!("stat -c '%n %a %U' {} 2>/dev/null", unit))) {
+            None => problems.push(format!("{} absent", unit)),
+            Some((_, mode, _)) if needs_exec && mode & 0o111 == 0 => problems.push(format!("{} non exécutable", unit)),
+            Some(_) => {}
+        }
+        checks.push(IntegrityCheck::new("Service", problems, unit));
+
+        let stat = run(&format!("stat -c '%n %a %U' {} 2>/dev/null", RMDB_DATA_DIRS.join(" ")));
+        checks.push(IntegrityCheck::new(
+            "Répertoires de données",
+            directory_problems(&stat, RMDB_DATA_DIRS),
+            &format!("{} répertoires présents", RMDB_DATA_DIRS.len()),
+        ));
+
+        checks
+    }
+
     /// Installe RMDB sur le système hôte
     pub fn install_rmdb(&self, executor: &CommandExecutor, rmdb_source_path: &str) -> Result<CommandOutput, ExecError> {
+        self.install_rmdb_with_progress(executor, rmdb_source_path, &mut |_| {})
+    }
+
+    /// Comme `install_rmdb`, en transmettant la sortie du téléchargement des modules et
+    /// de la compilation ligne par ligne (estimation de progression)
+    pub fn install_rmdb_with_progress(&self, executor: &CommandExecutor, rmdb_source_path: &str, on_line: &mut dyn FnMut(&str)) -> Result<CommandOutput, ExecError> {
         // Vérifier que le répertoire source existe
         let source_path = std::path::Path::new(rmdb_source_path);
         if !source_path.exists() {
@@ -81,15 +211,15 @@ impl HostDeployment {
         executor.run_shell(&copy_cmd, true)?;
 
         // Étape 3: Télécharger les dépendances Go
-        let go_mod_cmd = format!("cd {} && go mod download", build_dir);
-        executor.run_shell(&go_mod_cmd, false)?;
+        let go_mod_cmd = format!("cd {} && go mod download -x", build_dir);
+        executor.run_shell_lines(&go_mod_cmd, false, on_line)?;
 
         // Étape 4: Compiler RMDB
         let build_cmd = format!(
-            "cd {}/cmd/rmdbd && CGO_ENABLED=0 go build -trimpath -ldflags \"-s -w\" -o /usr/local/bin/rmdbd . && chmod +x /usr/local/bin/rmdbd",
+            "cd {}/cmd/rmdbd && CGO_ENABLED=0 go build -v -trimpath -ldflags \"-s -w\" -o /usr/local/bin/rmdbd . && chmod +x /usr/local/bin/rmdbd",
             build_dir
         );
-        let build_result = executor.run_shell(&build_cmd, true)?;
+        let build_result = executor.run_shell_lines(&build_cmd, true, on_line)?;
 
         // Étape 5: Créer la configuration
         let config_cmd = format!(
@@ -99,8 +229,7 @@ impl HostDeployment {
         executor.run_shell(&config_cmd, true)?;
 
         // Étape 6: Créer les répertoires de données
-        let data_dirs = "/var/lib/rmdb/www /var/lib/rmdb/tftpboot /var/lib/rmdb/images /var/lib/rmdb/vms /var/lib/rmdb/overlays /var/lib/rmdb/backups /var/lib/rmdb/audits /var/lib/rmdb/checksums /var/lib/rmdb/metrics";
-        let mkdir_data_cmd = format!("mkdir -p {}", data_dirs);
+        let mkdir_data_cmd = format!("mkdir -p {}", RMDB_DATA_DIRS.join(" "));
         executor.run_shell(&mkdir_data_cmd, true)?;
 
         // Étape 7: Créer le service systemd ou OpenRC
@@ -174,8 +303,10 @@ depend() {
 
         if has_systemd {
             executor.run_shell("systemctl start rmdbd", true)
-        } else {
+        } else if executor.capabilities().has_rc_service {
             executor.run_shell("rc-service rmdbd start", true)
+        } else {
+            self.start_rmdb_direct(executor)
         }
     }
 
@@ -187,8 +318,10 @@ depend() {
 
         if has_systemd {
             executor.run_shell("systemctl stop rmdbd", true)
-        } else {
+        } else if executor.capabilities().has_rc_service {
             executor.run_shell("rc-service rmdbd stop", true)
+        } else {
+            self.stop_rmdb_direct(executor)
         }
     }
 
@@ -200,8 +333,10 @@ depend() {
 
         if has_systemd {
             executor.run_shell("systemctl restart rmdbd", true)
-        } else {
+        } else if executor.capabilities().has_rc_service {
             executor.run_shell("rc-service rmdbd restart", true)
+        } else {
+            self.restart_rmdb_direct(executor)
         }
     }
 
@@ -215,13 +350,76 @@ depend() {
             let cmd = "systemctl is-active rmdbd 2>/dev/null || echo 'inactive'";
             let output = executor.run_shell(cmd, true)?;
             Ok(output.stdout.trim().to_string())
-        } else {
+        } else if executor.capabilities().has_rc_service {
             let cmd = "rc-service rmdbd status 2>/dev/null | grep -q 'started' && echo 'active' || echo 'inactive'";
             let output = executor.run_shell(cmd, true)?;
             Ok(output.stdout.trim().to_string())
+        } else {
+            Ok(if self.rmdb_direct_pid(executor).is_some() { "active" } else { "inactive" }.to_string())
         }
     }
 
+    /// Lance rmdbd en arrière-plan, sans systemd ni OpenRC, et note son PID dans `RMDB_PID_FILE`
+    ///
+    /// La sortie est redirigée vers le journal : la commande rend la main sans attendre rmdbd.
+    pub fn start_rmdb_direct(&self, executor: &dyn Executor) -> Result<CommandOutput, ExecError> {
+        if let Some(pid) = self.rmdb_direct_pid(executor) {
+            return Ok(CommandOutput {
+                stdout: format!("rmdbd déjà actif (PID {})", pid),
+                stderr: String::new(),
+                exit_code: Some(0),
+            });
+        }
+        // Une seconde d'attente permet de détecter un arrêt immédiat (configuration invalide...)
+        let cmd = format!(
+            "nohup {bin} -config {cfg} >>{log} 2>&1 </dev/null & echo $! > {pid}; sleep 1; \
+             if [ -d /proc/$(cat {pid}) ]; then echo \"rmdbd démarré (PID $(cat {pid}))\"; \
+             else rm -f {pid}; echo \"rmdbd s'est arrêté aussitôt, voir {log}\" >&2; exit 1; fi",
+            bin = RMDB_BINARY, cfg = RMDB_CONFIG_FILE, log = RMDB_DIRECT_LOG, pid = RMDB_PID_FILE
+        );
+        let output = executor.run_shell(&cmd, true)?;
+        if output.exit_code != Some(0) {
+            return Err(ExecError::Failed(output.stderr.trim().to_string()));
+        }
+        Ok(output)
+    }
+
+    /// Arrête rmdbd lancé par `start_rmdb_direct` (SIGTERM, puis SIGKILL après 5 s)
+    pub fn stop_rmdb_direct(&self, executor: &dyn Executor) -> Result<CommandOutput, ExecError> {
+        let Some(pid) = self.rmdb_direct_pid(executor) else {
+            let _ = executor.run_shell(&format!("rm -f {}", RMDB_PID_FILE), true);
+            return Ok(CommandOutput {
+                stdout: "rmdbd n'est pas actif".to_string(),
+                stderr: String::new(),
+                exit_code: Some(0),
+            });
+        };
+        let cmd = format!(
+            "kill {pid}; for i in 1 2 3 4 5 6 7 8 9 10; do [ -d /proc/{pid} ] || break; sleep 0.5; done; \
+             [ -d /proc/{pid} ] && kill -9 {pid}; rm -f {file}; echo 'rmdbd arrêté'",
+            pid = pid, file = RMDB_PID_FILE
+        );
+        executor.run_shell(&cmd, true)
+    }
+
+    /// Redémarre rmdbd lancé sans système d'init
+    pub fn restart_rmdb_direct(&self, executor: &dyn Executor) -> Result<CommandOutput, ExecError> {
+        self.stop_rmdb_direct(executor)?;
+        self.start_rmdb_direct(executor)
+    }
+
+    /// PID de rmdbd lancé sans système d'init, s'il tourne encore
+    ///
+    /// Le nom du processus est vérifié : un PID réutilisé par un autre programme est ignoré.
+    pub fn rmdb_direct_pid(&self, executor: &dyn Executor) -> Option<u32> {
+        let content = executor.run_shell(&format!("cat {} 2>/dev/null", RMDB_PID_FILE), false).ok()?;
+        let pid = parse_pid_file(&content.stdout)?;
+        let alive = executor.run_shell(&format!("grep -qx rmdbd /proc/{}/comm 2>/dev/null && echo alive", pid), false)
+            .map(|o| o.stdout.contains("alive"))
+            .unwrap_or(false);
+        alive.then_some(pid)
+    }
+
     /// Active le service RMDB au démarrage
     pub fn enable_rmdb(&self, executor: &CommandExecutor) -> Result<CommandOutput, ExecError> {
         let has_systemd = executor.run_shell("command -v systemctl >/dev/null 2>&1 && echo 'yes' || echo 'no'", false)
@@ -275,5 +473,137 @@ depend() {
             exit_code: Some(0),
         })
     }
+
+    /// Détecte le pare-feu actif (firewalld, ufw ou nftables)
+    pub fn detect_firewall(&self, executor: &CommandExecutor) -> Option<FirewallBackend> {
+        let caps = executor.capabilities();
+        let firewalld_active = caps.has_firewalld
+            && executor.run_shell("firewall-cmd --state 2>/dev/null", false)
+                .map(|o| o.stdout.trim() == "running")
+                .unwrap_or(false);
+        let ufw_active = caps.has_ufw
+            && executor.run_shell("ufw status 2>/dev/null", true)
+                .map(|o| o.stdout.contains("Status: active"))
+                .unwrap_or(false);
+
+        // Si plusieurs pare-feux sont actifs, privilégier celui de la distribution
+        let prefers_ufw = matches!(
+            self.distribution.distro,
+            LinuxDistribution::Debian | LinuxDistribution::Ubuntu
+        );
+        match (firewalld_active, ufw_active) {
+            (true, true) if prefers_ufw => return Some(FirewallBackend::Ufw),
+            (true, _) => return Some(FirewallBackend::Firewalld),
+            (false, true) => return Some(FirewallBackend::Ufw),
+            _ => {}
+        }
+
+        let nft_active = caps.has_nft
+            && executor.run_shell("nft list chain inet filter input >/dev/null 2>&1 && echo 'yes' || echo 'no'", true)
+                .map(|o| o.stdout.contains("yes"))
+                .unwrap_or(false);
+        if nft_active {
+            Some(FirewallBackend::Nftables)
+        } else {
+            None
+        }
+    }
+
+    /// Ouvre les ports donnés (port, protocole) dans le pare-feu actif
+    pub fn open_firewall_ports(&self, executor: &CommandExecutor, ports: &[(u16, &str)]) -> Result<FirewallReport, ExecError> {
+        let backend = self.detect_firewall(executor)
+            .ok_or_else(|| ExecError::MissingTool("Aucun pare-feu actif détecté (firewalld, ufw, nftables)".to_string()))?;
+
+        let mut report = FirewallReport {
+            backend,
+            opened: Vec::new(),
+            already_open: Vec::new(),
+            failed: Vec::new(),
+        };
+
+        for (port, proto) in ports {
+            let label = format!("{}/{}", port, proto);
+            let (check_cmd, open_cmd) = match backend {
+                FirewallBackend::Firewalld => (
+                    format!("firewall-cmd --query-port={} >/dev/null 2>&1 && echo 'open' || echo 'closed'", label),
+                    format!("firewall-cmd --add-port={0} && firewall-cmd --permanent --add-port={0}", label),
+                ),
+                FirewallBackend::Ufw => (
+                    format!("ufw status | grep -qE '^{}[[:space:]]+ALLOW' && echo 'open' || echo 'closed'", label),
+                    format!("ufw allow {}", label),
+                ),
+                FirewallBackend::Nftables => (
+                    format!("nft list chain inet filter input | grep -q '{} dport {} accept' && echo 'open' || echo 'closed'", proto, port),
+                    // En tête de chaîne : une règle ajoutée après un `drop` final ne serait jamais atteinte
+                    format!("nft insert rule inet filter input {} dport {} accept", proto, port),
+                ),
+            };
+
+            let is_open = executor.run_shell(&check_cmd, true)?.stdout.trim() == "open";
+            if is_open {
+                report.already_open.push(label);
+                continue;
+            }
+
+            match executor.run_shell(&open_cmd, true) {
+                Ok(output) if output.exit_code == Some(0) => report.opened.push(label),
+                _ => report.failed.push(label),
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Analyse une ligne `stat -c '%n %a %U'` : chemin, permissions (octal) et propriétaire
+fn parse_stat_line(line: &str) -> Option<(String, u32, String)> {
+    let mut parts = line.trim().rsplitn(3, ' ');
+    let owner = parts.next()?.to_string();
+    let mode = u32::from_str_radix(parts.next()?, 8).ok()?;
+    let path = parts.next()?.to_string();
+    Some((path, mode, owner))
+}
+
+/// Répertoires absents, n'appartenant pas à root ou modifiables par tous
+fn directory_problems(stat_output: &str, dirs: &[&str]) -> Vec<String> {
+    let entries: Vec<(String, u32, String)> = stat_output.lines().filter_map(parse_stat_line).collect();
+    dirs.iter()
+        .filter_map(|dir| match entries.iter().find(|(path, _, _)| path == dir) {
+            None => Some(format!("{} absent", dir)),
+            Some((_, _, owner)) if owner != "root" => Some(format!("{} appartient à {}", dir, owner)),
+            Some((_, mode, _)) if mode & 0o002 != 0 => Some(format!("{} modifiable par tous ({:o})", dir, mode)),
+            Some(_) => None,
+        })
+        .collect()
+}
+
+/// Lit le PID d'un fichier PID (première ligne, entier strictement positif)
+pub fn parse_pid_file(content: &str) -> Option<u32> {
+    content.lines().next()?.trim().parse().ok().filter(|pid| *pid > 0)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pid_file() {
+        assert_eq!(parse_pid_file("1234\n"), Some(1234));
+        assert_eq!(parse_pid_file(" 42 "), Some(42));
+        assert_eq!(parse_pid_file("0"), None);
+        assert_eq!(parse_pid_file(""), None);
+        assert_eq!(parse_pid_file("abc"), None);
+    }
+
+    #[test]
+    fn test_directory_problems() {
+        let stat = "/var/lib/rmdb/www 755 root\n/var/lib/rmdb/vms 777 root\n/var/lib/rmdb/images 750 rmdb\n";
+        let dirs = ["/var/lib/rmdb/www", "/var/lib/rmdb/vms", "/var/lib/rmdb/images", "/var/lib/rmdb/backups"];
+        let problems = directory_problems(stat, &dirs);
+        assert_eq!(problems.len(), 3);
+        assert!(problems[0].contains("modifiable par tous"));
+        assert!(problems[1].contains("appartient à rmdb"));
+        assert!(problems[2].ends_with("backups absent"));
+        assert_eq!(parse_stat_line("/usr/local/bin/rmdbd 755 root"), Some(("/usr/local/bin/rmdbd".to_string(), 0o755, "root".to_string())));
+    }
+}
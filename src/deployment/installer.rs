@@ -1,6 +1,6 @@
 /// Module principal d'installation de RMDB
 /// Gère l'installation sur host, container Alpine, et VM Rocky Linux
-use crate::pres::executor::{CommandExecutor, CommandOutput, ExecError};
+use crate::pres::executor::{CommandExecutor, CommandOutput, ExecError, Executor};
 use crate::data::distribution::DistributionInfo;
 use crate::deployment::logger::DeploymentLogger;
 
@@ -37,6 +37,16 @@ impl InstallationMode {
             InstallationMode::TerminalTUI => "tui",
         }
     }
+
+    /// Inverse de `short_name`
+    pub fn from_short_name(name: &str) -> Option<Self> {
+        match name.trim() {
+            "desktop" => Some(InstallationMode::DesktopGUI),
+            "web" => Some(InstallationMode::WebServer),
+            "tui" => Some(InstallationMode::TerminalTUI),
+            _ => None,
+        }
+    }
 }
 
 /// Type d'installation RMDB
@@ -62,6 +72,8 @@ pub struct InstallationConfig {
     pub install_go: bool,
     pub logger: Option<DeploymentLogger>,
     pub installation_mode: Option<InstallationMode>,
+    /// Paquets installés après RMDB, avec le gestionnaire de paquets de la cible
+    pub extra_packages: Vec<String>,
 }
 
 impl InstallationConfig {
@@ -77,6 +89,7 @@ impl InstallationConfig {
             install_go: true,
             logger: None,
             installation_mode: None,
+            extra_packages: Vec::new(),
         }
     }
 
@@ -119,20 +132,217 @@ impl InstallationConfig {
         self.installation_mode = Some(mode);
         self
     }
+
+    pub fn with_extra_packages(mut self, packages: Vec<String>) -> Self {
+        self.extra_packages = packages;
+        self
+    }
+}
+
+/// Liste de paquets saisie (séparés par des espaces), sans doublons
+///
+/// Les noms sont passés tels quels au shell : seuls lettres, chiffres et `.+-_:@=` sont acceptés.
+pub fn parse_package_list(input: &str) -> Result<Vec<String>, String> {
+    let mut packages: Vec<String> = Vec::new();
+    for name in input.split_whitespace() {
+        if !name.chars().all(|c| c.is_ascii_alphanumeric() || ".+-_:@=".contains(c)) || name.starts_with('-') {
+            return Err(format!("Nom de paquet invalide: {}", name));
+        }
+        if !packages.iter().any(|p| p == name) {
+            packages.push(name.to_string());
+        }
+    }
+    Ok(packages)
+}
+
+/// Bilan des paquets supplémentaires
+#[derive(Debug, Default, PartialEq)]
+pub struct ExtraPackagesReport {
+    pub installed: Vec<String>,
+    /// Paquet et première ligne de l'erreur
+    pub failed: Vec<(String, String)>,
+}
+
+impl ExtraPackagesReport {
+    /// Ajoute le bilan à la sortie d'une installation : réussites sur stdout, échecs sur stderr
+    fn append_to(&self, output: &mut CommandOutput) {
+        if !self.installed.is_empty() {
+            output.stdout.push_str(&format!("\nPaquets supplémentaires installés: {}", self.installed.join(", ")));
+        }
+        for (package, reason) in &self.failed {
+            if !output.stderr.is_empty() {
+                output.stderr.push('\n');
+            }
+            output.stderr.push_str(&format!("Paquet {} non installé: {}", package, reason));
+        }
+    }
+}
+
+/// Installe les paquets un par un avec `command(paquet)` ; un échec n'interrompt pas les suivants,
+/// seule l'annulation arrête la série
+pub fn install_packages_one_by_one(
+    executor: &dyn Executor,
+    packages: &[String],
+    command: impl Fn(&str) -> String,
+) -> Result<ExtraPackagesReport, ExecError> {
+    let mut report = ExtraPackagesReport::default();
+    for package in packages {
+        match executor.run_shell(&command(package), true) {
+            Ok(output) if output.exit_code == Some(0) => report.installed.push(package.clone()),
+            Ok(output) => {
+                let reason = output.stderr.lines().chain(output.stdout.lines()).rfind(|l| !l.trim().is_empty())
+                    .map(|l| l.trim().to_string())
+                    .unwrap_or_else(|| format!("code de sortie {:?}", output.exit_code));
+                report.failed.push((package.clone(), reason));
+            }
+            Err(ExecError::Cancelled) => return Err(ExecError::Cancelled),
+            Err(e) => report.failed.push((package.clone(), e.to_string())),
+        }
+    }
+    Ok(report)
+}
+
+/// Indice de progression reconnu dans une ligne de sortie d'installation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ProgressCue {
+    /// Position annoncée par l'outil (`(12/48) Installing ...` d'apk, `Building [==> ] 120/250` de cargo)
+    Fraction(usize, usize),
+    /// Une unité de travail terminée, sans total connu (`Compiling ...`, module ou paquet Go)
+    Tick,
+}
+
+/// Reconnaît les marqueurs de progression de apk, cargo et go (`go mod download -x`, `go build -v`)
+pub fn parse_progress_cue(line: &str) -> Option<ProgressCue> {
+    let line = line.trim();
+    let parse_fraction = |text: &str| -> Option<ProgressCue> {
+        let (done, total) = text.trim_end_matches(':').split_once('/')?;
+        Some(ProgressCue::Fraction(done.trim().parse().ok()?, total.trim().parse().ok()?))
+    };
+
+    if let Some(rest) = line.strip_prefix('(') {
+        if let Some(cue) = rest.split_once(')').and_then(|(fraction, _)| parse_fraction(fraction)) {
+            return Some(cue);
+        }
+    }
+    if line.starts_with("Building [") {
+        let after_bar = line.split_once(']').map(|(_, rest)| rest)?;
+        return after_bar.split_whitespace().next().and_then(parse_fraction);
+    }
+    if line.starts_with("Compiling ") || line.starts_with("Downloaded ") || line.starts_with("go: downloading ") {
+        return Some(ProgressCue::Tick);
+    }
+    // `go mod download -x` : une ligne par module récupéré
+    if line.starts_with("# get ") && line.contains(": 200 ") {
+        return Some(ProgressCue::Tick);
+    }
+    // `go build -v` : un chemin de paquet par ligne
+    if !line.is_empty() && !line.contains(char::is_whitespace) && line.contains('/') && !line.starts_with('/') {
+        return Some(ProgressCue::Tick);
+    }
+    None
 }
 
+/// Nombre d'unités sans total connu pour lesquelles l'estimation atteint 50 %
+const PROGRESS_HALF_WAY_TICKS: f64 = 30.0;
+
+/// Estimation grossière de l'avancement d'une commande à partir de sa sortie
+///
+/// L'estimation ne recule jamais et reste sous 100 % tant que la commande n'est pas terminée.
+#[derive(Debug, Default)]
+pub struct ProgressEstimator {
+    ticks: usize,
+    fraction: f64,
+}
+
+impl ProgressEstimator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Prend en compte une ligne ; renvoie la nouvelle fraction (0.0 à 1.0) si elle a progressé
+    pub fn feed(&mut self, line: &str) -> Option<f64> {
+        let candidate = match parse_progress_cue(line)? {
+            ProgressCue::Fraction(done, total) if total > 0 => done.min(total) as f64 / total as f64,
+            ProgressCue::Fraction(_, _) => return None,
+            ProgressCue::Tick => {
+                self.ticks += 1;
+                self.ticks as f64 / (self.ticks as f64 + PROGRESS_HALF_WAY_TICKS)
+            }
+        };
+        let candidate = candidate.min(0.99);
+        if candidate > self.fraction {
+            self.fraction = candidate;
+            Some(candidate)
+        } else {
+            None
+        }
+    }
+
+    pub fn fraction(&self) -> f64 {
+        self.fraction
+    }
+}
+
+/// Rappel de progression : étape en cours et avancement global estimé (0.0 à 1.0)
+pub type ProgressCallback<'a> = Box<dyn Fn(&str, f64) + 'a>;
+
 /// Gestionnaire d'installation principal
-pub struct RMDBInstaller {
+pub struct RMDBInstaller<'a> {
     config: InstallationConfig,
     distribution: DistributionInfo,
+    progress: Option<ProgressCallback<'a>>,
 }
 
-impl RMDBInstaller {
+impl<'a> RMDBInstaller<'a> {
     pub fn new(config: InstallationConfig) -> Self {
         Self {
             config,
             distribution: DistributionInfo::detect(),
+            progress: None,
+        }
+    }
+
+    pub fn with_progress(mut self, callback: impl Fn(&str, f64) + 'a) -> Self {
+        self.progress = Some(Box::new(callback));
+        self
+    }
+
+    fn report(&self, step: &str, fraction: f64) {
+        if let Some(ref progress) = self.progress {
+            progress(step, fraction);
+        }
+    }
+
+    /// Installe Rust et Go si demandé (0 à 30 % de l'avancement global)
+    fn install_toolchains(&self, executor: &CommandExecutor, target: &str) -> Result<(), ExecError> {
+        if self.config.install_rust {
+            self.report("Installation de Rust", 0.0);
+            self.log_info(&format!("Vérification/installation de Rust{}...", target));
+            let rust_installer = RustInstaller::new();
+            rust_installer.install(executor)?;
+        }
+
+        if self.config.install_go {
+            self.report("Installation de Go", 0.15);
+            self.log_info(&format!("Vérification/installation de Go{}...", target));
+            let go_installer = GoInstaller::new();
+            go_installer.install(executor)?;
+        }
+        Ok(())
+    }
+
+    /// Installe les paquets supplémentaires de la configuration (à partir de 90 % de l'avancement)
+    fn install_extra_packages(&self, executor: &CommandExecutor, command: impl Fn(&str) -> String) -> Result<ExtraPackagesReport, ExecError> {
+        if self.config.extra_packages.is_empty() {
+            return Ok(ExtraPackagesReport::default());
+        }
+        self.report("Paquets supplémentaires", 0.9);
+        self.log_info(&format!("Installation des paquets supplémentaires: {}", self.config.extra_packages.join(" ")));
+        let report = install_packages_one_by_one(executor, &self.config.extra_packages, command)?;
+        for (package, reason) in &report.failed {
+            self.log_warning(&format!("Paquet {} non installé: {}", package, reason));
         }
+        Ok(report)
     }
 
     /// Exécute l'installation complète selon le type choisi
@@ -148,31 +358,32 @@ impl RMDBInstaller {
     fn install_on_host(&self, executor: &CommandExecutor) -> Result<CommandOutput, ExecError> {
         self.log_info("Début de l'installation de RMDB sur le système hôte");
 
-        // Étape 1: Installer Rust si nécessaire
-        if self.config.install_rust {
-            self.log_info("Vérification/installation de Rust...");
-            let rust_installer = RustInstaller::new();
-            rust_installer.install(executor)?;
-        }
+        // Étapes 1 et 2: Installer Rust et Go si nécessaire
+        self.install_toolchains(executor, "")?;
 
-        // Étape 2: Installer Go si nécessaire
-        if self.config.install_go {
-            self.log_info("Vérification/installation de Go...");
-            let go_installer = GoInstaller::new();
-            go_installer.install(executor)?;
-        }
-
-        // Étape 3: Installer RMDB sur le host
+        // Étape 3: Installer RMDB sur le host (la compilation occupe le reste de la barre)
+        self.report("Compilation de RMDB", 0.3);
         self.log_info("Installation de RMDB sur le système hôte...");
         let host_deployment = crate::deployment::host::HostDeployment::new();
-        host_deployment.install_rmdb(executor, &self.config.rmdb_source_path)?;
+        let mut estimator = ProgressEstimator::new();
+        host_deployment.install_rmdb_with_progress(executor, &self.config.rmdb_source_path, &mut |line| {
+            self.log_debug(line);
+            if let Some(fraction) = estimator.feed(line) {
+                self.report("Compilation de RMDB", 0.3 + 0.7 * fraction);
+            }
+        })?;
 
+        let extras = self.install_extra_packages(executor, |package| self.distribution.install_command(&[package]))?;
+
+        self.report("Installation terminée", 1.0);
         self.log_info("Installation sur le système hôte terminée avec succès");
-        Ok(CommandOutput {
+        let mut output = CommandOutput {
             stdout: "RMDB installé avec succès sur le système hôte".to_string(),
             stderr: String::new(),
             exit_code: Some(0),
-        })
+        };
+        extras.append_to(&mut output);
+        Ok(output)
     }
 
     /// Installation dans un container Alpine
@@ -185,19 +396,10 @@ impl RMDBInstaller {
             .ok_or_else(|| ExecError::Failed("Version Alpine requise".to_string()))?;
 
         // Étape 1: Installer Rust et Go sur le host si nécessaire
-        if self.config.install_rust {
-            self.log_info("Vérification/installation de Rust sur le host...");
-            let rust_installer = RustInstaller::new();
-            rust_installer.install(executor)?;
-        }
-
-        if self.config.install_go {
-            self.log_info("Vérification/installation de Go sur le host...");
-            let go_installer = GoInstaller::new();
-            go_installer.install(executor)?;
-        }
+        self.install_toolchains(executor, " sur le host")?;
 
         // Étape 2: Créer le container Alpine
+        self.report("Création du container", 0.3);
         self.log_info(&format!("Création du container Alpine {}...", container_name));
         let logger = if let Some(ref _existing_logger) = self.config.logger {
             DeploymentLogger::new().unwrap_or_else(|_| DeploymentLogger::default())
@@ -212,15 +414,22 @@ impl RMDBInstaller {
         lxc_deployment.create_container(executor)?;
 
         // Étape 3: Installer RMDB dans le container
+        self.report("Installation de RMDB dans le container", 0.6);
         self.log_info("Installation de RMDB dans le container...");
         lxc_deployment.install_rmdb_in_container(executor, &self.config.rmdb_source_path)?;
+        let extras = self.install_extra_packages(executor, |package| {
+            format!("lxc-attach -n {} -- apk add -q {} 2>&1", container_name, package)
+        })?;
+        self.report("Installation terminée", 1.0);
 
         self.log_info("Installation dans le container terminée avec succès");
-        Ok(CommandOutput {
+        let mut output = CommandOutput {
             stdout: format!("RMDB installé avec succès dans le container {}", container_name),
             stderr: String::new(),
             exit_code: Some(0),
-        })
+        };
+        extras.append_to(&mut output);
+        Ok(output)
     }
 
     /// Installation dans une VM Rocky Linux
@@ -233,34 +442,32 @@ impl RMDBInstaller {
             .ok_or_else(|| ExecError::Failed("Version Rocky requise".to_string()))?;
 
         // Étape 1: Installer Rust et Go sur le host si nécessaire
-        if self.config.install_rust {
-            self.log_info("Vérification/installation de Rust sur le host...");
-            let rust_installer = RustInstaller::new();
-            rust_installer.install(executor)?;
-        }
-
-        if self.config.install_go {
-            self.log_info("Vérification/installation de Go sur le host...");
-            let go_installer = GoInstaller::new();
-            go_installer.install(executor)?;
-        }
+        self.install_toolchains(executor, " sur le host")?;
 
         // Étape 2: Créer la VM Rocky Linux
+        self.report("Création de la VM", 0.3);
         self.log_info(&format!("Création de la VM Rocky Linux {}...", vm_name));
+        // Sans accès direct à la VM, dnf installe les paquets supplémentaires via cloud-init
         let vm_deployment = crate::deployment::vm::VMDeployment::new(
             vm_name.clone(),
             rocky_version.clone(),
-        );
+        ).with_extra_packages(self.config.extra_packages.clone());
 
         vm_deployment.create_vm(executor)?;
 
         // Étape 3: Installer RMDB dans la VM
+        self.report("Installation de RMDB dans la VM", 0.6);
         self.log_info("Installation de RMDB dans la VM...");
         vm_deployment.install_rmdb_in_vm(executor, &self.config.rmdb_source_path)?;
+        self.report("Installation terminée", 1.0);
 
         self.log_info("Installation dans la VM terminée avec succès");
+        let mut stdout = format!("RMDB installé avec succès dans la VM {}", vm_name);
+        if !self.config.extra_packages.is_empty() {
+            stdout.push_str(&format!("\nPaquets supplémentaires ajoutés au script cloud-init: {}", self.config.extra_packages.join(", ")));
+        }
         Ok(CommandOutput {
-            stdout: format!("RMDB installé avec succès dans la VM {}", vm_name),
+            stdout,
             stderr: String::new(),
             exit_code: Some(0),
         })
@@ -271,6 +478,18 @@ impl RMDBInstaller {
             logger.info(message);
         }
     }
+
+    fn log_debug(&self, message: &str) {
+        if let Some(ref logger) = self.config.logger {
+            logger.debug(message);
+        }
+    }
+
+    fn log_warning(&self, message: &str) {
+        if let Some(ref logger) = self.config.logger {
+            logger.warn(message);
+        }
+    }
 }
 
 /// Installateur de Rust
@@ -424,12 +643,12 @@ impl GoInstaller {
         executor.run_shell(&download_cmd, false)?;
 
         // Supprimer l'ancienne installation si elle existe
-        let remove_old = "sudo rm -rf /usr/local/go";
+        let remove_old = "rm -rf /usr/local/go";
         let _ = executor.run_shell(remove_old, true);
 
         // Extraire Go
         let extract_cmd = format!(
-            "sudo tar -C /usr/local -xzf /tmp/{}",
+            "tar -C /usr/local -xzf /tmp/{}",
             go_tarball
         );
         executor.run_shell(&extract_cmd, true)?;
@@ -439,7 +658,7 @@ impl GoInstaller {
 export PATH=$PATH:/usr/local/go/bin
 "#;
         let setup_cmd = format!(
-            "sudo bash -c 'cat > /etc/profile.d/go.sh << \"EOF\"\n{}\nEOF' && sudo chmod +x /etc/profile.d/go.sh",
+            "cat > /etc/profile.d/go.sh << 'EOF'\n{}EOF\nchmod +x /etc/profile.d/go.sh",
             path_setup
         );
         executor.run_shell(&setup_cmd, true)?;
@@ -512,3 +731,43 @@ export PATH=$PATH:/usr/local/go/bin
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_cues() {
+        assert_eq!(parse_progress_cue("(12/48) Installing musl-dev (1.2.4-r2)"), Some(ProgressCue::Fraction(12, 48)));
+        assert_eq!(parse_progress_cue("    Building [=====>     ] 120/250: serde"), Some(ProgressCue::Fraction(120, 250)));
+        assert_eq!(parse_progress_cue("   Compiling serde v1.0.200"), Some(ProgressCue::Tick));
+        assert_eq!(parse_progress_cue("github.com/gorilla/mux"), Some(ProgressCue::Tick));
+        assert_eq!(parse_progress_cue("error: could not compile `rmdb`"), None);
+        assert_eq!(parse_progress_cue("/usr/local/bin/rmdbd"), None);
+
+        let mut estimator = ProgressEstimator::new();
+        assert_eq!(estimator.feed("(1/4) Installing a"), Some(0.25));
+        assert_eq!(estimator.feed("(1/8) Installing b"), None);
+        assert_eq!(estimator.feed("(4/4) Installing c"), Some(0.99));
+        assert_eq!(estimator.fraction(), 0.99);
+    }
+
+    #[test]
+    fn test_extra_packages() {
+        use crate::pres::executor::MockExecutor;
+
+        assert_eq!(parse_package_list(" htop  node-exporter htop ").unwrap(), ["htop", "node-exporter"]);
+        assert!(parse_package_list("htop; reboot").is_err());
+        assert!(parse_package_list("--allow-untrusted").is_err());
+
+        let failure = CommandOutput { exit_code: Some(1), stdout: String::new(), stderr: "ERROR: unable to select packages:\n  absent (no such package)\n".to_string() };
+        let executor = MockExecutor::new().on_output("apk add absent", failure);
+        let packages = parse_package_list("htop absent zabbix-agent").unwrap();
+        let report = install_packages_one_by_one(&executor, &packages, |p| format!("apk add {}", p)).unwrap();
+        assert_eq!(report.installed, ["htop", "zabbix-agent"]);
+        assert_eq!(report.failed, [("absent".to_string(), "absent (no such package)".to_string())]);
+
+        let cancelled = MockExecutor::new().on_error("apk add", ExecError::Cancelled);
+        assert!(install_packages_one_by_one(&cancelled, &packages, |p| format!("apk add {}", p)).is_err());
+        assert_eq!(cancelled.commands().len(), 1);
+    }
+}
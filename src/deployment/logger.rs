@@ -4,9 +4,60 @@ use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Niveau de verbosité d'un journal de déploiement
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Debug,
+    Info,
+    Warn,
+    Error,
+}
+
+impl Level {
+    pub const ALL: [Level; 4] = [Level::Debug, Level::Info, Level::Warn, Level::Error];
+
+    /// Étiquette écrite entre crochets dans le journal
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Level::Debug => "DEBUG",
+            Level::Info => "INFO",
+            Level::Warn => "WARN",
+            Level::Error => "ERROR",
+        }
+    }
+
+    /// `debug`, `info`, `warn` (ou `warning`), `error`, sans tenir compte de la casse
+    pub fn parse(value: &str) -> Option<Level> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "debug" => Some(Level::Debug),
+            "info" => Some(Level::Info),
+            "warn" | "warning" => Some(Level::Warn),
+            "error" => Some(Level::Error),
+            _ => None,
+        }
+    }
+
+    /// Niveau demandé par `RMDB_LOG_LEVEL` (info par défaut)
+    pub fn from_env() -> Level {
+        std::env::var("RMDB_LOG_LEVEL")
+            .ok()
+            .and_then(|value| Level::parse(&value))
+            .unwrap_or(Level::Info)
+    }
+
+    /// Niveau d'une ligne `[ts] [NIVEAU] message` déjà écrite
+    pub fn of_line(line: &str) -> Option<Level> {
+        let rest = line.strip_prefix('[')?;
+        let rest = &rest[rest.find("] [")? + 3..];
+        let tag = &rest[..rest.find(']')?];
+        Level::ALL.into_iter().find(|level| level.as_str() == tag)
+    }
+}
+
 pub struct DeploymentLogger {
     log_file: Mutex<BufWriter<File>>,
     log_dir: PathBuf,
+    level: Level,
 }
 
 impl DeploymentLogger {
@@ -48,10 +99,24 @@ impl DeploymentLogger {
         Ok(Self {
             log_file: Mutex::new(writer),
             log_dir,
+            level: Level::from_env(),
         })
     }
 
-    fn log(&self, level: &str, message: &str) {
+    /// Ignore les messages moins importants que `level`
+    pub fn with_level(mut self, level: Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    fn log(&self, level: Level, message: &str) {
+        if level < self.level {
+            return;
+        }
         // Utiliser SystemTime pour éviter la dépendance chrono
         let timestamp = match SystemTime::now().duration_since(UNIX_EPOCH) {
             Ok(duration) => {
@@ -61,7 +126,7 @@ impl DeploymentLogger {
             }
             Err(_) => "0.000".to_string(),
         };
-        let log_line = format!("[{}] [{}] {}\n", timestamp, level, message);
+        let log_line = format!("[{}] [{}] {}\n", timestamp, level.as_str(), message);
         
         // Écrire dans le fichier
         if let Ok(mut writer) = self.log_file.lock() {
@@ -74,19 +139,19 @@ impl DeploymentLogger {
     }
 
     pub fn info(&self, message: &str) {
-        self.log("INFO", message);
+        self.log(Level::Info, message);
     }
 
     pub fn warn(&self, message: &str) {
-        self.log("WARN", message);
+        self.log(Level::Warn, message);
     }
 
     pub fn error(&self, message: &str) {
-        self.log("ERROR", message);
+        self.log(Level::Error, message);
     }
 
     pub fn debug(&self, message: &str) {
-        self.log("DEBUG", message);
+        self.log(Level::Debug, message);
     }
 
     pub fn step(&self, step: u32, total: u32, message: &str) {
@@ -106,7 +171,7 @@ impl DeploymentLogger {
         }
         if !stderr.is_empty() {
             for line in stderr.lines() {
-                self.warn(&format!("STDERR: {}", line));
+                self.debug(&format!("STDERR: {}", line));
             }
         }
         if let Some(code) = exit_code {
@@ -121,6 +186,43 @@ impl DeploymentLogger {
     pub fn log_path(&self) -> &Path {
         &self.log_dir
     }
+
+    /// Journaux de déploiement existants, du plus récent au plus ancien
+    ///
+    /// Cherche dans /var/log/rmdb et dans ./logs (repli utilisé par `new`).
+    pub fn list_logs() -> Vec<PathBuf> {
+        let current_dir = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        logs_in(&[PathBuf::from("/var/log/rmdb"), current_dir.join("logs")])
+    }
+
+    /// Journal du déploiement le plus récent
+    pub fn latest() -> Option<PathBuf> {
+        Self::list_logs().into_iter().next()
+    }
+
+    /// Horodatage (secondes Unix) contenu dans le nom `deployment_<ts>.log`
+    pub fn log_timestamp(path: &Path) -> Option<u64> {
+        path.file_name()?
+            .to_str()?
+            .strip_prefix("deployment_")?
+            .strip_suffix(".log")?
+            .parse()
+            .ok()
+    }
+}
+
+/// Journaux `deployment_<ts>.log` des répertoires donnés, triés du plus récent au plus ancien
+fn logs_in(dirs: &[PathBuf]) -> Vec<PathBuf> {
+    let mut logs: Vec<(u64, PathBuf)> = dirs
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter_map(|path| DeploymentLogger::log_timestamp(&path).map(|ts| (ts, path)))
+        .collect();
+    logs.sort_by_key(|(ts, _)| std::cmp::Reverse(*ts));
+    logs.dedup_by(|a, b| a.1 == b.1);
+    logs.into_iter().map(|(_, path)| path).collect()
 }
 
 impl Default for DeploymentLogger {
@@ -150,3 +252,35 @@ impl NullLogger {
     pub fn command_output(&self, _stdout: &str, _stderr: &str, _exit_code: Option<i32>) {}
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_logs_sorted_newest_first() {
+        let dir = std::env::temp_dir().join(format!("rmdb_logger_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["deployment_100.log", "deployment_300.log", "deployment_200.log", "notes.txt"] {
+            File::create(dir.join(name)).unwrap();
+        }
+
+        let logs = logs_in(&[dir.clone(), dir.join("absent")]);
+        let names: Vec<_> = logs.iter().map(|p| p.file_name().unwrap().to_string_lossy().to_string()).collect();
+        assert_eq!(names, ["deployment_300.log", "deployment_200.log", "deployment_100.log"]);
+        assert_eq!(DeploymentLogger::log_timestamp(&logs[0]), Some(300));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_level_parse_and_line() {
+        assert_eq!(Level::parse(" Debug "), Some(Level::Debug));
+        assert_eq!(Level::parse("warning"), Some(Level::Warn));
+        assert_eq!(Level::parse("trace"), None);
+        assert!(Level::Debug < Level::Info && Level::Warn < Level::Error);
+
+        assert_eq!(Level::of_line("[1700000000.123] [WARN] STDERR: x"), Some(Level::Warn));
+        assert_eq!(Level::of_line("[1700000000.123] [DEBUG] STDOUT: [INFO] y"), Some(Level::Debug));
+        assert_eq!(Level::of_line("texte libre"), None);
+    }
+}
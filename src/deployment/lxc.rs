@@ -1,15 +1,201 @@
-use crate::pres::executor::{CommandExecutor, CommandOutput, ExecError};
+use crate::pres::executor::{CommandExecutor, CommandOutput, ExecError, Executor};
 use crate::deployment::logger::DeploymentLogger;
+use crate::data::deploy_timings::DeployTimings;
 use crate::data::distribution::DistributionInfo;
+use std::collections::{BTreeMap, HashSet};
 use std::process::Command;
 use std::fs;
-use std::path::Path;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Répertoire où sont déplacés les containers avant suppression
+pub const LXC_TRASH_DIR: &str = "/var/lib/lxc/.rmdb-trash";
+/// Nombre maximal de sauvegardes conservées dans la corbeille
+pub const LXC_TRASH_MAX_BACKUPS: usize = 5;
+/// Port de l'API RMDB à l'intérieur du container
+const RMDB_API_PORT: u16 = 8080;
+/// Plus ancienne version de LXC prise en charge (`lxc-ls -f`, templates actuels)
+pub const LXC_MIN_SUPPORTED_VERSION: (u32, u32) = (2, 0);
+/// Version qui introduit les clés `lxc.net.*`, `lxc.rootfs.path`, `lxc.uts.name`
+/// (les anciennes clés sont refusées à partir de LXC 3.0)
+const LXC_NEW_CONFIG_KEYS_VERSION: (u32, u32) = (2, 1);
+/// Clés sans lesquelles LXC ne peut pas démarrer un container (syntaxe actuelle, syntaxe d'avant LXC 2.1)
+const REQUIRED_CONFIG_KEYS: &[(&str, &str)] = &[("lxc.rootfs.path", "lxc.rootfs"), ("lxc.uts.name", "lxc.utsname")];
+/// Clés de configuration actuelles et leur équivalent avant LXC 2.1
+const LXC_LEGACY_CONFIG_KEYS: &[(&str, &str)] = &[
+    ("lxc.net.0.type", "lxc.network.type"),
+    ("lxc.net.0.link", "lxc.network.link"),
+    ("lxc.net.0.flags", "lxc.network.flags"),
+    ("lxc.net.0.ipv4.address", "lxc.network.ipv4"),
+    ("lxc.net.0.ipv4.gateway", "lxc.network.ipv4.gateway"),
+    ("lxc.net.0.hwaddr", "lxc.network.hwaddr"),
+    ("lxc.idmap", "lxc.id_map"),
+    ("lxc.rootfs.path", "lxc.rootfs"),
+    ("lxc.uts.name", "lxc.utsname"),
+];
+
+/// Type d'interface réseau du container
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NetMode {
+    /// Paire veth reliée à un pont (`br0`, `lxcbr0`...)
+    Veth,
+    /// Interface macvlan sur une interface physique
+    Macvlan,
+}
+
+impl NetMode {
+    pub fn config_value(&self) -> &'static str {
+        match self {
+            NetMode::Veth => "veth",
+            NetMode::Macvlan => "macvlan",
+        }
+    }
+}
+
+/// Mode de copie de `lxc-copy`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CloneMode {
+    /// Copie complète du rootfs : le clone est indépendant de la source
+    Copy,
+    /// Instantané du stockage de la source (btrfs, zfs, lvm)
+    Snapshot,
+    /// Couche overlay au-dessus du rootfs de la source, qui ne doit plus être supprimée
+    Overlay,
+}
+
+impl CloneMode {
+    pub const ALL: [CloneMode; 3] = [CloneMode::Copy, CloneMode::Snapshot, CloneMode::Overlay];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CloneMode::Copy => "Copie complète",
+            CloneMode::Snapshot => "Instantané (btrfs, zfs, lvm)",
+            CloneMode::Overlay => "Overlay (dépend de la source)",
+        }
+    }
+
+    fn flags(&self) -> &'static str {
+        match self {
+            CloneMode::Copy => "",
+            CloneMode::Snapshot => " -s",
+            CloneMode::Overlay => " -s -B overlay",
+        }
+    }
+}
+
+/// Mode des containers visé par la configuration par défaut
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LxcPrivilege {
+    /// Containers créés par root : `/etc/lxc/default.conf`
+    Privileged,
+    /// Containers de l'utilisateur courant : `~/.config/lxc/default.conf`, avec idmap
+    Unprivileged,
+}
+
+impl LxcPrivilege {
+    pub fn default_config_path(&self) -> PathBuf {
+        match self {
+            LxcPrivilege::Privileged => PathBuf::from("/etc/lxc/default.conf"),
+            LxcPrivilege::Unprivileged => std::env::var_os("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+                .unwrap_or_else(|| PathBuf::from(".config"))
+                .join("lxc/default.conf"),
+        }
+    }
+}
+
+/// Gestionnaire de services trouvé dans un container, qui choisit les commandes de rmdbd
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerInit {
+    /// `rc-service` (Alpine)
+    OpenRC,
+    /// `systemctl`, systemd étant le processus d'init du container
+    Systemd,
+    /// Ni l'un ni l'autre : rmdbd est lancé et arrêté directement
+    Direct,
+}
+
+impl ContainerInit {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ContainerInit::OpenRC => "OpenRC",
+            ContainerInit::Systemd => "systemd",
+            ContainerInit::Direct => "sans gestionnaire",
+        }
+    }
+
+    /// Interprète la sortie de `CONTAINER_INIT_SCRIPT` ; `None` si elle est absente ou inattendue
+    pub fn parse(output: &str) -> Option<Self> {
+        match output.lines().last().map(str::trim) {
+            Some("openrc") => Some(ContainerInit::OpenRC),
+            Some("systemd") => Some(ContainerInit::Systemd),
+            Some("none") => Some(ContainerInit::Direct),
+            _ => None,
+        }
+    }
+
+    /// Commande `verb` (start, stop ou restart) de rmdbd dans le container, avec repli sur
+    /// le lancement ou l'arrêt direct du binaire si le service échoue
+    pub fn service_command(&self, container: &str, verb: &str) -> String {
+        let attach = format!("lxc-attach -n {} --", container);
+        let direct = match verb {
+            "start" => format!("{} {} &", attach, RMDBD_DIRECT_START),
+            "stop" => format!("{} pkill rmdbd", attach),
+            _ => format!("({0} pkill rmdbd && sleep 1 && {0} {1} &)", attach, RMDBD_DIRECT_START),
+        };
+        match self {
+            ContainerInit::OpenRC => format!("{} rc-service rmdbd {} 2>&1 || {}", attach, verb, direct),
+            ContainerInit::Systemd => format!("{} systemctl {} rmdbd 2>&1 || {}", attach, verb, direct),
+            ContainerInit::Direct => direct,
+        }
+    }
+
+    /// Dernières lignes du journal de rmdbd : journald d'abord sous systemd, le fichier de log sinon
+    pub fn logs_command(&self, container: &str, lines: usize) -> String {
+        let journal = format!("lxc-attach -n {} -- journalctl -u rmdbd -n {} --no-pager 2>/dev/null", container, lines);
+        let file = format!("lxc-attach -n {} -- tail -{} {} 2>/dev/null", container, lines, RMDBD_CONTAINER_LOG);
+        match self {
+            ContainerInit::Systemd => format!("{} || {} || echo 'Logs non disponibles'", journal, file),
+            _ => format!("{} || {} || echo 'Logs non disponibles'", file, journal),
+        }
+    }
+
+    /// Suivi en direct du journal de rmdbd
+    pub fn follow_logs_command(&self, container: &str) -> String {
+        match self {
+            ContainerInit::Systemd => format!("lxc-attach -n {} -- journalctl -u rmdbd -f -n 50 --no-pager", container),
+            _ => format!("lxc-attach -n {} -- tail -n 50 -f {}", container, RMDBD_CONTAINER_LOG),
+        }
+    }
+}
+
+/// Résultat de `LXCDeployment::ensure_default_config`
+#[derive(Debug, Clone, PartialEq)]
+pub enum DefaultConfigStatus {
+    /// Le fichier existait déjà : il n'a pas été modifié
+    Present(PathBuf),
+    /// Le fichier a été créé avec ce contenu
+    Created(PathBuf, String),
+}
 
 pub struct LXCDeployment {
     container_name: String,
     alpine_version: String,
     pub logger: Option<DeploymentLogger>,
     distribution: DistributionInfo,
+    /// Miroir Alpine passé au template (réseaux isolés ou miroirs internes)
+    mirror: Option<String>,
+    /// Version de LXC détectée (majeure, mineure), pour choisir la syntaxe de configuration
+    lxc_version: Option<(u32, u32)>,
+    /// Interface à laquelle rattacher le container ; `None` garde le réseau LXC par défaut
+    network: Option<(String, NetMode)>,
+    /// Débit maximal du téléchargement du template, en Ko/s (via `trickle`)
+    rate_limit: Option<u32>,
+    /// Cache de template local (`rootfs.tar.xz` et `meta.tar.xz`) pour les réseaux isolés
+    local_template: Option<PathBuf>,
+    /// Attentes et tentatives de la création et du démarrage
+    timings: DeployTimings,
 }
 
 impl LXCDeployment {
@@ -19,7 +205,165 @@ impl LXCDeployment {
             alpine_version,
             logger: None,
             distribution: DistributionInfo::detect(),
+            mirror: None,
+            lxc_version: None,
+            network: None,
+            rate_limit: None,
+            local_template: None,
+            timings: DeployTimings::default(),
+        }
+    }
+
+    /// Remplace les attentes par défaut (clé `deploy_timings` de `tui.json`)
+    pub fn with_timings(mut self, timings: DeployTimings) -> Self {
+        self.timings = timings;
+        self
+    }
+
+    /// Télécharge Alpine depuis `url` (ex: `http://miroir.local/alpine`) au lieu du miroir par défaut
+    pub fn with_mirror(mut self, url: impl Into<String>) -> Self {
+        self.mirror = Some(url.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    pub fn mirror(&self) -> Option<&str> {
+        self.mirror.as_deref()
+    }
+
+    /// Limite le débit du téléchargement du template à `kbps` Ko/s
+    ///
+    /// `lxc-create` n'a pas d'option de débit : la commande est lancée sous `trickle`, dont
+    /// la limite s'applique aussi aux téléchargements des scripts de template. Sans `trickle`,
+    /// la création se fait sans limite et un avertissement est journalisé.
+    pub fn with_rate_limit(mut self, kbps: u32) -> Self {
+        self.rate_limit = Some(kbps).filter(|&k| k > 0);
+        self
+    }
+
+    pub fn rate_limit(&self) -> Option<u32> {
+        self.rate_limit
+    }
+
+    /// Crée le container depuis un cache de template local, sans téléchargement
+    ///
+    /// `path` contient `rootfs.tar.xz` et `meta.tar.xz`, comme le cache du template `download`
+    /// (`/var/cache/lxc/download/alpine/<version>/amd64/default`) copié depuis une machine connectée.
+    pub fn with_local_template(mut self, path: impl Into<PathBuf>) -> Self {
+        self.local_template = Some(path.into());
+        self
+    }
+
+    pub fn local_template(&self) -> Option<&Path> {
+        self.local_template.as_deref()
+    }
+
+    /// Commande de création avec le template `local` si un cache local est défini
+    fn local_template_command(&self) -> Option<String> {
+        let dir = self.local_template.as_ref()?;
+        Some(format!(
+            "lxc-create -n {} -t local -- --metadata '{}' --fstree '{}'",
+            self.container_name,
+            dir.join(LOCAL_TEMPLATE_META).display(),
+            dir.join(LOCAL_TEMPLATE_ROOTFS).display()
+        ))
+    }
+
+    /// Vérifie que les sources de téléchargement du template répondent (délai court)
+    ///
+    /// Le template `alpine` télécharge depuis le miroir Alpine, le template `download`
+    /// depuis le serveur d'images de LXC : si aucun ne répond, la création ne peut qu'échouer.
+    pub fn check_template_sources(&self, executor: &dyn Executor) -> TemplateSources {
+        let reachable = |url: &str| {
+            let cmd = format!(
+                "curl -fsI --max-time 5 '{url}' >/dev/null 2>&1 || wget -q --spider -T 5 '{url}' >/dev/null 2>&1",
+                url = url
+            );
+            executor.run_shell(&cmd, false).is_ok_and(|o| o.exit_code == Some(0))
+        };
+        let mirror = self.mirror.clone().unwrap_or_else(|| DEFAULT_ALPINE_MIRROR.to_string());
+        TemplateSources {
+            mirror_reachable: reachable(&format!("{}/", mirror)),
+            images_reachable: reachable(&format!("{}/", LXC_IMAGES_SERVER)),
+            mirror,
+        }
+    }
+
+    /// Indique si `trickle`, utilisé pour limiter le débit, est installé
+    pub fn rate_limit_available(executor: &dyn Executor) -> bool {
+        executor
+            .run_shell("command -v trickle >/dev/null 2>&1", false)
+            .is_ok_and(|o| o.exit_code == Some(0))
+    }
+
+    /// Rattache le container à `bridge` (ex: `br0` pour le PXE sur le LAN) au lieu du réseau par défaut
+    pub fn with_network(mut self, bridge: String, mode: NetMode) -> Self {
+        self.network = Some((bridge, mode));
+        self
+    }
+
+    pub fn network(&self) -> Option<(&str, NetMode)> {
+        self.network.as_ref().map(|(bridge, mode)| (bridge.as_str(), *mode))
+    }
+
+    /// Écrit `lxc.net.0.type`/`lxc.net.0.link` dans la configuration du container créé
+    ///
+    /// Les lignes existantes sont remplacées sur place : avec les anciennes clés, l'ordre
+    /// des lignes `lxc.network.*` délimite les interfaces.
+    fn apply_network(&self, executor: &CommandExecutor) -> Result<(), ExecError> {
+        let Some((ref bridge, mode)) = self.network else {
+            return Ok(());
+        };
+        let config = self.find_container_config_path(executor)
+            .ok_or_else(|| ExecError::Failed(format!("Configuration du container '{}' introuvable", self.container_name)))?;
+        let (type_key, link_key) = (self.config_key("lxc.net.0.type"), self.config_key("lxc.net.0.link"));
+        let cmd = format!(
+            "if grep -q '^{t}[[:space:]]*=' {cfg}; then \
+             sed -i -e '/^{l}[[:space:]]*=/d' -e 's|^{t}[[:space:]]*=.*|{t} = {mode}\\n{l} = {bridge}|' {cfg}; \
+             else printf '{t} = {mode}\\n{l} = {bridge}\\n' >> {cfg}; fi",
+            t = type_key, l = link_key, mode = mode.config_value(), bridge = bridge, cfg = config
+        );
+        self.log_command(&cmd);
+        let output = executor.run_shell(&cmd, true)?;
+        if output.exit_code != Some(0) {
+            return Err(ExecError::Failed(format!("Modification de {} impossible: {}", config, output.stderr.trim())));
         }
+        self.log_info(&format!("Réseau: {} sur {}", mode.config_value(), bridge));
+        Ok(())
+    }
+
+    /// Renseigne la version de LXC (voir `LXCDeployment::lxc_version`)
+    pub fn with_lxc_version(mut self, version: Option<(u32, u32)>) -> Self {
+        self.lxc_version = version;
+        self
+    }
+
+    pub fn detected_lxc_version(&self) -> Option<(u32, u32)> {
+        self.lxc_version
+    }
+
+    /// Détecte la version de LXC via `lxc-ls --version`, puis `lxc-create --version`
+    pub fn lxc_version(executor: &dyn Executor) -> Option<(u32, u32)> {
+        ["lxc-ls --version 2>/dev/null", "lxc-create --version 2>/dev/null"]
+            .iter()
+            .filter_map(|cmd| executor.run_shell(cmd, false).ok())
+            .find_map(|output| parse_lxc_version(&output.stdout))
+    }
+
+    /// Nom de la clé de configuration `key` (syntaxe actuelle) pour la version de LXC détectée
+    fn config_key(&self, key: &'static str) -> &'static str {
+        config_key_for(self.lxc_version, key)
+    }
+
+    /// Commande de création avec le template `alpine` (miroir inclus s'il est défini)
+    fn alpine_template_command(&self) -> String {
+        let mut cmd = format!(
+            "lxc-create -n {} -t alpine -- --release v{}",
+            self.container_name, self.alpine_version
+        );
+        if let Some(ref mirror) = self.mirror {
+            cmd.push_str(&format!(" --mirror '{}'", mirror.replace('\'', "'\\''")));
+        }
+        cmd
     }
 
     pub fn with_logger(mut self, logger: DeploymentLogger) -> Self {
@@ -154,8 +498,7 @@ impl LXCDeployment {
         self.log_info(&format!("Début de la création du container '{}'", self.container_name));
 
         if !self.check_lxc_installed() {
-            let packages = vec!["lxc", "lxc-templates"];
-            let install_cmd = self.distribution.install_command(&packages.iter().map(|s| *s).collect::<Vec<_>>());
+            let install_cmd = self.distribution.lxc_install_commands(&self.distribution.lxc_packages()).join(" && ");
             let msg = format!("LXC n'est pas installé. Installez-le avec: {}", install_cmd);
             self.log_error(&msg);
             return Err(ExecError::MissingTool(msg));
@@ -187,62 +530,96 @@ impl LXCDeployment {
         // LXC 1.x utilise: lxc-create -n name -t template -- --release version
         // LXC 2.x+ peut nécessiter une syntaxe différente ou utiliser download
         // Essayer d'abord avec le template alpine, puis avec download si échec
-        let cmd1 = format!(
-            "lxc-create -n {} -t alpine -- --release v{}",
-            self.container_name, self.alpine_version
-        );
-        
-        self.log_command(&cmd1);
-        let result1 = executor.run_shell(&cmd1, true);
-        
-        // Si la première commande échoue, essayer avec download (LXC 2.x+)
-        let final_result = if result1.is_err() || (result1.is_ok() && result1.as_ref().unwrap().exit_code != Some(0)) {
-            self.log_warn("La première méthode de création a échoué, essai avec 'download'");
-            let cmd2 = format!(
-                "lxc-create -n {} -t download -- --dist alpine --release {} --arch amd64",
-                self.container_name, self.alpine_version
-            );
-            self.log_command(&cmd2);
-            let result2 = executor.run_shell(&cmd2, true);
-            
-            if let Ok(ref output) = result2 {
+        let final_result = if let Some(cmd) = self.local_template_command() {
+            // Cache local (réseau isolé) : aucun téléchargement, pas de repli sur 'download'
+            self.log_command(&cmd);
+            let result = executor.run_shell(&cmd, true);
+            if let Ok(ref output) = result {
                 self.log_command_output(output);
                 if output.exit_code == Some(0) {
-                    self.log_info("Container créé avec succès (méthode download)");
+                    self.log_info("Container créé avec succès (template local)");
                 } else {
-                    self.log_error("Échec de la création du container (méthode download)");
                     self.log_error(&format!("stderr: {}", output.stderr));
                 }
             }
-            result2
+            result
         } else {
-            if let Ok(ref output) = result1 {
-                self.log_command_output(output);
-                if output.exit_code == Some(0) {
-                    self.log_info("Container créé avec succès (méthode alpine)");
-                } else {
-                    self.log_error(&format!("stderr: {}", output.stderr));
+            let rate_limit = match self.rate_limit {
+                Some(kbps) if Self::rate_limit_available(executor) => {
+                    self.log_info(&format!("Débit du téléchargement limité à {} Ko/s (trickle)", kbps));
+                    Some(kbps)
                 }
+                Some(_) => {
+                    self.log_warn("trickle n'est pas installé : le téléchargement ne sera pas limité");
+                    None
+                }
+                None => None,
+            };
+            let cmd1 = with_rate_limit_command(self.alpine_template_command(), rate_limit);
+            if let Some(ref mirror) = self.mirror {
+                self.log_info(&format!("Miroir Alpine: {}", mirror));
+            }
+        
+            self.log_command(&cmd1);
+            let result1 = executor.run_shell(&cmd1, true);
+        
+            // Si la première commande échoue, essayer avec download (LXC 2.x+)
+            if result1.is_err() || (result1.is_ok() && result1.as_ref().unwrap().exit_code != Some(0)) {
+                self.log_warn("La première méthode de création a échoué, essai avec 'download'");
+                if self.mirror.is_some() {
+                    // Le template download lit des images, pas un dépôt de paquets Alpine
+                    self.log_warn("Le miroir Alpine ne s'applique pas au template 'download'");
+                }
+                let cmd2 = with_rate_limit_command(
+                    format!(
+                        "lxc-create -n {} -t download -- --dist alpine --release {} --arch amd64",
+                        self.container_name, self.alpine_version
+                    ),
+                    rate_limit,
+                );
+                self.log_command(&cmd2);
+                let result2 = executor.run_shell(&cmd2, true);
+            
+                if let Ok(ref output) = result2 {
+                    self.log_command_output(output);
+                    if output.exit_code == Some(0) {
+                        self.log_info("Container créé avec succès (méthode download)");
+                    } else {
+                        self.log_error("Échec de la création du container (méthode download)");
+                        self.log_error(&format!("stderr: {}", output.stderr));
+                    }
+                }
+                result2
+            } else {
+                if let Ok(ref output) = result1 {
+                    self.log_command_output(output);
+                    if output.exit_code == Some(0) {
+                        self.log_info("Container créé avec succès (méthode alpine)");
+                    } else {
+                        self.log_error(&format!("stderr: {}", output.stderr));
+                    }
+                }
+                result1
             }
-            result1
         };
 
         // Vérifier RÉELLEMENT que le container existe après création
         if let Ok(ref output) = final_result {
             if output.exit_code == Some(0) {
                 self.log_info("Vérification de l'existence réelle du container...");
-                std::thread::sleep(std::time::Duration::from_millis(500));
+                std::thread::sleep(self.timings.verify_delay(0));
                 
                 // Essayer plusieurs fois avec des délais croissants
                 let mut verified = false;
-                for attempt in 1..=5 {
+                let attempts = self.timings.verify_attempts();
+                for attempt in 1..=attempts {
                     if self.check_container_exists_with_executor(executor) {
                         verified = true;
                         self.log_info(&format!("Container vérifié et existant (tentative {})", attempt));
                         break;
                     }
-                    if attempt < 5 {
-                        std::thread::sleep(std::time::Duration::from_millis(500 * attempt as u64));
+                    if attempt < attempts {
+                        std::thread::sleep(self.timings.verify_delay(attempt));
                     }
                 }
                 
@@ -251,6 +628,12 @@ impl LXCDeployment {
                     self.log_error("Cela peut indiquer un problème de permissions ou de configuration LXC");
                     // Ne pas échouer, mais logger l'avertissement
                 }
+
+                // Le container n'a pas encore démarré : le réseau choisi s'appliquera au premier démarrage
+                if let Err(e) = self.apply_network(executor) {
+                    self.log_error(&format!("Réseau non appliqué: {}", e));
+                    return Err(e);
+                }
             }
         }
 
@@ -312,7 +695,7 @@ impl LXCDeployment {
         
         // Maintenant vérifier via les commandes LXC pour confirmer que LXC le gère
         // 1. Essayer lxc-ls avec sudo
-        let cmd_ls = format!("sudo -n lxc-ls -1 2>/dev/null | grep -q '^{}$' && echo 'found' || echo 'not found'", self.container_name);
+        let cmd_ls = format!("lxc-ls -1 2>/dev/null | grep -q '^{}$' && echo 'found' || echo 'not found'", self.container_name);
         if let Ok(output) = executor.run_shell(&cmd_ls, true) {
             if output.stdout.contains("found") {
                 return true;
@@ -342,7 +725,7 @@ impl LXCDeployment {
     }
     
     /// Vérifie strictement que le container n'existe plus (vérification du système de fichiers uniquement)
-    pub fn check_container_fully_removed(executor: &CommandExecutor, name: &str) -> bool {
+    pub fn check_container_fully_removed(executor: &dyn Executor, name: &str) -> bool {
         let container_paths = vec![
             format!("/var/lib/lxc/{}", name),
             format!("{}/.local/share/lxc/{}", std::env::var("HOME").unwrap_or_default(), name),
@@ -352,7 +735,8 @@ impl LXCDeployment {
         for path in &container_paths {
             let cmd_check = format!("test -d {} && echo 'exists' || echo 'not exists'", path);
             if let Ok(output) = executor.run_shell(&cmd_check, true) {
-                if output.stdout.contains("exists") {
+                // « not exists » contient aussi « exists » : comparer la ligne entière
+                if output.stdout.trim() == "exists" {
                     return false; // Le répertoire existe encore
                 }
             }
@@ -361,13 +745,412 @@ impl LXCDeployment {
         true // Aucun répertoire trouvé, le container est vraiment supprimé
     }
     
+    /// Active ou désactive le démarrage automatique du container au boot de l'hôte
+    ///
+    /// Modifie `lxc.start.auto` (et `lxc.start.delay` si fourni) dans la configuration,
+    /// puis relit le fichier pour vérifier que la valeur a bien été appliquée.
+    pub fn set_autostart(executor: &dyn Executor, name: &str, enabled: bool, delay: Option<u32>) -> Result<(), ExecError> {
+        let config = format!("/var/lib/lxc/{}/config", name);
+        let check = executor.run_shell(&format!("test -f {} && echo 'present' || echo 'missing'", config), true)?;
+        if !check.stdout.contains("present") {
+            return Err(ExecError::Failed(format!("Configuration introuvable: {}", config)));
+        }
+
+        let mut entries = format!("lxc.start.auto = {}\n", if enabled { 1 } else { 0 });
+        if let (true, Some(delay)) = (enabled, delay) {
+            entries.push_str(&format!("lxc.start.delay = {}\n", delay));
+        }
+        let cmd = format!(
+            "sed -i -e '/^lxc\\.start\\.auto[[:space:]]*=/d' -e '/^lxc\\.start\\.delay[[:space:]]*=/d' {cfg} && printf '{entries}' >> {cfg}",
+            cfg = config,
+            entries = entries
+        );
+        let output = executor.run_shell(&cmd, true)?;
+        if output.exit_code != Some(0) {
+            return Err(ExecError::Failed(format!("Modification de {} impossible: {}", config, output.stderr.trim())));
+        }
+
+        // Relire la configuration pour confirmer la nouvelle valeur
+        let verify = executor.run_shell(&format!("grep -E '^lxc\\.start\\.auto' {}", config), true)?;
+        let applied = verify.stdout
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .any(|(_, value)| value.trim() == if enabled { "1" } else { "0" });
+        if !applied {
+            return Err(ExecError::Failed("La valeur lxc.start.auto n'a pas été appliquée".to_string()));
+        }
+        Ok(())
+    }
+
+    /// Contenu de `/var/lib/lxc/<name>/config`, lu avec les droits administrateur
+    pub fn read_config(executor: &dyn Executor, name: &str) -> Result<String, ExecError> {
+        let config = format!("/var/lib/lxc/{}/config", name);
+        let output = executor.run_shell(&format!("cat {}", config), true)?;
+        if output.exit_code != Some(0) {
+            return Err(ExecError::Failed(format!("Lecture de {} impossible: {}", config, output.stderr.trim())));
+        }
+        Ok(output.stdout)
+    }
+
+    /// Remplace la configuration du container par `content`, refusée si elle ne passe pas
+    /// `validate_container_config`
+    ///
+    /// La version précédente est conservée dans `config.bak`.
+    pub fn write_config(executor: &dyn Executor, name: &str, content: &str) -> Result<(), ExecError> {
+        validate_container_config(content).map_err(ExecError::Failed)?;
+        let config = format!("/var/lib/lxc/{}/config", name);
+        let mut contents = content.to_string();
+        if !contents.ends_with('\n') {
+            contents.push('\n');
+        }
+        let cmd = format!(
+            "cp -p {cfg} {cfg}.bak && cat > {cfg} <<'RMDB_EOF'\n{contents}RMDB_EOF",
+            cfg = config,
+            contents = contents
+        );
+        let output = executor.run_shell(&cmd, true)?;
+        if output.exit_code != Some(0) {
+            return Err(ExecError::Failed(format!("Écriture de {} impossible: {}", config, output.stderr.trim())));
+        }
+        Ok(())
+    }
+
+    /// Diagnostic de détection d'un container : version de LXC, `lxc-ls` avec et sans droits,
+    /// `lxc list`, système de fichiers et `list_all_containers`
+    ///
+    /// Les résultats alimentent à la fois l'affichage et le rapport texte (`diagnostic_report`).
+    pub fn diagnose_detection(executor: &CommandExecutor, name: &str, version: Option<(u32, u32)>) -> Vec<DiagnosticResult> {
+        let mut results = Vec::new();
+
+        results.push(match version {
+            Some(v) if is_unsupported_lxc_version(v) => {
+                DiagnosticResult::new("Version de LXC", DiagnosticStatus::Warning, format!("{}.{} (non prise en charge)", v.0, v.1))
+            }
+            Some(v) => DiagnosticResult::new("Version de LXC", DiagnosticStatus::Ok, format!("{}.{}", v.0, v.1)),
+            None => DiagnosticResult::new("Version de LXC", DiagnosticStatus::Warning, "inconnue".to_string()),
+        });
+
+        let listed = |output: &CommandOutput| output.stdout.lines().any(|line| line.trim() == name);
+
+        let title = "1. Test lxc-ls (avec sudo)";
+        results.push(match executor.run_shell("lxc-ls -1 2>&1", true) {
+            Ok(output) if listed(&output) => DiagnosticResult::new(title, DiagnosticStatus::Ok, "Container trouvé".to_string()),
+            Ok(output) => {
+                let mut result = DiagnosticResult::new(title, DiagnosticStatus::Failed, "Container non trouvé".to_string());
+                if !output.stdout.trim().is_empty() {
+                    let preview = output.stdout.lines().take(3).collect::<Vec<_>>().join(", ");
+                    result.details.push(format!("Containers vus: {}", preview));
+                }
+                result
+            }
+            Err(e) => DiagnosticResult::new(title, DiagnosticStatus::Failed, format!("Erreur: {}", e)),
+        });
+
+        let title = "2. Test lxc-ls (sans sudo)";
+        results.push(match executor.run_shell("lxc-ls -1 2>&1", false) {
+            Ok(output) if listed(&output) => DiagnosticResult::new(title, DiagnosticStatus::Ok, "Container trouvé".to_string()),
+            Ok(_) => DiagnosticResult::new(title, DiagnosticStatus::Warning, "Container non trouvé (normal si permissions requises)".to_string()),
+            Err(e) => DiagnosticResult::new(title, DiagnosticStatus::Warning, format!("Erreur (attendu): {}", e)),
+        });
+
+        let title = "3. Test lxc list";
+        results.push(match executor.run_shell("lxc list --format csv -c n 2>&1", false) {
+            Ok(output) if output.stdout.lines().any(|line| line.contains(name)) => {
+                DiagnosticResult::new(title, DiagnosticStatus::Ok, "Container trouvé".to_string())
+            }
+            Ok(_) => DiagnosticResult::new(title, DiagnosticStatus::Failed, "Container non trouvé".to_string()),
+            Err(e) => DiagnosticResult::new(title, DiagnosticStatus::Warning, format!("Erreur: {}", e)),
+        });
+
+        let title = "4. Test système de fichiers";
+        let found: Vec<String> = [format!("/var/lib/lxc/{}", name), format!("/var/lib/lxd/containers/{}", name)]
+            .into_iter()
+            .filter(|path| {
+                executor
+                    .run_shell(&format!("test -d {} && echo 'found' || echo 'not found'", path), true)
+                    .is_ok_and(|o| o.stdout.trim() == "found")
+            })
+            .collect();
+        results.push(if found.is_empty() {
+            DiagnosticResult::new(title, DiagnosticStatus::Failed, "Container non trouvé dans le système de fichiers".to_string())
+        } else {
+            DiagnosticResult::new(title, DiagnosticStatus::Ok, format!("Trouvé: {}", found.join(", ")))
+        });
+
+        let title = "5. Test list_all_containers()";
+        results.push(match Self::list_all_containers(executor) {
+            Ok(containers) if containers.iter().any(|c| c.name == name) => DiagnosticResult::new(
+                title,
+                DiagnosticStatus::Ok,
+                format!("Container trouvé ({} containers au total)", containers.len()),
+            ),
+            Ok(containers) => {
+                let mut result = DiagnosticResult::new(
+                    title,
+                    DiagnosticStatus::Failed,
+                    format!("Container non trouvé ({} autres containers vus)", containers.len()),
+                );
+                result.details.extend(containers.iter().take(3).map(|c| format!("- {} ({})", c.name, c.status)));
+                result
+            }
+            Err(e) => DiagnosticResult::new(title, DiagnosticStatus::Failed, format!("Erreur: {}", e)),
+        });
+
+        let title = "6. Politique de sécurité (SELinux/AppArmor)";
+        let policy = Self::check_mac_policy(executor);
+        let denials = Self::mac_denials(executor, policy, name);
+        let status = match policy {
+            _ if !denials.is_empty() => DiagnosticStatus::Failed,
+            MacPolicyStatus::SelinuxEnforcing => DiagnosticStatus::Warning,
+            _ => DiagnosticStatus::Ok,
+        };
+        let mut result = DiagnosticResult::new(title, status, policy.label().to_string());
+        result.details.extend(denials.iter().map(|d| format!("- {}", d)));
+        result.details.extend(mac_policy_hint(policy, &denials));
+        results.push(result);
+
+        results
+    }
+
+    /// Politique de contrôle d'accès obligatoire active sur l'hôte
+    ///
+    /// `getenforce` est consulté en premier (RHEL, Fedora) ; à défaut, le module AppArmor
+    /// (Debian, Ubuntu). Une commande absente vaut « aucune politique ».
+    pub fn check_mac_policy(executor: &dyn Executor) -> MacPolicyStatus {
+        if let Ok(output) = executor.run_shell("getenforce 2>/dev/null", false) {
+            match output.stdout.trim() {
+                "Enforcing" => return MacPolicyStatus::SelinuxEnforcing,
+                "Permissive" => return MacPolicyStatus::SelinuxPermissive,
+                _ => {}
+            }
+        }
+        match executor.run_shell("cat /sys/module/apparmor/parameters/enabled 2>/dev/null", false) {
+            Ok(output) if output.stdout.trim() == "Y" => MacPolicyStatus::AppArmor,
+            _ => MacPolicyStatus::None,
+        }
+    }
+
+    /// Refus récents de la politique active concernant LXC ou le container `name`
+    ///
+    /// SELinux : `ausearch` (auditd), puis le journal du noyau si auditd est absent.
+    /// AppArmor : journal du noyau uniquement.
+    pub fn mac_denials(executor: &dyn Executor, policy: MacPolicyStatus, name: &str) -> Vec<String> {
+        let read = |cmd: &str| executor.run_shell(cmd, true).map(|o| o.stdout).unwrap_or_default();
+        let log = match policy {
+            MacPolicyStatus::None => return Vec::new(),
+            MacPolicyStatus::SelinuxEnforcing | MacPolicyStatus::SelinuxPermissive => {
+                let audit = read("ausearch -m avc -ts recent 2>/dev/null | tail -n 50");
+                if audit.trim().is_empty() {
+                    read("dmesg 2>/dev/null | grep -i 'avc:.*denied' | tail -n 50")
+                } else {
+                    audit
+                }
+            }
+            MacPolicyStatus::AppArmor => read("dmesg 2>/dev/null | grep -i 'apparmor=\"DENIED\"' | tail -n 50"),
+        };
+        filter_denials(&log, name)
+    }
+
+    /// Champs de `lxc-info -n <name>` (`State`, `PID`, `IP`, `Memory use`...)
+    ///
+    /// Un container arrêté n'a que `Name` et `State` ; un container inconnu ou
+    /// `lxc-info` absent donne une table vide. Essaie avec sudo puis sans.
+    pub fn info_fields(executor: &dyn Executor, name: &str) -> BTreeMap<String, String> {
+        let cmd = format!("lxc-info -n {} 2>/dev/null", name);
+        [true, false]
+            .into_iter()
+            .filter_map(|requires_admin| executor.run_shell(&cmd, requires_admin).ok())
+            .map(|output| parse_lxc_info(&output.stdout))
+            .find(|fields| !fields.is_empty())
+            .unwrap_or_default()
+    }
+
+    /// Gestionnaire de services du container (`rc-service`, `systemctl` ou aucun)
+    ///
+    /// `None` si `lxc-attach` échoue (container arrêté, gelé ou absent) : rien à conserver.
+    pub fn detect_container_init(executor: &dyn Executor, name: &str) -> Option<ContainerInit> {
+        let cmd = format!("lxc-attach -n {} -- sh -c '{}' 2>/dev/null", name, CONTAINER_INIT_SCRIPT);
+        executor
+            .run_shell(&cmd, true)
+            .ok()
+            .filter(|output| output.exit_code == Some(0))
+            .and_then(|output| ContainerInit::parse(&output.stdout))
+    }
+
+    /// Consommation mémoire et CPU du container, lue dans ses fichiers cgroup (v1 ou v2)
+    ///
+    /// Les fichiers sont lus depuis le container (`lxc-attach`), où l'espace de noms cgroup
+    /// expose ceux du container à la racine de `/sys/fs/cgroup`. Deux relevés à
+    /// `CPU_SAMPLE_INTERVAL` d'écart donnent l'utilisation CPU courante.
+    pub fn resource_usage(executor: &dyn Executor, name: &str) -> ResourceUsage {
+        let cmd = format!("lxc-attach -n {} -- sh -c '{}' 2>/dev/null", name, CGROUP_READ_SCRIPT);
+        let sample = || executor.run_shell(&cmd, true).map(|o| parse_cgroup_usage(&o.stdout)).unwrap_or_default();
+
+        let first = sample();
+        let started = std::time::Instant::now();
+        std::thread::sleep(CPU_SAMPLE_INTERVAL);
+        let mut usage = sample();
+        let elapsed_usec = started.elapsed().as_micros() as f64;
+        if let (Some(before), Some(after)) = (first.cpu_time_usec, usage.cpu_time_usec) {
+            usage.cpu_percent = Some(after.saturating_sub(before) as f64 / elapsed_usec * 100.0);
+        }
+        usage
+    }
+
+    /// Vérifie que RMDB répond à l'intérieur du container : processus rmdbd, port de l'API
+    /// en écoute et réponse locale de l'API (curl, ou wget sur Alpine minimal).
+    pub fn check_rmdb_health(executor: &dyn Executor, name: &str) -> RmdbHealth {
+        let attach = |cmd: &str| executor.run_shell(&format!("lxc-attach -n {} -- sh -c \"{}\"", name, cmd), true);
+        let mut checks = Vec::new();
+
+        let process = attach("pgrep -x rmdbd");
+        checks.push(match process {
+            Ok(out) if out.exit_code == Some(0) => HealthCheck::passed(
+                "Processus rmdbd",
+                format!("PID {}", out.stdout.split_whitespace().collect::<Vec<_>>().join(", ")),
+            ),
+            Ok(_) => HealthCheck::failed("Processus rmdbd", "rmdbd ne tourne pas".to_string()),
+            Err(e) => HealthCheck::failed("Processus rmdbd", e.to_string()),
+        });
+
+        let port_label = format!("Port {}/tcp en écoute", RMDB_API_PORT);
+        checks.push(match attach("netstat -ltn 2>/dev/null || ss -ltn") {
+            Ok(out) if is_port_listening(&out.stdout, RMDB_API_PORT) => HealthCheck::passed(&port_label, "LISTEN".to_string()),
+            Ok(_) => HealthCheck::failed(&port_label, "aucune socket en écoute".to_string()),
+            Err(e) => HealthCheck::failed(&port_label, e.to_string()),
+        });
+
+        let url = format!("http://127.0.0.1:{}/api/system/metrics", RMDB_API_PORT);
+        let probe = format!(
+            "if command -v curl >/dev/null; then curl -fsS -m 5 -o /dev/null {url}; else wget -q -T 5 -O /dev/null {url}; fi",
+            url = url
+        );
+        checks.push(match attach(&probe) {
+            Ok(out) if out.exit_code == Some(0) => HealthCheck::passed("API locale", url),
+            Ok(out) => {
+                let reason = out.stderr.lines().next().unwrap_or("pas de réponse").trim().to_string();
+                HealthCheck::failed("API locale", reason)
+            }
+            Err(e) => HealthCheck::failed("API locale", e.to_string()),
+        });
+
+        RmdbHealth { checks }
+    }
+
+    /// Mesure la taille du template Alpine dans le cache LXC (en octets)
+    pub fn template_cache_size(executor: &dyn Executor) -> Option<u64> {
+        let cmd = "du -sb /var/cache/lxc/download/alpine /var/cache/lxc/alpine 2>/dev/null | awk '{s+=$1} END {print s+0}'";
+        let output = executor.run_shell(cmd, false).ok()?;
+        output.stdout.trim().parse::<u64>().ok().filter(|size| *size > 0)
+    }
+
+    /// Déplace le répertoire du container vers une sauvegarde horodatée avant suppression
+    ///
+    /// Les sauvegardes sont placées dans `LXC_TRASH_DIR` ; seules les
+    /// `LXC_TRASH_MAX_BACKUPS` plus récentes sont conservées.
+    pub fn backup_before_remove(executor: &CommandExecutor, name: &str) -> io::Result<PathBuf> {
+        let to_io = |e: ExecError| io::Error::other(e.to_string());
+
+        let source = format!("/var/lib/lxc/{}", name);
+        let check = executor.run_shell(&format!("test -d {} && echo 'exists' || echo 'missing'", source), true)
+            .map_err(to_io)?;
+        if !check.stdout.contains("exists") {
+            return Err(io::Error::new(io::ErrorKind::NotFound, format!("Répertoire introuvable: {}", source)));
+        }
+
+        let timestamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+        let backup = PathBuf::from(LXC_TRASH_DIR).join(format!("{}-{}", name, timestamp));
+        let move_cmd = format!("mkdir -p {} && mv {} {}", LXC_TRASH_DIR, source, backup.display());
+        let output = executor.run_shell(&move_cmd, true).map_err(to_io)?;
+        if output.exit_code != Some(0) {
+            return Err(io::Error::other(format!("Échec de la sauvegarde: {}", output.stderr.trim())));
+        }
+
+        // Limiter l'espace disque utilisé par la corbeille
+        let prune_cmd = format!(
+            "ls -1dt {}/*/ 2>/dev/null | tail -n +{} | xargs -r rm -rf",
+            LXC_TRASH_DIR, LXC_TRASH_MAX_BACKUPS + 1
+        );
+        let _ = executor.run_shell(&prune_cmd, true);
+
+        Ok(backup)
+    }
+
+    /// Noms des containers gérés : `lxc-ls` (LXC) et `lxc list` (LXD), avec sudo
+    ///
+    /// Erreur si `lxc-ls` échoue (session sudo expirée...) : une liste vide ferait passer chaque
+    /// container réel pour un fantôme. `lxc list` est facultatif (hôte sans LXD). Les noms ne sont
+    /// pas filtrés par `validate_container_name`, plus stricte que LXC (`web.prod`).
+    pub fn managed_container_names(executor: &dyn Executor) -> Result<HashSet<String>, ExecError> {
+        let output = executor.run_shell("lxc-ls -1 2>/dev/null", true)?;
+        if output.exit_code != Some(0) {
+            return Err(ExecError::Failed(format!("lxc-ls a échoué (code {:?}) : liste des containers inconnue", output.exit_code)));
+        }
+        let mut names: HashSet<String> = output.stdout.split_whitespace().map(str::to_string).collect();
+
+        if let Ok(output) = executor.run_shell("lxc list --format csv -c n 2>/dev/null", true) {
+            if output.exit_code == Some(0) {
+                names.extend(output.stdout.split_whitespace().map(str::to_string));
+            }
+        }
+        Ok(names)
+    }
+
+    /// Containers incohérents entre le disque et LXC
+    ///
+    /// Parcourt `GHOST_SCAN_ROOTS` (répertoires cachés ignorés, dont la corbeille) et compare
+    /// avec `managed_container_names` : répertoire non géré, container géré sans répertoire,
+    /// ou même nom sous LXC et LXD. Erreur, sans rien signaler, si la liste des containers gérés
+    /// est illisible.
+    pub fn find_ghost_containers(executor: &dyn Executor) -> Result<Vec<GhostContainer>, ExecError> {
+        let managed = Self::managed_container_names(executor)?;
+        let mut on_disk: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for root in GHOST_SCAN_ROOTS {
+            let Ok(output) = executor.run_shell(&format!("ls -1Ap {} 2>/dev/null", root), true) else {
+                continue;
+            };
+            for entry in output.stdout.lines().filter_map(|line| line.trim().strip_suffix('/')) {
+                if !entry.starts_with('.') {
+                    on_disk.entry(entry.to_string()).or_default().push(format!("{}/{}", root, entry));
+                }
+            }
+        }
+
+        let mut ghosts = Vec::new();
+        for (name, paths) in &on_disk {
+            let kind = if paths.len() > 1 {
+                GhostKind::Duplicate
+            } else if !managed.contains(name) {
+                GhostKind::OnDiskOnly
+            } else {
+                continue;
+            };
+            ghosts.push(GhostContainer { name: name.clone(), kind, paths: paths.clone() });
+        }
+        let mut listed_only: Vec<&String> = managed.iter().filter(|name| !on_disk.contains_key(*name)).collect();
+        listed_only.sort();
+        for name in listed_only {
+            ghosts.push(GhostContainer { name: name.clone(), kind: GhostKind::ListedOnly, paths: Vec::new() });
+        }
+        Ok(ghosts)
+    }
+
     /// Nettoie les entrées fantômes d'un container (détecté par lxc-ls mais n'existant pas dans le système de fichiers)
+    ///
+    /// Un répertoire `/var/lib/lxc/<name>` restant est d'abord déplacé dans la corbeille
+    /// (`backup_before_remove`) ; rien n'est nettoyé si ce déplacement échoue.
     pub fn cleanup_ghost_container(executor: &CommandExecutor, name: &str) -> Result<CommandOutput, ExecError> {
+        match Self::backup_before_remove(executor, name) {
+            Ok(_) => {}
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(ExecError::Failed(e.to_string())),
+        }
+
         // Essayer de nettoyer les caches LXC
         let cleanup_cmds = vec![
-            format!("sudo -n lxc-ls -1 2>/dev/null | grep -q '^{}$' || true", name), // Vérifier si détecté
-            format!("sudo -n rm -f /var/lib/lxc/.lxc-lock-{} 2>/dev/null || true", name), // Supprimer les locks
-            format!("sudo -n rm -f /var/lib/lxc/{}/.lxc-lock 2>/dev/null || true", name), // Supprimer les locks dans le répertoire
+            format!("lxc-ls -1 2>/dev/null | grep -q '^{}$' || true", name), // Vérifier si détecté
+            format!("rm -f /var/lib/lxc/.lxc-lock-{} 2>/dev/null || true", name), // Supprimer les locks
+            format!("rm -f /var/lib/lxc/{}/.lxc-lock 2>/dev/null || true", name), // Supprimer les locks dans le répertoire
         ];
         
         for cmd in &cleanup_cmds {
@@ -393,7 +1176,8 @@ impl LXCDeployment {
         for path in &possible_paths {
             let cmd_check = format!("test -f {} && echo 'found' || echo 'not found'", path);
             if let Ok(output) = executor.run_shell(&cmd_check, true) {
-                if output.stdout.contains("found") {
+                // "not found" contient aussi "found" : comparer la ligne entière
+                if output.stdout.trim() == "found" {
                     return Some(path.clone());
                 }
             }
@@ -403,6 +1187,15 @@ impl LXCDeployment {
     }
 
     pub fn start_container(&self, executor: &CommandExecutor) -> Result<CommandOutput, ExecError> {
+        // Gelé, le container tourne déjà : le dégeler suffit
+        if Self::is_frozen(executor, &self.container_name) {
+            self.log_info(&format!("Container '{}' gelé (FROZEN) : dégel au lieu du démarrage", self.container_name));
+            let result = Self::unfreeze_container(executor, &self.container_name);
+            if let Ok(ref output) = result {
+                self.log_command_output(output);
+            }
+            return result;
+        }
         self.log_info(&format!("Démarrage du container '{}'", self.container_name));
         
         // Trouver le chemin du fichier de configuration
@@ -478,8 +1271,7 @@ impl LXCDeployment {
 
         // Essayer aussi avec sudo
         if !verification.detectable_by_ls {
-            let cmd_ls_sudo = format!("sudo -n lxc-ls -1 2>/dev/null | grep -q '^{}$' && echo 'found' || echo 'not found'", self.container_name);
-            if let Ok(output) = executor.run_shell(&cmd_ls_sudo, true) {
+            if let Ok(output) = executor.run_shell(&cmd_ls, true) {
                 if output.stdout.contains("found") {
                     verification.detectable_by_ls = true;
                 }
@@ -516,7 +1308,9 @@ impl LXCDeployment {
             Ok(status) => {
                 verification.can_get_status = true;
                 verification.is_running = status == "RUNNING";
-                if !verification.is_running {
+                if status == "FROZEN" {
+                    verification.errors.push("Container gelé (FROZEN) : ses processus sont suspendus, dégelez-le".to_string());
+                } else if !verification.is_running {
                     verification.errors.push(format!("Container existe mais n'est pas en cours d'exécution (statut: {})", status));
                 }
             }
@@ -580,18 +1374,38 @@ impl LXCDeployment {
 
     // Fonctions de gestion générale des containers (tous les containers, pas seulement rmdb)
     
+    /// Liste les containers avec leur état, démarrage automatique et adresses IP (`lxc-ls -f`)
+    ///
+    /// Les containers visibles uniquement avec sudo et ceux de l'utilisateur sont fusionnés.
+    pub fn list_all_containers_detailed(executor: &dyn Executor) -> Vec<ContainerInfo> {
+        let mut containers: Vec<ContainerInfo> = Vec::new();
+        let attempts = [("lxc-ls -f 2>/dev/null", true), ("lxc-ls -f 2>/dev/null", false)];
+        for (cmd, requires_admin) in attempts {
+            if let Ok(output) = executor.run_shell(cmd, requires_admin) {
+                for info in parse_lxc_ls_fancy(&output.stdout) {
+                    if !containers.iter().any(|c| c.name == info.name) {
+                        containers.push(info);
+                    }
+                }
+            }
+        }
+        containers
+    }
+
     /// Liste tous les containers LXC disponibles
     pub fn list_all_containers(executor: &CommandExecutor) -> Result<Vec<ContainerInfo>, ExecError> {
-        let mut containers = Vec::new();
-        let mut found_names = std::collections::HashSet::new();
+        // 0. lxc-ls -f donne directement l'état, sans requête par container
+        let mut containers = Self::list_all_containers_detailed(executor);
+        let mut found_names: std::collections::HashSet<String> =
+            containers.iter().map(|c| c.name.clone()).collect();
         
         // Essayer plusieurs méthodes selon la version de LXC et combiner les résultats
         // IMPORTANT: Toujours essayer avec sudo car sur RHEL/CentOS, les containers
         // créés avec sudo ne sont visibles qu'avec sudo
         
         // 1. lxc-ls avec sudo (PRIORITAIRE - car création se fait avec sudo)
-        let cmd1_sudo = "sudo -n lxc-ls -1 2>&1";
-        if let Ok(output) = executor.run_shell(cmd1_sudo, true) {
+        let cmd1 = "lxc-ls -1 2>&1";
+        if let Ok(output) = executor.run_shell(cmd1, true) {
             // Filtrer les lignes qui sont des erreurs (commencent par "sudo:" ou contiennent "error")
             for line in output.stdout.lines() {
                 let line = line.trim();
@@ -617,16 +1431,12 @@ impl LXCDeployment {
                     && !found_names.contains(line) {
                     found_names.insert(line.to_string());
                     let status = Self::get_container_status_by_name(executor, line).unwrap_or_else(|_| "UNKNOWN".to_string());
-                    containers.push(ContainerInfo {
-                        name: line.to_string(),
-                        status,
-                    });
+                    containers.push(ContainerInfo::new(line.to_string(), status));
                 }
             }
         }
         
         // 2. lxc-ls sans sudo (au cas où certains containers sont accessibles sans sudo)
-        let cmd1 = "lxc-ls -1 2>&1";
         if let Ok(output) = executor.run_shell(cmd1, false) {
             for line in output.stdout.lines() {
                 let line = line.trim();
@@ -642,10 +1452,7 @@ impl LXCDeployment {
                     && !found_names.contains(line) {
                     found_names.insert(line.to_string());
                     let status = Self::get_container_status_by_name(executor, line).unwrap_or_else(|_| "UNKNOWN".to_string());
-                    containers.push(ContainerInfo {
-                        name: line.to_string(),
-                        status,
-                    });
+                    containers.push(ContainerInfo::new(line.to_string(), status));
                 }
             }
         }
@@ -699,10 +1506,7 @@ impl LXCDeployment {
                                 && is_valid_name
                                 && !found_names.contains(&name) {
                                 found_names.insert(name.clone());
-                                containers.push(ContainerInfo {
-                                    name,
-                                    status: status.to_uppercase(),
-                                });
+                                containers.push(ContainerInfo::new(name, status.to_uppercase()));
                             }
                         } else if parts.len() == 1 {
                             // Format simple avec juste le nom
@@ -726,10 +1530,7 @@ impl LXCDeployment {
                                 && !found_names.contains(&name) {
                                 found_names.insert(name.clone());
                                 let status = Self::get_container_status_by_name(executor, &name).unwrap_or_else(|_| "UNKNOWN".to_string());
-                                containers.push(ContainerInfo {
-                                    name,
-                                    status,
-                                });
+                                containers.push(ContainerInfo::new(name, status));
                             }
                         }
                     }
@@ -741,7 +1542,7 @@ impl LXCDeployment {
         let paths = vec!["/var/lib/lxc", "/var/lib/lxd/containers"];
         for base_path in paths {
             // Utiliser sudo pour lister les répertoires
-            let cmd_ls = format!("sudo -n ls -1 {} 2>&1", base_path);
+            let cmd_ls = format!("ls -1 {} 2>&1", base_path);
             if let Ok(output) = executor.run_shell(&cmd_ls, true) {
                 for line in output.stdout.lines() {
                     let name = line.trim().to_string();
@@ -768,8 +1569,8 @@ impl LXCDeployment {
                         && !found_names.contains(&name) {
                         // Vérifier que c'est bien un container (présence d'un fichier config ou rootfs)
                         let container_path = format!("{}/{}", base_path, name);
-                        let config_check = format!("sudo -n test -f {}/config && echo 'yes' || echo 'no'", container_path);
-                        let rootfs_check = format!("sudo -n test -d {}/rootfs && echo 'yes' || echo 'no'", container_path);
+                        let config_check = format!("test -f {}/config && echo 'yes' || echo 'no'", container_path);
+                        let rootfs_check = format!("test -d {}/rootfs && echo 'yes' || echo 'no'", container_path);
                         
                         let is_container = if let Ok(config_out) = executor.run_shell(&config_check, true) {
                             config_out.stdout.contains("yes")
@@ -784,10 +1585,7 @@ impl LXCDeployment {
                         if is_container {
                             found_names.insert(name.clone());
                             let status = Self::get_container_status_by_name(executor, &name).unwrap_or_else(|_| "UNKNOWN".to_string());
-                            containers.push(ContainerInfo {
-                                name,
-                                status,
-                            });
+                            containers.push(ContainerInfo::new(name, status));
                         }
                     }
                 }
@@ -804,10 +1602,7 @@ impl LXCDeployment {
                         if config_path.exists() || rootfs_path.exists() {
                             found_names.insert(name.clone());
                             let status = Self::get_container_status_by_name(executor, &name).unwrap_or_else(|_| "UNKNOWN".to_string());
-                            containers.push(ContainerInfo {
-                                name,
-                                status,
-                            });
+                            containers.push(ContainerInfo::new(name, status));
                         }
                     }
                 }
@@ -821,23 +1616,10 @@ impl LXCDeployment {
     }
     
     /// Obtient le statut d'un container par son nom
-    fn get_container_status_by_name(executor: &CommandExecutor, name: &str) -> Result<String, ExecError> {
-        // Essayer lxc-info avec sudo (prioritaire car création se fait avec sudo)
-        let cmd1_sudo = format!("sudo -n lxc-info -n {} -s 2>/dev/null | grep 'State:' | awk '{{print $2}}' || echo 'UNKNOWN'", name);
-        if let Ok(output) = executor.run_shell(&cmd1_sudo, true) {
-            let status = output.stdout.trim().to_string();
-            if status != "UNKNOWN" && !status.is_empty() && !status.to_lowercase().contains("error") {
-                return Ok(status);
-            }
-        }
-        
-        // Essayer lxc-info sans sudo
-        let cmd1 = format!("lxc-info -n {} -s 2>/dev/null | grep 'State:' | awk '{{print $2}}' || echo 'UNKNOWN'", name);
-        if let Ok(output) = executor.run_shell(&cmd1, false) {
-            let status = output.stdout.trim().to_string();
-            if status != "UNKNOWN" && !status.is_empty() && !status.to_lowercase().contains("error") {
-                return Ok(status);
-            }
+    fn get_container_status_by_name(executor: &dyn Executor, name: &str) -> Result<String, ExecError> {
+        // Essayer lxc-info (avec sudo d'abord, car la création se fait avec sudo)
+        if let Some(state) = Self::info_fields(executor, name).remove("State").filter(|s| !s.is_empty()) {
+            return Ok(state);
         }
         
         // Essayer lxc list
@@ -850,8 +1632,8 @@ impl LXCDeployment {
         }
         
         // Essayer lxc-attach avec sudo
-        let cmd3_sudo = format!("sudo -n lxc-attach -n {} -- echo 'running' 2>/dev/null && echo 'RUNNING' || echo 'STOPPED'", name);
-        if let Ok(output) = executor.run_shell(&cmd3_sudo, true) {
+        let cmd3 = format!("lxc-attach -n {} -- echo 'running' 2>/dev/null && echo 'RUNNING' || echo 'STOPPED'", name);
+        if let Ok(output) = executor.run_shell(&cmd3, true) {
             if output.stdout.contains("RUNNING") {
                 return Ok("RUNNING".to_string());
             }
@@ -874,7 +1656,6 @@ impl LXCDeployment {
         Ok("UNKNOWN".to_string())
     }
     
-    /// Démarre un container par son nom
     /// Trouve le chemin du fichier de configuration d'un container par son nom
     fn find_container_config_path_by_name(executor: &CommandExecutor, name: &str) -> Option<String> {
         let possible_paths = vec![
@@ -895,7 +1676,14 @@ impl LXCDeployment {
         None
     }
 
+    /// Démarre un container par son nom
+    ///
+    /// Un container gelé (FROZEN) tourne déjà : `lxc-start` échouerait, il faut le dégeler.
     pub fn start_container_by_name(executor: &CommandExecutor, name: &str) -> Result<CommandOutput, ExecError> {
+        if Self::is_frozen(executor, name) {
+            return Err(ExecError::Failed(format!("Le container '{}' est gelé (FROZEN) : dégelez-le au lieu de le démarrer", name)));
+        }
+
         // Trouver le chemin du fichier de configuration
         let config_path = Self::find_container_config_path_by_name(executor, name);
         let cmd = if let Some(config) = &config_path {
@@ -967,16 +1755,80 @@ impl LXCDeployment {
         executor.run_shell(&cmd, true)
     }
     
+    /// Le container est gelé par `lxc-freeze` (état FROZEN)
+    pub fn is_frozen(executor: &dyn Executor, name: &str) -> bool {
+        Self::get_container_status_by_name(executor, name).is_ok_and(|state| state == "FROZEN")
+    }
+
+    /// Gèle un container démarré (`lxc-freeze`) : ses processus sont suspendus sans être arrêtés
+    pub fn freeze_container(executor: &dyn Executor, name: &str) -> Result<CommandOutput, ExecError> {
+        let state = Self::get_container_status_by_name(executor, name)?;
+        if state != "RUNNING" {
+            return Err(ExecError::Failed(format!("Le container '{}' n'est pas démarré (état: {})", name, state)));
+        }
+        executor.run_shell(&format!("lxc-freeze -n {}", name), true)
+    }
+
+    /// Dégèle un container gelé (`lxc-unfreeze`) : il reprend là où il s'était arrêté
+    pub fn unfreeze_container(executor: &dyn Executor, name: &str) -> Result<CommandOutput, ExecError> {
+        let state = Self::get_container_status_by_name(executor, name)?;
+        if state != "FROZEN" {
+            return Err(ExecError::Failed(format!("Le container '{}' n'est pas gelé (état: {})", name, state)));
+        }
+        executor.run_shell(&format!("lxc-unfreeze -n {}", name), true)
+    }
+
+    /// Clone un container avec `lxc-copy -n src -N dst`
+    ///
+    /// Une source démarrée est arrêtée pendant la copie puis redémarrée. La destination
+    /// ne doit pas exister ; le clone est vérifié par la présence de sa configuration.
+    pub fn clone_container(executor: &dyn Executor, src: &str, dst: &str, mode: CloneMode) -> Result<(), ExecError> {
+        if !Self::check_container_fully_removed(executor, dst) {
+            return Err(ExecError::Failed(format!("Le container '{}' existe déjà", dst)));
+        }
+
+        if Self::is_frozen(executor, src) {
+            return Err(ExecError::Failed(format!("Le container '{}' est gelé (FROZEN) : dégelez-le avant de le cloner", src)));
+        }
+        let was_running = Self::get_container_status_by_name(executor, src).is_ok_and(|state| state == "RUNNING");
+        if was_running {
+            let output = executor.run_shell(&format!("lxc-stop -n {}", src), true)?;
+            if output.exit_code != Some(0) {
+                return Err(ExecError::Failed(format!("Arrêt de '{}' impossible: {}", src, output.stderr.trim())));
+            }
+        }
+
+        let copied = executor.run_shell(&format!("lxc-copy -n {} -N {}{} 2>&1", src, dst, mode.flags()), true);
+
+        // La source retrouve son état, que la copie ait réussi ou non
+        let restarted = if was_running {
+            executor.run_shell(&format!("lxc-start -n {}", src), true).map(|o| o.exit_code == Some(0)).unwrap_or(false)
+        } else {
+            true
+        };
+
+        let output = copied?;
+        if output.exit_code != Some(0) {
+            let reason = output.stdout.lines().chain(output.stderr.lines()).find(|l| !l.trim().is_empty()).unwrap_or("erreur inconnue");
+            return Err(ExecError::Failed(format!("lxc-copy a échoué: {}", reason.trim())));
+        }
+        let check = executor.run_shell(&format!("test -f /var/lib/lxc/{}/config && echo 'present' || echo 'missing'", dst), true)?;
+        if check.stdout.trim() != "present" {
+            return Err(ExecError::Failed(format!("lxc-copy a terminé, mais le container '{}' est introuvable", dst)));
+        }
+        if !restarted {
+            return Err(ExecError::Failed(format!("Clone '{}' créé, mais le redémarrage de '{}' a échoué", dst, src)));
+        }
+        Ok(())
+    }
+
     /// Obtient des informations détaillées sur un container
     pub fn get_container_info(executor: &CommandExecutor, name: &str) -> Result<ContainerDetails, ExecError> {
         let status = Self::get_container_status_by_name(executor, name)?;
         
         // Obtenir l'IP si le container est en cours d'exécution
         let ip = if status == "RUNNING" {
-            let ip_cmd = format!("lxc-info -n {} -i 2>/dev/null | grep 'IP:' | awk '{{print $2}}' || echo ''", name);
-            executor.run_shell(&ip_cmd, false)
-                .map(|o| o.stdout.trim().to_string())
-                .unwrap_or_default()
+            Self::info_fields(executor, name).remove("IP").unwrap_or_default()
         } else {
             String::new()
         };
@@ -1000,6 +1852,290 @@ impl LXCDeployment {
 pub struct ContainerInfo {
     pub name: String,
     pub status: String,
+    /// Démarrage automatique au boot (`None` si inconnu)
+    pub autostart: Option<bool>,
+    pub ipv4: Vec<String>,
+    pub ipv6: Vec<String>,
+}
+
+impl ContainerInfo {
+    pub fn new(name: impl Into<String>, status: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            status: status.into(),
+            autostart: None,
+            ipv4: Vec::new(),
+            ipv6: Vec::new(),
+        }
+    }
+}
+
+/// Miroir Alpine utilisé par le template `alpine` sans `with_mirror`
+pub const DEFAULT_ALPINE_MIRROR: &str = "https://dl-cdn.alpinelinux.org/alpine";
+/// Serveur d'images du template `download`
+pub const LXC_IMAGES_SERVER: &str = "https://images.linuxcontainers.org";
+const LOCAL_TEMPLATE_ROOTFS: &str = "rootfs.tar.xz";
+const LOCAL_TEMPLATE_META: &str = "meta.tar.xz";
+
+/// Joignabilité des sources du template (voir `LXCDeployment::check_template_sources`)
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateSources {
+    pub mirror: String,
+    pub mirror_reachable: bool,
+    pub images_reachable: bool,
+}
+
+impl TemplateSources {
+    /// Aucune source joignable : hôte hors ligne ou isolé
+    pub fn is_offline(&self) -> bool {
+        !self.mirror_reachable && !self.images_reachable
+    }
+}
+
+/// Répertoires parcourus à la recherche de containers fantômes
+pub const GHOST_SCAN_ROOTS: &[&str] = &["/var/lib/lxc", "/var/lib/lxd/containers"];
+
+/// Incohérence relevée par `LXCDeployment::find_ghost_containers`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GhostKind {
+    /// Répertoire sur le disque que ni LXC ni LXD ne gère
+    OnDiskOnly,
+    /// Container listé par LXC sans répertoire sur le disque
+    ListedOnly,
+    /// Même nom sous `/var/lib/lxc` et `/var/lib/lxd/containers`
+    Duplicate,
+}
+
+impl GhostKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            GhostKind::OnDiskOnly => "répertoire non géré",
+            GhostKind::ListedOnly => "géré sans répertoire",
+            GhostKind::Duplicate => "doublon LXC/LXD",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct GhostContainer {
+    pub name: String,
+    pub kind: GhostKind,
+    /// Répertoires trouvés pour ce nom
+    pub paths: Vec<String>,
+}
+
+impl GhostContainer {
+    /// Nettoyable par l'interface : répertoire LXC non géré (mis à la corbeille) ou verrous
+    /// d'un container listé sans répertoire. Les répertoires LXD et les doublons sont laissés à l'opérateur.
+    pub fn can_clean(&self) -> bool {
+        match self.kind {
+            GhostKind::OnDiskOnly => self.paths.iter().all(|path| path.starts_with("/var/lib/lxc/")),
+            GhostKind::ListedOnly => true,
+            GhostKind::Duplicate => false,
+        }
+    }
+}
+
+/// Longueur maximale d'un nom de container : il sert aussi de nom d'hôte (`lxc.uts.name`)
+pub const CONTAINER_NAME_MAX_LEN: usize = 63;
+
+/// Vérifie un nom de container et le renvoie normalisé (espaces autour retirés)
+///
+/// Le nom devient un répertoire de `/var/lib/lxc`, un argument de `lxc-*` et le nom d'hôte du
+/// container : lettres et chiffres ASCII, `-` et `_`, 63 caractères au plus, commençant par une
+/// lettre ou un chiffre (sinon `-n -x` serait lu comme une option) et ne finissant pas par `-`.
+pub fn validate_container_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Nom de container vide".to_string());
+    }
+    if let Some(c) = name.chars().find(|c| !(c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))) {
+        return Err(format!("Caractère '{}' interdit dans '{}' (lettres, chiffres, - et _ uniquement)", c, name));
+    }
+    if name.len() > CONTAINER_NAME_MAX_LEN {
+        return Err(format!("Nom trop long: {} caractères (maximum {})", name.len(), CONTAINER_NAME_MAX_LEN));
+    }
+    if !name.starts_with(|c: char| c.is_ascii_alphanumeric()) {
+        return Err(format!("'{}' doit commencer par une lettre ou un chiffre", name));
+    }
+    if name.ends_with('-') {
+        return Err(format!("'{}' ne doit pas finir par '-' (nom d'hôte invalide)", name));
+    }
+    Ok(name.to_string())
+}
+
+/// Vérifie qu'un répertoire de cache de template contient les deux archives attendues
+pub fn validate_local_template(dir: &Path) -> Result<(), String> {
+    if !dir.is_dir() {
+        return Err(format!("{} n'est pas un répertoire", dir.display()));
+    }
+    let missing: Vec<&str> = [LOCAL_TEMPLATE_ROOTFS, LOCAL_TEMPLATE_META]
+        .into_iter()
+        .filter(|file| !dir.join(file).is_file())
+        .collect();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Fichier(s) manquant(s) dans {}: {}", dir.display(), missing.join(", ")))
+    }
+}
+
+/// Préfixe `cmd` par `trickle` (mode autonome, débit descendant en Ko/s) si une limite est donnée
+pub fn with_rate_limit_command(cmd: String, kbps: Option<u32>) -> String {
+    match kbps {
+        Some(kbps) => format!("trickle -s -d {} {}", kbps, cmd),
+        None => cmd,
+    }
+}
+
+/// Indique si une sortie de `lxc-create` trahit un échec de téléchargement (DNS, miroir injoignable...)
+pub fn is_network_failure(output: &str) -> bool {
+    const PATTERNS: &[&str] = &[
+        "could not resolve",
+        "temporary failure in name resolution",
+        "network is unreachable",
+        "connection timed out",
+        "connection refused",
+        "failed to download",
+        "unable to fetch",
+        "failed to fetch",
+        "no route to host",
+    ];
+    let output = output.to_lowercase();
+    PATTERNS.iter().any(|pattern| output.contains(pattern))
+}
+
+/// Extrait (majeure, mineure) de la sortie de `lxc-ls --version` (ex: "4.0.12", "lxc-ls 1.0.11")
+pub fn parse_lxc_version(output: &str) -> Option<(u32, u32)> {
+    output.split_whitespace().find_map(|word| {
+        let mut parts = word.split('.');
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.split(|c: char| !c.is_ascii_digit()).next()?.parse().ok()?;
+        Some((major, minor))
+    })
+}
+
+/// Vrai si la version est antérieure à `LXC_MIN_SUPPORTED_VERSION`
+pub fn is_unsupported_lxc_version(version: (u32, u32)) -> bool {
+    version < LXC_MIN_SUPPORTED_VERSION
+}
+
+/// Vérifie qu'une configuration de container reste utilisable avant de l'écrire
+///
+/// Les clés de `REQUIRED_CONFIG_KEYS` doivent avoir une valeur (ancienne ou nouvelle syntaxe) ;
+/// une ligne `RMDB_EOF` est refusée car elle terminerait l'écriture par heredoc.
+pub fn validate_container_config(content: &str) -> Result<(), String> {
+    let keys: HashSet<&str> = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| line.split_once('='))
+        .filter(|(_, value)| !value.trim().is_empty())
+        .map(|(key, _)| key.trim())
+        .collect();
+    let missing: Vec<&str> = REQUIRED_CONFIG_KEYS
+        .iter()
+        .filter(|(key, legacy)| !keys.contains(key) && !keys.contains(legacy))
+        .map(|(key, _)| *key)
+        .collect();
+    if !missing.is_empty() {
+        return Err(format!("Clé obligatoire absente ou vide : {}", missing.join(", ")));
+    }
+    if content.lines().any(|line| line.trim() == "RMDB_EOF") {
+        return Err("La ligne « RMDB_EOF » est réservée".to_string());
+    }
+    Ok(())
+}
+
+/// Clé de configuration adaptée à la version ; une version inconnue garde la syntaxe actuelle
+pub fn config_key_for(version: Option<(u32, u32)>, key: &'static str) -> &'static str {
+    match version {
+        Some(v) if v < LXC_NEW_CONFIG_KEYS_VERSION => LXC_LEGACY_CONFIG_KEYS
+            .iter()
+            .find(|(current, _)| *current == key)
+            .map(|(_, legacy)| *legacy)
+            .unwrap_or(key),
+        _ => key,
+    }
+}
+
+/// Plage (premier id, nombre) de `user` dans le contenu de /etc/subuid ou /etc/subgid
+pub fn parse_subid_range(content: &str, user: &str) -> Option<(u32, u32)> {
+    content.lines().find_map(|line| {
+        let mut fields = line.trim().split(':');
+        if fields.next()? != user {
+            return None;
+        }
+        Some((fields.next()?.parse().ok()?, fields.next()?.parse().ok()?))
+    })
+}
+
+/// Contenu d'un `default.conf` : interface veth sur `lxcbr0`, plus les idmap
+/// (plages uid, gid) pour des containers non privilégiés
+pub fn default_config_contents(version: Option<(u32, u32)>, idmap: Option<((u32, u32), (u32, u32))>) -> String {
+    let key = |k| config_key_for(version, k);
+    let mut lines = vec![
+        "# Configuration LXC par défaut (RMDB)".to_string(),
+        format!("{} = veth", key("lxc.net.0.type")),
+        format!("{} = lxcbr0", key("lxc.net.0.link")),
+        format!("{} = up", key("lxc.net.0.flags")),
+        format!("{} = 00:16:3e:xx:xx:xx", key("lxc.net.0.hwaddr")),
+    ];
+    if let Some(((uid, uid_count), (gid, gid_count))) = idmap {
+        lines.push(format!("{} = u 0 {} {}", key("lxc.idmap"), uid, uid_count));
+        lines.push(format!("{} = g 0 {} {}", key("lxc.idmap"), gid, gid_count));
+    }
+    lines.join("\n") + "\n"
+}
+
+/// Analyse la sortie tabulaire de `lxc-ls -f`
+///
+/// Les colonnes sont découpées selon la position des titres de l'en-tête, car
+/// les valeurs (listes d'IP) peuvent contenir des espaces.
+pub fn parse_lxc_ls_fancy(output: &str) -> Vec<ContainerInfo> {
+    let mut lines = output.lines().filter(|l| !l.trim().is_empty());
+    let header = match lines.find(|l| l.trim_start().starts_with("NAME")) {
+        Some(header) => header,
+        None => return Vec::new(),
+    };
+
+    // Position de début de chaque colonne
+    let mut columns: Vec<(&str, usize)> = Vec::new();
+    let mut in_word = false;
+    for (i, c) in header.char_indices() {
+        if c != ' ' && !in_word {
+            let name = header[i..].split_whitespace().next().unwrap_or("");
+            columns.push((name, i));
+        }
+        in_word = c != ' ';
+    }
+
+    let field = |line: &str, column: &str| -> Option<String> {
+        let index = columns.iter().position(|(name, _)| *name == column)?;
+        let start = columns[index].1;
+        let end = columns.get(index + 1).map(|(_, pos)| *pos).unwrap_or(line.len());
+        line.get(start.min(line.len())..end.min(line.len())).map(|v| v.trim().to_string())
+    };
+    let addresses = |value: Option<String>| -> Vec<String> {
+        value
+            .map(|v| v.split(',').map(|a| a.trim().to_string()).filter(|a| !a.is_empty() && a != "-").collect())
+            .unwrap_or_default()
+    };
+
+    lines
+        .filter_map(|line| {
+            let name = field(line, "NAME").filter(|n| !n.is_empty())?;
+            let mut info = ContainerInfo::new(name, field(line, "STATE").unwrap_or_default().to_uppercase());
+            info.autostart = field(line, "AUTOSTART").and_then(|v| match v.as_str() {
+                "1" | "YES" => Some(true),
+                "0" | "NO" => Some(false),
+                _ => None,
+            });
+            info.ipv4 = addresses(field(line, "IPV4"));
+            info.ipv6 = addresses(field(line, "IPV6"));
+            Some(info)
+        })
+        .collect()
 }
 
 #[derive(Debug, Clone)]
@@ -1022,18 +2158,331 @@ pub struct ContainerVerification {
     pub errors: Vec<String>,
 }
 
+/// Résultat d'une vérification de santé de RMDB dans un container
+#[derive(Debug, Clone)]
+pub struct HealthCheck {
+    pub label: String,
+    pub passed: bool,
+    /// PID, URL testée ou raison de l'échec
+    pub detail: String,
+}
+
+impl HealthCheck {
+    fn passed(label: &str, detail: String) -> Self {
+        Self { label: label.to_string(), passed: true, detail }
+    }
+
+    fn failed(label: &str, detail: String) -> Self {
+        Self { label: label.to_string(), passed: false, detail }
+    }
+}
+
+/// Santé de RMDB à l'intérieur d'un container (voir `LXCDeployment::check_rmdb_health`)
+#[derive(Debug, Clone)]
+pub struct RmdbHealth {
+    pub checks: Vec<HealthCheck>,
+}
+
+impl RmdbHealth {
+    pub fn is_healthy(&self) -> bool {
+        self.checks.iter().all(|c| c.passed)
+    }
+}
+
+/// Lancement de rmdbd sans gestionnaire de services, dans le container
+const RMDBD_DIRECT_START: &str = "/usr/local/bin/rmdbd -config /etc/rmdbd/config.json";
+
+/// Fichier de log de rmdbd dans le container
+const RMDBD_CONTAINER_LOG: &str = "/var/log/rmdbd.log";
+
+/// Affiche `openrc`, `systemd` (seulement si systemd est l'init du container) ou `none`
+const CONTAINER_INIT_SCRIPT: &str = "if command -v rc-service >/dev/null 2>&1; then echo openrc; \
+    elif command -v systemctl >/dev/null 2>&1 && [ -d /run/systemd/system ]; then echo systemd; \
+    else echo none; fi";
+
+/// Écart entre les deux relevés CPU de `LXCDeployment::resource_usage`
+const CPU_SAMPLE_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Affiche le type de `/sys/fs/cgroup` puis `fichier=contenu` pour chaque fichier utile (v2, puis v1)
+const CGROUP_READ_SCRIPT: &str = "stat -fc %T /sys/fs/cgroup; \
+    for f in memory.current memory.max cpu.stat cpu.max \
+    memory/memory.usage_in_bytes memory/memory.limit_in_bytes \
+    cpuacct/cpuacct.usage cpu/cpu.cfs_quota_us cpu/cpu.cfs_period_us; \
+    do [ -r /sys/fs/cgroup/$f ] && echo \"$f=$(tr \"\\n\" \" \" < /sys/fs/cgroup/$f)\"; done";
+
+/// Au-delà, une limite mémoire cgroup v1 signifie « pas de limite »
+const CGROUP_V1_UNLIMITED: u64 = 1 << 60;
+
+/// Organisation des cgroups vue depuis le container
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CgroupVersion {
+    V1,
+    V2,
+}
+
+/// Consommation d'un container (voir `LXCDeployment::resource_usage`)
+///
+/// Une limite à `None` signifie que le container n'est pas limité.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResourceUsage {
+    pub cgroup: Option<CgroupVersion>,
+    /// Mémoire utilisée et limite, en octets
+    pub memory_used: Option<u64>,
+    pub memory_limit: Option<u64>,
+    /// Temps CPU cumulé, en microsecondes
+    pub cpu_time_usec: Option<u64>,
+    /// Utilisation CPU sur l'intervalle de mesure, en % d'un cœur (100 = un cœur occupé)
+    pub cpu_percent: Option<f64>,
+    /// Limite CPU en nombre de cœurs (`cpu.max`, `cpu.cfs_quota_us`)
+    pub cpu_limit: Option<f64>,
+}
+
+/// Analyse la sortie `Clé: valeur` de `lxc-info`
+///
+/// Les clés répétées (une ligne `IP:` par adresse) sont regroupées, séparées par `, `.
+pub fn parse_lxc_info(output: &str) -> BTreeMap<String, String> {
+    let mut fields: BTreeMap<String, String> = BTreeMap::new();
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (key, value) = (key.trim(), value.trim());
+        if key.is_empty() {
+            continue;
+        }
+        fields
+            .entry(key.to_string())
+            .and_modify(|existing| {
+                existing.push_str(", ");
+                existing.push_str(value);
+            })
+            .or_insert_with(|| value.to_string());
+    }
+    fields
+}
+
+/// Analyse la sortie de `CGROUP_READ_SCRIPT` (type de système de fichiers, puis `fichier=contenu`)
+pub fn parse_cgroup_usage(output: &str) -> ResourceUsage {
+    let mut lines = output.lines();
+    let cgroup = match lines.next().map(str::trim) {
+        Some("cgroup2fs") => Some(CgroupVersion::V2),
+        Some("tmpfs") => Some(CgroupVersion::V1),
+        _ => None,
+    };
+    let files: std::collections::HashMap<&str, &str> = lines
+        .filter_map(|line| line.split_once('='))
+        .map(|(file, value)| (file.trim(), value.trim()))
+        .collect();
+    let number = |file: &str| files.get(file).and_then(|v| v.parse::<u64>().ok());
+
+    let mut usage = ResourceUsage { cgroup, ..Default::default() };
+    match cgroup {
+        Some(CgroupVersion::V2) => {
+            usage.memory_used = number("memory.current");
+            usage.memory_limit = number("memory.max");
+            usage.cpu_time_usec = files.get("cpu.stat").and_then(|stat| {
+                let mut fields = stat.split_whitespace();
+                std::iter::from_fn(|| Some((fields.next()?, fields.next()?)))
+                    .find(|(key, _)| *key == "usage_usec")
+                    .and_then(|(_, value)| value.parse().ok())
+            });
+            usage.cpu_limit = files.get("cpu.max").and_then(|max| {
+                let (quota, period) = max.split_once(' ')?;
+                Some(quota.parse::<f64>().ok()? / period.trim().parse::<f64>().ok()?)
+            });
+        }
+        Some(CgroupVersion::V1) => {
+            usage.memory_used = number("memory/memory.usage_in_bytes");
+            usage.memory_limit = number("memory/memory.limit_in_bytes").filter(|&limit| limit < CGROUP_V1_UNLIMITED);
+            usage.cpu_time_usec = number("cpuacct/cpuacct.usage").map(|nsec| nsec / 1000);
+            let quota = files.get("cpu/cpu.cfs_quota_us").and_then(|v| v.parse::<i64>().ok()).filter(|&q| q > 0);
+            usage.cpu_limit = quota.zip(number("cpu/cpu.cfs_period_us")).map(|(q, p)| q as f64 / p as f64);
+        }
+        None => {}
+    }
+    usage
+}
+
+/// Issue d'un test de diagnostic
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DiagnosticStatus {
+    Ok,
+    Warning,
+    Failed,
+}
+
+impl DiagnosticStatus {
+    /// Étiquette du rapport texte, lisible sans couleurs ni Unicode
+    pub fn tag(&self) -> &'static str {
+        match self {
+            DiagnosticStatus::Ok => "[OK]",
+            DiagnosticStatus::Warning => "[!]",
+            DiagnosticStatus::Failed => "[X]",
+        }
+    }
+}
+
+/// Un test du diagnostic de détection (voir `LXCDeployment::diagnose_detection`)
+#[derive(Debug, Clone)]
+pub struct DiagnosticResult {
+    pub title: String,
+    pub status: DiagnosticStatus,
+    pub summary: String,
+    /// Lignes complémentaires (containers vus...)
+    pub details: Vec<String>,
+}
+
+impl DiagnosticResult {
+    fn new(title: &str, status: DiagnosticStatus, summary: String) -> Self {
+        Self { title: title.to_string(), status, summary, details: Vec::new() }
+    }
+}
+
+/// Politique de contrôle d'accès obligatoire de l'hôte (voir `LXCDeployment::check_mac_policy`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MacPolicyStatus {
+    None,
+    SelinuxPermissive,
+    SelinuxEnforcing,
+    AppArmor,
+}
+
+impl MacPolicyStatus {
+    pub fn label(&self) -> &'static str {
+        match self {
+            MacPolicyStatus::None => "Aucune politique active",
+            MacPolicyStatus::SelinuxPermissive => "SELinux en mode permissif",
+            MacPolicyStatus::SelinuxEnforcing => "SELinux en mode enforcing",
+            MacPolicyStatus::AppArmor => "AppArmor actif",
+        }
+    }
+}
+
+/// Lignes de refus (`denied`) mentionnant LXC ou le container, les 5 plus récentes
+pub fn filter_denials(log: &str, name: &str) -> Vec<String> {
+    let name = name.to_lowercase();
+    let lines: Vec<String> = log
+        .lines()
+        .map(str::trim)
+        .filter(|line| {
+            let lower = line.to_lowercase();
+            lower.contains("denied") && (lower.contains("lxc") || (!name.is_empty() && lower.contains(&name)))
+        })
+        .map(str::to_string)
+        .collect();
+    lines[lines.len().saturating_sub(5)..].to_vec()
+}
+
+/// Conseils ciblés après un échec de création ou de démarrage, selon la politique et ses refus
+pub fn mac_policy_hint(policy: MacPolicyStatus, denials: &[String]) -> Vec<String> {
+    match policy {
+        MacPolicyStatus::SelinuxEnforcing | MacPolicyStatus::SelinuxPermissive if !denials.is_empty() => vec![
+            "SELinux a refusé des opérations de LXC.".to_string(),
+            "Détails: sudo ausearch -m avc -ts recent".to_string(),
+            "Test: sudo setenforce 0, puis relancer (remettre avec sudo setenforce 1)".to_string(),
+            "Correctif: installer container-selinux, ou générer un module avec audit2allow".to_string(),
+        ],
+        MacPolicyStatus::SelinuxEnforcing => vec![
+            "SELinux est en mode enforcing : aucun refus récent trouvé, mais il peut bloquer LXC.".to_string(),
+            "Vérifier avec: sudo ausearch -m avc -ts recent".to_string(),
+        ],
+        MacPolicyStatus::AppArmor if !denials.is_empty() => vec![
+            "AppArmor a refusé des opérations de LXC.".to_string(),
+            "Vérifier lxc.apparmor.profile dans la configuration du container".to_string(),
+            "Détails: sudo dmesg | grep DENIED".to_string(),
+        ],
+        _ => Vec::new(),
+    }
+}
+
+/// Rapport texte du diagnostic, à joindre à un signalement
+pub fn diagnostic_report(name: &str, results: &[DiagnosticResult]) -> String {
+    let mut report = format!(
+        "Diagnostic du container '{}' ({})\n\n",
+        name,
+        chrono::Local::now().format("%Y-%m-%d %H:%M:%S")
+    );
+    for result in results {
+        report.push_str(&format!("{}:\n  {} {}\n", result.title, result.status.tag(), result.summary));
+        for detail in &result.details {
+            report.push_str(&format!("  {}\n", detail));
+        }
+    }
+    report
+}
+
+/// Indique si la sortie de `netstat -ltn` ou `ss -ltn` contient une socket en écoute sur `port`
+pub fn is_port_listening(output: &str, port: u16) -> bool {
+    let suffix = format!(":{}", port);
+    output.lines()
+        .filter(|line| line.contains("LISTEN"))
+        .flat_map(|line| line.split_whitespace())
+        .any(|field| field.ends_with(&suffix))
+}
+
+/// État global d'une vérification de container
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VerificationStatus {
+    Ok,
+    Degraded,
+    Failed,
+}
+
+/// Gravité d'un problème détecté lors de la vérification
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IssueSeverity {
+    /// Le container reste utilisable
+    Warning,
+    /// Le container n'est pas utilisable
+    Critical,
+}
+
+#[derive(Debug, Clone)]
+pub struct Issue {
+    pub severity: IssueSeverity,
+    pub message: String,
+}
+
+impl ContainerVerification {
+    /// Liste les problèmes détectés, du plus grave au moins grave
+    pub fn issues(&self) -> Vec<Issue> {
+        let checks = [
+            (self.exists, IssueSeverity::Critical, "Container non détecté par check_container_exists()"),
+            (self.can_attach, IssueSeverity::Critical, "Impossible d'accéder au container via lxc-attach"),
+            (self.can_get_status, IssueSeverity::Warning, "Impossible d'obtenir le statut du container"),
+            (self.detectable_by_ls || self.detectable_by_list, IssueSeverity::Warning, "Container non détecté par lxc-ls ou lxc list"),
+            (self.detectable_by_filesystem, IssueSeverity::Warning, "Container non trouvé dans le système de fichiers"),
+            (self.is_running, IssueSeverity::Warning, "Container créé mais n'est pas en cours d'exécution"),
+        ];
+
+        checks.iter()
+            .filter(|(ok, _, _)| !ok)
+            .map(|(_, severity, message)| Issue {
+                severity: *severity,
+                message: message.to_string(),
+            })
+            .collect()
+    }
+
+    /// Résume la vérification : Failed si un problème critique, Degraded si avertissements
+    pub fn overall(&self) -> VerificationStatus {
+        let issues = self.issues();
+        if issues.iter().any(|i| i.severity == IssueSeverity::Critical) {
+            VerificationStatus::Failed
+        } else if issues.is_empty() {
+            VerificationStatus::Ok
+        } else {
+            VerificationStatus::Degraded
+        }
+    }
+}
+
 impl LXCDeployment {
     pub fn get_container_status(&self, executor: &CommandExecutor) -> Result<String, ExecError> {
         // Essayer plusieurs méthodes pour obtenir le statut du container
         // 1. lxc-info (LXC 1.x)
-        let cmd1 = format!("lxc-info -n {} -s 2>/dev/null | grep 'State:' | awk '{{print $2}}' || echo 'UNKNOWN'", self.container_name);
-        let output1 = executor.run_shell(&cmd1, false);
-        
-        if let Ok(ref out) = output1 {
-            let status = out.stdout.trim().to_string();
-            if status != "UNKNOWN" && !status.is_empty() {
-                return Ok(status);
-            }
+        if let Some(state) = Self::info_fields(executor, &self.container_name).remove("State").filter(|s| !s.is_empty()) {
+            return Ok(state);
         }
         
         // 2. lxc list (LXC 2.x+)
@@ -1090,7 +2539,7 @@ impl LXCDeployment {
                 Ok(_) => {
                     self.log_info("Container démarré");
                     // Attendre un peu que le container soit prêt
-                    std::thread::sleep(std::time::Duration::from_secs(2));
+                    std::thread::sleep(self.timings.container_start());
                 }
                 Err(e) => {
                     let msg = format!("Impossible de démarrer le container: {}", e);
@@ -1322,6 +2771,77 @@ chmod +x /etc/init.d/rmdbd && rc-update add rmdbd default 2>/dev/null || true'",
         }
     }
 
+    /// Réseau, rootfs et nom d'hôte de la configuration par défaut, dans la syntaxe de la version détectée
+    fn default_network_config(&self) -> String {
+        [
+            ("lxc.net.0.type", "veth".to_string()),
+            ("lxc.net.0.link", "lxcbr0".to_string()),
+            ("lxc.net.0.flags", "up".to_string()),
+            ("lxc.net.0.ipv4.address", "auto".to_string()),
+            ("lxc.net.0.ipv4.gateway", "auto".to_string()),
+            ("lxc.rootfs.path", format!("dir:{}", self.distribution.lxc_container_path())),
+            ("lxc.uts.name", "${container_name}".to_string()),
+        ]
+        .iter()
+        .map(|(key, value)| format!("{} = {}", self.config_key(key), value))
+        .collect::<Vec<_>>()
+        .join("\n")
+    }
+
+    /// Crée la configuration LXC par défaut du mode `mode` si elle est absente
+    ///
+    /// En mode non privilégié, les plages de `/etc/subuid` et `/etc/subgid` de l'utilisateur
+    /// fournissent les lignes idmap ; sans plage, rien n'est écrit.
+    pub fn ensure_default_config(&self, executor: &CommandExecutor, mode: LxcPrivilege) -> Result<DefaultConfigStatus, ExecError> {
+        let path = mode.default_config_path();
+        if path.exists() {
+            self.log_info(&format!("Configuration existante: {}", path.display()));
+            return Ok(DefaultConfigStatus::Present(path));
+        }
+
+        match mode {
+            LxcPrivilege::Privileged => {
+                let contents = default_config_contents(self.lxc_version, None);
+                let cmd = format!(
+                    "mkdir -p {dir} && cat > {path} <<'RMDB_EOF'\n{contents}RMDB_EOF",
+                    dir = path.parent().unwrap_or(Path::new("/etc/lxc")).display(),
+                    path = path.display(),
+                    contents = contents
+                );
+                self.log_command(&cmd);
+                let output = executor.run_shell(&cmd, true)?;
+                if output.exit_code != Some(0) {
+                    return Err(ExecError::Failed(format!("Écriture de {} impossible: {}", path.display(), output.stderr.trim())));
+                }
+                self.log_info(&format!("Configuration créée: {}", path.display()));
+                Ok(DefaultConfigStatus::Created(path, contents))
+            }
+            LxcPrivilege::Unprivileged => {
+                let user = std::env::var("USER").unwrap_or_default();
+                let range = |file: &str| {
+                    fs::read_to_string(file).ok().and_then(|content| parse_subid_range(&content, &user))
+                };
+                let (Some(uids), Some(gids)) = (range("/etc/subuid"), range("/etc/subgid")) else {
+                    return Err(ExecError::Failed(format!(
+                        "Aucune plage pour '{}' dans /etc/subuid et /etc/subgid (usermod --add-subuids/--add-subgids)",
+                        user
+                    )));
+                };
+                let contents = default_config_contents(self.lxc_version, Some((uids, gids)));
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir).map_err(|e| {
+                        ExecError::Failed(format!("Impossible de créer {}: {}", dir.display(), e))
+                    })?;
+                }
+                fs::write(&path, &contents).map_err(|e| {
+                    ExecError::Failed(format!("Impossible d'écrire {}: {}", path.display(), e))
+                })?;
+                self.log_info(&format!("Configuration créée: {}", path.display()));
+                Ok(DefaultConfigStatus::Created(path, contents))
+            }
+        }
+    }
+
     fn setup_lxc_config_for_rhel(&self, _executor: &CommandExecutor) -> Result<(), ExecError> {
         // Créer le répertoire de configuration LXC si nécessaire
         let config_path = self.distribution.lxc_config_path();
@@ -1350,14 +2870,8 @@ chmod +x /etc/init.d/rmdbd && rc-update add rmdbd default 2>/dev/null || true'",
                      lxc.include = /usr/share/lxc/config/common.conf\n\
                      lxc.include = /usr/share/lxc/config/userns.conf\n\
                      lxc.arch = x86_64\n\
-                     lxc.net.0.type = veth\n\
-                     lxc.net.0.link = lxcbr0\n\
-                     lxc.net.0.flags = up\n\
-                     lxc.net.0.ipv4.address = auto\n\
-                     lxc.net.0.ipv4.gateway = auto\n\
-                     lxc.rootfs.path = dir:{}\n\
-                     lxc.utsname = ${{container_name}}\n",
-                    self.distribution.lxc_container_path()
+                     {}\n",
+                    self.default_network_config()
                 )
             } else {
                 // Configuration standard
@@ -1365,14 +2879,8 @@ chmod +x /etc/init.d/rmdbd && rc-update add rmdbd default 2>/dev/null || true'",
                     "# Configuration LXC par défaut pour RMDB\n\
                      lxc.include = /usr/share/lxc/config/common.conf\n\
                      lxc.arch = x86_64\n\
-                     lxc.net.0.type = veth\n\
-                     lxc.net.0.link = lxcbr0\n\
-                     lxc.net.0.flags = up\n\
-                     lxc.net.0.ipv4.address = auto\n\
-                     lxc.net.0.ipv4.gateway = auto\n\
-                     lxc.rootfs.path = dir:{}\n\
-                     lxc.utsname = ${{container_name}}\n",
-                    self.distribution.lxc_container_path()
+                     {}\n",
+                    self.default_network_config()
                 )
             };
 
@@ -1389,4 +2897,346 @@ chmod +x /etc/init.d/rmdbd && rc-update add rmdbd default 2>/dev/null || true'",
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagnostic_report() {
+        let mut missing = DiagnosticResult::new("5. Test list_all_containers()", DiagnosticStatus::Failed, "Container non trouvé (1 autres containers vus)".to_string());
+        missing.details.push("- test-01 (STOPPED)".to_string());
+        let results = vec![
+            DiagnosticResult::new("Version de LXC", DiagnosticStatus::Ok, "5.0".to_string()),
+            missing,
+        ];
+        let report = diagnostic_report("rmdb", &results);
+        assert!(report.starts_with("Diagnostic du container 'rmdb' ("));
+        assert!(report.contains("Version de LXC:\n  [OK] 5.0\n"));
+        assert!(report.ends_with("  [X] Container non trouvé (1 autres containers vus)\n  - test-01 (STOPPED)\n"));
+    }
+
+    #[test]
+    fn test_mac_policy_denials() {
+        use crate::pres::executor::MockExecutor;
+
+        let executor = MockExecutor::new()
+            .on("getenforce", "Enforcing\n")
+            .on("ausearch", "type=AVC msg=audit(1700000000.1:42): avc:  denied  { write } for comm=\"lxc-start\"\n\
+                             type=AVC msg=audit(1700000000.2:43): avc:  denied  { read } for comm=\"httpd\"\n\
+                             type=AVC msg=audit(1700000000.3:44): avc:  denied  { mount } for path=\"/var/lib/lxc/rmdb/rootfs\"\n");
+        let policy = LXCDeployment::check_mac_policy(&executor);
+        assert_eq!(policy, MacPolicyStatus::SelinuxEnforcing);
+        let denials = LXCDeployment::mac_denials(&executor, policy, "rmdb");
+        assert_eq!(denials.len(), 2);
+        assert!(mac_policy_hint(policy, &denials)[0].starts_with("SELinux a refusé"));
+        assert!(mac_policy_hint(MacPolicyStatus::AppArmor, &[]).is_empty());
+
+        let apparmor = MockExecutor::new().on("apparmor/parameters", "Y\n");
+        assert_eq!(LXCDeployment::check_mac_policy(&apparmor), MacPolicyStatus::AppArmor);
+        assert_eq!(LXCDeployment::check_mac_policy(&MockExecutor::new()), MacPolicyStatus::None);
+    }
+
+    #[test]
+    fn test_parse_cgroup_usage() {
+        let v2 = "cgroup2fs\nmemory.current=52428800 \nmemory.max=max \ncpu.stat=usage_usec 1500000 user_usec 1000000 system_usec 500000 \ncpu.max=50000 100000 \n";
+        let usage = parse_cgroup_usage(v2);
+        assert_eq!(usage.cgroup, Some(CgroupVersion::V2));
+        assert_eq!(usage.memory_used, Some(52428800));
+        assert_eq!(usage.memory_limit, None);
+        assert_eq!(usage.cpu_time_usec, Some(1500000));
+        assert_eq!(usage.cpu_limit, Some(0.5));
+
+        let v1 = "tmpfs\nmemory/memory.usage_in_bytes=1048576 \nmemory/memory.limit_in_bytes=9223372036854771712 \n\
+                  cpuacct/cpuacct.usage=2000000000 \ncpu/cpu.cfs_quota_us=-1 \ncpu/cpu.cfs_period_us=100000 \n";
+        let usage = parse_cgroup_usage(v1);
+        assert_eq!(usage.cgroup, Some(CgroupVersion::V1));
+        assert_eq!(usage.memory_limit, None);
+        assert_eq!(usage.cpu_time_usec, Some(2000000));
+        assert_eq!(usage.cpu_limit, None);
+        assert_eq!(parse_cgroup_usage(""), ResourceUsage::default());
+    }
 
+    #[test]
+    fn test_parse_lxc_ls_fancy() {
+        let output = "\
+NAME    STATE   AUTOSTART GROUPS IPV4                  IPV6 UNPRIVILEGED
+rmdb    RUNNING 1         -      10.0.3.5, 192.168.1.9 -    false
+test-01 STOPPED 0         -      -                     -    false
+";
+        let containers = parse_lxc_ls_fancy(output);
+        assert_eq!(containers.len(), 2);
+        assert_eq!(containers[0].name, "rmdb");
+        assert_eq!(containers[0].status, "RUNNING");
+        assert_eq!(containers[0].autostart, Some(true));
+        assert_eq!(containers[0].ipv4, vec!["10.0.3.5", "192.168.1.9"]);
+        assert!(containers[0].ipv6.is_empty());
+        assert_eq!(containers[1].autostart, Some(false));
+        assert!(containers[1].ipv4.is_empty());
+        assert!(parse_lxc_ls_fancy("").is_empty());
+    }
+
+    #[test]
+    fn test_mirror_and_network_failure() {
+        let deploy = LXCDeployment::new("rmdb".to_string(), "3.20".to_string());
+        assert_eq!(deploy.alpine_template_command(), "lxc-create -n rmdb -t alpine -- --release v3.20");
+
+        let deploy = deploy.with_mirror("http://miroir.local/alpine/");
+        assert_eq!(deploy.mirror(), Some("http://miroir.local/alpine"));
+        assert!(deploy.alpine_template_command().ends_with("--mirror 'http://miroir.local/alpine'"));
+
+        assert_eq!(deploy.with_rate_limit(0).rate_limit(), None);
+        assert_eq!(
+            with_rate_limit_command("lxc-create -n rmdb -t alpine".to_string(), Some(512)),
+            "trickle -s -d 512 lxc-create -n rmdb -t alpine"
+        );
+
+        assert!(is_network_failure("wget: Could not resolve host: dl-cdn.alpinelinux.org"));
+        assert!(is_network_failure("ERROR: Failed to download http://.../APKINDEX.tar.gz"));
+        assert!(!is_network_failure("lxc-create: Container already exists"));
+    }
+
+    #[test]
+    fn test_parse_lxc_info() {
+        use crate::pres::executor::MockExecutor;
+        let running = "Name:           rmdb\nState:          RUNNING\nPID:            4242\n\
+                       IP:             10.0.3.15\nIP:             fd42::15\nMemory use:     48.20 MiB\n \
+                       TX bytes:      1.20 KiB\n";
+        let fields = parse_lxc_info(running);
+        assert_eq!(fields["State"], "RUNNING");
+        assert_eq!(fields["IP"], "10.0.3.15, fd42::15");
+        assert_eq!(fields["Memory use"], "48.20 MiB");
+        assert_eq!(fields["TX bytes"], "1.20 KiB");
+
+        // Container arrêté : seuls Name et State sont présents
+        let stopped = parse_lxc_info("Name:           rmdb\nState:          STOPPED\n");
+        assert_eq!(stopped.len(), 2);
+        assert!(!stopped.contains_key("IP"));
+
+        let executor = MockExecutor::new().on("lxc-info -n rmdb", running);
+        assert_eq!(LXCDeployment::info_fields(&executor, "rmdb")["PID"], "4242");
+        assert!(LXCDeployment::info_fields(&MockExecutor::new(), "absent").is_empty());
+    }
+
+    #[test]
+    fn test_verification_issues() {
+        let passing = ContainerVerification {
+            exists: true,
+            detectable_by_ls: true,
+            detectable_by_list: false,
+            detectable_by_filesystem: true,
+            can_get_status: true,
+            can_attach: true,
+            is_running: true,
+            errors: Vec::new(),
+        };
+        assert!(passing.issues().is_empty());
+        assert_eq!(passing.overall(), VerificationStatus::Ok);
+
+        let stopped = ContainerVerification { is_running: false, ..passing.clone() };
+        assert_eq!(stopped.overall(), VerificationStatus::Degraded);
+
+        let no_status = ContainerVerification { can_get_status: false, ..passing.clone() };
+        assert_eq!(no_status.overall(), VerificationStatus::Degraded);
+
+        let failing = ContainerVerification { can_attach: false, detectable_by_filesystem: false, ..passing };
+        let severities: Vec<IssueSeverity> = failing.issues().iter().map(|issue| issue.severity).collect();
+        assert_eq!(severities, [IssueSeverity::Critical, IssueSeverity::Warning]);
+        assert_eq!(failing.overall(), VerificationStatus::Failed);
+    }
+
+    #[test]
+    fn test_detect_container_init() {
+        use crate::pres::executor::MockExecutor;
+        let systemd = MockExecutor::new().on("command -v rc-service", "systemd\n");
+        let init = LXCDeployment::detect_container_init(&systemd, "debian").unwrap();
+        assert_eq!(init, ContainerInit::Systemd);
+        assert!(init.service_command("debian", "restart").starts_with("lxc-attach -n debian -- systemctl restart rmdbd"));
+        assert!(init.logs_command("debian", 500).starts_with("lxc-attach -n debian -- journalctl -u rmdbd"));
+
+        // lxc-attach muet (container arrêté) : pas de gestionnaire à conserver
+        assert_eq!(LXCDeployment::detect_container_init(&MockExecutor::new(), "rmdb"), None);
+        assert_eq!(
+            ContainerInit::Direct.service_command("rmdb", "stop"),
+            "lxc-attach -n rmdb -- pkill rmdbd"
+        );
+    }
+
+    #[test]
+    fn test_local_template() {
+        let dir = std::env::temp_dir().join(format!("rmdb-template-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("rootfs.tar.xz"), "").unwrap();
+        assert!(validate_local_template(&dir).unwrap_err().ends_with("meta.tar.xz"));
+        fs::write(dir.join("meta.tar.xz"), "").unwrap();
+        assert_eq!(validate_local_template(&dir), Ok(()));
+
+        let deploy = LXCDeployment::new("rmdb".to_string(), "3.20".to_string()).with_local_template(&dir);
+        let cmd = deploy.local_template_command().unwrap();
+        assert!(cmd.starts_with("lxc-create -n rmdb -t local -- --metadata '"));
+        assert!(cmd.ends_with("rootfs.tar.xz'"));
+        let _ = fs::remove_dir_all(&dir);
+
+        let offline = TemplateSources { mirror: DEFAULT_ALPINE_MIRROR.to_string(), mirror_reachable: false, images_reachable: false };
+        assert!(offline.is_offline());
+    }
+
+    #[test]
+    fn test_is_port_listening() {
+        let netstat = "\
+Active Internet connections (only servers)
+Proto Recv-Q Send-Q Local Address           Foreign Address         State
+tcp        0      0 0.0.0.0:8080            0.0.0.0:*               LISTEN
+tcp        0      0 10.0.3.5:48080          10.0.3.1:22             ESTABLISHED
+";
+        assert!(is_port_listening(netstat, 8080));
+        assert!(!is_port_listening(netstat, 48080));
+        assert!(is_port_listening("LISTEN 0 128 [::]:8080 [::]:*", 8080));
+        assert!(!is_port_listening("", 8080));
+    }
+
+    #[test]
+    fn test_lxc_version_and_config_keys() {
+        assert_eq!(parse_lxc_version("4.0.12\n"), Some((4, 0)));
+        assert_eq!(parse_lxc_version("lxc-ls 1.0.11"), Some((1, 0)));
+        assert_eq!(parse_lxc_version("5.0.3~git2209-g5a7b9ce67"), Some((5, 0)));
+        assert_eq!(parse_lxc_version("command not found"), None);
+
+        assert!(is_unsupported_lxc_version((1, 0)));
+        assert!(!is_unsupported_lxc_version((3, 0)));
+
+        assert_eq!(config_key_for(Some((2, 0)), "lxc.uts.name"), "lxc.utsname");
+        assert_eq!(config_key_for(Some((4, 0)), "lxc.uts.name"), "lxc.uts.name");
+        assert_eq!(config_key_for(None, "lxc.rootfs.path"), "lxc.rootfs.path");
+    }
+
+    #[test]
+    fn test_default_config_contents() {
+        let subuid = "root:100000:65536\nalice:165536:65536\n";
+        assert_eq!(parse_subid_range(subuid, "alice"), Some((165536, 65536)));
+        assert_eq!(parse_subid_range(subuid, "bob"), None);
+
+        let privileged = default_config_contents(Some((4, 0)), None);
+        assert!(privileged.contains("lxc.net.0.link = lxcbr0\n"));
+        assert!(!privileged.contains("idmap"));
+
+        let legacy = default_config_contents(Some((2, 0)), Some(((165536, 65536), (165536, 65536))));
+        assert!(legacy.contains("lxc.network.type = veth"));
+        assert!(legacy.contains("lxc.id_map = u 0 165536 65536\n"));
+        assert!(legacy.contains("lxc.id_map = g 0 165536 65536\n"));
+    }
+
+    #[test]
+    fn test_autostart_and_removal_with_mock() {
+        use crate::pres::executor::MockExecutor;
+
+        let mock = MockExecutor::new()
+            .on("test -f /var/lib/lxc/rmdb/config", "present\n")
+            .on("grep -E", "lxc.start.auto = 1\n");
+        assert!(LXCDeployment::set_autostart(&mock, "rmdb", true, Some(5)).is_ok());
+        let issued = mock.issued();
+        assert_eq!(issued.len(), 3);
+        assert!(issued.iter().all(|(_, admin)| *admin));
+        assert!(issued[1].0.contains("lxc.start.delay = 5"));
+
+        let missing = MockExecutor::new().on("test -f", "missing\n");
+        assert!(LXCDeployment::set_autostart(&missing, "rmdb", false, None).is_err());
+        assert_eq!(missing.commands().len(), 1);
+
+        let gone = MockExecutor::new().on("test -d", "not exists\n");
+        assert!(LXCDeployment::check_container_fully_removed(&gone, "rmdb"));
+        let left = MockExecutor::new().on("test -d /var/lib/lxc/rmdb", "exists\n").on("test -d", "not exists\n");
+        assert!(!LXCDeployment::check_container_fully_removed(&left, "rmdb"));
+    }
+
+    #[test]
+    fn test_clone_container() {
+        use crate::pres::executor::MockExecutor;
+
+        let mock = MockExecutor::new()
+            .on("lxc-info -n rmdb", "Name: rmdb\nState: RUNNING\n")
+            .on("test -d", "not exists\n")
+            .on("test -f /var/lib/lxc/rmdb-2/config", "present\n");
+        assert!(LXCDeployment::clone_container(&mock, "rmdb", "rmdb-2", CloneMode::Overlay).is_ok());
+        let commands = mock.commands();
+        let position = |prefix: &str| commands.iter().position(|c| c.starts_with(prefix)).unwrap();
+        assert!(position("lxc-stop -n rmdb") < position("lxc-copy -n rmdb -N rmdb-2 -s -B overlay"));
+        assert!(position("lxc-copy") < position("lxc-start -n rmdb"));
+
+        let frozen = MockExecutor::new().on("lxc-info -n rmdb", "Name: rmdb\nState: FROZEN\n").on("test -d", "not exists\n");
+        assert!(LXCDeployment::clone_container(&frozen, "rmdb", "rmdb-2", CloneMode::Copy).is_err());
+
+        let taken = MockExecutor::new().on("test -d /var/lib/lxc/rmdb-2", "exists\n");
+        assert!(LXCDeployment::clone_container(&taken, "rmdb", "rmdb-2", CloneMode::Copy).is_err());
+        assert!(!taken.commands().iter().any(|c| c.contains("lxc-copy")));
+    }
+
+    #[test]
+    fn test_validate_container_name() {
+        assert_eq!(validate_container_name("  rmdb-2 "), Ok("rmdb-2".to_string()));
+        assert!(validate_container_name("9lab_x").is_ok());
+        assert!(validate_container_name("").is_err());
+        assert!(validate_container_name("-rmdb").is_err());
+        assert!(validate_container_name("rmdb-").is_err());
+        assert!(validate_container_name("rm db").is_err());
+        assert!(validate_container_name("../etc").is_err());
+        assert!(validate_container_name("conteneur-é").is_err());
+        assert!(validate_container_name(&"a".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn test_find_ghost_containers() {
+        use crate::pres::executor::MockExecutor;
+
+        let mock = MockExecutor::new()
+            .on("lxc-ls", "rmdb\nweb\norphan\nweb.prod\n")
+            .on("lxc list", "")
+            .on("ls -1Ap /var/lib/lxc ", ".rmdb-trash/\nlxc-monitord.log\nrmdb/\nweb/\nold/\nweb.prod/\n")
+            .on("ls -1Ap /var/lib/lxd/containers", "web/\n");
+        let ghosts = LXCDeployment::find_ghost_containers(&mock).unwrap();
+        let summary: Vec<(&str, GhostKind)> = ghosts.iter().map(|g| (g.name.as_str(), g.kind)).collect();
+        assert_eq!(summary, [("old", GhostKind::OnDiskOnly), ("web", GhostKind::Duplicate), ("orphan", GhostKind::ListedOnly)]);
+        assert_eq!(ghosts[0].paths, ["/var/lib/lxc/old"]);
+        assert!(ghosts[0].can_clean() && !ghosts[1].can_clean() && ghosts[2].can_clean());
+
+        // lxc-ls en échec (sudo expiré) : aucun container réel ne doit passer pour un fantôme
+        let failing = MockExecutor::new()
+            .on_output("lxc-ls", CommandOutput { exit_code: Some(1), stdout: String::new(), stderr: String::new() })
+            .on("ls -1Ap /var/lib/lxc ", "rmdb/\n");
+        assert!(LXCDeployment::find_ghost_containers(&failing).is_err());
+    }
+
+    #[test]
+    fn test_freeze_and_unfreeze() {
+        use crate::pres::executor::MockExecutor;
+
+        let frozen = MockExecutor::new().on("lxc-info -n web", "Name: web\nState: FROZEN\n");
+        assert!(LXCDeployment::is_frozen(&frozen, "web"));
+        assert!(LXCDeployment::freeze_container(&frozen, "web").is_err());
+        assert!(LXCDeployment::unfreeze_container(&frozen, "web").is_ok());
+        assert!(frozen.commands().iter().any(|c| c.starts_with("lxc-unfreeze -n web")));
+
+        let running = MockExecutor::new().on("lxc-info -n web", "Name: web\nState: RUNNING\n");
+        assert!(LXCDeployment::freeze_container(&running, "web").is_ok());
+        assert!(LXCDeployment::unfreeze_container(&running, "web").is_err());
+        assert!(running.commands().iter().any(|c| c.starts_with("lxc-freeze -n web")));
+    }
+
+    #[test]
+    fn test_write_config_validates_content() {
+        use crate::pres::executor::MockExecutor;
+
+        let current = "lxc.rootfs.path = dir:/var/lib/lxc/web/rootfs\nlxc.uts.name = web\n";
+        assert!(validate_container_config(current).is_ok());
+        assert!(validate_container_config("lxc.rootfs = /srv/web\nlxc.utsname = web").is_ok());
+        assert!(validate_container_config("lxc.rootfs.path =\nlxc.uts.name = web\n").is_err());
+        assert!(validate_container_config("# lxc.uts.name = web\nlxc.rootfs.path = dir:/r\n").is_err());
+
+        let executor = MockExecutor::new();
+        assert!(LXCDeployment::write_config(&executor, "web", "lxc.uts.name = web\n").is_err());
+        assert!(executor.commands().is_empty());
+        assert!(LXCDeployment::write_config(&executor, "web", current.trim_end()).is_ok());
+        let command = &executor.commands()[0];
+        assert!(command.starts_with("cp -p /var/lib/lxc/web/config /var/lib/lxc/web/config.bak"));
+        assert!(command.ends_with("lxc.uts.name = web\nRMDB_EOF"));
+    }
+}
@@ -4,3 +4,5 @@ pub mod logger;
 pub mod installer;
 pub mod vm;
 
+pub mod service_monitor;
+pub mod quick_status;
@@ -0,0 +1,147 @@
+use crate::data::api_client::APIClient;
+use crate::data::capabilities::Capabilities;
+use crate::deployment::lxc::{DiagnosticStatus, LXCDeployment};
+use crate::deployment::service_monitor::ServiceMonitor;
+use crate::pres::executor::Executor;
+
+/// Services de RMDB suivis par « Statut des Services » et l'état rapide
+pub const RMDB_SERVICES: &[&str] = &["rmdbd", "dhcp", "dns", "tftp", "http"];
+
+/// Vue d'ensemble « tout va bien ? » : hôte, container, API et services
+///
+/// Chaque point est obtenu par une seule commande courte (pas de repli `lxc-attach`,
+/// ping de l'API avec le délai court de `APIClient::ping`).
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuickStatus {
+    pub host_installed: bool,
+    pub host_running: bool,
+    pub container: String,
+    pub container_exists: bool,
+    /// État `lxc-info` du container (RUNNING, STOPPED, FROZEN...), `None` s'il est illisible
+    pub container_state: Option<String>,
+    /// Erreur du ping de l'API, `None` si elle répond
+    pub api_error: Option<String>,
+    pub services_active: usize,
+    pub services_total: usize,
+}
+
+/// Relève l'état rapide de RMDB sur l'hôte, dans le container `container` et via l'API
+pub fn quick_status(executor: &dyn Executor, capabilities: &Capabilities, api: &APIClient, container: &str) -> QuickStatus {
+    // Le ping de l'API (jusqu'à 2 s) se déroule pendant les vérifications locales
+    std::thread::scope(|scope| {
+        let ping = scope.spawn(|| api.ping().err().map(|e| e.to_string()));
+        let mut status = local_status(executor, capabilities, container);
+        status.api_error = ping.join().unwrap_or_else(|_| Some("vérification interrompue".to_string()));
+        status
+    })
+}
+
+/// Points vérifiés sur l'hôte, sans l'API
+fn local_status(executor: &dyn Executor, capabilities: &Capabilities, container: &str) -> QuickStatus {
+    let monitor = ServiceMonitor::new(capabilities);
+    let host_installed = executor
+        .run_shell("test -f /usr/local/bin/rmdbd && echo 'installed' || echo 'missing'", false)
+        .is_ok_and(|o| o.stdout.trim() == "installed");
+    let statuses = monitor.statuses(executor, RMDB_SERVICES);
+    let container_exists = !LXCDeployment::check_container_fully_removed(executor, container);
+    let container_state = if container_exists {
+        LXCDeployment::info_fields(executor, container).remove("State").filter(|state| !state.is_empty())
+    } else {
+        None
+    };
+
+    QuickStatus {
+        host_installed,
+        host_running: statuses[0] == "active",
+        container: container.to_string(),
+        container_exists,
+        container_state,
+        api_error: None,
+        services_active: statuses.iter().filter(|status| *status == "active").count(),
+        services_total: statuses.len(),
+    }
+}
+
+impl QuickStatus {
+    /// Une ligne par point, avec son niveau pour la couleur
+    pub fn lines(&self) -> Vec<(DiagnosticStatus, String)> {
+        let host = match (self.host_installed, self.host_running) {
+            (_, true) => (DiagnosticStatus::Ok, "RMDB hôte : installé, actif".to_string()),
+            (true, false) => (DiagnosticStatus::Warning, "RMDB hôte : installé, arrêté".to_string()),
+            (false, false) => (DiagnosticStatus::Warning, "RMDB hôte : non installé".to_string()),
+        };
+        let container = match (self.container_exists, self.container_state.as_deref()) {
+            (false, _) => (DiagnosticStatus::Warning, format!("Container '{}' : absent", self.container)),
+            (true, Some("RUNNING")) => (DiagnosticStatus::Ok, format!("Container '{}' : démarré", self.container)),
+            (true, Some("FROZEN")) => (DiagnosticStatus::Warning, format!("Container '{}' : gelé", self.container)),
+            (true, Some(state)) => (DiagnosticStatus::Warning, format!("Container '{}' : {}", self.container, state)),
+            (true, None) => (DiagnosticStatus::Warning, format!("Container '{}' : état inconnu", self.container)),
+        };
+        let api = match &self.api_error {
+            None => (DiagnosticStatus::Ok, "API : joignable".to_string()),
+            Some(error) => (DiagnosticStatus::Failed, format!("API : injoignable ({})", error)),
+        };
+        let services_level = match self.services_active {
+            0 => DiagnosticStatus::Failed,
+            n if n < self.services_total => DiagnosticStatus::Warning,
+            _ => DiagnosticStatus::Ok,
+        };
+        let services = (services_level, format!("Services : {}/{} actifs", self.services_active, self.services_total));
+        vec![host, container, api, services]
+    }
+
+    /// Rien à signaler : RMDB tourne (hôte ou container), l'API répond et tous les services sont actifs
+    pub fn is_ok(&self) -> bool {
+        (self.host_running || self.container_state.as_deref() == Some("RUNNING"))
+            && self.api_error.is_none()
+            && self.services_active == self.services_total
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::api_client::{APIError, ClientBackend};
+    use crate::pres::executor::MockExecutor;
+    use std::time::Duration;
+
+    struct Unreachable;
+
+    impl ClientBackend for Unreachable {
+        fn get(&self, _: &str, _: Option<&str>) -> Result<String, APIError> {
+            Err(APIError::Network("connexion refusée".to_string()))
+        }
+        fn post(&self, _: &str, _: &str, _: Option<&str>) -> Result<String, APIError> {
+            Err(APIError::Network("connexion refusée".to_string()))
+        }
+        fn delete(&self, _: &str, _: Option<&str>) -> Result<(), APIError> {
+            Err(APIError::Network("connexion refusée".to_string()))
+        }
+        fn ping(&self, _: Duration) -> Result<(), APIError> {
+            Err(APIError::Network("connexion refusée".to_string()))
+        }
+    }
+
+    #[test]
+    fn test_quick_status() {
+        let capabilities = Capabilities { has_systemctl: true, ..Default::default() };
+        let executor = MockExecutor::new()
+            .on("/usr/local/bin/rmdbd", "installed\n")
+            .on("is-active rmdbd", "active\n")
+            .on("is-active dhcp", "active\n")
+            .on("is-active", "inactive\n")
+            .on("test -d /var/lib/lxc/rmdb", "exists\n")
+            .on("lxc-info -n rmdb", "Name: rmdb\nState: STOPPED\n");
+        let api = APIClient::with_backend(Box::new(Unreachable));
+        let status = quick_status(&executor, &capabilities, &api, "rmdb");
+
+        assert!(status.host_installed && status.host_running);
+        assert_eq!(status.container_state.as_deref(), Some("STOPPED"));
+        assert_eq!((status.services_active, status.services_total), (2, 5));
+        assert!(!status.is_ok());
+        let lines = status.lines();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[2].0, DiagnosticStatus::Failed);
+        assert_eq!(lines[3].1, "Services : 2/5 actifs");
+    }
+}
@@ -0,0 +1,253 @@
+use crate::data::capabilities::Capabilities;
+use crate::deployment::host::HostDeployment;
+use crate::pres::executor::{CommandOutput, ExecError, Executor};
+use std::time::Duration;
+
+/// Service géré sans système d'init par `HostDeployment` (fichier PID)
+const DIRECT_SERVICE: &str = "rmdbd";
+/// Nombre de lignes affichées quand le fichier de log a été remplacé pendant l'observation
+const ROTATED_LOG_LINES: usize = 50;
+
+/// Pilote un service selon l'outil d'init disponible (systemd, OpenRC, ou lancement direct de rmdbd)
+pub struct ServiceMonitor<'a> {
+    capabilities: &'a Capabilities,
+}
+
+/// Bilan d'un redémarrage observé : état final et lignes de journal apparues depuis
+#[derive(Debug, Clone)]
+pub struct RestartReport {
+    pub service: String,
+    /// Échec de la commande de redémarrage elle-même
+    pub restart_error: Option<String>,
+    /// État à la fin de l'observation ("active", "failed"...)
+    pub status: String,
+    /// Origine des lignes : journal systemd ou fichier de log
+    pub log_source: String,
+    pub new_lines: Vec<String>,
+}
+
+impl RestartReport {
+    /// Lignes du journal signalant une erreur
+    pub fn error_lines(&self) -> Vec<&str> {
+        self.new_lines.iter().map(String::as_str).filter(|line| is_error_line(line)).collect()
+    }
+
+    /// Redémarré, actif et sans erreur dans le journal
+    pub fn is_clean(&self) -> bool {
+        self.restart_error.is_none() && self.status == "active" && self.error_lines().is_empty()
+    }
+}
+
+/// Repère une ligne de journal d'erreur (niveaux usuels, arrêts du processus)
+pub fn is_error_line(line: &str) -> bool {
+    let lower = line.to_lowercase();
+    ["error", "erreur", "fatal", "panic", "failed", "échec", "level=err"].iter().any(|word| lower.contains(word))
+}
+
+impl<'a> ServiceMonitor<'a> {
+    pub fn new(capabilities: &'a Capabilities) -> Self {
+        Self { capabilities }
+    }
+
+    /// État d'un service ("active", "inactive"... ou "inconnu")
+    pub fn status(&self, executor: &dyn Executor, service: &str) -> String {
+        self.statuses(executor, &[service]).remove(0)
+    }
+
+    /// États de plusieurs services, dans l'ordre de `services` ; les requêtes partent ensemble
+    /// (`Executor::run_many`)
+    pub fn statuses(&self, executor: &dyn Executor, services: &[&str]) -> Vec<String> {
+        let commands: Vec<Option<String>> = services.iter().map(|service| self.status_command(service)).collect();
+        let shell: Vec<&str> = commands.iter().flatten().map(String::as_str).collect();
+        let mut outputs = executor.run_many(&shell, false).into_iter();
+
+        commands
+            .iter()
+            .map(|command| match command {
+                // `systemctl is-active` affiche déjà son état avant le `|| echo` : on garde la première ligne
+                Some(_) => outputs
+                    .next()
+                    .and_then(Result::ok)
+                    .and_then(|o| o.stdout.lines().next().map(|l| l.trim().to_string()))
+                    .filter(|status| !status.is_empty())
+                    .unwrap_or_else(|| "inconnu".to_string()),
+                // Sans système d'init, rmdbd est suivi par son fichier PID
+                None => {
+                    let running = HostDeployment::new().rmdb_direct_pid(executor).is_some();
+                    if running { "active" } else { "inactive" }.to_string()
+                }
+            })
+            .collect()
+    }
+
+    /// Activation au démarrage de chaque service, dans l'ordre de `services`
+    ///
+    /// `systemctl is-enabled` (une requête par service) ou un seul `rc-update show` avec OpenRC ;
+    /// `None` quand la réponse est illisible, sans objet (unité `static`) ou sans système d'init.
+    pub fn boot_enabled(&self, executor: &dyn Executor, services: &[&str]) -> Vec<Option<bool>> {
+        if self.capabilities.has_systemctl {
+            let commands: Vec<String> = services
+                .iter()
+                .map(|service| format!("systemctl is-enabled {} 2>/dev/null || true", service))
+                .collect();
+            let shell: Vec<&str> = commands.iter().map(String::as_str).collect();
+            executor
+                .run_many(&shell, false)
+                .into_iter()
+                .map(|output| output.ok().and_then(|o| parse_is_enabled(&o.stdout)))
+                .collect()
+        } else if self.capabilities.has_rc_service {
+            let output = executor.run_shell("rc-update show 2>/dev/null", false).map(|o| o.stdout).unwrap_or_default();
+            services.iter().map(|service| parse_rc_update_show(&output, service)).collect()
+        } else {
+            vec![None; services.len()]
+        }
+    }
+
+    /// Commande qui affiche l'état du service ; `None` pour rmdbd sans système d'init
+    fn status_command(&self, service: &str) -> Option<String> {
+        if self.capabilities.has_systemctl {
+            Some(format!("systemctl is-active {} 2>/dev/null || echo inactive", service))
+        } else if self.capabilities.has_rc_service {
+            Some(format!("rc-service {} status 2>/dev/null | grep -q started && echo active || echo inactive", service))
+        } else if service == DIRECT_SERVICE {
+            None
+        } else {
+            Some(format!("pgrep -f {} >/dev/null && echo active || echo inactive", service))
+        }
+    }
+
+    /// Redémarre le service avec l'outil d'init disponible
+    pub fn restart(&self, executor: &dyn Executor, service: &str) -> Result<CommandOutput, ExecError> {
+        if self.capabilities.has_systemctl {
+            executor.run_shell(&format!("systemctl restart {}", service), true)
+        } else if self.capabilities.has_rc_service {
+            executor.run_shell(&format!("rc-service {} restart", service), true)
+        } else if service == DIRECT_SERVICE {
+            HostDeployment::new().restart_rmdb_direct(executor)
+        } else {
+            Err(ExecError::MissingTool("Ni systemd ni OpenRC : redémarrage impossible".to_string()))
+        }
+    }
+
+    /// Redémarre le service, l'observe pendant `seconds` secondes, puis relève son état
+    /// et les lignes ajoutées à son journal depuis le redémarrage
+    ///
+    /// Avec systemd, le journal est lu depuis l'heure du redémarrage (`journalctl --since`) ;
+    /// sinon, le fichier `/var/log/<service>.log` est lu à partir de sa taille avant le redémarrage.
+    pub fn restart_and_tail(&self, executor: &dyn Executor, service: &str, seconds: u64) -> RestartReport {
+        let read = |cmd: &str| executor.run_shell(cmd, true).map(|o| o.stdout).unwrap_or_default();
+        let log_file = format!("/var/log/{}.log", service);
+
+        let (log_source, mark) = if self.capabilities.has_systemctl {
+            ("journal systemd".to_string(), read("date +%s").trim().parse::<u64>().ok())
+        } else {
+            (log_file.clone(), read(&format!("stat -c %s {} 2>/dev/null", log_file)).trim().parse::<u64>().ok())
+        };
+
+        let restart_error = match self.restart(executor, service) {
+            Ok(output) if output.exit_code == Some(0) => None,
+            Ok(output) => Some(first_line(&output.stderr).unwrap_or_else(|| format!("code de sortie {:?}", output.exit_code))),
+            Err(e) => Some(e.to_string()),
+        };
+        std::thread::sleep(Duration::from_secs(seconds));
+
+        let log = if self.capabilities.has_systemctl {
+            let since = mark.map(|epoch| format!(" --since @{}", epoch)).unwrap_or_default();
+            read(&format!("journalctl -u {}{} --no-pager 2>/dev/null", service, since))
+        } else {
+            let size = read(&format!("stat -c %s {} 2>/dev/null", log_file)).trim().parse::<u64>().ok();
+            match (mark, size) {
+                // Fichier remplacé (rotation) : la fin du nouveau fichier
+                (Some(before), Some(after)) if after < before => {
+                    read(&format!("tail -n {} {}", ROTATED_LOG_LINES, log_file))
+                }
+                (Some(before), Some(_)) => read(&format!("tail -c +{} {}", before + 1, log_file)),
+                _ => read(&format!("tail -n {} {} 2>/dev/null", ROTATED_LOG_LINES, log_file)),
+            }
+        };
+
+        RestartReport {
+            service: service.to_string(),
+            restart_error,
+            status: self.status(executor, service),
+            log_source,
+            new_lines: log
+                .lines()
+                .filter(|line| !line.trim().is_empty() && !line.starts_with("-- "))
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+}
+
+/// Sortie de `systemctl is-enabled` : activé, désactivé ou sans objet (`static`, `generated`...)
+fn parse_is_enabled(output: &str) -> Option<bool> {
+    match output.lines().next().map(str::trim)? {
+        "enabled" | "enabled-runtime" | "alias" => Some(true),
+        "disabled" | "masked" | "masked-runtime" | "indirect" => Some(false),
+        _ => None,
+    }
+}
+
+/// Présence du service dans un niveau d'exécution de `rc-update show` (`  rmdbd | default`)
+///
+/// `None` si la sortie est vide (commande absente ou en échec).
+fn parse_rc_update_show(output: &str, service: &str) -> Option<bool> {
+    if output.trim().is_empty() {
+        return None;
+    }
+    let enabled = output.lines().any(|line| {
+        let mut fields = line.splitn(2, '|');
+        fields.next().map(str::trim) == Some(service) && fields.next().is_some_and(|levels| !levels.trim().is_empty())
+    });
+    Some(enabled)
+}
+
+fn first_line(text: &str) -> Option<String> {
+    text.lines().map(str::trim).find(|l| !l.is_empty()).map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pres::executor::MockExecutor;
+
+    #[test]
+    fn test_restart_and_tail_systemd() {
+        let capabilities = Capabilities { has_systemctl: true, ..Default::default() };
+        let executor = MockExecutor::new()
+            .on("date +%s", "1700000000\n")
+            .on("journalctl", "-- Logs begin --\nrmdbd[42]: API en écoute sur :8080\nrmdbd[42]: ERROR tftp: bind :69 failed\n")
+            .on("is-active", "active\n");
+        let report = ServiceMonitor::new(&capabilities).restart_and_tail(&executor, "rmdbd", 0);
+
+        assert_eq!(report.status, "active");
+        assert!(report.restart_error.is_none());
+        assert_eq!(report.new_lines.len(), 2);
+        assert_eq!(report.error_lines(), ["rmdbd[42]: ERROR tftp: bind :69 failed"]);
+        assert!(!report.is_clean());
+        assert!(executor.issued().iter().any(|(cmd, _)| cmd.contains("--since @1700000000")));
+    }
+
+    #[test]
+    fn test_boot_enabled() {
+        let systemd = Capabilities { has_systemctl: true, ..Default::default() };
+        let executor = MockExecutor::new()
+            .on("is-enabled rmdbd", "enabled\n")
+            .on("is-enabled dhcp", "disabled\n")
+            .on("is-enabled dns", "static\n");
+        assert_eq!(
+            ServiceMonitor::new(&systemd).boot_enabled(&executor, &["rmdbd", "dhcp", "dns"]),
+            [Some(true), Some(false), None]
+        );
+
+        let openrc = Capabilities { has_rc_service: true, ..Default::default() };
+        let executor = MockExecutor::new().on("rc-update show", "            rmdbd | default\n   rmdbd-tftp |\n       sshd | boot default\n");
+        assert_eq!(
+            ServiceMonitor::new(&openrc).boot_enabled(&executor, &["rmdbd", "rmdbd-tftp", "dhcp"]),
+            [Some(true), Some(false), Some(false)]
+        );
+        assert_eq!(ServiceMonitor::new(&openrc).boot_enabled(&MockExecutor::new(), &["rmdbd"]), [None]);
+    }
+}
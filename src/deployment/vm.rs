@@ -9,6 +9,7 @@ pub struct VMDeployment {
     rocky_version: String,
     pub logger: Option<DeploymentLogger>,
     distribution: DistributionInfo,
+    extra_packages: Vec<String>,
 }
 
 impl VMDeployment {
@@ -18,9 +19,16 @@ impl VMDeployment {
             rocky_version,
             logger: None,
             distribution: DistributionInfo::detect(),
+            extra_packages: Vec::new(),
         }
     }
 
+    /// Paquets ajoutés à la liste `packages` du script cloud-init (installés par dnf au premier démarrage)
+    pub fn with_extra_packages(mut self, packages: Vec<String>) -> Self {
+        self.extra_packages = packages;
+        self
+    }
+
     pub fn with_logger(mut self, logger: DeploymentLogger) -> Self {
         self.logger = Some(logger);
         self
@@ -128,7 +136,7 @@ impl VMDeployment {
 
         // Créer le répertoire pour les disques VM
         let vm_dir = format!("/var/lib/libvirt/images/{}", self.vm_name);
-        let mkdir_cmd = format!("sudo mkdir -p {}", vm_dir);
+        let mkdir_cmd = format!("mkdir -p {}", vm_dir);
         executor.run_shell(&mkdir_cmd, true)?;
 
         // Télécharger l'ISO Rocky Linux si nécessaire
@@ -144,7 +152,7 @@ impl VMDeployment {
                 self.rocky_version, self.rocky_version
             );
             let download_cmd = format!(
-                "cd {} && sudo wget -q --show-progress -O {} {}",
+                "cd {} && wget -q --show-progress -O {} {}",
                 vm_dir, iso_path, iso_url
             );
             executor.run_shell(&download_cmd, true)?;
@@ -154,7 +162,7 @@ impl VMDeployment {
         let disk_path = format!("{}/{}.qcow2", vm_dir, self.vm_name);
         let disk_size = "20G"; // Taille par défaut
         let create_disk_cmd = format!(
-            "sudo qemu-img create -f qcow2 {} {}",
+            "qemu-img create -f qcow2 {} {}",
             disk_path, disk_size
         );
         self.log_command(&create_disk_cmd);
@@ -164,7 +172,7 @@ impl VMDeployment {
         // Note: Cette commande nécessite un environnement graphique ou VNC
         // Pour une installation non-interactive, on peut utiliser cloud-init
         let create_vm_cmd = format!(
-            "sudo virt-install \
+            "virt-install \
             --name {} \
             --ram 2048 \
             --vcpus 2 \
@@ -304,7 +312,7 @@ packages:
   - git
   - gcc
   - make
-
+{extra_packages}
 write_files:
   - path: /root/install_rmdb.sh
     permissions: '0755'
@@ -357,7 +365,8 @@ write_files:
 
 runcmd:
   - /root/install_rmdb.sh
-"#
+"#,
+            extra_packages = self.extra_packages.iter().map(|p| format!("  - {}\n", p)).collect::<String>()
         );
 
         // Sauvegarder le script dans un fichier temporaire
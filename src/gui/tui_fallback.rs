@@ -4,7 +4,7 @@
 pub fn run_tui() {
     // Utiliser l'interface TUI existante
     use crate::pres::main_app::MainApp;
-    let mut app = MainApp::new();
+    let mut app = MainApp::new(crate::data::tui_config::TuiConfig::load());
     app.run();
 }
 
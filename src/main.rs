@@ -1,13 +1,23 @@
 use rmdb::pres::main_app::MainApp;
+use rmdb::data::tui_config::TuiConfig;
 use rmdb::gui::desktop_client::DesktopClient;
 use rmdb::deployment::installer::InstallationMode;
 
 fn main() {
     // Vérifier si on doit lancer le GUI
-    let args: Vec<String> = std::env::args().collect();
+    let mut args: Vec<String> = std::env::args().collect();
+    // Préférences : ~/.config/rmdb/tui.json, puis variables d'environnement, puis options
+    // (--ascii, --no-anim, --api-url), combinables avec les autres options
+    let config = TuiConfig::load().apply_args(&mut args);
     
     if args.len() > 1 {
         let first_arg = &args[1];
+        if first_arg == "--test-ui" {
+            // Test d'affichage des couleurs et glyphes, puis sortie
+            let mut app = MainApp::new(config);
+            app.run_display_test();
+            return;
+        }
         if first_arg == "--gui" || first_arg == "-g" || first_arg == "gui" {
             // Lancer le GUI
             #[cfg(feature = "gui-gtk")]
@@ -39,15 +49,19 @@ fn main() {
         } else {
             // Si ce n'est pas --gui, afficher un message d'aide
             eprintln!("Option inconnue: {}", first_arg);
-            eprintln!("Usage: {} [--gui|-g|gui]", args[0]);
+            eprintln!("Usage: {} [--ascii] [--no-anim] [--api-url URL] [--test-ui] [--gui|-g|gui]", args[0]);
             eprintln!("  --gui, -g, gui  : Lancer l'interface graphique");
+            eprintln!("  --ascii         : Dessiner l'interface terminal en ASCII");
+            eprintln!("  --no-anim       : Supprimer les pauses décoratives");
+            eprintln!("  --api-url URL   : URL de l'API RMDB (sinon RMDB_API_URL ou tui.json)");
+            eprintln!("  --test-ui       : Afficher le test des couleurs et glyphes, puis quitter");
             eprintln!("  (sans option)   : Lancer l'interface terminal (TUI)");
             std::process::exit(1);
         }
     }
     
     // Lancer le TUI par défaut (pas d'arguments)
-    let mut app = MainApp::new();
+    let mut app = MainApp::new(config);
     app.run();
 }
 
@@ -0,0 +1,242 @@
+use crate::data::capabilities::Capabilities;
+use crate::deployment::host::RMDB_PID_FILE;
+use crate::deployment::lxc::ContainerInit;
+use crate::pres::main_menu::MainMenuAction;
+
+/// Environnement dans lequel les commandes d'une action sont prévisualisées
+pub struct PreviewContext<'a> {
+    /// Container visé par les actions « Container RMDB »
+    pub container: &'a str,
+    /// Configuration du container trouvée sur le disque (`lxc-start -f`), `None` si introuvable
+    pub container_config: Option<&'a str>,
+    pub capabilities: &'a Capabilities,
+    /// Gestionnaire de services du container s'il a déjà été détecté (OpenRC sinon)
+    pub container_init: Option<ContainerInit>,
+}
+
+/// Ce que fait une action du menu, en une ou deux phrases
+pub fn describe(action: &MainMenuAction) -> &'static str {
+    match action {
+        MainMenuAction::Favorites => "Liste les actions épinglées et récentes ; Entrée relance l'action choisie.",
+        MainMenuAction::QuickStatus => "Résume en quatre lignes RMDB sur l'hôte, le container RMDB, l'API et les services (lecture seule).",
+        MainMenuAction::ServicesTheme
+        | MainMenuAction::IPXETheme
+        | MainMenuAction::ClientsTheme
+        | MainMenuAction::VMsTheme
+        | MainMenuAction::ConfigurationTheme
+        | MainMenuAction::MonitoringTheme
+        | MainMenuAction::SystemTheme
+        | MainMenuAction::LXCManage
+        | MainMenuAction::ContainersTheme
+        | MainMenuAction::HostTheme
+        | MainMenuAction::InstallMenu => "Ouvre un sous-menu, ou revient au menu principal.",
+        MainMenuAction::ServiceStatus => "Affiche l'état de rmdbd et des services DHCP, DNS, TFTP et HTTP, et s'ils sont activés au démarrage.",
+        MainMenuAction::ServiceDHCP
+        | MainMenuAction::ServiceDNS
+        | MainMenuAction::ServiceTFTP
+        | MainMenuAction::ServiceHTTP => "Affiche les informations du service.",
+        MainMenuAction::ServiceStart => "Démarre rmdbd sur l'hôte s'il n'est pas déjà actif.",
+        MainMenuAction::ServiceStop => "Arrête rmdbd sur l'hôte s'il est actif.",
+        MainMenuAction::ServiceRestart => "Redémarre rmdbd sur l'hôte, puis affiche son état et les nouvelles lignes de son journal.",
+        MainMenuAction::IPXEMenu => "Affiche le menu iPXE servi par RMDB (API).",
+        MainMenuAction::IPXEEntries => "Liste les entrées du menu iPXE (API).",
+        MainMenuAction::IPXEGenerate => "Régénère le menu iPXE après avoir montré les différences (API).",
+        MainMenuAction::IPXESave => "Enregistre dans un fichier local le menu iPXE servi actuellement (lecture seule, le menu n'est pas régénéré).",
+        MainMenuAction::ClientsLeases => "Liste les baux DHCP (API, ou fichier de baux local).",
+        MainMenuAction::ClientsLeasesWatch => "Actualise la liste des baux DHCP en continu.",
+        MainMenuAction::ClientsConnected => "Liste les clients PXE connectés (API).",
+        MainMenuAction::VMsList => "Liste les VMs déclarées dans RMDB (API).",
+        MainMenuAction::VMsCreate => "Crée une VM après vérification du disque (API).",
+        MainMenuAction::VMsManage => "Démarre, arrête ou supprime une VM (API).",
+        MainMenuAction::VMsOverlays => "Liste les overlays des VMs (API).",
+        MainMenuAction::VMsOverlaysCleanup => "Repère les overlays sans VM et propose de les supprimer (API).",
+        MainMenuAction::ConfigView => "Affiche la configuration du serveur (API).",
+        MainMenuAction::ConfigDiff => "Compare la configuration du serveur avec un fichier JSON local.",
+        MainMenuAction::ConfigEdit => "Édite la configuration dans l'interface, puis l'enregistre via l'API.",
+        MainMenuAction::ConfigEditExternal => "Ouvre la configuration dans l'éditeur externe, puis l'enregistre via l'API.",
+        MainMenuAction::ConfigProfiles => "Ajoute, supprime ou active un profil de connexion à l'API.",
+        MainMenuAction::ConfigSaveTui => "Écrit les préférences en vigueur dans tui.json.",
+        MainMenuAction::MonitoringLogs => "Affiche les journaux du serveur (API).",
+        MainMenuAction::MonitoringLogsFollow => "Suit le journal de rmdbd en direct jusqu'à Q.",
+        MainMenuAction::MonitoringHealth => "Affiche l'état de santé du serveur (API).",
+        MainMenuAction::MonitoringDeployLogs => "Liste les journaux de déploiement locaux et ouvre celui choisi.",
+        MainMenuAction::MonitoringDeployHistory => "Liste les déploiements et installations passés, avec leur résultat.",
+        MainMenuAction::SystemInfo => "Affiche le nom d'hôte, l'uptime, les CPU et la mémoire.",
+        MainMenuAction::SystemDiagnosticConsole => "Exécute des commandes saisies librement (mode expert).",
+        MainMenuAction::SystemDisplayTest => "Affiche toutes les couleurs, les glyphes, un tableau et une barre de défilement pour vérifier le rendu du terminal.",
+        MainMenuAction::SystemSudoReset => "Oublie les identifiants sudo en cache.",
+        MainMenuAction::SystemRequirements => "Liste les outils attendus et la commande qui installe ceux qui manquent.",
+        MainMenuAction::DeployLXC => "Crée le container Alpine, y installe RMDB et vérifie le service.",
+        MainMenuAction::DeployStatus => "Affiche l'état du container RMDB et de son service.",
+        MainMenuAction::LXCStart => "Démarre le container RMDB.",
+        MainMenuAction::LXCStop => "Arrête le container RMDB.",
+        MainMenuAction::LXCRestart => "Arrête puis redémarre le container RMDB.",
+        MainMenuAction::LXCLogs => "Affiche l'état du container et les journaux LXC de l'hôte.",
+        MainMenuAction::LXCShell => "Ouvre un shell dans le container, TUI suspendu ; un code de sortie en erreur est expliqué au retour.",
+        MainMenuAction::LXCStats => "Affiche les informations et la consommation du container.",
+        MainMenuAction::LXCRmdbStart => "Démarre rmdbd dans le container.",
+        MainMenuAction::LXCRmdbStop => "Arrête rmdbd dans le container.",
+        MainMenuAction::LXCRmdbRestart => "Redémarre rmdbd dans le container.",
+        MainMenuAction::LXCRmdbLogs => "Affiche les 500 dernières lignes du journal de rmdbd dans le container.",
+        MainMenuAction::LXCConfig => "Affiche la configuration LXC du container.",
+        MainMenuAction::LXCConfigEdit => "Ouvre la configuration LXC du container dans l'éditeur, vérifie les clés obligatoires puis l'enregistre (copie dans config.bak).",
+        MainMenuAction::LXCDiagnostic => "Vérifie pourquoi le container est, ou n'est pas, détecté (lecture seule).",
+        MainMenuAction::LXCDestroy => "Arrête puis supprime définitivement le container RMDB, après confirmation.",
+        MainMenuAction::ContainersList => "Liste tous les containers LXC de l'hôte.",
+        MainMenuAction::ContainersStart => "Démarre un ou plusieurs containers choisis ; propose de dégeler ceux qui sont gelés.",
+        MainMenuAction::ContainersStop => "Arrête un ou plusieurs containers choisis.",
+        MainMenuAction::ContainersFreeze => "Suspend les processus des containers choisis sans les arrêter.",
+        MainMenuAction::ContainersUnfreeze => "Reprend l'exécution des containers gelés choisis.",
+        MainMenuAction::ContainersRestart => "Arrête puis redémarre le container choisi.",
+        MainMenuAction::ContainersAdd => "Crée un container Alpine sous le nom saisi.",
+        MainMenuAction::ContainersClone => "Copie un container sous un nouveau nom (lxc-copy).",
+        MainMenuAction::ContainersDestroy => "Supprime un ou plusieurs containers choisis, après confirmation.",
+        MainMenuAction::ContainersReinstall => "Supprime puis recrée le container choisi et y réinstalle RMDB.",
+        MainMenuAction::ContainersAutostart => "Active ou désactive le démarrage du container au boot (lxc.start.auto).",
+        MainMenuAction::ContainersHealth => "Vérifie le service RMDB et son API dans le container choisi.",
+        MainMenuAction::ContainersGhosts => "Compare /var/lib/lxc et /var/lib/lxd/containers avec lxc-ls et propose de nettoyer chaque incohérence.",
+        MainMenuAction::ContainersRepairConfig => "Vérifie les default.conf de LXC et crée ceux qui manquent.",
+        MainMenuAction::HostInstall => "Compile et installe rmdbd sur l'hôte, puis propose d'ouvrir les ports du pare-feu.",
+        MainMenuAction::HostStatus => "Affiche l'installation, l'état et le mode de rmdbd sur l'hôte.",
+        MainMenuAction::HostVerify => "Vérifie le binaire, la configuration et les répertoires de RMDB (lecture seule).",
+        MainMenuAction::HostStart => "Démarre rmdbd sur l'hôte.",
+        MainMenuAction::HostStop => "Arrête rmdbd sur l'hôte.",
+        MainMenuAction::HostRestart => "Redémarre rmdbd sur l'hôte.",
+        MainMenuAction::HostEnable => "Active le démarrage de rmdbd au boot.",
+        MainMenuAction::HostDisable => "Désactive le démarrage de rmdbd au boot.",
+        MainMenuAction::HostChangeMode => "Change le mode d'utilisation enregistré dans /etc/rmdbd/config.json.",
+        MainMenuAction::HostUninstall => "Arrête rmdbd et supprime son binaire et son service, après confirmation.",
+        MainMenuAction::InstallOnHost => "Installe Rust, Go et RMDB sur l'hôte.",
+        MainMenuAction::InstallInContainer => "Crée un container Alpine et y installe RMDB.",
+        MainMenuAction::InstallInVM => "Crée une VM Rocky Linux et prépare l'installation de RMDB (cloud-init).",
+        MainMenuAction::Quit => "Quitte l'interface.",
+        MainMenuAction::IPXEConfig
+        | MainMenuAction::ClientsHistory
+        | MainMenuAction::ConfigNetwork
+        | MainMenuAction::ConfigSecurity
+        | MainMenuAction::MonitoringMetrics
+        | MainMenuAction::MonitoringDashboard
+        | MainMenuAction::SystemServices
+        | MainMenuAction::SystemProcesses => "Pas encore disponible dans cette version.",
+    }
+}
+
+/// Commande d'un service de l'hôte selon l'outil d'init détecté
+fn host_service_command(capabilities: &Capabilities, verb: &str) -> String {
+    if capabilities.has_systemctl {
+        format!("systemctl {} rmdbd", verb)
+    } else if capabilities.has_rc_service {
+        format!("rc-service rmdbd {}", verb)
+    } else {
+        format!("(sans système d'init) rmdbd {} via son fichier PID {}", verb, RMDB_PID_FILE)
+    }
+}
+
+/// Les commandes de l'action sont lancées avec sudo (`requires_admin`) ; les lectures de
+/// journaux et `sudo -k` tournent avec les droits de l'utilisateur
+pub fn runs_as_admin(action: &MainMenuAction) -> bool {
+    !matches!(
+        action,
+        MainMenuAction::MonitoringLogsFollow
+            | MainMenuAction::SystemSudoReset
+            | MainMenuAction::LXCLogs
+            | MainMenuAction::LXCRmdbLogs
+    )
+}
+
+/// Commandes principales que lancerait l'action dans cet environnement
+///
+/// Vide pour les actions qui passent par l'API ou restent dans l'interface. Les vérifications
+/// préalables en lecture seule (existence du container, état du service) ne sont pas listées.
+pub fn command_preview(action: &MainMenuAction, ctx: &PreviewContext) -> Vec<String> {
+    let c = ctx.container;
+    let init = ctx.container_init.unwrap_or(ContainerInit::OpenRC);
+    let lxc = |tool: &str| match ctx.container_config {
+        Some(config) => format!("{} -f {} -n {}", tool, config, c),
+        None => format!("{} -P /var/lib/lxc -n {}", tool, c),
+    };
+    let boot = |enable: bool| {
+        if ctx.capabilities.has_systemctl {
+            format!("systemctl {} rmdbd", if enable { "enable" } else { "disable" })
+        } else {
+            format!("rc-update {} rmdbd default", if enable { "add" } else { "del" })
+        }
+    };
+    match action {
+        MainMenuAction::ServiceStart | MainMenuAction::HostStart => vec![host_service_command(ctx.capabilities, "start")],
+        MainMenuAction::ServiceStop | MainMenuAction::HostStop => vec![host_service_command(ctx.capabilities, "stop")],
+        MainMenuAction::ServiceRestart | MainMenuAction::HostRestart => vec![host_service_command(ctx.capabilities, "restart")],
+        MainMenuAction::HostEnable => vec![boot(true)],
+        MainMenuAction::HostDisable => vec![boot(false)],
+        MainMenuAction::HostUninstall => vec![
+            host_service_command(ctx.capabilities, "stop"),
+            boot(false),
+            "rm -f /usr/local/bin/rmdbd".to_string(),
+            if ctx.capabilities.has_systemctl {
+                "rm -f /etc/systemd/system/rmdbd.service && systemctl daemon-reload".to_string()
+            } else {
+                "rm -f /etc/init.d/rmdbd".to_string()
+            },
+        ],
+        MainMenuAction::MonitoringLogsFollow => vec![if ctx.capabilities.has_systemctl {
+            "journalctl -u rmdbd -f -n 50".to_string()
+        } else {
+            "tail -n 50 -f /var/log/rmdbd.log".to_string()
+        }],
+        MainMenuAction::SystemSudoReset => vec!["sudo -k".to_string()],
+        MainMenuAction::DeployStatus | MainMenuAction::LXCStats => vec![format!("lxc-info -n {}", c)],
+        MainMenuAction::LXCStart => vec![lxc("lxc-start")],
+        MainMenuAction::LXCStop => vec![lxc("lxc-stop")],
+        MainMenuAction::LXCRestart => vec![lxc("lxc-stop"), lxc("lxc-start")],
+        MainMenuAction::LXCLogs => vec![
+            format!("lxc-info -n {} -S", c),
+            format!("journalctl -u lxc@{}.service -n 500 --no-pager", c),
+        ],
+        MainMenuAction::LXCShell => vec![format!("lxc-attach -n {}", c)],
+        MainMenuAction::LXCConfig => vec![format!("cat /var/lib/lxc/{}/config", c)],
+        MainMenuAction::LXCConfigEdit => vec![
+            format!("cat /var/lib/lxc/{}/config", c),
+            format!("cp -p /var/lib/lxc/{0}/config /var/lib/lxc/{0}/config.bak", c),
+        ],
+        MainMenuAction::LXCRmdbStart => vec![init.service_command(c, "start")],
+        MainMenuAction::LXCRmdbStop => vec![init.service_command(c, "stop")],
+        MainMenuAction::LXCRmdbRestart => vec![init.service_command(c, "restart")],
+        MainMenuAction::LXCRmdbLogs => vec![init.logs_command(c, 500)],
+        MainMenuAction::LXCDestroy => vec![
+            lxc("lxc-stop"),
+            format!("lxc-destroy -n {}", c),
+        ],
+        MainMenuAction::ContainersStart => vec!["lxc-start -f <config> -n <container choisi>".to_string()],
+        MainMenuAction::ContainersStop => vec!["lxc-stop -f <config> -n <container choisi>".to_string()],
+        MainMenuAction::ContainersFreeze => vec!["lxc-freeze -n <container choisi>".to_string()],
+        MainMenuAction::ContainersUnfreeze => vec!["lxc-unfreeze -n <container choisi>".to_string()],
+        MainMenuAction::ContainersDestroy => vec!["lxc-destroy -f -n <container choisi>".to_string()],
+        MainMenuAction::ContainersClone => vec!["lxc-copy -n <source> -N <clone> [-s | -s -B overlay]".to_string()],
+        MainMenuAction::ContainersAutostart => vec!["sed -i '/^lxc.start.auto/d' /var/lib/lxc/<container>/config && echo 'lxc.start.auto = 1' >> …".to_string()],
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_preview_follows_environment() {
+        let openrc = Capabilities { has_rc_service: true, ..Default::default() };
+        let ctx = PreviewContext { container: "labo", container_config: Some("/var/lib/lxc/labo/config"), capabilities: &openrc, container_init: None };
+        assert_eq!(command_preview(&MainMenuAction::HostRestart, &ctx), ["rc-service rmdbd restart"]);
+        assert_eq!(command_preview(&MainMenuAction::HostEnable, &ctx), ["rc-update add rmdbd default"]);
+        assert_eq!(command_preview(&MainMenuAction::LXCStart, &ctx), ["lxc-start -f /var/lib/lxc/labo/config -n labo"]);
+        assert!(command_preview(&MainMenuAction::ConfigView, &ctx).is_empty());
+        assert_eq!(command_preview(&MainMenuAction::LXCDestroy, &ctx)[1], "lxc-destroy -n labo");
+        assert!(runs_as_admin(&MainMenuAction::LXCDestroy));
+        assert!(!runs_as_admin(&MainMenuAction::LXCRmdbLogs));
+
+        let systemd = Capabilities { has_systemctl: true, ..Default::default() };
+        let ctx = PreviewContext { container: "rmdb", container_config: None, capabilities: &systemd, container_init: Some(ContainerInit::Systemd) };
+        assert_eq!(command_preview(&MainMenuAction::LXCStop, &ctx), ["lxc-stop -P /var/lib/lxc -n rmdb"]);
+        assert!(command_preview(&MainMenuAction::HostUninstall, &ctx).contains(&"systemctl disable rmdbd".to_string()));
+        assert!(command_preview(&MainMenuAction::LXCRmdbStop, &ctx)[0].starts_with("lxc-attach -n rmdb -- systemctl stop rmdbd"));
+    }
+}
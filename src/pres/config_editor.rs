@@ -0,0 +1,443 @@
+use crate::pres::ui::{UI, Color};
+use crate::pres::input::{InputReader, Key};
+use crate::pres::select_list::{SelectList, SelectOutcome};
+use serde_json::Value;
+use std::fs::{self, DirBuilder, OpenOptions};
+use std::io::Write;
+use std::os::unix::fs::{DirBuilderExt, OpenOptionsExt};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Élément du chemin courant dans l'arbre de configuration
+#[derive(Debug, Clone, PartialEq)]
+pub enum PathSegment {
+    Key(String),
+    Index(usize),
+}
+
+/// Entrée affichée pour le niveau courant
+#[derive(Debug, Clone)]
+pub struct ConfigEntry {
+    pub segment: PathSegment,
+    pub label: String,
+    pub preview: String,
+    /// Objet ou tableau : Entrée ouvre le niveau au lieu d'éditer
+    pub is_container: bool,
+}
+
+/// Fin de l'édition
+#[derive(Debug, Clone, PartialEq)]
+pub enum EditorOutcome {
+    /// S pressé : configuration à enregistrer
+    Save(Value),
+    /// Q pressé à la racine
+    Cancelled,
+}
+
+/// Navigateur de configuration JSON : un niveau à la fois, édition typée des feuilles
+pub struct ConfigEditor {
+    root: Value,
+    path: Vec<PathSegment>,
+    modified: bool,
+}
+
+impl ConfigEditor {
+    pub fn new(root: Value) -> Self {
+        Self {
+            root,
+            path: Vec::new(),
+            modified: false,
+        }
+    }
+
+    pub fn is_modified(&self) -> bool {
+        self.modified
+    }
+
+    /// Chemin courant, ex: `network.interfaces[0]` (`/` à la racine)
+    pub fn path_label(&self) -> String {
+        if self.path.is_empty() {
+            return "/".to_string();
+        }
+        let mut label = String::new();
+        for segment in &self.path {
+            match segment {
+                PathSegment::Key(key) if label.is_empty() => label.push_str(key),
+                PathSegment::Key(key) => label.push_str(&format!(".{}", key)),
+                PathSegment::Index(i) => label.push_str(&format!("[{}]", i)),
+            }
+        }
+        label
+    }
+
+    fn current(&self) -> &Value {
+        self.path.iter().fold(&self.root, |value, segment| child(value, segment).unwrap_or(value))
+    }
+
+    fn current_mut(&mut self) -> &mut Value {
+        let mut value = &mut self.root;
+        for segment in &self.path {
+            value = match (value, segment) {
+                (Value::Object(map), PathSegment::Key(key)) => map.get_mut(key).expect("chemin valide"),
+                (Value::Array(items), PathSegment::Index(i)) => &mut items[*i],
+                _ => unreachable!("chemin de configuration invalide"),
+            };
+        }
+        value
+    }
+
+    /// Entrées du niveau courant
+    pub fn entries(&self) -> Vec<ConfigEntry> {
+        let entry = |segment: PathSegment, label: String, value: &Value| ConfigEntry {
+            segment,
+            label,
+            preview: preview(value),
+            is_container: value.is_object() || value.is_array(),
+        };
+        match self.current() {
+            Value::Object(map) => map
+                .iter()
+                .map(|(key, value)| entry(PathSegment::Key(key.clone()), key.clone(), value))
+                .collect(),
+            Value::Array(items) => items
+                .iter()
+                .enumerate()
+                .map(|(i, value)| entry(PathSegment::Index(i), format!("[{}]", i), value))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Valeur d'une entrée du niveau courant
+    pub fn value(&self, segment: &PathSegment) -> Option<&Value> {
+        child(self.current(), segment)
+    }
+
+    /// Ouvre un objet ou un tableau du niveau courant
+    pub fn enter(&mut self, segment: &PathSegment) -> bool {
+        match self.value(segment) {
+            Some(value) if value.is_object() || value.is_array() => {
+                self.path.push(segment.clone());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Remonte d'un niveau et renvoie l'entrée quittée (`None` à la racine)
+    pub fn leave(&mut self) -> Option<PathSegment> {
+        self.path.pop()
+    }
+
+    /// Remplace la valeur d'une entrée du niveau courant
+    pub fn set(&mut self, segment: &PathSegment, new_value: Value) {
+        let target = match (self.current_mut(), segment) {
+            (Value::Object(map), PathSegment::Key(key)) => map.get_mut(key),
+            (Value::Array(items), PathSegment::Index(i)) => items.get_mut(*i),
+            _ => None,
+        };
+        if let Some(target) = target {
+            if *target != new_value {
+                *target = new_value;
+                self.modified = true;
+            }
+        }
+    }
+
+    pub fn into_value(self) -> Value {
+        self.root
+    }
+
+    /// Parcourt la configuration jusqu'à S (enregistrer) ou Q à la racine
+    pub fn run(&mut self, ui: &UI, input: &InputReader) -> EditorOutcome {
+        let mut selected = 0;
+        loop {
+            let entries = self.entries();
+            let title = format!("Éditer Configuration: {}{}", self.path_label(), if self.modified { " *" } else { "" });
+            let outcome = SelectList::new(&title, &entries, |e| format!("{}  {}", e.label, e.preview))
+                .with_color(|e| if e.is_container { Color::Info } else { Color::Fg })
+                .with_hint("Entrée: Ouvrir/Modifier | S: Enregistrer | Q: Remonter")
+                .with_action_keys(&['s'])
+                .with_selected(selected)
+                .run(ui, input);
+
+            match outcome {
+                SelectOutcome::Selected(index) => {
+                    let segment = entries[index].segment.clone();
+                    if self.enter(&segment) {
+                        selected = 0;
+                    } else {
+                        self.edit_leaf(ui, input, &entries[index]);
+                        selected = index;
+                    }
+                }
+                SelectOutcome::Action(_, _) => return EditorOutcome::Save(self.root.clone()),
+                SelectOutcome::Cancelled => match self.leave() {
+                    Some(segment) => {
+                        selected = self.entries().iter().position(|e| e.segment == segment).unwrap_or(0);
+                    }
+                    None => return EditorOutcome::Cancelled,
+                },
+            }
+        }
+    }
+
+    /// Édite une feuille : bascule pour les booléens, saisie validée pour le reste
+    fn edit_leaf(&mut self, ui: &UI, input: &InputReader, entry: &ConfigEntry) {
+        let Some(current) = self.value(&entry.segment).cloned() else {
+            return;
+        };
+        if let Value::Bool(b) = current {
+            self.set(&entry.segment, Value::Bool(!b));
+            return;
+        }
+
+        let mut error: Option<String> = None;
+        loop {
+            ui.clear_screen();
+            ui.draw_header("Modifier une valeur");
+            let (box_x, box_y, _, box_h) = ui.get_box_dimensions();
+            let mut y = box_y + 5;
+
+            ui.set_color(Color::Info);
+            ui.draw_text_clipped(box_x + 2, y, &format!("Clé: {} → {}", self.path_label(), entry.label), usize::MAX);
+            y += 1;
+            ui.set_color(Color::Fg);
+            ui.draw_text_clipped(box_x + 2, y, &format!("Valeur actuelle ({}): {}", type_name(&current), current), usize::MAX);
+            y += 2;
+            if let Some(ref message) = error {
+                ui.set_color(Color::Error);
+                ui.draw_text_clipped(box_x + 2, y, &format!("✗ {}", message), usize::MAX);
+            }
+            y += 2;
+            ui.set_color(Color::Reset);
+            ui.draw_text(box_x + 2, box_h.saturating_sub(2), "Entrée: Valider | Échap: Annuler");
+
+            let Some(text) = read_line(ui, input, box_x + 2, y) else {
+                return;
+            };
+            match parse_edited_value(&current, &text) {
+                Ok(value) => {
+                    self.set(&entry.segment, value);
+                    return;
+                }
+                Err(message) => error = Some(message),
+            }
+        }
+    }
+}
+
+fn child<'v>(value: &'v Value, segment: &PathSegment) -> Option<&'v Value> {
+    match (value, segment) {
+        (Value::Object(map), PathSegment::Key(key)) => map.get(key),
+        (Value::Array(items), PathSegment::Index(i)) => items.get(*i),
+        _ => None,
+    }
+}
+
+/// Aperçu d'une valeur sur une ligne
+fn preview(value: &Value) -> String {
+    match value {
+        Value::Object(map) => format!("{{…}} ({} clé(s))", map.len()),
+        Value::Array(items) => format!("[…] ({} élément(s))", items.len()),
+        other => other.to_string(),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "nul",
+        Value::Bool(_) => "booléen",
+        Value::Number(n) if n.is_f64() => "nombre",
+        Value::Number(_) => "entier",
+        Value::String(_) => "texte",
+        Value::Array(_) => "tableau",
+        Value::Object(_) => "objet",
+    }
+}
+
+/// Convertit la saisie selon le type de la valeur d'origine
+///
+/// Les entiers restent entiers ; une valeur nulle accepte du JSON, sinon du texte.
+pub fn parse_edited_value(original: &Value, text: &str) -> Result<Value, String> {
+    let text = text.trim();
+    match original {
+        Value::Bool(_) => match text {
+            "true" | "1" | "oui" => Ok(Value::Bool(true)),
+            "false" | "0" | "non" => Ok(Value::Bool(false)),
+            _ => Err(format!("Booléen attendu (true/false): '{}'", text)),
+        },
+        Value::Number(n) if !n.is_f64() => text
+            .parse::<i64>()
+            .map(Value::from)
+            .map_err(|_| format!("Entier attendu: '{}'", text)),
+        Value::Number(_) => text
+            .parse::<f64>()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number)
+            .ok_or_else(|| format!("Nombre attendu: '{}'", text)),
+        Value::Null => Ok(serde_json::from_str(text).unwrap_or_else(|_| Value::String(text.to_string()))),
+        _ => Ok(Value::String(text.to_string())),
+    }
+}
+
+/// Saisie d'une ligne (toutes les lettres, `q` compris) ; Échap annule
+pub(crate) fn read_line(ui: &UI, input: &InputReader, x: u16, y: u16) -> Option<String> {
+    read_line_masked(ui, input, x, y, false)
+}
+
+/// Comme `read_line`, mais chaque caractère s'affiche `*` (jetons, mots de passe)
+pub(crate) fn read_secret(ui: &UI, input: &InputReader, x: u16, y: u16) -> Option<String> {
+    read_line_masked(ui, input, x, y, true)
+}
+
+fn read_line_masked(ui: &UI, input: &InputReader, x: u16, y: u16, masked: bool) -> Option<String> {
+    let mut buffer = String::new();
+    loop {
+        ui.clear_line(y);
+        ui.set_color(Color::Fg);
+        // Le curseur reste après le texte dessiné
+        let shown = if masked { "*".repeat(buffer.chars().count()) } else { buffer.clone() };
+        ui.draw_text(x, y, &format!("> {}", shown));
+        ui.show_cursor();
+        let key = input.read_key();
+        ui.hide_cursor();
+        match key {
+            Ok(Key::Char(c)) => buffer.push(c),
+            Ok(Key::Quit) if input.main_menu_requested() => return None,
+            Ok(Key::Interrupt) => return None,
+            Ok(Key::Quit) => buffer.push('q'),
+            Ok(Key::Backspace) => {
+                buffer.pop();
+            }
+            Ok(Key::Enter) => return Some(buffer),
+            Ok(Key::Escape) => return None,
+            _ => {}
+        }
+    }
+}
+
+/// Éditeurs à essayer, dans l'ordre : préférence `editor`, `$VISUAL`, `$EDITOR`, puis nano et vi
+pub fn editor_candidates(configured: Option<&str>, visual: Option<&str>, editor: Option<&str>) -> Vec<String> {
+    let mut candidates: Vec<String> = Vec::new();
+    for name in [configured, visual, editor, Some("nano"), Some("vi")].into_iter().flatten() {
+        let name = name.trim();
+        if !name.is_empty() && !candidates.iter().any(|c| c == name) {
+            candidates.push(name.to_string());
+        }
+    }
+    candidates
+}
+
+/// Ouvre `initial` dans le premier éditeur disponible et renvoie le texte enregistré
+///
+/// `file_name` donne l'extension vue par l'éditeur (coloration syntaxique).
+/// Le terminal doit déjà être rendu à l'éditeur (`RawModeGuard::suspend`). Le texte passe par
+/// un fichier créé dans un répertoire temporaire privé (0700), supprimés ensuite. Un éditeur
+/// introuvable (code 127 du shell) laisse place au suivant ; une sortie en erreur annule l'édition.
+pub fn run_external_editor(initial: &str, file_name: &str, candidates: &[String]) -> Result<String, String> {
+    let dir = create_private_temp_dir().map_err(|e| format!("Impossible de créer le répertoire temporaire: {}", e))?;
+    let result = edit_file(&dir.join(file_name), initial, candidates);
+    let _ = fs::remove_dir_all(&dir);
+    result
+}
+
+/// Crée un répertoire temporaire neuf, accessible du seul utilisateur
+///
+/// `mkdir` échoue si le nom existe déjà (y compris un lien symbolique) : on réessaie alors
+/// avec un autre nom plutôt que de réutiliser un chemin préparé par un tiers.
+fn create_private_temp_dir() -> std::io::Result<PathBuf> {
+    let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.subsec_nanos()).unwrap_or(0);
+    let mut last_error = None;
+    for attempt in 0..16u32 {
+        let dir = std::env::temp_dir().join(format!("rmdb-edit-{}-{:08x}{:02x}", std::process::id(), nanos, attempt));
+        match DirBuilder::new().mode(0o700).create(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => last_error = Some(e),
+            Err(e) => return Err(e),
+        }
+    }
+    Err(last_error.unwrap_or_else(|| std::io::Error::from(std::io::ErrorKind::AlreadyExists)))
+}
+
+fn edit_file(path: &Path, initial: &str, candidates: &[String]) -> Result<String, String> {
+    let write = || -> std::io::Result<()> {
+        let mut file = OpenOptions::new().write(true).create_new(true).mode(0o600).open(path)?;
+        file.write_all(initial.as_bytes())
+    };
+    write().map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+
+    let mut result = Err("Aucun éditeur trouvé (editor, VISUAL, EDITOR, nano, vi)".to_string());
+    for editor in candidates {
+        // Passé au shell : la valeur peut contenir des options (`code --wait`)
+        let status = Command::new("sh")
+            .arg("-c")
+            .arg(format!("{} \"$1\"", editor))
+            .arg("sh")
+            .arg(path)
+            .status();
+        match status {
+            Ok(status) if status.code() == Some(127) => continue,
+            Ok(status) if status.success() => {
+                result = fs::read_to_string(path).map_err(|e| format!("Impossible de relire {}: {}", path.display(), e));
+            }
+            Ok(status) => result = Err(format!("{} s'est terminé en erreur ({})", editor, status)),
+            Err(e) => result = Err(format!("Impossible de lancer {}: {}", editor, e)),
+        }
+        break;
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_navigate_and_edit() {
+        let mut editor = ConfigEditor::new(json!({"dhcp": {"enabled": true, "lease": 3600}, "dns": ["1.1.1.1"]}));
+        assert!(!editor.enter(&PathSegment::Key("missing".to_string())));
+        assert!(editor.enter(&PathSegment::Key("dhcp".to_string())));
+        assert_eq!(editor.path_label(), "dhcp");
+        assert_eq!(editor.entries().len(), 2);
+
+        editor.set(&PathSegment::Key("lease".to_string()), json!(7200));
+        assert!(editor.is_modified());
+        assert_eq!(editor.leave(), Some(PathSegment::Key("dhcp".to_string())));
+        assert_eq!(editor.leave(), None);
+        assert_eq!(editor.into_value()["dhcp"]["lease"], json!(7200));
+    }
+
+    #[test]
+    fn test_parse_edited_value() {
+        assert_eq!(parse_edited_value(&json!(10), "42"), Ok(json!(42)));
+        assert!(parse_edited_value(&json!(10), "4.2").is_err());
+        assert_eq!(parse_edited_value(&json!(0.5), "1.5"), Ok(json!(1.5)));
+        assert!(parse_edited_value(&json!(0.5), "abc").is_err());
+        assert_eq!(parse_edited_value(&json!("a"), " eth0 "), Ok(json!("eth0")));
+        assert_eq!(parse_edited_value(&Value::Null, "12"), Ok(json!(12)));
+    }
+
+    #[test]
+    fn test_private_temp_dir() {
+        use std::os::unix::fs::PermissionsExt;
+        let first = create_private_temp_dir().unwrap();
+        let second = create_private_temp_dir().unwrap();
+        assert_ne!(first, second);
+        assert_eq!(fs::metadata(&first).unwrap().permissions().mode() & 0o777, 0o700);
+        fs::remove_dir(&first).unwrap();
+        fs::remove_dir(&second).unwrap();
+    }
+
+    #[test]
+    fn test_editor_candidates() {
+        assert_eq!(editor_candidates(None, None, None), ["nano", "vi"]);
+        assert_eq!(
+            editor_candidates(Some("code --wait"), Some(" "), Some("vi")),
+            ["code --wait", "vi", "nano"]
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use crate::pres::input::InputReader;
+use crate::pres::ui::{Color, UI};
+
+/// Lignes de l'exemple de tableau, plus nombreuses que les lignes visibles pour afficher la barre de défilement
+const SAMPLE_ROWS: &[(&str, &str, &str)] = &[
+    ("rmdb", "RUNNING", "10.0.3.12"),
+    ("labo", "STOPPED", "-"),
+    ("build", "FROZEN", "10.0.3.40"),
+    ("test-ipxe", "RUNNING", "10.0.3.51"),
+    ("archive", "STOPPED", "-"),
+    ("web", "RUNNING", "10.0.3.77"),
+    ("dns", "RUNNING", "10.0.3.2"),
+];
+const SAMPLE_VISIBLE_ROWS: usize = 4;
+
+/// Écran de test d'affichage : chaque `Color` (texte et fond), les glyphes, un cadre,
+/// un tableau avec sélection et barre de défilement, une barre de progression
+///
+/// Sert à vérifier le rendu d'un terminal ou d'un thème ; toute touche revient.
+pub fn run(ui: &UI, input: &InputReader) {
+    ui.clear_screen();
+    ui.draw_header("Test d'affichage");
+    let (box_x, _, box_w, box_h) = ui.get_box_dimensions();
+    let left = box_x + 2;
+    let right = box_x + (box_w / 2).max(40);
+    let mut y = ui.menu_top();
+
+    ui.set_color(Color::Fg);
+    ui.draw_text(left, y, "Couleurs (texte, puis fond si défini) :");
+    let glyph_top = y;
+    y += 1;
+    for color in Color::ALL {
+        ui.set_color(color);
+        ui.draw_text(left, y, &format!("{:<10} Texte d'exemple 0123", color.name()));
+        if color.has_background() {
+            ui.set_bg_color(color);
+            ui.set_color(Color::Fg);
+            ui.draw_text(left + 32, y, " fond ");
+            ui.set_color(Color::Reset);
+        }
+        y += 1;
+    }
+    ui.set_color(Color::Reset);
+
+    let mut glyph_y = glyph_top;
+    ui.set_color(Color::Fg);
+    ui.draw_text_clipped(right, glyph_y, &format!("Glyphes ({}) :", if ui.asciify { "ASCII" } else { "UTF-8" }), usize::MAX);
+    glyph_y += 1;
+    let glyphs: Vec<String> = ui.glyphs().iter().map(|(_, glyph)| glyph.to_string()).collect();
+    ui.draw_text_clipped(right, glyph_y, &glyphs.join(" "), usize::MAX);
+    glyph_y += 1;
+    ui.draw_text_clipped(right, glyph_y, "╔═╗ ║ ╚═╝ → ← • … « »", usize::MAX);
+    glyph_y += 2;
+    ui.draw_text(right, glyph_y, "Progression :");
+    ui.draw_progress_bar(right, glyph_y + 1, 24, 0.6);
+
+    y += 1;
+    let table_w: u16 = 40;
+    let table_h = SAMPLE_VISIBLE_ROWS as u16 + 3;
+    if y + table_h + 3 < box_h {
+        ui.draw_box(left, y, table_w, table_h);
+        ui.set_color(Color::Accent);
+        ui.draw_text(left + 2, y + 1, &format!("{:<12}{:<10}{}", "Nom", "État", "IP"));
+        for (row, (name, state, ip)) in SAMPLE_ROWS.iter().take(SAMPLE_VISIBLE_ROWS).enumerate() {
+            let line = format!("{:<12}{:<10}{:<14}", name, state, ip);
+            let row_y = y + 2 + row as u16;
+            if row == 1 {
+                ui.set_color(Color::Selection);
+                ui.draw_text(left + 1, row_y, &format!("{}{}", ui.glyph("selected"), line));
+            } else {
+                ui.set_color(Color::Fg);
+                ui.draw_text(left + 2, row_y, &line);
+            }
+        }
+        ui.draw_scrollbar(left + table_w - 2, y, table_h, SAMPLE_ROWS.len(), SAMPLE_VISIBLE_ROWS, 1);
+        y += table_h;
+    }
+
+    ui.set_color(Color::Info);
+    let term = std::env::var("TERM").unwrap_or_default();
+    let colorterm = std::env::var("COLORTERM").unwrap_or_default();
+    ui.draw_text_clipped(left, y + 1, &format!("Terminal {}x{} | TERM={} | COLORTERM={}", box_w, box_h, term, colorterm), usize::MAX);
+    ui.set_color(Color::Reset);
+    ui.draw_text(left, box_h.saturating_sub(2), "Appuyez sur une touche pour revenir...");
+    let _ = input.read_key();
+}
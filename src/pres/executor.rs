@@ -1,5 +1,12 @@
 use crate::data::capabilities::Capabilities;
-use std::process::Command;
+use std::cell::RefCell;
+use std::io::{BufRead, BufReader};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ActionMode {
@@ -19,7 +26,10 @@ pub struct CommandOutput {
 pub enum ExecError {
     NotAllowed(String),
     MissingTool(String),
+    PermissionDenied(String),
     Failed(String),
+    /// Commande interrompue à la demande de l'utilisateur
+    Cancelled,
 }
 
 impl std::fmt::Display for ExecError {
@@ -27,19 +37,98 @@ impl std::fmt::Display for ExecError {
         match self {
             ExecError::NotAllowed(msg) => write!(f, "Action non autorisée: {}", msg),
             ExecError::MissingTool(msg) => write!(f, "Outil manquant: {}", msg),
+            ExecError::PermissionDenied(msg) => write!(f, "Permission refusée: {}", msg),
             ExecError::Failed(msg) => write!(f, "Échec: {}", msg),
+            ExecError::Cancelled => write!(f, "Opération annulée"),
         }
     }
 }
 
+/// Programmes qui modifient le système, refusés en mode Lecture seule
+const WRITE_COMMANDS: &[&str] = &[
+    "rm", "rmdir", "mv", "cp", "dd", "tee", "truncate", "touch", "mkdir", "ln", "chmod", "chown",
+    "mkfs", "mount", "umount", "kill", "killall", "pkill", "reboot", "shutdown", "poweroff",
+    "useradd", "userdel", "usermod", "passwd", "apt", "apt-get", "dnf", "yum", "pacman", "zypper",
+    "apk", "rpm", "dpkg", "lxc-create", "lxc-destroy", "lxc-start", "lxc-stop", "lxc-copy",
+    "lxc-freeze", "lxc-unfreeze", "qemu-img", "virt-install", "ufw", "firewall-cmd", "nft",
+    "iptables", "wget", "install",
+];
+
+/// Sous-commandes de gestion de services qui modifient l'état du système
+const WRITE_SUBCOMMANDS: &[(&str, &[&str])] = &[
+    ("systemctl", &["start", "stop", "restart", "reload", "enable", "disable", "mask", "unmask", "kill", "daemon-reload"]),
+    ("service", &["start", "stop", "restart", "reload"]),
+    ("rc-service", &["start", "stop", "restart", "reload"]),
+    ("rc-update", &["add", "del"]),
+    ("sv", &["up", "down", "restart", "start", "stop"]),
+    ("virsh", &["start", "destroy", "shutdown", "reboot", "undefine", "define", "create", "snapshot-create-as", "snapshot-delete"]),
+];
+
+/// Chemins sensibles dont même la lecture est refusée en mode Lecture seule
+const SENSITIVE_PATHS: &[&str] = &["/etc/shadow", "/etc/gshadow", "/etc/sudoers", "/root/", ".ssh/", "/proc/kcore"];
+
+/// Jeton d'annulation partagé par les commandes d'une opération longue
+///
+/// Tant qu'il est installé dans l'exécuteur, chaque commande en cours est surveillée :
+/// `request_check` est appelé pendant l'attente et, s'il renvoie vrai, le processus est
+/// arrêté et la commande échoue avec `ExecError::Cancelled`, comme toutes les suivantes.
+#[derive(Clone)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+    request_check: Option<Arc<dyn Fn() -> bool + Send + Sync>>,
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self {
+            cancelled: Arc::new(AtomicBool::new(false)),
+            request_check: None,
+        }
+    }
+
+    /// Fonction appelée pendant l'attente d'une commande (ex: Échap pressé puis confirmé)
+    pub fn with_request_check(mut self, check: impl Fn() -> bool + Send + Sync + 'static) -> Self {
+        self.request_check = Some(Arc::new(check));
+        self
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Vérifie une demande d'annulation en attente ; vrai si l'opération est annulée
+    fn poll(&self) -> bool {
+        if !self.is_cancelled() && self.request_check.as_ref().is_some_and(|check| check()) {
+            self.cancel();
+        }
+        self.is_cancelled()
+    }
+}
+
+impl Default for CancelToken {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 pub struct CommandExecutor {
     mode: ActionMode,
     caps: Capabilities,
+    cancel: Option<CancelToken>,
 }
 
 impl CommandExecutor {
     pub fn new(mode: ActionMode, caps: Capabilities) -> Self {
-        Self { mode, caps }
+        Self { mode, caps, cancel: None }
+    }
+
+    /// Rend les commandes suivantes interruptibles (`None` pour revenir aux commandes bloquantes)
+    pub fn set_cancel_token(&mut self, token: Option<CancelToken>) {
+        self.cancel = token;
     }
 
     pub fn set_mode(&mut self, mode: ActionMode) {
@@ -50,36 +139,242 @@ impl CommandExecutor {
         self.mode
     }
 
-    pub fn run_shell(&self, cmd: &str, requires_admin: bool) -> Result<CommandOutput, ExecError> {
+    pub fn capabilities(&self) -> &Capabilities {
+        &self.caps
+    }
+
+    /// Indique si une commande peut être exécutée dans le mode courant
+    ///
+    /// En mode Lecture seule, les commandes qui écrivent (programmes de la liste noire,
+    /// redirections vers un fichier, arrêt/démarrage de services) ou qui touchent des
+    /// chemins sensibles sont refusées, même si elles ne demandent pas de droits admin.
+    pub fn is_command_allowed(&self, cmd: &str, requires_admin: bool) -> bool {
+        if requires_admin && self.mode != ActionMode::Admin {
+            return false;
+        }
+        if self.mode != ActionMode::ReadOnly {
+            return true;
+        }
+        !has_file_redirect(cmd)
+            && !SENSITIVE_PATHS.iter().any(|path| cmd.contains(path))
+            && command_segments(cmd).iter().all(|segment| !is_write_segment(segment))
+    }
+
+    /// Vérifie une demande d'annulation entre deux commandes (pendant une attente)
+    pub fn cancel_requested(&self) -> bool {
+        self.cancel.as_ref().is_some_and(|token| token.poll())
+    }
+
+    fn check_allowed(&self, cmd: &str, requires_admin: bool) -> Result<(), ExecError> {
+        if self.cancel.as_ref().is_some_and(|token| token.is_cancelled()) {
+            return Err(ExecError::Cancelled);
+        }
+        if self.mode == ActionMode::ReadOnly && !requires_admin && !self.is_command_allowed(cmd, false) {
+            return Err(ExecError::PermissionDenied(
+                "commande refusée en mode Lecture seule".to_string(),
+            ));
+        }
         if requires_admin {
             if self.mode != ActionMode::Admin {
                 return Err(ExecError::NotAllowed(
                     "Action admin refusée: passez en mode Admin".to_string(),
                 ));
             }
-            if !self.caps.has_sudo {
+            if !self.caps.has_sudo && !self.caps.is_root() {
                 return Err(ExecError::MissingTool(
                     "sudo est requis en mode Admin mais introuvable".to_string(),
                 ));
             }
         }
+        Ok(())
+    }
+
+    /// Shell qui exécutera `cmd` : via `sudo -n` pour une action admin en mode Admin,
+    /// sauf si l'interface tourne déjà en root
+    fn shell_command(&self, cmd: &str, requires_admin: bool) -> Command {
+        if requires_admin && self.mode == ActionMode::Admin && !self.caps.is_root() {
+            let mut c = Command::new("sudo");
+            c.args(["-n", "sh", "-c", cmd]);
+            c
+        } else {
+            let mut c = Command::new("sh");
+            c.args(["-lc", cmd]);
+            c
+        }
+    }
+
+    /// Lance une commande longue (ex: `tail -f`) dont la sortie est lue ligne par ligne
+    pub fn spawn_stream(&self, cmd: &str, requires_admin: bool) -> Result<StreamingCommand, ExecError> {
+        self.check_allowed(cmd, requires_admin)?;
+
+        // `exec` remplace le shell pour que le signal d'arrêt atteigne directement la commande
+        let exec_cmd = format!("exec {}", cmd);
+        let mut command = self.shell_command(&exec_cmd, requires_admin);
+
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| ExecError::Failed(format!("Impossible de lancer la commande: {}", e)))?;
+
+        let (sender, receiver) = mpsc::channel();
+        if let Some(stdout) = child.stdout.take() {
+            let sender = sender.clone();
+            thread::spawn(move || {
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    if sender.send(line).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        if let Some(stderr) = child.stderr.take() {
+            thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    if sender.send(line).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        Ok(StreamingCommand { child, receiver })
+    }
+
+    /// Exécute une commande en transmettant chaque ligne de sortie à `on_line` dès sa réception
+    ///
+    /// stdout et stderr sont fusionnés (dans l'ordre d'arrivée) et renvoyés dans `stdout`.
+    pub fn run_shell_lines(&self, cmd: &str, requires_admin: bool, on_line: &mut dyn FnMut(&str)) -> Result<CommandOutput, ExecError> {
+        self.check_allowed(cmd, requires_admin)?;
+
+        let merged = format!("{{ {}\n}} 2>&1", cmd);
+        let mut command = self.shell_command(&merged, requires_admin);
+
+        let child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| ExecError::Failed(format!("Impossible de lancer la commande: {}", e)))?;
+
+        self.wait_child(child, on_line)
+    }
+
+    /// Lance un programme interactif (shell...) sur le terminal et renvoie son code de sortie
+    ///
+    /// Le terminal doit déjà lui être rendu (`RawModeGuard::suspend`). Avec `requires_admin`,
+    /// `sudo -n` réutilise la session ouverte au démarrage : aucune invite ne se mêle au programme.
+    pub fn run_interactive(&self, cmd: &str, requires_admin: bool) -> Result<Option<i32>, ExecError> {
+        self.check_allowed(cmd, requires_admin)?;
+        self.shell_command(cmd, requires_admin)
+            .status()
+            .map(|status| status.code())
+            .map_err(|e| ExecError::Failed(format!("Impossible de lancer la commande: {}", e)))
+    }
+
+    /// Attend la fin d'un processus en relayant sa sortie ligne par ligne ; l'arrête si
+    /// le jeton d'annulation est déclenché
+    fn wait_child(&self, mut child: Child, on_line: &mut dyn FnMut(&str)) -> Result<CommandOutput, ExecError> {
+        let (sender, receiver) = mpsc::channel();
+        if let Some(stdout) = child.stdout.take() {
+            thread::spawn(move || {
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    if sender.send(line).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        let stderr_reader = child.stderr.take().map(|stderr| {
+            thread::spawn(move || {
+                let mut collected = String::new();
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    collected.push_str(&line);
+                    collected.push('\n');
+                }
+                collected
+            })
+        });
+
+        let mut stdout = String::new();
+        loop {
+            match receiver.recv_timeout(Duration::from_millis(100)) {
+                Ok(line) => {
+                    on_line(&line);
+                    stdout.push_str(&line);
+                    stdout.push('\n');
+                }
+                Err(RecvTimeoutError::Timeout) => {}
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+            if self.cancel.as_ref().is_some_and(|token| token.poll()) {
+                terminate_child(&mut child);
+                return Err(ExecError::Cancelled);
+            }
+        }
+
+        let status = child
+            .wait()
+            .map_err(|e| ExecError::Failed(format!("Impossible d'attendre la commande: {}", e)))?;
+        let stderr = stderr_reader.and_then(|reader| reader.join().ok()).unwrap_or_default();
+
+        Ok(CommandOutput {
+            exit_code: status.code(),
+            stdout,
+            stderr,
+        })
+    }
+
+    /// Exécute des commandes indépendantes en parallèle, une par thread ; les résultats
+    /// suivent l'ordre de `cmds`
+    ///
+    /// Réservé aux vérifications courtes (états de services, tests de fichiers). Avec sudo,
+    /// le ticket est rafraîchi une fois avant le lancement : les `sudo -n` simultanés
+    /// réutilisent le même ticket et aucun ne peut demander de mot de passe.
+    pub fn run_many(&self, cmds: &[&str], requires_admin: bool) -> Vec<Result<CommandOutput, ExecError>> {
+        if cmds.len() < 2 {
+            return cmds.iter().map(|cmd| self.run_shell(cmd, requires_admin)).collect();
+        }
+        if requires_admin && self.mode == ActionMode::Admin && !self.caps.is_root() {
+            let _ = Command::new("sudo").args(["-n", "-v"]).stdin(Stdio::null()).status();
+        }
+        thread::scope(|scope| {
+            let handles: Vec<_> = cmds
+                .iter()
+                .map(|cmd| scope.spawn(move || self.run_shell(cmd, requires_admin)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap_or_else(|_| Err(ExecError::Failed("Vérification interrompue".to_string()))))
+                .collect()
+        })
+    }
+
+    /// Exécute `cmd` et renvoie sa sortie
+    ///
+    /// `cmd` ne contient jamais `sudo` : avec `requires_admin`, l'élévation est ajoutée ici
+    /// (et omise si l'interface tourne en root).
+    pub fn run_shell(&self, cmd: &str, requires_admin: bool) -> Result<CommandOutput, ExecError> {
+        self.check_allowed(cmd, requires_admin)?;
+
+        // Opération interruptible : la sortie est lue au fil de l'eau pour surveiller l'annulation
+        if self.cancel.is_some() {
+            let child = self.shell_command(cmd, requires_admin)
+                .stdin(Stdio::null())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .map_err(|e| ExecError::Failed(format!("Impossible d'exécuter la commande: {}", e)))?;
+            return self.wait_child(child, &mut |_| {});
+        }
 
         // Si la commande nécessite des privilèges admin et qu'on est en mode Admin,
         // on doit préfixer la commande avec sudo -n (non-interactif, utilise le timestamp)
         // sudo -n utilise le timestamp sudo valide obtenu lors de l'authentification
-        let out = if requires_admin && self.mode == ActionMode::Admin {
-            // Exécuter avec sudo -n pour utiliser le timestamp sudo valide
-            Command::new("sudo")
-                .args(["-n", "sh", "-c", cmd])
-                .output()
-                .map_err(|e| ExecError::Failed(format!("Impossible d'exécuter la commande avec sudo: {}", e)))?
-        } else {
-            // Exécuter sans sudo
-            Command::new("sh")
-            .args(["-lc", cmd])
+        let out = self.shell_command(cmd, requires_admin)
             .output()
-                .map_err(|e| ExecError::Failed(format!("Impossible d'exécuter la commande: {}", e)))?
-        };
+            .map_err(|e| ExecError::Failed(format!("Impossible d'exécuter la commande: {}", e)))?;
 
         let stdout = String::from_utf8_lossy(&out.stdout).to_string();
         let stderr = String::from_utf8_lossy(&out.stderr).to_string();
@@ -92,3 +387,255 @@ impl CommandExecutor {
     }
 }
 
+/// Exécution de commandes shell, réelle (`CommandExecutor`) ou scriptée (`MockExecutor`)
+///
+/// Les fonctions qui n'ont besoin que de `run_shell` prennent un `&dyn Executor` pour
+/// pouvoir être testées sans lancer de processus.
+pub trait Executor {
+    fn run_shell(&self, cmd: &str, requires_admin: bool) -> Result<CommandOutput, ExecError>;
+
+    /// Exécute des commandes indépendantes ; les résultats suivent l'ordre de `cmds`
+    ///
+    /// Séquentiel par défaut ; `CommandExecutor` les lance en parallèle.
+    fn run_many(&self, cmds: &[&str], requires_admin: bool) -> Vec<Result<CommandOutput, ExecError>> {
+        cmds.iter().map(|cmd| self.run_shell(cmd, requires_admin)).collect()
+    }
+}
+
+impl Executor for CommandExecutor {
+    fn run_shell(&self, cmd: &str, requires_admin: bool) -> Result<CommandOutput, ExecError> {
+        CommandExecutor::run_shell(self, cmd, requires_admin)
+    }
+
+    fn run_many(&self, cmds: &[&str], requires_admin: bool) -> Vec<Result<CommandOutput, ExecError>> {
+        CommandExecutor::run_many(self, cmds, requires_admin)
+    }
+}
+
+/// Exécuteur de test : renvoie des sorties préparées et mémorise les commandes reçues
+///
+/// La première règle dont le motif est contenu dans la commande s'applique ; sans règle,
+/// la commande réussit avec une sortie vide.
+#[derive(Default)]
+pub struct MockExecutor {
+    rules: Vec<(String, Result<CommandOutput, ExecError>)>,
+    issued: RefCell<Vec<(String, bool)>>,
+}
+
+impl MockExecutor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Les commandes contenant `pattern` réussissent avec `stdout`
+    pub fn on(self, pattern: &str, stdout: &str) -> Self {
+        self.on_output(pattern, CommandOutput { exit_code: Some(0), stdout: stdout.to_string(), stderr: String::new() })
+    }
+
+    /// Les commandes contenant `pattern` renvoient `output` (code de sortie compris)
+    pub fn on_output(mut self, pattern: &str, output: CommandOutput) -> Self {
+        self.rules.push((pattern.to_string(), Ok(output)));
+        self
+    }
+
+    /// Les commandes contenant `pattern` échouent avec `error`
+    pub fn on_error(mut self, pattern: &str, error: ExecError) -> Self {
+        self.rules.push((pattern.to_string(), Err(error)));
+        self
+    }
+
+    /// Commandes reçues, dans l'ordre, avec leur indicateur `requires_admin`
+    pub fn issued(&self) -> Vec<(String, bool)> {
+        self.issued.borrow().clone()
+    }
+
+    /// Commandes reçues, dans l'ordre
+    pub fn commands(&self) -> Vec<String> {
+        self.issued.borrow().iter().map(|(cmd, _)| cmd.clone()).collect()
+    }
+}
+
+impl Executor for MockExecutor {
+    fn run_shell(&self, cmd: &str, requires_admin: bool) -> Result<CommandOutput, ExecError> {
+        self.issued.borrow_mut().push((cmd.to_string(), requires_admin));
+        self.rules
+            .iter()
+            .find(|(pattern, _)| cmd.contains(pattern.as_str()))
+            .map(|(_, result)| result.clone())
+            .unwrap_or_else(|| Ok(CommandOutput { exit_code: Some(0), stdout: String::new(), stderr: String::new() }))
+    }
+}
+
+/// Découpe une ligne shell en commandes simples (séparées par ; | & && || $( ou `)
+fn command_segments(cmd: &str) -> Vec<String> {
+    cmd.split([';', '|', '&', '`', '(', ')', '\n'])
+        .map(|s| s.trim().trim_start_matches('$').trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+/// Détecte une redirection de sortie vers un fichier (hors /dev/null et duplication de descripteur)
+fn has_file_redirect(cmd: &str) -> bool {
+    cmd.match_indices('>').any(|(i, _)| {
+        let target = cmd[i + 1..].trim_start_matches('>').trim_start();
+        !(target.is_empty() || target.starts_with('&') || target.starts_with("/dev/null"))
+    })
+}
+
+/// Vérifie si une commande simple modifie le système
+fn is_write_segment(segment: &str) -> bool {
+    let mut words = segment
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c| c == '"' || c == '\''))
+        // Ignorer les affectations de variables et les préfixes d'exécution
+        .skip_while(|w| {
+            w.contains('=')
+                || w.parse::<f64>().is_ok()
+                || matches!(*w, "sudo" | "-n" | "env" | "exec" | "nohup" | "timeout")
+        });
+
+    let program = match words.next() {
+        Some(word) => word.rsplit('/').next().unwrap_or(word),
+        None => return false,
+    };
+    if WRITE_COMMANDS.contains(&program) || program.starts_with("mkfs.") {
+        return true;
+    }
+    match program {
+        // La commande exécutée dans le container est vérifiée comme une commande locale
+        "lxc-attach" => {
+            let inner: Vec<&str> = words.skip_while(|w| *w != "--").skip(1).collect();
+            return inner.is_empty() || is_write_segment(&inner.join(" "));
+        }
+        "sed" => return words.any(|w| w.starts_with("-i") || w == "--in-place"),
+        _ => {}
+    }
+    if let Some((_, subcommands)) = WRITE_SUBCOMMANDS.iter().find(|(name, _)| *name == program) {
+        return words.any(|w| subcommands.contains(&w));
+    }
+    false
+}
+
+/// Commande en cours d'exécution dont la sortie arrive au fil de l'eau
+///
+/// Le processus est arrêté (SIGTERM puis SIGKILL) à la destruction.
+pub struct StreamingCommand {
+    child: Child,
+    receiver: Receiver<String>,
+}
+
+impl StreamingCommand {
+    /// Récupère les lignes reçues depuis le dernier appel, sans bloquer
+    pub fn try_lines(&self) -> Vec<String> {
+        self.receiver.try_iter().collect()
+    }
+
+    /// Indique si la commande est toujours en cours
+    pub fn is_running(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+
+    /// Arrête la commande et attend sa fin
+    pub fn stop(&mut self) {
+        terminate_child(&mut self.child);
+    }
+}
+
+/// Arrête un processus (SIGTERM puis SIGKILL) et attend sa fin
+fn terminate_child(child: &mut Child) {
+    if !matches!(child.try_wait(), Ok(None)) {
+        return;
+    }
+    // SIGTERM d'abord : sudo le relaie à la commande, contrairement à SIGKILL
+    let _ = Command::new("kill")
+        .args(["-TERM", &child.id().to_string()])
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status();
+    for _ in 0..10 {
+        if !matches!(child.try_wait(), Ok(None)) {
+            return;
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+impl Drop for StreamingCommand {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_only_denylist() {
+        let executor = CommandExecutor::new(ActionMode::ReadOnly, Capabilities::default());
+        assert!(executor.is_command_allowed("lxc-ls -f 2>/dev/null", false));
+        assert!(executor.is_command_allowed("systemctl status rmdbd 2>&1 | head -20", false));
+        assert!(!executor.is_command_allowed("rm -rf /var/lib/lxc/rmdb", false));
+        assert!(!executor.is_command_allowed("echo test > /tmp/out", false));
+        assert!(!executor.is_command_allowed("sudo -n systemctl restart rmdbd", false));
+        assert!(!executor.is_command_allowed("cat /etc/shadow", false));
+        assert!(!executor.is_command_allowed("ls; /usr/bin/lxc-destroy -n rmdb", false));
+        assert!(executor.is_command_allowed("lxc-attach -n rmdb -- tail -n 50 /var/log/rmdbd.log", false));
+        assert!(!executor.is_command_allowed("lxc-attach -n rmdb -- rc-service rmdbd stop", false));
+
+        let executor = CommandExecutor::new(ActionMode::Safe, Capabilities::default());
+        assert!(executor.is_command_allowed("rm -f /tmp/rmdb.tmp", false));
+        assert!(!executor.is_command_allowed("rm -f /tmp/rmdb.tmp", true));
+    }
+
+    #[test]
+    fn test_root_runs_admin_commands_without_sudo() {
+        let caps = Capabilities { running_as_root: true, ..Capabilities::default() };
+        let executor = CommandExecutor::new(ActionMode::Admin, caps);
+        let output = executor.run_shell("echo ok", true).unwrap();
+        assert_eq!(output.stdout.trim(), "ok");
+
+        let executor = CommandExecutor::new(ActionMode::Admin, Capabilities::default());
+        assert!(matches!(executor.run_shell("echo ok", true), Err(ExecError::MissingTool(_))));
+    }
+
+    #[test]
+    fn test_mock_executor_scripts_outputs() {
+        let mock = MockExecutor::new()
+            .on("lxc-ls", "rmdb\n")
+            .on_error("lxc-destroy", ExecError::Failed("verrou".to_string()));
+        let executor: &dyn Executor = &mock;
+        assert_eq!(executor.run_shell("lxc-ls -1", true).unwrap().stdout, "rmdb\n");
+        assert!(matches!(executor.run_shell("lxc-destroy -n rmdb", true), Err(ExecError::Failed(_))));
+        assert_eq!(executor.run_shell("true", false).unwrap().exit_code, Some(0));
+        assert_eq!(mock.issued()[1], ("lxc-destroy -n rmdb".to_string(), true));
+        assert_eq!(mock.commands().len(), 3);
+    }
+
+    #[test]
+    fn test_cancel_token_stops_command() {
+        let mut executor = CommandExecutor::new(ActionMode::Safe, Capabilities::default());
+        executor.set_cancel_token(Some(CancelToken::new().with_request_check(|| true)));
+        let started = std::time::Instant::now();
+        assert!(matches!(executor.run_shell("sleep 5", false), Err(ExecError::Cancelled)));
+        assert!(started.elapsed() < Duration::from_secs(3));
+        // Une fois annulé, les commandes suivantes ne sont plus lancées
+        assert!(matches!(executor.run_shell("true", false), Err(ExecError::Cancelled)));
+
+        executor.set_cancel_token(Some(CancelToken::new()));
+        let output = executor.run_shell("echo ok; echo err >&2", false).unwrap();
+        assert_eq!((output.stdout.as_str(), output.stderr.as_str()), ("ok\n", "err\n"));
+    }
+
+    #[test]
+    fn test_run_many_keeps_order_in_parallel() {
+        let executor = CommandExecutor::new(ActionMode::Safe, Capabilities::default());
+        let started = std::time::Instant::now();
+        let results = executor.run_many(&["sleep 1; echo a", "sleep 1; echo b", "echo c"], false);
+        assert!(started.elapsed() < Duration::from_millis(1900));
+        let outputs: Vec<String> = results.into_iter().map(|r| r.unwrap().stdout).collect();
+        assert_eq!(outputs, ["a\n", "b\n", "c\n"]);
+    }
+}
@@ -1,29 +1,165 @@
+use std::cell::Cell;
 use std::io::{self, Read};
+use std::time::Duration;
+use crate::pres::terminal::RawModeGuard;
 
 pub enum Key {
     Up,
     Down,
+    Home,
+    End,
+    PageUp,
+    PageDown,
     Enter,
     Quit,
     Backspace,
+    /// Échap seul (pas le début d'une séquence de touche spéciale)
+    Escape,
+    /// Ctrl-P (palette d'actions)
+    CtrlP,
+    /// Ctrl-C : quitte depuis le menu principal, annule l'écran ou la saisie en cours ailleurs
+    Interrupt,
     Char(char),
     Unknown,
 }
 
-pub struct InputReader;
+/// Délai au-delà duquel un Échap n'est plus considéré comme le début d'une séquence
+const ESCAPE_SEQUENCE_DELAY_MS: i32 = 30;
+
+/// Lecture directe du descripteur 0, sans le tampon de `io::stdin()`
+///
+/// `stdin_ready` interroge le descripteur : un tampon intermédiaire lui cacherait la fin
+/// d'une séquence d'échappement déjà lue.
+struct RawStdin;
+
+impl Read for RawStdin {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        // SAFETY: le tampon est valide pour `buf.len()` octets
+        let read = unsafe { libc::read(libc::STDIN_FILENO, buf.as_mut_ptr() as *mut libc::c_void, buf.len()) };
+        if read < 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(read as usize)
+        }
+    }
+}
+
+/// Indique si stdin a des octets à lire dans les `timeout_ms` millisecondes
+fn stdin_ready(timeout_ms: i32) -> bool {
+    let mut fds = libc::pollfd { fd: libc::STDIN_FILENO, events: libc::POLLIN, revents: 0 };
+    // SAFETY: un seul descripteur valide, la structure vit pendant l'appel
+    unsafe { libc::poll(&mut fds, 1, timeout_ms) > 0 }
+}
+
+/// Lit sans bloquer la touche éventuellement pressée pendant une commande et indique
+/// s'il s'agit d'Échap ; les autres touches sont ignorées.
+pub fn escape_pressed() -> bool {
+    let mut escape = false;
+    while stdin_ready(0) {
+        let mut byte = [0u8; 1];
+        if RawStdin.read(&mut byte).unwrap_or(0) == 0 {
+            break;
+        }
+        escape = byte[0] == 0x1b && !stdin_ready(ESCAPE_SEQUENCE_DELAY_MS);
+        if !escape && byte[0] == 0x1b {
+            // Séquence d'une touche spéciale : la consommer entièrement
+            while stdin_ready(0) {
+                let _ = RawStdin.read(&mut byte);
+            }
+        }
+    }
+    escape
+}
+
+pub struct InputReader {
+    vim_keys: bool,
+    /// Ctrl-G pressé : retour au menu principal en attente
+    main_menu_requested: Cell<bool>,
+}
 
 impl InputReader {
     pub fn new() -> Self {
-        Self
+        Self { vim_keys: false, main_menu_requested: Cell::new(false) }
+    }
+
+    /// Active la navigation style vim (j/k/g/G) dans les menus et listes
+    pub fn with_vim_keys(mut self, enabled: bool) -> Self {
+        self.vim_keys = enabled;
+        self
+    }
+
+    pub fn vim_keys(&self) -> bool {
+        self.vim_keys
+    }
+
+    /// Traduit j/k/g/G en touches de navigation si les touches vim sont actives
+    pub fn navigation_key(&self, key: Key) -> Key {
+        if !self.vim_keys {
+            return key;
+        }
+        match key {
+            Key::Char('j') => Key::Down,
+            Key::Char('k') => Key::Up,
+            Key::Char('g') => Key::Home,
+            Key::Char('G') => Key::End,
+            other => other,
+        }
+    }
+
+    /// Lit une touche destinée à la navigation (menus, listes, visionneuses)
+    ///
+    /// À ne pas utiliser pour la saisie de texte : j/k/g/G y seraient détournés.
+    pub fn read_nav_key(&self) -> Result<Key, io::Error> {
+        self.read_key().map(|key| self.navigation_key(key))
     }
 
+    /// Lit une touche
+    ///
+    /// Après Ctrl-G, renvoie `Quit` sans rien lire jusqu'à ce que la boucle principale prenne
+    /// la demande (`take_main_menu_request`) : chaque saisie et chaque écran se ferment tour à tour.
     pub fn read_key(&self) -> Result<Key, io::Error> {
-        let mut stdin = io::stdin();
+        if self.main_menu_requested.get() {
+            return Ok(Key::Quit);
+        }
         let mut first = [0u8; 1];
-        
-        stdin.read_exact(&mut first)?;
-        
+        RawStdin.read_exact(&mut first)?;
+        self.decode(first[0])
+    }
+
+    /// Indique qu'un retour au menu principal (Ctrl-G) est en attente
+    pub fn main_menu_requested(&self) -> bool {
+        self.main_menu_requested.get()
+    }
+
+    /// Prend la demande de retour au menu principal (boucle principale uniquement)
+    pub fn take_main_menu_request(&self) -> bool {
+        self.main_menu_requested.replace(false)
+    }
+
+    /// Attend une touche pendant au plus `timeout` ; retourne `None` si aucune touche n'a été pressée
+    pub fn poll_key(&self, timeout: Duration) -> Result<Option<Key>, io::Error> {
+        let deciseconds = (timeout.as_millis() / 100).clamp(1, 255) as u8;
+        RawModeGuard::set_read_timeout(deciseconds);
+        let mut first = [0u8; 1];
+        let read = RawStdin.read(&mut first);
+        RawModeGuard::set_read_timeout(0);
+
+        match read? {
+            0 => Ok(None),
+            _ => self.decode(first[0]).map(Some),
+        }
+    }
+
+    /// Décode une touche à partir de son premier octet (lit la suite des séquences d'échappement)
+    fn decode(&self, first: u8) -> Result<Key, io::Error> {
+        let mut stdin = RawStdin;
+        let first = [first];
+
         if first[0] == 0x1b {
+            // Les séquences des touches spéciales arrivent d'un bloc : rien derrière, c'est Échap
+            if !stdin_ready(ESCAPE_SEQUENCE_DELAY_MS) {
+                return Ok(Key::Escape);
+            }
             let mut second = [0u8; 1];
             match stdin.read_exact(&mut second) {
                 Ok(_) if second[0] == b'[' => {
@@ -33,20 +169,50 @@ impl InputReader {
                             match third[0] {
                                 b'A' => return Ok(Key::Up),
                                 b'B' => return Ok(Key::Down),
+                                b'H' => return Ok(Key::Home),
+                                b'F' => return Ok(Key::End),
+                                // Séquences de la forme ESC [ <n> ~
+                                b'0'..=b'9' => {
+                                    let mut code = vec![third[0]];
+                                    let mut next = [0u8; 1];
+                                    while stdin.read_exact(&mut next).is_ok() && next[0] != b'~' {
+                                        if !next[0].is_ascii_digit() || code.len() >= 3 {
+                                            return Ok(Key::Unknown);
+                                        }
+                                        code.push(next[0]);
+                                    }
+                                    return Ok(Self::tilde_key(&code));
+                                }
                                 _ => return Ok(Key::Unknown),
                             }
                         }
                         Err(_) => return Ok(Key::Unknown),
                     }
                 }
+                Ok(_) if second[0] == b'O' => {
+                    let mut third = [0u8; 1];
+                    match stdin.read_exact(&mut third) {
+                        Ok(_) if third[0] == b'H' => return Ok(Key::Home),
+                        Ok(_) if third[0] == b'F' => return Ok(Key::End),
+                        _ => return Ok(Key::Unknown),
+                    }
+                }
                 _ => return Ok(Key::Unknown),
             }
         }
         
         match first[0] {
             b'\n' | b'\r' => Ok(Key::Enter),
-            b'q' | 3 => Ok(Key::Quit),
+            b'q' => Ok(Key::Quit),
+            // Ctrl-C arrive comme un octet : `stty raw`/`-isig` coupe la génération de SIGINT
+            0x03 => Ok(Key::Interrupt),
             0x7f | 0x08 => Ok(Key::Backspace), // DEL / BS
+            0x10 => Ok(Key::CtrlP),
+            // Ctrl-G : retour au menu principal depuis n'importe quel écran
+            0x07 => {
+                self.main_menu_requested.set(true);
+                Ok(Key::Quit)
+            }
             b' ' => Ok(Key::Char(' ')),
             c if c.is_ascii_alphanumeric() || c.is_ascii_punctuation() => {
                 Ok(Key::Char(c as char))
@@ -54,6 +220,17 @@ impl InputReader {
             _ => Ok(Key::Unknown),
         }
     }
+
+    /// Décode les touches envoyées sous la forme ESC [ <n> ~ (xterm, rxvt, console Linux)
+    fn tilde_key(code: &[u8]) -> Key {
+        match code {
+            b"1" | b"7" => Key::Home,
+            b"4" | b"8" => Key::End,
+            b"5" => Key::PageUp,
+            b"6" => Key::PageDown,
+            _ => Key::Unknown,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -65,4 +242,20 @@ mod tests {
         let _reader = InputReader::new();
         assert!(true);
     }
+
+    #[test]
+    fn test_vim_keys_mapping() {
+        let reader = InputReader::new().with_vim_keys(true);
+        assert!(matches!(reader.navigation_key(Key::Char('j')), Key::Down));
+        assert!(matches!(reader.navigation_key(Key::Char('G')), Key::End));
+        let reader = InputReader::new();
+        assert!(matches!(reader.navigation_key(Key::Char('j')), Key::Char('j')));
+    }
+
+    #[test]
+    fn test_ctrl_c_is_interrupt() {
+        let reader = InputReader::new();
+        assert!(matches!(reader.decode(0x03), Ok(Key::Interrupt)));
+        assert!(matches!(reader.decode(b'q'), Ok(Key::Quit)));
+    }
 }
@@ -1,8 +1,8 @@
-use crate::pres::ui::{UI, Color};
-use crate::pres::input::{InputReader, Key};
+use crate::pres::ui::{UI, Color, LayoutMode};
+use crate::pres::input::{escape_pressed, InputReader, Key};
 use crate::pres::terminal::RawModeGuard;
-use crate::pres::sudo::SudoKeepAliveGuard;
-use crate::pres::executor::{CommandExecutor, ActionMode as ExecActionMode, ExecError};
+use crate::pres::sudo::{invalidate_sudo_session, SudoKeepAliveGuard};
+use crate::pres::executor::{CancelToken, CommandExecutor, CommandOutput, ActionMode as ExecActionMode, ExecError, Executor};
 use crate::pres::main_menu::{
     get_main_menu, get_services_submenu, get_ipxe_submenu, get_clients_submenu,
     get_vms_submenu, get_configuration_submenu, get_monitoring_submenu, get_system_submenu,
@@ -10,13 +10,36 @@ use crate::pres::main_menu::{
     MainMenuAction, MainMenuItem
 };
 use crate::pres::install_menu::get_mode_selection_menu;
-use crate::deployment::installer::{RMDBInstaller, InstallationConfig, InstallationType, InstallationMode};
+use crate::pres::select_list::{ListState, SelectList, SelectOutcome, SortColumn, SortState};
+use crate::pres::text_viewer::{prompt_export, TextViewer};
+use crate::pres::display_test;
+use crate::pres::action_preview::{self, PreviewContext};
+use crate::pres::config_editor::{editor_candidates, read_line, read_secret, run_external_editor, ConfigEditor, EditorOutcome};
+use crate::deployment::installer::{parse_package_list, ProgressEstimator, RMDBInstaller, InstallationConfig, InstallationType, InstallationMode};
 use crate::data::capabilities::Capabilities;
 use crate::data::distribution::DistributionInfo;
-use crate::data::api_client::{APIClient, VM, APIError, DHCPLease, ConnectedClient, SystemMetrics, IPXEEntry, VMOverlay, RepairResult, RepairProblem, TestResult, SecurityMetrics};
-use crate::deployment::lxc::LXCDeployment;
-use crate::deployment::host::HostDeployment;
-use crate::deployment::logger::DeploymentLogger;
+use crate::data::config_diff::{json_diff, line_diff, DiffKind, LineChange};
+use crate::data::dhcp_leases::{find_dhcp_leases_path, parse_leases_as, LeaseFormat};
+use crate::data::net;
+use crate::data::overlay_cleanup::{find_orphan_overlays, orphan_reason};
+use crate::data::deploy_cache::{self, DeployRecord};
+use crate::data::deploy_history::{DeployHistory, DeployKind, DeployResult, HistoryEntry};
+use crate::data::cache_file::log_warning;
+use crate::data::favorites::Favorites;
+use crate::data::tui_config::{self, TuiConfig};
+use crate::data::profiles::{validate_profile_url, ApiProfile, Profiles};
+use crate::data::disk_image::{check_disk_path, validate_disk_path};
+use crate::data::system_info::{check_free_space, FreeSpace, SystemInfo, CONTAINER_SPACE_ESTIMATE, HOST_BUILD_SPACE_ESTIMATE, HOST_DATA_SPACE_ESTIMATE};
+use crate::pres::utils::{column_width, display_width, format_duration, format_size, pad_to_width, truncate_string};
+use crate::data::api_client::{APIClient, VM, APIError, DHCPLease, VMOverlay, demo_mode_enabled, error_report};
+use crate::deployment::lxc::{diagnostic_report, is_network_failure, is_unsupported_lxc_version, mac_policy_hint, validate_container_name, validate_local_template, CgroupVersion, CloneMode, ContainerInfo, ContainerInit, DiagnosticStatus, DefaultConfigStatus, GhostKind, LXCDeployment, LxcPrivilege, IssueSeverity, NetMode, VerificationStatus, LXC_IMAGES_SERVER, LXC_MIN_SUPPORTED_VERSION, LXC_TRASH_DIR};
+use crate::deployment::host::{HostDeployment, RMDB_REQUIRED_PORTS};
+use crate::deployment::logger::{DeploymentLogger, Level};
+use crate::deployment::service_monitor::{is_error_line, ServiceMonitor};
+use crate::deployment::quick_status::{quick_status, RMDB_SERVICES};
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
 use std::io::{self, Write};
 
@@ -24,6 +47,28 @@ fn yesno(v: bool) -> &'static str {
     if v { "oui" } else { "non" }
 }
 
+/// Ce que permet (✓) et interdit (✗) chaque mode, affiché pendant le choix du mode
+fn describe_mode(mode: ExecActionMode) -> Vec<&'static str> {
+    match mode {
+        ExecActionMode::ReadOnly => vec![
+            "✓ Consultation : statuts, journaux, listes, configuration",
+            "✗ Aucune commande qui modifie le système",
+            "✗ Pas de sudo : services, containers et installation indisponibles",
+        ],
+        ExecActionMode::Safe => vec![
+            "✓ Consultation et commandes sans privilèges",
+            "✓ Modifications via l'API RMDB (overlays, configuration)",
+            "✗ Pas de sudo : services, containers et installation indisponibles",
+        ],
+        ExecActionMode::Admin => vec![
+            "✓ Démarrer, arrêter et redémarrer les services RMDB",
+            "✓ Créer, supprimer et réinstaller des containers LXC",
+            "✓ Installer et désinstaller RMDB sur l'hôte",
+            "⚠ Exécute des commandes via sudo : mot de passe demandé",
+        ],
+    }
+}
+
 
 
 enum MenuState {
@@ -31,6 +76,126 @@ enum MenuState {
     SubMenu(String, Vec<MainMenuItem>),
 }
 
+/// Effet du choix d'un élément sur la navigation entre menus
+#[derive(Debug, PartialEq)]
+enum MenuTransition {
+    /// Ouvre le sous-menu de la catégorie donnée
+    Push(&'static str),
+    /// Revient au menu principal
+    Pop,
+    /// Quitte l'application
+    Quit,
+    /// Exécute l'action sans changer de menu
+    Run,
+}
+
+/// Catégorie du sous-menu ouvert par une action de thème
+fn theme_category(action: &MainMenuAction) -> Option<&'static str> {
+    match action {
+        MainMenuAction::ServicesTheme => Some("Services"),
+        MainMenuAction::IPXETheme => Some("IPXE"),
+        MainMenuAction::ClientsTheme => Some("Clients"),
+        MainMenuAction::VMsTheme => Some("VMs"),
+        MainMenuAction::ConfigurationTheme => Some("Configuration"),
+        MainMenuAction::MonitoringTheme => Some("Monitoring"),
+        MainMenuAction::SystemTheme => Some("Système"),
+        // LXCManage n'est plus un sous-menu, c'est une action directe
+        MainMenuAction::ContainersTheme => Some("Containers LXC"),
+        MainMenuAction::HostTheme => Some("RMDB Hôte"),
+        _ => None,
+    }
+}
+
+/// Détermine la transition de menu provoquée par une action, sans toucher à l'affichage
+///
+/// Dans un sous-menu, l'élément « Retour » porte l'action de thème de sa propre catégorie.
+fn next_menu_state(current: &MenuState, action: &MainMenuAction) -> MenuTransition {
+    let category = theme_category(action);
+    if let (MenuState::SubMenu(current_category, _), Some(category)) = (current, category) {
+        if current_category == category {
+            return MenuTransition::Pop;
+        }
+    }
+    match (action, category) {
+        (MainMenuAction::Quit, _) => MenuTransition::Quit,
+        (_, Some(category)) => MenuTransition::Push(category),
+        _ => MenuTransition::Run,
+    }
+}
+
+/// Construit les éléments du sous-menu d'une catégorie
+fn submenu_for(category: &str, expert_tools: bool) -> Vec<MainMenuItem> {
+    match category {
+        "Services" => get_services_submenu(),
+        "IPXE" => get_ipxe_submenu(),
+        "Clients" => get_clients_submenu(),
+        "VMs" => get_vms_submenu(),
+        "Configuration" => get_configuration_submenu(),
+        "Monitoring" => get_monitoring_submenu(),
+        "Système" => get_system_submenu(expert_tools),
+        "Containers LXC" => get_containers_submenu(),
+        "RMDB Hôte" => get_host_submenu(),
+        _ => get_main_menu(),
+    }
+}
+
+/// Action proposée par la palette : « Catégorie › Libellé »
+struct QuickAction {
+    label: String,
+    action: MainMenuAction,
+}
+
+/// Toutes les actions des sous-menus, à plat (sans « Retour », séparateurs ni « Quitter »)
+fn quick_actions(expert_tools: bool) -> Vec<QuickAction> {
+    let mut actions = Vec::new();
+    for item in get_main_menu() {
+        match theme_category(&item.action) {
+            Some(category) => {
+                for sub in submenu_for(category, expert_tools) {
+                    if theme_category(&sub.action).is_some() || sub.label.starts_with("---") {
+                        continue;
+                    }
+                    actions.push(QuickAction { label: format!("{} › {}", category, sub.label), action: sub.action });
+                }
+            }
+            None if matches!(item.action, MainMenuAction::Quit | MainMenuAction::Favorites) => {}
+            None => actions.push(QuickAction { label: item.label.to_string(), action: item.action }),
+        }
+    }
+    actions
+}
+
+/// Entrée de la palette qui correspond à une action
+fn find_quick_action<'a>(actions: &'a [QuickAction], action: &MainMenuAction) -> Option<&'a QuickAction> {
+    actions.iter().find(|a| std::mem::discriminant(&a.action) == std::mem::discriminant(action))
+}
+
+/// Filtre sans casse : chaque mot de la recherche doit apparaître dans le libellé
+fn filter_quick_actions<'a>(actions: &'a [QuickAction], query: &str) -> Vec<&'a QuickAction> {
+    let words: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+    actions
+        .iter()
+        .filter(|a| {
+            let label = a.label.to_lowercase();
+            words.iter().all(|w| label.contains(w.as_str()))
+        })
+        .collect()
+}
+
+/// Nombre maximal d'appels à l'API proposés par l'écran de nouvelle tentative
+const API_MAX_ATTEMPTS: u32 = 5;
+
+/// Nombre d'étapes affichées pendant le déploiement du container
+const DEPLOY_STEPS: usize = 6;
+
+/// Journal affiché pendant une opération longue (en-tête, étape en cours, dernières lignes)
+struct OperationLog {
+    title: &'static str,
+    step: String,
+    lines: Vec<(String, Color)>,
+    color: Color,
+}
+
 pub struct MainApp {
     ui: UI,
     input_reader: InputReader,
@@ -49,22 +214,151 @@ pub struct MainApp {
     #[allow(dead_code)]
     current_submenu: Option<Vec<MainMenuItem>>,
     distribution: DistributionInfo,
+    /// Interruption refusée à la question de `cancel_token` : le journal d'opération est à redessiner
+    abort_declined: Arc<AtomicBool>,
+    /// Active les outils avancés (console de diagnostic), via RMDB_EXPERT=1
+    expert_mode: bool,
+    /// Tri choisi pour chaque liste, conservé pendant la session
+    container_sort: SortState,
+    vm_sort: SortState,
+    lease_sort: SortState,
+    /// Données de l'API fictives (RMDB_DEMO=1)
+    demo_mode: bool,
+    /// Container visé par les raccourcis RMDB (RMDB_CONTAINER, "rmdb" par défaut)
+    default_container: String,
+    /// Journal de l'opération longue en cours d'affichage
+    operation_log: Option<OperationLog>,
+    /// Pauses décoratives entre les écrans (désactivées par RMDB_NO_ANIM=1 ou --no-anim)
+    animations_enabled: bool,
+    /// Actions épinglées et récentes (~/.config/rmdb/favorites.json)
+    favorites: Favorites,
+    /// Préférences effectives (tui.json, variables d'environnement, options)
+    tui_config: TuiConfig,
+    /// Gestionnaire de services de chaque container, détecté au premier usage et conservé pendant la session
+    container_inits: HashMap<String, ContainerInit>,
 }
 
-impl MainApp {
-    const MIN_WIDTH: u16 = 80;
-    const MIN_HEIGHT: u16 = 24;
+/// Nom du service RMDB (systemd ou OpenRC)
+const RMDB_SERVICE_NAME: &str = "rmdbd";
+/// Fichier de log du service RMDB
+const RMDB_LOG_PATH: &str = "/var/log/rmdbd.log";
+/// Durée d'observation du journal après un redémarrage des services
+const RESTART_OBSERVE_SECS: u64 = 5;
+
+/// Colonnes de tri de la liste des containers
+const CONTAINER_SORT_COLUMNS: &[SortColumn<ContainerInfo>] = &[
+    ("Nom", |a, b| a.name.cmp(&b.name)),
+    ("État", |a, b| a.status.cmp(&b.status).then_with(|| a.name.cmp(&b.name))),
+];
+
+/// État d'un service ("active", "inactive"... ou "inconnu") selon l'outil d'init disponible
+fn query_service_status(executor: &dyn Executor, capabilities: &Capabilities, service: &str) -> String {
+    ServiceMonitor::new(capabilities).status(executor, service)
+}
+
+/// VMs portant le même nom qu'un container (casse ignorée, espaces autour retirés)
+fn vm_name_collisions<'a>(vm_names: impl IntoIterator<Item = &'a str>, container_name: &str) -> Vec<&'a str> {
+    vm_names
+        .into_iter()
+        .filter(|vm| vm.trim().eq_ignore_ascii_case(container_name.trim()))
+        .collect()
+}
+
+/// Cause probable de l'échec de `lxc-attach` (ou du shell ouvert), d'après le code de sortie
+/// et l'état `lxc-info` du container
+fn attach_failure_hint(code: &str, state: Option<&str>) -> String {
+    match state {
+        Some("STOPPED") => "container arrêté, démarrez-le d'abord (Démarrer)".to_string(),
+        Some("FROZEN") => "container gelé, dégelez-le d'abord (Containers LXC › Dégeler)".to_string(),
+        None => "état du container illisible : existe-t-il encore, la session sudo est-elle ouverte ?".to_string(),
+        Some(_) if code == "127" => "shell introuvable dans le container".to_string(),
+        Some(_) => "le container tourne : code renvoyé par la dernière commande du shell".to_string(),
+    }
+}
+
+/// Symbole et couleur d'un résultat de diagnostic
+fn diagnostic_style(status: DiagnosticStatus) -> (&'static str, Color) {
+    match status {
+        DiagnosticStatus::Ok => ("✓", Color::Success),
+        DiagnosticStatus::Warning => ("⚠", Color::Warning),
+        DiagnosticStatus::Failed => ("✗", Color::Error),
+    }
+}
+
+/// Ligne d'un container dans les listes de sélection ; le nom occupe `name_width` colonnes
+fn container_label(c: &ContainerInfo, name_width: usize) -> String {
+    let autostart = if c.autostart == Some(true) { " [auto]" } else { "" };
+    format!("{} ({}){}", pad_to_width(&c.name, name_width), c.status, autostart)
+}
+
+/// Largeur de la colonne des noms : le plus long nom, tronqué pour garder le statut dans la boîte
+fn container_name_width(containers: &[ContainerInfo], box_w: u16, other_columns: usize) -> usize {
+    let max = (box_w as usize).saturating_sub(other_columns + 4).max(8);
+    column_width("Nom", containers.iter().map(|c| c.name.as_str()), max)
+}
+
+fn container_color(c: &ContainerInfo) -> Color {
+    match c.status.as_str() {
+        "RUNNING" => Color::Success,
+        "STOPPED" => Color::Warning,
+        "FROZEN" => Color::Info,
+        _ => Color::Fg,
+    }
+}
+
+/// Colonnes de tri de la liste des VMs
+const VM_SORT_COLUMNS: &[SortColumn<VM>] = &[
+    ("Nom", |a, b| a.name.cmp(&b.name)),
+    ("Catégorie", |a, b| a.category.cmp(&b.category).then_with(|| a.name.cmp(&b.name))),
+    ("Taille", |a, b| a.size.cmp(&b.size)),
+];
+
+/// Colonnes de tri des leases DHCP ("Récent" conserve l'ordre du fichier, plus récentes en premier)
+const LEASE_SORT_COLUMNS: &[SortColumn<DHCPLease>] = &[
+    ("Récent", |_, _| std::cmp::Ordering::Equal),
+    ("IP", |a, b| match (a.ip.parse::<std::net::IpAddr>(), b.ip.parse::<std::net::IpAddr>()) {
+        (Ok(ip_a), Ok(ip_b)) => ip_a.cmp(&ip_b),
+        _ => a.ip.cmp(&b.ip),
+    }),
+    ("Expiration", |a, b| a.expires_at.cmp(&b.expires_at)),
+];
+
+/// Analyse une taille de terminal au format `LARGEURxHAUTEUR`
+fn parse_size(value: &str) -> Option<(u16, u16)> {
+    let (w, h) = value.trim().split_once(['x', 'X'])?;
+    Some((w.trim().parse().ok()?, h.trim().parse().ok()?))
+}
 
-    pub fn new() -> Self {
+impl MainApp {
+    pub fn new(config: TuiConfig) -> Self {
         let menu = get_main_menu();
         let labels: Vec<&'static str> = menu.iter().map(|m| m.label).collect();
         let capabilities = Capabilities::detect();
         let capabilities_for_executor = capabilities.clone();
         let distribution = DistributionInfo::detect();
+        let vim_keys = config.vim_keys.unwrap_or(false);
+        let expert_mode = config.expert.unwrap_or(false);
+        let animations_enabled = !config.no_anim.unwrap_or(false);
+        // Un nom refusé par LXC ferait échouer chaque action sur le container : on garde celui par défaut
+        let default_container = validate_container_name(&config.container()).unwrap_or_else(|e| {
+            log_warning(&format!("Nom de container ignoré (tui.json ou RMDB_CONTAINER): {}", e));
+            TuiConfig::default().container()
+        });
         
+        let mut ui = UI::new();
+        // Rendu ASCII forcé (option `--ascii`), même si la locale annonce UTF-8
+        if config.ascii == Some(true) {
+            ui.asciify = true;
+        }
+        // Taille minimale de la disposition compacte, ex: RMDB_MIN_SIZE=60x20
+        if let Some((w, h)) = config.min_size.as_deref().and_then(parse_size) {
+            ui.terminal.compact_min_width = w;
+            ui.terminal.compact_min_height = h;
+        }
+
         Self {
-            ui: UI::new(),
-            input_reader: InputReader::new(),
+            ui,
+            input_reader: InputReader::new().with_vim_keys(vim_keys),
             _raw_mode: RawModeGuard::enable(),
             sudo_keepalive: None,
             selected_menu: 0,
@@ -78,9 +372,76 @@ impl MainApp {
             menu_state: MenuState::Main,
             current_submenu: None,
             distribution,
+            abort_declined: Arc::new(AtomicBool::new(false)),
+            expert_mode,
+            container_sort: SortState::default(),
+            vm_sort: SortState::default(),
+            lease_sort: SortState::default(),
+            demo_mode: demo_mode_enabled(),
+            default_container,
+            operation_log: None,
+            animations_enabled,
+            favorites: Favorites::load(),
+            tui_config: config,
+            container_inits: HashMap::new(),
+        }
+    }
+
+    /// Gestionnaire de services du container (OpenRC si `lxc-attach` ne répond pas encore)
+    ///
+    /// Détecté au premier appel, comme les outils de l'hôte dans `Capabilities`, puis conservé.
+    fn container_init(&mut self, name: &str) -> ContainerInit {
+        if let Some(init) = self.container_inits.get(name) {
+            return *init;
+        }
+        match LXCDeployment::detect_container_init(&self.executor, name) {
+            Some(init) => {
+                self.container_inits.insert(name.to_string(), init);
+                init
+            }
+            None => ContainerInit::OpenRC,
+        }
+    }
+
+    /// Oublie le gestionnaire de services détecté : le container va être supprimé ou recréé
+    fn forget_container_init(&mut self, name: &str) {
+        self.container_inits.remove(name);
+    }
+
+    /// Client de l'API RMDB à l'URL configurée (RMDB_API_URL, `--api-url`, tui.json)
+    fn api_client(&self) -> APIClient {
+        let client = APIClient::new(self.tui_config.api_url());
+        match self.tui_config.api_token() {
+            Some(token) => client.with_auth_token(token),
+            None => client,
+        }
+    }
+
+    /// Niveau des journaux de déploiement (RMDB_LOG_LEVEL ou tui.json, info par défaut)
+    fn log_level(&self) -> Level {
+        self.tui_config.log_level.as_deref().and_then(Level::parse).unwrap_or(Level::Info)
+    }
+
+    fn deployment_logger(&self) -> Result<DeploymentLogger, std::io::Error> {
+        DeploymentLogger::new().map(|logger| logger.with_level(self.log_level()))
+    }
+
+    /// Laisse un message affiché un instant ; les attentes fonctionnelles
+    /// (arrêt d'un container...) doivent utiliser `thread::sleep` directement
+    fn cosmetic_pause(&self, duration: Duration) {
+        if self.animations_enabled {
+            std::thread::sleep(duration);
         }
     }
 
+    /// Écran de test d'affichage seul (`--test-ui`), sans séquence de démarrage
+    pub fn run_display_test(&mut self) {
+        self.ui.update_terminal_size();
+        self.ui.hide_cursor();
+        display_test::run(&self.ui, &self.input_reader);
+        self.needs_full_redraw = true;
+    }
+
     pub fn run(&mut self) {
         if !self.boot_sequence() {
             return;
@@ -90,16 +451,24 @@ impl MainApp {
         self.render_full();
         
         loop {
+            // Ctrl-G : les écrans se sont fermés un à un, on repart du menu principal
+            if self.input_reader.take_main_menu_request() {
+                self.return_to_main_menu();
+                self.needs_full_redraw = true;
+                self.render_full();
+            }
+
             self.ui.update_terminal_size();
-            if self.ui.terminal.width() < Self::MIN_WIDTH || self.ui.terminal.height() < Self::MIN_HEIGHT {
+            if !self.ui.is_valid_size() {
                 self.show_terminal_size_warning();
                 let _ = self.input_reader.read_key();
                 self.needs_full_redraw = true;
                 continue;
             }
 
-            match self.input_reader.read_key() {
-                Ok(Key::Quit) => {
+            match self.input_reader.read_nav_key() {
+                Ok(Key::Quit) if self.input_reader.main_menu_requested() => continue,
+                Ok(Key::Quit) | Ok(Key::Interrupt) => {
                     match &self.menu_state {
                         MenuState::SubMenu(_, _) => {
                             self.return_to_main_menu();
@@ -120,27 +489,16 @@ impl MainApp {
                         }
                     }
                 }
-                Ok(Key::Up) => {
-                    if self.selected_menu > 0 {
-                        self.selected_menu -= 1;
-                        self.update_menu_offset();
-                        self.render_menu_only();
-                    } else {
-                        self.selected_menu = self.menu_items.len() - 1;
-                        self.update_menu_offset();
-                        self.render_menu_only();
-                    }
-                }
-                Ok(Key::Down) => {
-                    if self.selected_menu < self.menu_items.len() - 1 {
-                        self.selected_menu += 1;
-                        self.update_menu_offset();
-                        self.render_menu_only();
-                    } else {
-                        self.selected_menu = 0;
-                        self.update_menu_offset();
-                        self.render_menu_only();
-                    }
+                Ok(Key::Up) => self.move_selection(|state, len, visible| state.up(len, visible)),
+                Ok(Key::Down) => self.move_selection(|state, len, visible| state.down(len, visible)),
+                Ok(Key::Home) => self.move_selection(|state, _, visible| state.home(visible)),
+                Ok(Key::End) => self.move_selection(|state, len, visible| state.end(len, visible)),
+                Ok(Key::PageUp) => self.move_selection(|state, _, visible| state.page_up(visible)),
+                Ok(Key::PageDown) => self.move_selection(|state, len, visible| state.page_down(len, visible)),
+                Ok(Key::Char(':')) | Ok(Key::CtrlP) => {
+                    self.show_command_palette();
+                    self.needs_full_redraw = true;
+                    self.render_full();
                 }
                 Ok(Key::Enter) => {
                     if !self.execute_menu() {
@@ -149,6 +507,15 @@ impl MainApp {
                     self.needs_full_redraw = true;
                     self.render_full();
                 }
+                Ok(Key::Char('?')) => {
+                    self.explain_selected_action();
+                    self.needs_full_redraw = true;
+                    self.render_full();
+                }
+                Ok(Key::Char(c)) if self.jump_to_category(c) => {
+                    self.needs_full_redraw = true;
+                    self.render_full();
+                }
                 _ => {}
             }
         }
@@ -156,6 +523,8 @@ impl MainApp {
         self.ui.clear_screen();
         self.ui.show_cursor();
         self.ui.set_color(Color::Reset);
+        // Arrêt du keep-alive sudo (et `sudo -k` si sudo_reset_on_exit)
+        self.sudo_keepalive = None;
     }
 
     fn boot_sequence(&mut self) -> bool {
@@ -178,11 +547,12 @@ impl MainApp {
             box_x + 9,
             y,
             &format!(
-                "systemctl={} rc-service={} sudo={} rmdbd={}",
+                "systemctl={} rc-service={} sudo={} rmdbd={} root={}",
                 yesno(caps.has_systemctl),
                 yesno(caps.has_rc_service),
                 yesno(caps.has_sudo),
-                yesno(caps.has_rmdbd)
+                yesno(caps.has_rmdbd),
+                yesno(caps.is_root())
             ),
         );
         y += 2;
@@ -203,49 +573,40 @@ impl MainApp {
         // Choix par défaut : Mode Admin (3)
         let mut selected_mode = ExecActionMode::Admin;
         let mut choice = None;
-        
-        // Afficher l'instruction initiale
-        self.ui.set_color(Color::Info);
-        let mode_text = match selected_mode {
-            ExecActionMode::ReadOnly => "▶ Mode sélectionné: 1 - Lecture seule",
-            ExecActionMode::Safe => "▶ Mode sélectionné: 2 - Safe",
-            ExecActionMode::Admin => "▶ Mode sélectionné: 3 - Admin (par défaut)",
-        };
-        self.ui.draw_text(box_x + 2, y, mode_text);
+        let description_y = y + 3;
+        let description_rows = (box_y + box_h).saturating_sub(description_y + 3);
+
         self.ui.set_color(Color::Reset);
         self.ui.draw_text(box_x + 2, y + 1, "Appuyez sur Entrée pour valider, ou 1/2/3 pour changer");
-        io::stdout().flush().unwrap();
-        
+
         while choice.is_none() {
+            // Mode sélectionné et ce qu'il permet, mis à jour à chaque changement
+            self.ui.clear_line(y);
+            self.ui.set_color(Color::Info);
+            let mode_text = match selected_mode {
+                ExecActionMode::ReadOnly => "▶ Mode sélectionné: 1 - Lecture seule",
+                ExecActionMode::Safe => "▶ Mode sélectionné: 2 - Safe",
+                ExecActionMode::Admin => "▶ Mode sélectionné: 3 - Admin (par défaut)",
+            };
+            self.ui.draw_text(box_x + 2, y, mode_text);
+            for row in 0..description_rows {
+                self.ui.clear_line(description_y + row);
+            }
+            for (row, line) in describe_mode(selected_mode).iter().take(description_rows as usize).enumerate() {
+                self.ui.set_color(if line.starts_with(['✗', '⚠']) { Color::Warning } else { Color::Fg });
+                self.ui.draw_text(box_x + 4, description_y + row as u16, line);
+            }
+            self.ui.set_color(Color::Reset);
+            io::stdout().flush().unwrap();
+
             match self.input_reader.read_key() {
-                Ok(Key::Char('1')) => {
-                    selected_mode = ExecActionMode::ReadOnly;
-                    // Mettre à jour l'affichage
-                    self.ui.set_color(Color::Info);
-                    let mode_text = "▶ Mode sélectionné: 1 - Lecture seule";
-                    self.ui.draw_text(box_x + 2, y, mode_text);
-                    self.ui.set_color(Color::Reset);
-                }
-                Ok(Key::Char('2')) => {
-                    selected_mode = ExecActionMode::Safe;
-                    // Mettre à jour l'affichage
-                    self.ui.set_color(Color::Info);
-                    let mode_text = "▶ Mode sélectionné: 2 - Safe";
-                    self.ui.draw_text(box_x + 2, y, mode_text);
-                    self.ui.set_color(Color::Reset);
-                }
-                Ok(Key::Char('3')) => {
-                    selected_mode = ExecActionMode::Admin;
-                    // Mettre à jour l'affichage
-                    self.ui.set_color(Color::Info);
-                    let mode_text = "▶ Mode sélectionné: 3 - Admin";
-                    self.ui.draw_text(box_x + 2, y, mode_text);
-                    self.ui.set_color(Color::Reset);
-                }
+                Ok(Key::Char('1')) => selected_mode = ExecActionMode::ReadOnly,
+                Ok(Key::Char('2')) => selected_mode = ExecActionMode::Safe,
+                Ok(Key::Char('3')) => selected_mode = ExecActionMode::Admin,
                 Ok(Key::Enter) => {
                     choice = Some(selected_mode);
                 }
-                Ok(Key::Quit) => return false,
+                Ok(Key::Quit) | Ok(Key::Interrupt) => return false,
                 _ => {}
             }
         }
@@ -253,11 +614,15 @@ impl MainApp {
         // Nettoyer les lignes d'affichage
         self.ui.clear_line(y);
         self.ui.clear_line(y + 1);
+        for row in 0..description_rows {
+            self.ui.clear_line(description_y + row);
+        }
         
         self.action_mode = choice.unwrap();
         self.executor.set_mode(self.action_mode);
 
-        if self.action_mode == ExecActionMode::Admin {
+        // Déjà root (ex: `sudo rmdb`) : ni sudo ni mot de passe
+        if self.action_mode == ExecActionMode::Admin && !self.capabilities.is_root() {
             if !self.capabilities.has_sudo {
                 self.show_error_message("Sudo absent", "Le mode Admin nécessite `sudo`, introuvable sur ce système.");
                 return false;
@@ -268,13 +633,31 @@ impl MainApp {
                 return false;
             }
 
-            self.sudo_keepalive = Some(SudoKeepAliveGuard::start(Duration::from_secs(60)));
+            let reset_on_exit = self.tui_config.sudo_reset_on_exit.unwrap_or(false);
+            self.sudo_keepalive = Some(SudoKeepAliveGuard::start(Duration::from_secs(60)).with_invalidate_on_drop(reset_on_exit));
         }
 
         self.ui.hide_cursor();
         true
     }
 
+    /// Oublie les identifiants sudo en cache ; en mode Admin, la prochaine action redemande le mot de passe
+    fn invalidate_sudo(&mut self) {
+        if self.capabilities.is_root() || !self.capabilities.has_sudo {
+            self.show_message("Session sudo", "Aucune session sudo à invalider.");
+            return;
+        }
+        let invalidated = match self.sudo_keepalive.as_ref() {
+            Some(keepalive) => keepalive.invalidate(),
+            None => invalidate_sudo_session(),
+        };
+        if invalidated {
+            self.show_message("Session sudo", "Session sudo invalidée : le mot de passe sera redemandé à la prochaine action Admin.");
+        } else {
+            self.show_error_message("Session sudo", "`sudo -k` a échoué.");
+        }
+    }
+
     fn ensure_admin(&mut self) -> bool {
         if self.action_mode != ExecActionMode::Admin {
             self.show_error_message("Mode insuffisant", "Cette action requiert le mode Admin.");
@@ -311,7 +694,7 @@ impl MainApp {
         self.ui.set_color(Color::Info);
         let msg_lines: Vec<&str> = message.split('\n').collect();
         for line in &msg_lines {
-            let line_len = line.len() as u16;
+            let line_len = display_width(line) as u16;
             let x = center_x.saturating_sub(line_len / 2);
             self.ui.draw_text(x, y, line);
             y += 1;
@@ -322,7 +705,7 @@ impl MainApp {
         self.ui.set_color(Color::Fg);
         let inst_lines: Vec<&str> = instruction.split('\n').collect();
         for line in &inst_lines {
-            let line_len = line.len() as u16;
+            let line_len = display_width(line) as u16;
             let x = center_x.saturating_sub(line_len / 2);
             self.ui.draw_text(x, y, line);
             y += 1;
@@ -332,7 +715,7 @@ impl MainApp {
         // Zone de saisie (indication visuelle)
         self.ui.set_color(Color::Warning);
         let prompt_text = "┌─────────────────────────────────────────┐";
-        let prompt_x = center_x.saturating_sub(prompt_text.len() as u16 / 2);
+        let prompt_x = center_x.saturating_sub(display_width(prompt_text) as u16 / 2);
         self.ui.draw_text(prompt_x, y, prompt_text);
         y += 1;
         
@@ -343,7 +726,7 @@ impl MainApp {
         self.ui.draw_text(prompt_x, y, &prompt_line);
         
         // Positionner le curseur à l'intérieur du rectangle, après le prompt
-        let password_input_x = prompt_x + 2 + prompt_label.len() as u16;
+        let password_input_x = prompt_x + 2 + display_width(prompt_label) as u16;
         let password_input_y = y;
         y += 1;
         
@@ -365,9 +748,9 @@ impl MainApp {
                     if !password.is_empty() {
                         password.pop();
                         // Effacer le dernier astérisque
-                        self.ui.set_cursor(password_input_x + password.len() as u16, password_input_y);
+                        self.ui.set_cursor(password_input_x + display_width(&password) as u16, password_input_y);
                         print!(" ");
-                        self.ui.set_cursor(password_input_x + password.len() as u16, password_input_y);
+                        self.ui.set_cursor(password_input_x + display_width(&password) as u16, password_input_y);
                         io::stdout().flush().unwrap();
                     }
                 }
@@ -377,7 +760,7 @@ impl MainApp {
                     print!("*");
                     io::stdout().flush().unwrap();
                 }
-                Ok(Key::Quit) => {
+                Ok(Key::Quit) | Ok(Key::Interrupt) => {
                     return false;
                 }
                 _ => {}
@@ -392,22 +775,22 @@ impl MainApp {
             let mut y_err = by + 6;
             self.ui.set_color(Color::Error);
             let error_msg = "✗ Mot de passe vide";
-            let error_x = center_x.saturating_sub(error_msg.len() as u16 / 2);
+            let error_x = center_x.saturating_sub(display_width(error_msg) as u16 / 2);
             self.ui.draw_text(error_x, y_err, error_msg);
             y_err += 2;
             self.ui.set_color(Color::Fg);
             let error_detail = "Veuillez saisir un mot de passe.";
-            let detail_x = center_x.saturating_sub(error_detail.len() as u16 / 2);
+            let detail_x = center_x.saturating_sub(display_width(error_detail) as u16 / 2);
             self.ui.draw_text(detail_x, y_err, error_detail);
             y_err += 2;
             self.ui.set_color(Color::Info);
             let retry_msg = "Appuyez sur Entrée pour réessayer, ou Q pour annuler";
-            let retry_x = center_x.saturating_sub(retry_msg.len() as u16 / 2);
+            let retry_x = center_x.saturating_sub(display_width(retry_msg) as u16 / 2);
             self.ui.draw_text(retry_x, y_err, retry_msg);
             self.ui.set_color(Color::Reset);
             io::stdout().flush().unwrap();
             match self.input_reader.read_key() {
-                Ok(Key::Quit) => return false,
+                Ok(Key::Quit) | Ok(Key::Interrupt) => return false,
                 _ => continue,
             }
         }
@@ -434,17 +817,17 @@ impl MainApp {
                 let mut y_err = by + 6;
                 self.ui.set_color(Color::Error);
                 let error_msg = "✗ Erreur lors du démarrage de sudo";
-                let error_x = center_x.saturating_sub(error_msg.len() as u16 / 2);
+                let error_x = center_x.saturating_sub(display_width(error_msg) as u16 / 2);
                 self.ui.draw_text(error_x, y_err, error_msg);
                 y_err += 2;
                 self.ui.set_color(Color::Info);
                 let retry_msg = "Appuyez sur Entrée pour réessayer, ou Q pour annuler";
-                let retry_x = center_x.saturating_sub(retry_msg.len() as u16 / 2);
+                let retry_x = center_x.saturating_sub(display_width(retry_msg) as u16 / 2);
                 self.ui.draw_text(retry_x, y_err, retry_msg);
                 self.ui.set_color(Color::Reset);
                 io::stdout().flush().unwrap();
                 match self.input_reader.read_key() {
-                    Ok(Key::Quit) => return false,
+                    Ok(Key::Quit) | Ok(Key::Interrupt) => return false,
                     _ => continue,
                 }
             }
@@ -466,17 +849,17 @@ impl MainApp {
             let mut y_err = by + 6;
             self.ui.set_color(Color::Error);
             let error_msg = "✗ Erreur lors de l'envoi du mot de passe";
-            let error_x = center_x.saturating_sub(error_msg.len() as u16 / 2);
+            let error_x = center_x.saturating_sub(display_width(error_msg) as u16 / 2);
             self.ui.draw_text(error_x, y_err, error_msg);
             y_err += 2;
             self.ui.set_color(Color::Info);
             let retry_msg = "Appuyez sur Entrée pour réessayer, ou Q pour annuler";
-            let retry_x = center_x.saturating_sub(retry_msg.len() as u16 / 2);
+            let retry_x = center_x.saturating_sub(display_width(retry_msg) as u16 / 2);
             self.ui.draw_text(retry_x, y_err, retry_msg);
             self.ui.set_color(Color::Reset);
             io::stdout().flush().unwrap();
             match self.input_reader.read_key() {
-                Ok(Key::Quit) => return false,
+                Ok(Key::Quit) | Ok(Key::Interrupt) => return false,
                 _ => continue,
             }
         }
@@ -493,17 +876,17 @@ impl MainApp {
                 let mut y_err = by + 6;
                 self.ui.set_color(Color::Error);
                 let error_msg = "✗ Erreur lors de la validation";
-                let error_x = center_x.saturating_sub(error_msg.len() as u16 / 2);
+                let error_x = center_x.saturating_sub(display_width(error_msg) as u16 / 2);
                 self.ui.draw_text(error_x, y_err, error_msg);
                 y_err += 2;
                 self.ui.set_color(Color::Info);
                 let retry_msg = "Appuyez sur Entrée pour réessayer, ou Q pour annuler";
-                let retry_x = center_x.saturating_sub(retry_msg.len() as u16 / 2);
+                let retry_x = center_x.saturating_sub(display_width(retry_msg) as u16 / 2);
                 self.ui.draw_text(retry_x, y_err, retry_msg);
                 self.ui.set_color(Color::Reset);
                 io::stdout().flush().unwrap();
                 match self.input_reader.read_key() {
-                    Ok(Key::Quit) => return false,
+                    Ok(Key::Quit) | Ok(Key::Interrupt) => return false,
                     _ => continue,
                 }
             }
@@ -533,22 +916,22 @@ impl MainApp {
             let mut y_err = by + 6;
             self.ui.set_color(Color::Error);
             let error_msg = "✗ Mot de passe incorrect";
-            let error_x = center_x.saturating_sub(error_msg.len() as u16 / 2);
+            let error_x = center_x.saturating_sub(display_width(error_msg) as u16 / 2);
             self.ui.draw_text(error_x, y_err, error_msg);
             y_err += 2;
             self.ui.set_color(Color::Fg);
             let error_detail = "Le mot de passe root que vous avez saisi est incorrect.";
-            let detail_x = center_x.saturating_sub(error_detail.len() as u16 / 2);
+            let detail_x = center_x.saturating_sub(display_width(error_detail) as u16 / 2);
             self.ui.draw_text(detail_x, y_err, error_detail);
             y_err += 1;
             self.ui.set_color(Color::Warning);
             let error_note = "Le mode Admin nécessite le mot de passe root.";
-            let note_x = center_x.saturating_sub(error_note.len() as u16 / 2);
+            let note_x = center_x.saturating_sub(display_width(error_note) as u16 / 2);
             self.ui.draw_text(note_x, y_err, error_note);
             y_err += 2;
             self.ui.set_color(Color::Info);
             let retry_msg = "Appuyez sur Entrée pour réessayer, ou Q pour annuler";
-            let retry_x = center_x.saturating_sub(retry_msg.len() as u16 / 2);
+            let retry_x = center_x.saturating_sub(display_width(retry_msg) as u16 / 2);
             self.ui.draw_text(retry_x, y_err, retry_msg);
             self.ui.set_color(Color::Reset);
             io::stdout().flush().unwrap();
@@ -556,7 +939,7 @@ impl MainApp {
             // Attendre la touche de l'utilisateur
             // IMPORTANT : On continue la boucle pour réessayer, on ne retourne PAS true
             match self.input_reader.read_key() {
-                Ok(Key::Quit) => return false, // L'utilisateur annule
+                Ok(Key::Quit) | Ok(Key::Interrupt) => return false, // L'utilisateur annule
                 Ok(Key::Enter) => continue, // Réessayer
                 _ => continue, // Par défaut, réessayer
             }
@@ -598,22 +981,22 @@ impl MainApp {
             let mut y_err = by + 6;
             self.ui.set_color(Color::Error);
             let error_msg = "✗ Échec de la vérification";
-            let error_x = center_x.saturating_sub(error_msg.len() as u16 / 2);
+            let error_x = center_x.saturating_sub(display_width(error_msg) as u16 / 2);
             self.ui.draw_text(error_x, y_err, error_msg);
             y_err += 2;
             self.ui.set_color(Color::Fg);
             let error_detail = "L'authentification n'a pas pu être vérifiée.";
-            let detail_x = center_x.saturating_sub(error_detail.len() as u16 / 2);
+            let detail_x = center_x.saturating_sub(display_width(error_detail) as u16 / 2);
             self.ui.draw_text(detail_x, y_err, error_detail);
             y_err += 1;
             self.ui.set_color(Color::Warning);
             let error_note = "Le mot de passe root est requis pour le mode Admin.";
-            let note_x = center_x.saturating_sub(error_note.len() as u16 / 2);
+            let note_x = center_x.saturating_sub(display_width(error_note) as u16 / 2);
             self.ui.draw_text(note_x, y_err, error_note);
             y_err += 2;
             self.ui.set_color(Color::Info);
             let retry_msg = "Appuyez sur Entrée pour réessayer, ou Q pour annuler";
-            let retry_x = center_x.saturating_sub(retry_msg.len() as u16 / 2);
+            let retry_x = center_x.saturating_sub(display_width(retry_msg) as u16 / 2);
             self.ui.draw_text(retry_x, y_err, retry_msg);
             self.ui.set_color(Color::Reset);
             io::stdout().flush().unwrap();
@@ -621,7 +1004,7 @@ impl MainApp {
             // Attendre la touche de l'utilisateur
             // IMPORTANT : On continue la boucle pour réessayer, on ne retourne PAS true
             match self.input_reader.read_key() {
-                Ok(Key::Quit) => return false, // L'utilisateur annule
+                Ok(Key::Quit) | Ok(Key::Interrupt) => return false, // L'utilisateur annule
                 Ok(Key::Enter) => continue, // Réessayer
                 _ => continue, // Par défaut, réessayer
             }
@@ -636,11 +1019,11 @@ impl MainApp {
         let yy = by + 6;
         self.ui.set_color(Color::Success);
         let success_msg = "✓ Authentification réussie";
-        let success_x = center_x.saturating_sub(success_msg.len() as u16 / 2);
+        let success_x = center_x.saturating_sub(display_width(success_msg) as u16 / 2);
         self.ui.draw_text(success_x, yy, success_msg);
         self.ui.set_color(Color::Reset);
         io::stdout().flush().unwrap();
-        std::thread::sleep(Duration::from_millis(800));
+        self.cosmetic_pause(Duration::from_millis(800));
         self.ui.hide_cursor();
         return true; // Sortir de la boucle avec succès - SEULEMENT si les deux tests ont réussi
         }
@@ -651,7 +1034,7 @@ impl MainApp {
             return;
         }
         self.ui.clear_screen();
-        self.ui.draw_header("RMDB - Serveur de Boot Réseau");
+        self.ui.draw_header_tagged("RMDB - Serveur de Boot Réseau", &self.distribution.short_label());
         self.render_menu();
         self.render_status();
         self.needs_full_redraw = false;
@@ -659,21 +1042,33 @@ impl MainApp {
 
     fn render_menu_only(&mut self) {
         self.render_menu();
+        // La position affichée dans la barre d'état suit la sélection
+        if self.menu_items.len() > self.ui.get_max_visible_items() {
+            self.render_status();
+        }
     }
 
     fn render_menu(&mut self) {
-        let (box_x, box_y, box_w, box_h) = self.ui.get_box_dimensions();
-        let menu_x = box_x + 2;
-        let menu_y = box_y + 5;
-        let menu_height = box_h - 10;
+        let (box_x, _, box_w, box_h) = self.ui.get_box_dimensions();
+        let (menu_x, menu_y) = match self.ui.layout_mode() {
+            LayoutMode::Full => (box_x + 2, self.ui.menu_top()),
+            LayoutMode::Compact => (box_x + 1, self.ui.menu_top()),
+        };
+        let menu_height = box_h.saturating_sub(menu_y + 5);
         let max_visible = self.ui.get_max_visible_items();
 
-        let items: Vec<(usize, &str)> = match &self.menu_state {
-            MenuState::Main => {
-                self.menu_items.iter().enumerate().map(|(i, s)| (i, *s)).collect()
-            }
+        // Au menu principal, le raccourci de chaque catégorie suit son libellé
+        let items: Vec<(usize, String)> = match &self.menu_state {
+            MenuState::Main => get_main_menu()
+                .iter()
+                .enumerate()
+                .map(|(i, m)| match m.hotkey {
+                    Some(key) => (i, format!("{}  [{}]", m.label, key)),
+                    None => (i, m.label.to_string()),
+                })
+                .collect(),
             MenuState::SubMenu(_, submenu) => {
-                submenu.iter().map(|m| m.label).enumerate().map(|(i, s)| (i, s)).collect()
+                submenu.iter().map(|m| m.label.to_string()).enumerate().collect()
             }
         };
 
@@ -706,23 +1101,83 @@ impl MainApp {
         let mode_str = match self.action_mode {
             ExecActionMode::ReadOnly => "Lecture seule",
             ExecActionMode::Safe => "Safe",
+            ExecActionMode::Admin if self.capabilities.is_root() => "Admin (root)",
             ExecActionMode::Admin => "Admin",
         };
         let back_hint = match &self.menu_state {
             MenuState::SubMenu(_, _) => " | Backspace/Q: Retour",
             MenuState::Main => "",
         };
-        let status_msg = format!("Mode: {} | Flèches: Navigation | Entrée: Sélectionner{} | Q: Quitter", mode_str, back_hint);
+        let demo_hint = if self.demo_mode { " | DÉMO (données fictives)" } else { "" };
+        let profile_hint = self.tui_config.profiles.active()
+            .map(|profile| format!(" | Profil: {}", profile.name))
+            .unwrap_or_default();
+        // Longue liste : la position complète la barre de défilement, ambiguë sur un petit terminal
+        let state = ListState { selected: self.selected_menu, offset: self.menu_offset };
+        let position = state
+            .position_label(self.menu_items.len(), self.ui.get_max_visible_items())
+            .map(|label| format!(" | {}", label))
+            .unwrap_or_default();
+        let status_msg = format!("Mode: {}{}{}{} | Flèches: Navigation | Entrée: Sélectionner | \":\": Actions | ?: Expliquer{} | Q: Quitter", mode_str, profile_hint, demo_hint, position, back_hint);
         self.ui.draw_status_bar(status_y, &status_msg);
     }
 
-    fn update_menu_offset(&mut self) {
-        let max_visible = self.ui.get_max_visible_items();
-        if self.selected_menu < self.menu_offset {
-            self.menu_offset = self.selected_menu;
-        } else if self.selected_menu >= self.menu_offset + max_visible {
-            self.menu_offset = self.selected_menu - max_visible + 1;
+    /// Décrit l'élément sélectionné et les commandes qu'il lancerait, sans rien exécuter
+    fn explain_selected_action(&mut self) {
+        let menu = match &self.menu_state {
+            MenuState::Main => get_main_menu(),
+            MenuState::SubMenu(_, submenu) => submenu.clone(),
+        };
+        let Some(item) = menu.get(self.selected_menu) else {
+            return;
+        };
+
+        let title = format!("Expliquer : {}", item.label);
+        let mut viewer = TextViewer::new(&title);
+        match next_menu_state(&self.menu_state, &item.action) {
+            MenuTransition::Pop => viewer.push("Revient au menu principal.", Color::Fg),
+            MenuTransition::Push(category) => viewer.push(format!("Ouvre le sous-menu « {} ».", category), Color::Fg),
+            MenuTransition::Quit | MenuTransition::Run => {
+                viewer.push(action_preview::describe(&item.action), Color::Fg);
+                viewer.push("", Color::Fg);
+
+                let config_path = format!("/var/lib/lxc/{}/config", self.default_container);
+                let ctx = PreviewContext {
+                    container: &self.default_container,
+                    container_config: std::path::Path::new(&config_path).exists().then_some(config_path.as_str()),
+                    capabilities: &self.capabilities,
+                    container_init: self.container_inits.get(&self.default_container).copied(),
+                };
+                let commands = action_preview::command_preview(&item.action, &ctx);
+                if commands.is_empty() {
+                    viewer.push(format!("Aucune commande shell : action via l'API RMDB ({}) ou l'interface.", self.tui_config.api_url()), Color::Info);
+                } else {
+                    let with_sudo = action_preview::runs_as_admin(&item.action) && !self.capabilities.is_root();
+                    let prefix = if with_sudo { "sudo " } else { "" };
+                    viewer.push("Commandes lancées (rien n'est exécuté ici) :", Color::Info);
+                    for command in commands {
+                        viewer.push(format!("  {}{}", prefix, command), Color::Fg);
+                    }
+                }
+                if self.action_mode != ExecActionMode::Admin {
+                    viewer.push("", Color::Fg);
+                    viewer.push("Hors mode Admin : les commandes d'écriture seront refusées.", Color::Warning);
+                }
+            }
         }
+        viewer.run(&self.ui, &self.input_reader);
+    }
+
+    /// Applique un déplacement à la sélection du menu courant puis redessine le menu
+    fn move_selection(&mut self, movement: impl FnOnce(&mut ListState, usize, usize)) {
+        let mut state = ListState {
+            selected: self.selected_menu,
+            offset: self.menu_offset,
+        };
+        movement(&mut state, self.menu_items.len(), self.ui.get_max_visible_items());
+        self.selected_menu = state.selected;
+        self.menu_offset = state.offset;
+        self.render_menu_only();
     }
 
     fn execute_menu(&mut self) -> bool {
@@ -736,99 +1191,163 @@ impl MainApp {
         }
 
         let item = &menu[self.selected_menu];
-        
-        // Vérifier si on est dans un sous-menu et si l'action est un retour
-        let is_return_action = match &self.menu_state {
-            MenuState::SubMenu(category, _) => {
-                match (&item.action, category.as_str()) {
-                    (MainMenuAction::ServicesTheme, "Services") => true,
-                    (MainMenuAction::IPXETheme, "IPXE") => true,
-                    (MainMenuAction::ClientsTheme, "Clients") => true,
-                    (MainMenuAction::VMsTheme, "VMs") => true,
-                    (MainMenuAction::ConfigurationTheme, "Configuration") => true,
-                    (MainMenuAction::MonitoringTheme, "Monitoring") => true,
-                    (MainMenuAction::SystemTheme, "Système") => true,
-                    // LXCManage n'est plus un sous-menu, c'est une action directe
-                    (MainMenuAction::ContainersTheme, "Containers LXC") => true,
-                    (MainMenuAction::HostTheme, "RMDB Hôte") => true,
-                    _ => false,
-                }
-            }
-            MenuState::Main => false,
-        };
-        
-        // Si c'est une action de retour, revenir au menu principal
-        if is_return_action {
-            self.return_to_main_menu();
-            return true;
-        }
-        
-        match &item.action {
-            MainMenuAction::Quit => return false,
-            MainMenuAction::ServicesTheme => {
-                self.menu_state = MenuState::SubMenu("Services".to_string(), get_services_submenu());
-                self.selected_menu = 0;
-                self.menu_offset = 0;
-                self.menu_items = get_services_submenu().iter().map(|m| m.label).collect();
-            }
-            MainMenuAction::IPXETheme => {
-                self.menu_state = MenuState::SubMenu("IPXE".to_string(), get_ipxe_submenu());
-                self.selected_menu = 0;
-                self.menu_offset = 0;
-                self.menu_items = get_ipxe_submenu().iter().map(|m| m.label).collect();
-            }
-            MainMenuAction::ClientsTheme => {
-                self.menu_state = MenuState::SubMenu("Clients".to_string(), get_clients_submenu());
-                self.selected_menu = 0;
-                self.menu_offset = 0;
-                self.menu_items = get_clients_submenu().iter().map(|m| m.label).collect();
-            }
-            MainMenuAction::VMsTheme => {
-                self.menu_state = MenuState::SubMenu("VMs".to_string(), get_vms_submenu());
-                self.selected_menu = 0;
-                self.menu_offset = 0;
-                self.menu_items = get_vms_submenu().iter().map(|m| m.label).collect();
-            }
-            MainMenuAction::ConfigurationTheme => {
-                self.menu_state = MenuState::SubMenu("Configuration".to_string(), get_configuration_submenu());
-                self.selected_menu = 0;
-                self.menu_offset = 0;
-                self.menu_items = get_configuration_submenu().iter().map(|m| m.label).collect();
-            }
-            MainMenuAction::MonitoringTheme => {
-                self.menu_state = MenuState::SubMenu("Monitoring".to_string(), get_monitoring_submenu());
+
+        match next_menu_state(&self.menu_state, &item.action) {
+            MenuTransition::Quit => return false,
+            MenuTransition::Pop => self.return_to_main_menu(),
+            MenuTransition::Push(category) => {
+                let submenu = submenu_for(category, self.expert_mode && self.action_mode == ExecActionMode::Admin);
+                self.menu_items = submenu.iter().map(|m| m.label).collect();
+                self.menu_state = MenuState::SubMenu(category.to_string(), submenu);
                 self.selected_menu = 0;
                 self.menu_offset = 0;
-                self.menu_items = get_monitoring_submenu().iter().map(|m| m.label).collect();
             }
-            MainMenuAction::SystemTheme => {
-                self.menu_state = MenuState::SubMenu("Système".to_string(), get_system_submenu());
-                self.selected_menu = 0;
-                self.menu_offset = 0;
-                self.menu_items = get_system_submenu().iter().map(|m| m.label).collect();
+            MenuTransition::Run => self.handle_action(&item.action),
+        }
+        true
+    }
+
+    /// Palette d'actions (`:` ou Ctrl-P) : recherche dans toutes les actions des sous-menus
+    ///
+    /// L'action choisie passe par `handle_action`, qui applique les mêmes contrôles de mode.
+    fn show_command_palette(&mut self) {
+        let actions = quick_actions(self.expert_mode && self.action_mode == ExecActionMode::Admin);
+        let mut query = String::new();
+        let mut state = ListState::new();
+
+        loop {
+            let matches = filter_quick_actions(&actions, &query);
+            self.ui.clear_screen();
+            self.ui.draw_header("Actions rapides");
+            let (box_x, box_y, box_w, box_h) = self.ui.get_box_dimensions();
+            let mut y = box_y + 4;
+
+            self.ui.set_color(Color::Info);
+            self.ui.draw_text(box_x + 2, y, &format!("Rechercher: {}", query));
+            y += 1;
+            self.ui.set_color(Color::Fg);
+            self.ui.draw_text(box_x + 2, y, &format!("{} action(s)", matches.len()));
+            y += 2;
+
+            let list_height = box_h.saturating_sub(y + 3);
+            let visible = (list_height as usize).max(1);
+            state.selected = state.selected.min(matches.len().saturating_sub(1));
+            state.scroll_to_selection(visible);
+            let list_y = y;
+            for (i, entry) in matches.iter().enumerate().skip(state.offset).take(visible) {
+                let is_selected = i == state.selected;
+                self.ui.set_color(if is_selected { Color::Selection } else { Color::Fg });
+                let marker = if is_selected { "▶" } else { " " };
+                let pin = if self.favorites.is_pinned(&entry.label) { "★ " } else { "" };
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("{} {}{}", marker, pin, entry.label), usize::MAX);
+                y += 1;
             }
-            // LXCManage n'est plus utilisé, remplacé par DeployStatus
-            MainMenuAction::ContainersTheme => {
-                self.menu_state = MenuState::SubMenu("Containers LXC".to_string(), get_containers_submenu());
-                self.selected_menu = 0;
-                self.menu_offset = 0;
-                self.menu_items = get_containers_submenu().iter().map(|m| m.label).collect();
+            self.ui.draw_scrollbar(box_w.saturating_sub(3), list_y, list_height, matches.len(), visible, state.offset);
+
+            self.ui.set_color(Color::Reset);
+            self.ui.draw_text(box_x + 2, box_h.saturating_sub(2), "Saisir: Filtrer | Entrée: Exécuter | *: Épingler | Échap: Fermer");
+            io::stdout().flush().unwrap();
+
+            // Les lettres (q, j, k...) servent à la recherche : pas de touches vim ici
+            match self.input_reader.read_key() {
+                Ok(Key::Char('*')) => {
+                    if let Some(entry) = matches.get(state.selected) {
+                        self.favorites.toggle_pin(&entry.label);
+                        let _ = self.favorites.save();
+                    }
+                }
+                Ok(Key::Char(c)) => {
+                    query.push(c);
+                    state = ListState::new();
+                }
+                Ok(Key::Quit) if self.input_reader.main_menu_requested() => return,
+                Ok(Key::Interrupt) => return,
+                Ok(Key::Quit) => {
+                    query.push('q');
+                    state = ListState::new();
+                }
+                Ok(Key::Backspace) => {
+                    query.pop();
+                    state = ListState::new();
+                }
+                Ok(Key::Up) => state.up(matches.len(), visible),
+                Ok(Key::Down) => state.down(matches.len(), visible),
+                Ok(Key::PageUp) => state.page_up(visible),
+                Ok(Key::PageDown) => state.page_down(matches.len(), visible),
+                Ok(Key::Enter) => {
+                    if let Some(entry) = matches.get(state.selected) {
+                        let action = entry.action.clone();
+                        self.handle_action(&action);
+                    }
+                    return;
+                }
+                Ok(Key::Escape) | Ok(Key::CtrlP) => return,
+                _ => {}
             }
-            MainMenuAction::HostTheme => {
-                self.menu_state = MenuState::SubMenu("RMDB Hôte".to_string(), get_host_submenu());
-                self.selected_menu = 0;
-                self.menu_offset = 0;
-                self.menu_items = get_host_submenu().iter().map(|m| m.label).collect();
+        }
+    }
+
+    /// Favoris et actions récentes : Entrée lance l'action, `*` l'épingle ou la désépingle
+    fn show_favorites(&mut self) {
+        let actions = quick_actions(self.expert_mode && self.action_mode == ExecActionMode::Admin);
+        let mut selected = 0;
+        loop {
+            // Les actions devenues indisponibles (console de diagnostic hors mode expert...) sont ignorées
+            let entries: Vec<&QuickAction> = self.favorites.entries().iter()
+                .filter_map(|label| actions.iter().find(|a| a.label == *label))
+                .collect();
+            if entries.is_empty() {
+                self.show_message(
+                    "Favoris / Récents",
+                    "Aucune action récente : les actions lancées apparaîtront ici (* dans la palette : les épingle).",
+                );
+                return;
             }
-            _ => {
-                self.handle_action(&item.action);
+
+            let favorites = &self.favorites;
+            let outcome = SelectList::new("Favoris / Récents", &entries, |a| {
+                format!("{} {}", if favorites.is_pinned(&a.label) { "★" } else { " " }, a.label)
+            })
+                .with_hint("Flèches: Naviguer | Entrée: Exécuter | *: Épingler/Désépingler | Q: Retour")
+                .with_action_keys(&['*'])
+                .with_selected(selected)
+                .run(&self.ui, &self.input_reader);
+
+            match outcome {
+                SelectOutcome::Selected(index) => {
+                    let action = entries[index].action.clone();
+                    self.handle_action(&action);
+                    return;
+                }
+                SelectOutcome::Action(_, index) => {
+                    let label = entries[index].label.clone();
+                    self.favorites.toggle_pin(&label);
+                    let _ = self.favorites.save();
+                    selected = self.favorites.entries().iter().position(|l| *l == label).unwrap_or(0);
+                }
+                SelectOutcome::Cancelled => return,
             }
         }
-        true
+    }
+
+    /// Ajoute l'action aux récents (persistés entre les sessions)
+    fn record_recent_action(&mut self, action: &MainMenuAction) {
+        let actions = quick_actions(true);
+        if let Some(entry) = find_quick_action(&actions, action) {
+            self.favorites.record(&entry.label);
+            let _ = self.favorites.save();
+        }
     }
 
     fn handle_action(&mut self, action: &MainMenuAction) {
+        self.record_recent_action(action);
         match action {
+            MainMenuAction::Favorites => {
+                self.show_favorites();
+            }
+            MainMenuAction::QuickStatus => {
+                self.show_quick_status();
+            }
             MainMenuAction::ServiceStatus => {
                 self.show_service_status();
             }
@@ -868,9 +1387,15 @@ impl MainApp {
             MainMenuAction::IPXEGenerate => {
                 self.generate_ipxe_menu();
             }
+            MainMenuAction::IPXESave => {
+                self.save_ipxe_menu();
+            }
             MainMenuAction::ClientsLeases => {
                 self.show_dhcp_leases();
             }
+            MainMenuAction::ClientsLeasesWatch => {
+                self.watch_dhcp_leases();
+            }
             MainMenuAction::ClientsConnected => {
                 self.show_connected_clients();
             }
@@ -886,18 +1411,59 @@ impl MainApp {
             MainMenuAction::VMsOverlays => {
                 self.show_vm_overlays();
             }
+            MainMenuAction::VMsOverlaysCleanup => {
+                self.cleanup_orphan_overlays();
+            }
             MainMenuAction::ConfigView => {
                 self.show_config();
             }
+            MainMenuAction::ConfigDiff => {
+                self.diff_config();
+            }
+            MainMenuAction::ConfigProfiles => {
+                self.manage_api_profiles();
+            }
+            MainMenuAction::ConfigSaveTui => {
+                self.save_tui_config();
+            }
+            MainMenuAction::ConfigEdit => {
+                self.edit_config();
+            }
+            MainMenuAction::ConfigEditExternal => {
+                self.edit_config_external();
+            }
             MainMenuAction::MonitoringLogs => {
                 self.show_logs();
             }
+            MainMenuAction::MonitoringLogsFollow => {
+                self.follow_service_logs();
+            }
             MainMenuAction::MonitoringHealth => {
                 self.show_health();
             }
+            MainMenuAction::MonitoringDeployLogs => {
+                self.show_deployment_logs();
+            }
+            MainMenuAction::MonitoringDeployHistory => {
+                self.show_deploy_history();
+            }
             MainMenuAction::SystemInfo => {
                 self.show_system_info();
             }
+            MainMenuAction::SystemRequirements => {
+                self.show_requirements();
+            }
+            MainMenuAction::SystemSudoReset => {
+                self.invalidate_sudo();
+            }
+            MainMenuAction::SystemDiagnosticConsole => {
+                if self.expert_mode && self.ensure_admin() {
+                    self.diagnostic_console();
+                }
+            }
+            MainMenuAction::SystemDisplayTest => {
+                self.run_display_test();
+            }
             MainMenuAction::DeployLXC => {
                 if self.ensure_admin() {
                     self.deploy_lxc_container();
@@ -934,6 +1500,12 @@ impl MainApp {
             MainMenuAction::LXCConfig => {
                 self.lxc_show_config();
             }
+            MainMenuAction::LXCConfigEdit => {
+                self.lxc_edit_config();
+            }
+            MainMenuAction::LXCDiagnostic => {
+                self.lxc_detection_diagnostic();
+            }
             MainMenuAction::LXCRmdbStart => {
                 if self.ensure_admin() {
                     self.lxc_rmdb_start();
@@ -970,6 +1542,16 @@ impl MainApp {
                     self.containers_stop();
                 }
             }
+            MainMenuAction::ContainersFreeze => {
+                if self.ensure_admin() {
+                    self.containers_freeze(true);
+                }
+            }
+            MainMenuAction::ContainersUnfreeze => {
+                if self.ensure_admin() {
+                    self.containers_freeze(false);
+                }
+            }
             MainMenuAction::ContainersRestart => {
                 if self.ensure_admin() {
                     self.containers_restart();
@@ -980,7 +1562,12 @@ impl MainApp {
                     self.containers_add();
                 }
             }
-            MainMenuAction::ContainersDestroy => {
+            MainMenuAction::ContainersClone => {
+                if self.ensure_admin() {
+                    self.containers_clone();
+                }
+            }
+            MainMenuAction::ContainersDestroy => {
                 if self.ensure_admin() {
                     self.containers_destroy();
                 }
@@ -990,6 +1577,26 @@ impl MainApp {
                     self.containers_reinstall();
                 }
             }
+            MainMenuAction::ContainersAutostart => {
+                if self.ensure_admin() {
+                    self.containers_autostart();
+                }
+            }
+            MainMenuAction::ContainersHealth => {
+                if self.ensure_admin() {
+                    self.containers_health();
+                }
+            }
+            MainMenuAction::ContainersGhosts => {
+                if self.ensure_admin() {
+                    self.containers_ghosts();
+                }
+            }
+            MainMenuAction::ContainersRepairConfig => {
+                if self.ensure_admin() {
+                    self.containers_repair_lxc_config();
+                }
+            }
             MainMenuAction::HostInstall => {
                 if self.ensure_admin() {
                     self.host_install();
@@ -998,6 +1605,9 @@ impl MainApp {
             MainMenuAction::HostStatus => {
                 self.host_status();
             }
+            MainMenuAction::HostVerify => {
+                self.host_verify_install();
+            }
             MainMenuAction::HostStart => {
                 if self.ensure_admin() {
                     self.host_start();
@@ -1023,6 +1633,11 @@ impl MainApp {
                     self.host_disable();
                 }
             }
+            MainMenuAction::HostChangeMode => {
+                if self.ensure_admin() {
+                    self.host_change_mode();
+                }
+            }
             MainMenuAction::HostUninstall => {
                 if self.ensure_admin() {
                     self.host_uninstall();
@@ -1059,43 +1674,102 @@ impl MainApp {
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
-        let services = vec!["rmdbd", "dhcp", "dns", "tftp", "http"];
-        for service in services {
-            let cmd = if self.capabilities.has_systemctl {
-                format!("systemctl is-active {} 2>/dev/null || echo inactive", service)
-            } else if self.capabilities.has_rc_service {
-                format!("rc-service {} status 2>/dev/null | grep -q started && echo active || echo inactive", service)
-            } else {
-                format!("pgrep -f {} >/dev/null && echo active || echo inactive", service)
+        let monitor = ServiceMonitor::new(&self.capabilities);
+        let statuses = monitor.statuses(&self.executor, RMDB_SERVICES);
+        let boot = monitor.boot_enabled(&self.executor, RMDB_SERVICES);
+
+        self.ui.set_color(Color::Accent);
+        self.ui.draw_text(box_x + 2, y, &format!("{:<10}{:<12}{}", "Service", "État", "Activé au démarrage"));
+        y += 1;
+        let mut lost_at_reboot = false;
+        for ((service, status), enabled) in RMDB_SERVICES.iter().zip(statuses).zip(boot) {
+            let active = status == "active";
+            let label = match enabled {
+                Some(true) => "oui",
+                Some(false) => "non",
+                None => "?",
+            };
+            // Actif maintenant mais absent au prochain démarrage : cause fréquente de panne après un reboot
+            let color = match (active, enabled) {
+                (true, Some(false)) => {
+                    lost_at_reboot = true;
+                    Color::Warning
+                }
+                (true, _) => Color::Success,
+                (false, _) => Color::Error,
             };
+            self.ui.set_color(color);
+            self.ui.draw_text(box_x + 2, y, &format!("{:<10}{:<12}{}", service, status, label));
+            y += 1;
+        }
+        if lost_at_reboot {
+            y += 1;
+            self.ui.set_color(Color::Warning);
+            self.ui.draw_text_clipped(box_x + 2, y, "⚠ Service actif mais non activé au démarrage : il ne redémarrera pas après un reboot.", usize::MAX);
+        }
 
-            let output = self.executor.run_shell(&cmd, false);
-            let status = output.map(|o| o.stdout.trim().to_string()).unwrap_or_else(|_| "inconnu".to_string());
-            
-            self.ui.set_color(if status == "active" { Color::Success } else { Color::Error });
-            self.ui.draw_text(box_x + 2, y, &format!("{}: {}", service, status));
+        self.ui.set_color(Color::Reset);
+        self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
+        let _ = self.input_reader.read_key();
+    }
+
+    /// Hôte, container, API et services, une ligne colorée chacun, sans défilement
+    fn show_quick_status(&mut self) {
+        self.ui.clear_screen();
+        self.ui.draw_header("État rapide");
+        let (box_x, box_y, box_w, _) = self.ui.get_box_dimensions();
+        let mut y = box_y + 5;
+
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, y, "Vérification...");
+        io::stdout().flush().unwrap();
+        let status = quick_status(&self.executor, &self.capabilities, &self.api_client(), &self.default_container);
+        self.ui.clear_line(y);
+
+        let width = (box_w as usize).saturating_sub(6);
+        for (level, line) in status.lines() {
+            let (symbol, color) = diagnostic_style(level);
+            self.ui.set_color(color);
+            self.ui.draw_text_clipped(box_x + 2, y, &format!("{} {}", symbol, line), width);
             y += 1;
         }
+        y += 1;
+        if status.is_ok() {
+            self.ui.set_color(Color::Success);
+            self.ui.draw_text(box_x + 2, y, "Tout est OK.");
+        } else {
+            self.ui.set_color(Color::Warning);
+            self.ui.draw_text(box_x + 2, y, "Détails : Services › Statut des Services, RMDB Hôte › Statut.");
+        }
 
         self.ui.set_color(Color::Reset);
         self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
         let _ = self.input_reader.read_key();
     }
 
+    /// Retourne l'état d'un service ("active", "inactive"...) selon l'outil d'init disponible
+    fn service_status(&self, service: &str) -> String {
+        query_service_status(&self.executor, &self.capabilities, service)
+    }
+
     fn show_service_info(&mut self, service_name: &str) {
         self.show_message("Service", &format!("Informations sur le service {}", service_name));
     }
 
     fn start_services(&mut self) {
-        let cmd = if self.capabilities.has_systemctl {
-            "sudo systemctl start rmdbd"
+        if self.service_status(RMDB_SERVICE_NAME) == "active" {
+            self.show_message("Services", &format!("Le service {} est déjà actif.", RMDB_SERVICE_NAME));
+            return;
+        }
+
+        let output = if self.capabilities.has_systemctl {
+            self.executor.run_shell("systemctl start rmdbd", true)
         } else if self.capabilities.has_rc_service {
-            "sudo rc-service rmdbd start"
+            self.executor.run_shell("rc-service rmdbd start", true)
         } else {
-            "sudo /usr/local/bin/rmdbd -config /etc/rmdbd/config.json &"
+            // Ni systemd ni OpenRC : rmdbd est lancé directement et suivi par son fichier PID
+            HostDeployment::new().start_rmdb_direct(&self.executor)
         };
-
-        let output = self.executor.run_shell(cmd, true);
         if output.is_ok() {
             self.show_message("Succès", "Services démarrés avec succès.");
         } else {
@@ -1104,15 +1778,19 @@ impl MainApp {
     }
 
     fn stop_services(&mut self) {
-        let cmd = if self.capabilities.has_systemctl {
-            "sudo systemctl stop rmdbd"
+        if matches!(self.service_status(RMDB_SERVICE_NAME).as_str(), "inactive" | "failed" | "unknown") {
+            self.show_message("Services", &format!("Le service {} est déjà arrêté.", RMDB_SERVICE_NAME));
+            return;
+        }
+
+        let output = if self.capabilities.has_systemctl {
+            self.executor.run_shell("systemctl stop rmdbd", true)
         } else if self.capabilities.has_rc_service {
-            "sudo rc-service rmdbd stop"
+            self.executor.run_shell("rc-service rmdbd stop", true)
         } else {
-            "sudo pkill -f rmdbd"
+            // Ni systemd ni OpenRC : rmdbd est lancé directement et suivi par son fichier PID
+            HostDeployment::new().stop_rmdb_direct(&self.executor)
         };
-
-        let output = self.executor.run_shell(cmd, true);
         if output.is_ok() {
             self.show_message("Succès", "Services arrêtés avec succès.");
         } else {
@@ -1120,32 +1798,59 @@ impl MainApp {
         }
     }
 
+    /// Redémarre rmdbd puis affiche ce qui a changé : état final et lignes de journal apparues
     fn restart_services(&mut self) {
-        let cmd = if self.capabilities.has_systemctl {
-            "sudo systemctl restart rmdbd"
-        } else if self.capabilities.has_rc_service {
-            "sudo rc-service rmdbd restart"
-        } else {
-            "sudo pkill -f rmdbd && sleep 1 && sudo /usr/local/bin/rmdbd -config /etc/rmdbd/config.json &"
-        };
+        self.ui.clear_screen();
+        self.ui.draw_header("Redémarrage des Services");
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(
+            box_x + 2,
+            box_y + 5,
+            &format!("Redémarrage de {}, observation pendant {} s...", RMDB_SERVICE_NAME, RESTART_OBSERVE_SECS),
+        );
+        self.ui.set_color(Color::Reset);
 
-        let output = self.executor.run_shell(cmd, true);
-        if output.is_ok() {
-            self.show_message("Succès", "Services redémarrés avec succès.");
+        let report = ServiceMonitor::new(&self.capabilities).restart_and_tail(&self.executor, RMDB_SERVICE_NAME, RESTART_OBSERVE_SECS);
+
+        let mut viewer = TextViewer::new("Redémarrage des Services");
+        match report.restart_error {
+            Some(ref error) => viewer.push(format!("✗ Impossible de redémarrer {}: {}", report.service, error), Color::Error),
+            None => viewer.push(format!("✓ Commande de redémarrage de {} exécutée.", report.service), Color::Success),
+        }
+        let status_color = if report.status == "active" { Color::Success } else { Color::Error };
+        viewer.push(format!("État après {} s: {}", RESTART_OBSERVE_SECS, report.status), status_color);
+        let errors = report.error_lines().len();
+        if report.is_clean() {
+            viewer.push("Le service a redémarré proprement.", Color::Success);
+        } else if errors > 0 {
+            viewer.push(format!("⚠ {} ligne(s) d'erreur depuis le redémarrage.", errors), Color::Warning);
+        }
+        viewer.push("", Color::Fg);
+        if report.new_lines.is_empty() {
+            viewer.push(format!("Aucune nouvelle ligne dans {}.", report.log_source), Color::Info);
         } else {
-            self.show_error_message("Erreur", "Impossible de redémarrer les services.");
+            viewer.push(format!("Nouvelles lignes ({}):", report.log_source), Color::Info);
+            for line in &report.new_lines {
+                let color = if is_error_line(line) { Color::Error } else { Color::Fg };
+                viewer.push(line.as_str(), color);
+            }
         }
+        viewer.run(&self.ui, &self.input_reader);
     }
 
     /// Affiche le menu iPXE généré
     fn show_ipxe_menu(&mut self) {
+        if !self.ensure_api_available() {
+            return;
+        }
         self.ui.clear_screen();
         self.ui.draw_header("Menu iPXE Généré");
 
         let (box_x, box_y, _box_w, box_h) = self.ui.get_box_dimensions();
         let mut y = box_y + 2;
 
-        let api_client = APIClient::new("http://localhost:8080".to_string());
+        let api_client = self.api_client();
 
         self.ui.set_color(Color::Info);
         self.ui.draw_text(box_x + 2, y, "Chargement du menu iPXE...");
@@ -1167,7 +1872,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("✗ Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ Erreur: {}", e), usize::MAX);
                 self.ui.set_color(Color::Fg);
                 self.ui.draw_text(box_x + 4, y + 1, "Assurez-vous que le serveur RMDB est démarré");
             }
@@ -1181,77 +1886,88 @@ impl MainApp {
 
     /// Affiche les entrées iPXE
     fn show_ipxe_entries(&mut self) {
-        self.ui.clear_screen();
-        self.ui.draw_header("Entrées de Menu iPXE");
-
-        let (box_x, box_y, _box_w, box_h) = self.ui.get_box_dimensions();
-        let mut y = box_y + 2;
+        if !self.ensure_api_available() {
+            return;
+        }
+        loop {
+            self.ui.clear_screen();
+            self.ui.draw_header("Entrées de Menu iPXE");
 
-        let api_client = APIClient::new("http://localhost:8080".to_string());
+            let (box_x, box_y, _box_w, box_h) = self.ui.get_box_dimensions();
+            let mut y = box_y + 2;
 
-        self.ui.set_color(Color::Info);
-        self.ui.draw_text(box_x + 2, y, "Chargement des entrées...");
-        y += 1;
+            let api_client = self.api_client();
 
-        match api_client.get_ipxe_entries() {
-            Ok(entries) => {
-                if entries.is_empty() {
-                    self.ui.set_color(Color::Warning);
-                    self.ui.draw_text(box_x + 2, y, "Aucune entrée iPXE trouvée.");
-                    y += 2;
-                } else {
-                    self.ui.set_color(Color::Fg);
-                    self.ui.draw_text(box_x + 2, y, &format!("Total: {} entrée(s)", entries.len()));
-                    y += 2;
+            self.ui.set_color(Color::Info);
+            self.ui.draw_text(box_x + 2, y, "Chargement des entrées...");
+            y += 1;
 
-                    let max_items = (box_h as usize).saturating_sub(8).min(entries.len());
-                    for (i, entry) in entries.iter().take(max_items).enumerate() {
+            match api_client.get_ipxe_entries() {
+                Ok(entries) => {
+                    if entries.is_empty() {
+                        self.ui.set_color(Color::Warning);
+                        self.ui.draw_text(box_x + 2, y, "Aucune entrée iPXE trouvée.");
+                        y += 2;
+                    } else {
                         self.ui.set_color(Color::Fg);
-                        self.ui.draw_text(box_x + 2, y, &format!("{}. {}", i + 1, entry.name));
-                        y += 1;
-                        self.ui.set_color(Color::Info);
-                        if let Some(ref desc) = entry.description {
-                            self.ui.draw_text(box_x + 4, y, &format!("Description: {}", desc));
+                        self.ui.draw_text(box_x + 2, y, &format!("Total: {} entrée(s)", entries.len()));
+                        y += 2;
+
+                        let max_items = (box_h as usize).saturating_sub(8).min(entries.len());
+                        for (i, entry) in entries.iter().take(max_items).enumerate() {
+                            self.ui.set_color(Color::Fg);
+                            self.ui.draw_text(box_x + 2, y, &format!("{}. {}", i + 1, entry.name));
                             y += 1;
-                        }
-                        self.ui.draw_text(box_x + 4, y, &format!("Type: {} | Activé: {}", entry.menu_type, if entry.enabled { "Oui" } else { "Non" }));
-                        y += 1;
-                        if let Some(ref target) = entry.boot_target {
-                            self.ui.draw_text(box_x + 4, y, &format!("Cible de boot: {}", target));
+                            self.ui.set_color(Color::Info);
+                            if let Some(ref desc) = entry.description {
+                                self.ui.draw_text(box_x + 4, y, &format!("Description: {}", desc));
+                                y += 1;
+                            }
+                            self.ui.draw_text(box_x + 4, y, &format!("Type: {} | Activé: {}", entry.menu_type, if entry.enabled { "Oui" } else { "Non" }));
+                            y += 1;
+                            if let Some(ref target) = entry.boot_target {
+                                self.ui.draw_text(box_x + 4, y, &format!("Cible de boot: {}", target));
+                                y += 1;
+                            }
                             y += 1;
                         }
-                        y += 1;
-                    }
 
-                    if entries.len() > max_items {
-                        self.ui.set_color(Color::Warning);
-                        self.ui.draw_text(box_x + 2, y, &format!("... et {} entrée(s) supplémentaire(s)", entries.len() - max_items));
-                        y += 1;
+                        if entries.len() > max_items {
+                            self.ui.set_color(Color::Warning);
+                            self.ui.draw_text(box_x + 2, y, &format!("... et {} entrée(s) supplémentaire(s)", entries.len() - max_items));
+                            y += 1;
+                        }
                     }
                 }
+                Err(e) => {
+                    self.ui.set_color(Color::Error);
+                    self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ Erreur: {}", e), usize::MAX);
+                }
             }
-            Err(e) => {
-                self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("✗ Erreur: {}", e));
+
+            y += 2;
+            if !self.read_refresh_key(box_x + 2, y) {
+                break;
             }
         }
-
-        y += 2;
-        self.ui.set_color(Color::Reset);
-        self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
-        let _ = self.input_reader.read_key();
     }
 
     /// Génère le menu iPXE
     fn generate_ipxe_menu(&mut self) {
+        if !self.ensure_api_available() {
+            return;
+        }
+        let api_client = self.api_client();
+        if !self.confirm_ipxe_regeneration(&api_client) {
+            return;
+        }
+
         self.ui.clear_screen();
         self.ui.draw_header("Génération du Menu iPXE");
 
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
-        let api_client = APIClient::new("http://localhost:8080".to_string());
-
         self.ui.set_color(Color::Info);
         self.ui.draw_text(box_x + 2, y, "Génération du menu iPXE en cours...");
         y += 2;
@@ -1274,7 +1990,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("✗ Erreur lors de la génération: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ Erreur lors de la génération: {}", e), usize::MAX);
             }
         }
 
@@ -1284,141 +2000,573 @@ impl MainApp {
         let _ = self.input_reader.read_key();
     }
 
-    fn show_dhcp_leases(&mut self) {
-        self.ui.clear_screen();
-        self.ui.draw_header("Leases DHCP");
-        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
-        let mut y = box_y + 5;
+    /// Compare le menu iPXE actuel à celui que produirait la régénération et demande confirmation
+    ///
+    /// Sans aperçu côté serveur, la confirmation rappelle que les personnalisations seront écrasées.
+    fn confirm_ipxe_regeneration(&mut self, api_client: &APIClient) -> bool {
+        self.show_refreshing();
+        // Pas de menu actuel (404) : rien à écraser
+        let current = match api_client.get_ipxe_menu() {
+            Ok(menu) => menu,
+            Err(e) if matches!(e.cause(), APIError::NotFound(_)) => String::new(),
+            Err(e) => {
+                return self.ask_yes_no_default(
+                    "Régénération du Menu iPXE",
+                    &format!("Menu actuel illisible ({}). Régénérer quand même et écraser le menu du serveur ?", e),
+                    true,
+                );
+            }
+        };
 
-        let cmd = "cat /var/lib/dhcp/dhcpd.leases 2>/dev/null | head -20 || echo 'Aucun lease trouvé'";
-        let output = self.executor.run_shell(cmd, false);
-        let content = output.map(|o| o.stdout).unwrap_or_else(|_| "Erreur lors de la lecture des leases".to_string());
+        let preview = match api_client.preview_ipxe_menu() {
+            Ok(Some(preview)) => preview,
+            Ok(None) | Err(_) => {
+                let question = format!(
+                    "Le serveur ne propose pas d'aperçu. La régénération remplace le menu actuel ({} lignes) : \
+                     les personnalisations manuelles seront perdues. Continuer ?",
+                    current.lines().count()
+                );
+                return self.ask_yes_no_default("Régénération du Menu iPXE", &question, true);
+            }
+        };
 
-        for line in content.lines().take(15) {
-            self.ui.set_color(Color::Fg);
-            self.ui.draw_text(box_x + 2, y, line);
-            y += 1;
+        let changes = line_diff(&current, &preview);
+        let (added, removed) = changes.iter().fold((0, 0), |(a, r), change| match change {
+            LineChange::Added(_) => (a + 1, r),
+            LineChange::Removed(_) => (a, r + 1),
+            LineChange::Same(_) => (a, r),
+        });
+        if added == 0 && removed == 0 {
+            return self.ask_yes_no(
+                "Régénération du Menu iPXE",
+                "Le menu régénéré est identique au menu actuel. Régénérer quand même ?",
+            );
         }
 
-        self.ui.set_color(Color::Reset);
-        self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
-        let _ = self.input_reader.read_key();
-    }
+        let mut viewer = TextViewer::new("Aperçu de la Régénération iPXE")
+            .with_hint("Flèches/PagePréc/PageSuiv: Défiler | E: Exporter | Q: Continuer vers la confirmation");
+        viewer.push(format!("+{} ligne(s), -{} ligne(s) par rapport au menu actuel", added, removed), Color::Info);
+        viewer.push("", Color::Fg);
+        for change in &changes {
+            match change {
+                LineChange::Same(line) => viewer.push(format!("  {}", line), Color::Fg),
+                LineChange::Added(line) => viewer.push(format!("+ {}", line), Color::Success),
+                LineChange::Removed(line) => viewer.push(format!("- {}", line), Color::Error),
+            }
+        }
+        viewer.run(&self.ui, &self.input_reader);
 
-    fn show_connected_clients(&mut self) {
-        self.show_message("Clients Connectés", "Liste des clients connectés");
+        self.ask_yes_no_default(
+            "Régénération du Menu iPXE",
+            &format!("Appliquer ces changements au menu du serveur (+{} / -{}) ?", added, removed),
+            true,
+        )
     }
 
-    /// Affiche la configuration RMDB (version améliorée avec API)
-    fn show_config(&mut self) {
+    /// Enregistre le menu iPXE servi actuellement dans un fichier local, sans le régénérer
+    fn save_ipxe_menu(&mut self) {
+        if !self.ensure_api_available() {
+            return;
+        }
         self.ui.clear_screen();
-        self.ui.draw_header("Configuration RMDB");
-
-        let (box_x, box_y, _box_w, box_h) = self.ui.get_box_dimensions();
-        let mut y = box_y + 2;
+        self.ui.draw_header("Exporter le Menu iPXE");
 
-        let api_client = APIClient::new("http://localhost:8080".to_string());
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let mut y = box_y + 5;
 
         self.ui.set_color(Color::Info);
-        self.ui.draw_text(box_x + 2, y, "Chargement de la configuration...");
+        self.ui.draw_text(box_x + 2, y, "Chemin du fichier de destination (ex: /srv/tftp/menu.ipxe) :");
         y += 1;
+        let Some(path) = read_line(&self.ui, &self.input_reader, box_x + 4, y) else {
+            return;
+        };
+        let path = path.trim().to_string();
+        y += 2;
+        if path.is_empty() {
+            return;
+        }
 
-        match api_client.get_config() {
-            Ok(config) => {
-                // Afficher la configuration formatée
-                let config_str = serde_json::to_string_pretty(&config)
-                    .unwrap_or_else(|_| "Erreur de formatage".to_string());
-                
-                self.ui.set_color(Color::Fg);
-                let max_lines = (box_h as usize).saturating_sub(8);
-                for (i, line) in config_str.lines().take(max_lines).enumerate() {
-                    self.ui.draw_text(box_x + 2, y + i as u16, line);
-                }
+        // Le répertoire parent doit exister
+        let target = std::path::Path::new(&path);
+        let parent_ok = match target.parent() {
+            Some(parent) if parent.as_os_str().is_empty() => true,
+            Some(parent) => parent.is_dir(),
+            None => false,
+        };
+        if !parent_ok || target.is_dir() {
+            self.show_error_message("Erreur", &format!("Chemin invalide ou répertoire parent inexistant: {}", path));
+            return;
+        }
 
-                if config_str.lines().count() > max_lines {
-                    self.ui.set_color(Color::Warning);
-                    self.ui.draw_text(box_x + 2, box_h - 3, &format!("... et {} ligne(s) supplémentaire(s)", config_str.lines().count() - max_lines));
+        let api_client = self.api_client();
+
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, y, "Lecture du menu iPXE en cours...");
+        y += 2;
+
+        match api_client.get_ipxe_menu() {
+            Ok(menu) => match std::fs::write(target, menu.as_bytes()) {
+                Ok(_) => {
+                    self.ui.set_color(Color::Success);
+                    self.ui.draw_text(box_x + 2, y, &format!("✓ Menu iPXE enregistré: {}", path));
+                    y += 1;
+                    self.ui.set_color(Color::Fg);
+                    self.ui.draw_text(box_x + 2, y, &format!("{} octets écrits ({} lignes)", menu.len(), menu.lines().count()));
                 }
-            }
+                Err(e) => {
+                    self.ui.set_color(Color::Error);
+                    self.ui.draw_text(box_x + 2, y, &format!("✗ Impossible d'écrire le fichier: {}", e));
+                }
+            },
             Err(e) => {
-                // Fallback vers méthode locale
-                self.ui.set_color(Color::Warning);
-                self.ui.draw_text(box_x + 2, y, "API non disponible, utilisation de la méthode locale...");
-                y += 2;
-                self.show_config_local();
-                return;
+                self.ui.set_color(Color::Error);
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ Erreur lors de la lecture du menu: {}", e), usize::MAX);
             }
         }
 
-        y = box_h - 2;
+        y += 2;
         self.ui.set_color(Color::Reset);
         self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
         let _ = self.input_reader.read_key();
     }
 
-    /// Affiche la configuration (méthode locale de fallback)
-    fn show_config_local(&mut self) {
-        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
-        let mut y = box_y + 5;
-
-        let config_path = "/etc/rmdbd/config.json";
-        let cmd = format!("cat {} 2>/dev/null | head -30 || echo 'Fichier de configuration non trouvé'", config_path);
-        let output = self.executor.run_shell(&cmd, false);
-        let content = output.map(|o| o.stdout).unwrap_or_else(|_| "Erreur lors de la lecture de la configuration".to_string());
+    fn show_dhcp_leases(&mut self) {
+        self.lease_view("Leases DHCP", false);
+    }
 
-        for line in content.lines().take(25) {
-            self.ui.set_color(Color::Fg);
-            self.ui.draw_text(box_x + 2, y, line);
-            y += 1;
+    /// Détecte le fichier de leases et son format ; affiche un message si aucun n'est trouvé
+    fn detect_leases_file(&mut self) -> Option<(std::path::PathBuf, LeaseFormat)> {
+        match find_dhcp_leases_path(&self.distribution.distro, &self.executor) {
+            Some(path) => {
+                let format = LeaseFormat::from_path(&path);
+                Some((path, format))
+            }
+            None => {
+                self.show_error_message("Leases DHCP", "Aucun fichier de leases trouvé (ISC dhcpd ou dnsmasq).");
+                None
+            }
         }
     }
 
-    /// Édite la configuration
-    fn edit_config(&mut self) {
-        self.show_message("Configuration", "Édition de configuration - À implémenter (nécessite éditeur de texte)");
+    /// Surveille les leases DHCP et met en évidence les nouvelles entrées
+    fn watch_dhcp_leases(&mut self) {
+        self.lease_view("Surveillance des Leases DHCP", true);
     }
 
-    /// Affiche la configuration réseau
-    fn show_network_config(&mut self) {
-        self.ui.clear_screen();
-        self.ui.draw_header("Configuration Réseau");
-
-        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
-        let mut y = box_y + 3;
+    /// Liste des leases DHCP : P ping l'IP, W envoie un wake-on-lan, o/O change le tri
+    ///
+    /// En mode `live`, le fichier est relu toutes les deux secondes et les leases apparues depuis
+    /// la lecture précédente sont affichées en vert pendant un cycle.
+    fn lease_view(&mut self, title: &str, live: bool) {
+        const REFRESH_INTERVAL: Duration = Duration::from_secs(2);
+        let (leases_path, format) = match self.detect_leases_file() {
+            Some(found) => found,
+            None => return,
+        };
+        let mut known: Option<Vec<(String, String)>> = None;
+        let mut content: Option<String> = None;
+        let mut list = ListState::new();
+        // La sélection suit la lease (par MAC) quand le fichier est relu ou la liste retriée
+        let mut selected_mac: Option<String> = None;
+        let mut status: Option<(String, Color)> = None;
 
-        self.ui.set_color(Color::Fg);
-        self.ui.draw_text(box_x + 2, y, "Services réseau:");
-        y += 2;
+        loop {
+            if live || content.is_none() {
+                let cmd = format!("cat {} 2>/dev/null", leases_path.display());
+                content = Some(self.executor.run_shell(&cmd, false).map(|o| o.stdout).unwrap_or_default());
+            }
+            let mut leases = parse_leases_as(content.as_deref().unwrap_or_default(), format);
+            // Les leases les plus récentes en premier, puis le tri choisi
+            leases.reverse();
+            let order = self.lease_sort.order(&leases, LEASE_SORT_COLUMNS);
+            let leases: Vec<DHCPLease> = order.into_iter().map(|i| leases[i].clone()).collect();
+            let current: Vec<(String, String)> = leases.iter().map(|l| (l.ip.clone(), l.mac.clone())).collect();
 
-        let services = vec![
-            ("DHCP", "Port 67"),
-            ("DNS", "Port 53"),
-            ("TFTP", "Port 69"),
-            ("HTTP", "Port 8080"),
-            ("NBD", "Port 10809"),
-        ];
+            self.ui.clear_screen();
+            self.ui.draw_header(title);
+            let (box_x, box_y, _, box_h) = self.ui.get_box_dimensions();
+            let mut y = box_y + 4;
 
-        for (service, port) in services {
             self.ui.set_color(Color::Info);
-            self.ui.draw_text(box_x + 4, y, &format!("{}: {}", service, port));
+            let mut summary = format!("{} lease(s) | {} ({})", leases.len(), leases_path.display(), format.display_name());
+            if live {
+                summary.push_str(&format!(" | Actualisé à {}", chrono::Local::now().format("%H:%M:%S")));
+            }
+            self.ui.draw_text_clipped(box_x + 2, y, &summary, usize::MAX);
+            y += 2;
+
+            self.ui.set_color(Color::Fg);
+            self.ui.draw_text(box_x + 2, y, &format!("  {:<16} {:<18} {:<20} {:<10}", "IP", "MAC", "Nom d'hôte", "État"));
             y += 1;
-        }
 
-        y += 2;
-        self.ui.set_color(Color::Reset);
-        self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
-        let _ = self.input_reader.read_key();
-    }
+            let max_rows = box_h.saturating_sub(y + 4) as usize;
+            list.selected = selected_mac.as_ref()
+                .and_then(|mac| leases.iter().position(|l| &l.mac == mac))
+                .unwrap_or(list.selected)
+                .min(leases.len().saturating_sub(1));
+            selected_mac = leases.get(list.selected).map(|l| l.mac.clone());
+            list.scroll_to_selection(max_rows);
+            if leases.is_empty() {
+                self.ui.set_color(Color::Warning);
+                self.ui.draw_text(box_x + 4, y, "Aucun lease trouvé");
+            }
+            for (i, lease) in leases.iter().enumerate().skip(list.offset).take(max_rows) {
+                // Une lease inconnue au rafraîchissement précédent est affichée en vert pendant un cycle
+                let is_new = known.as_ref()
+                    .map(|k| !k.contains(&(lease.ip.clone(), lease.mac.clone())))
+                    .unwrap_or(false);
+                let color = if i == list.selected {
+                    Color::Selection
+                } else if is_new {
+                    Color::Success
+                } else {
+                    Color::Fg
+                };
+                let marker = if i == list.selected { "▶" } else { " " };
+                self.ui.set_color(color);
+                self.ui.draw_text(box_x + 2, y, &format!("{} {:<16} {:<18} {:<20} {:<10}",
+                    marker, lease.ip, lease.mac, lease.hostname.as_deref().unwrap_or("-"), lease.state));
+                y += 1;
+            }
 
-    /// Affiche la configuration de sécurité
-    fn show_security_config(&mut self) {
+            if let Some((message, color)) = &status {
+                self.ui.set_color(*color);
+                self.ui.draw_text(box_x + 2, box_h.saturating_sub(3), message);
+            }
+            self.ui.set_color(Color::Reset);
+            self.ui.draw_text(box_x + 2, box_h.saturating_sub(2), &format!(
+                "Flèches: Sélectionner | P: Ping | W: Wake-on-LAN | o/O: Tri ({}) | Q: Quitter",
+                self.lease_sort.label(LEASE_SORT_COLUMNS)));
+            known = Some(current);
+
+            let key = if live {
+                match self.input_reader.poll_key(REFRESH_INTERVAL) {
+                    Ok(Some(key)) => self.input_reader.navigation_key(key),
+                    Ok(None) => continue,
+                    Err(_) => break,
+                }
+            } else {
+                match self.input_reader.read_key() {
+                    Ok(key) => self.input_reader.navigation_key(key),
+                    Err(_) => break,
+                }
+            };
+            match key {
+                Key::Quit | Key::Interrupt => break,
+                Key::Up => list.up(leases.len(), max_rows),
+                Key::Down => list.down(leases.len(), max_rows),
+                Key::PageUp => list.page_up(max_rows),
+                Key::PageDown => list.page_down(leases.len(), max_rows),
+                Key::Home => list.home(max_rows),
+                Key::End => list.end(leases.len(), max_rows),
+                Key::Char('p') | Key::Char('P') => {
+                    if let Some(lease) = leases.get(list.selected) {
+                        status = Some(if net::ping(&lease.ip) {
+                            (format!("✓ {} répond au ping", lease.ip), Color::Success)
+                        } else {
+                            (format!("✗ {} ne répond pas", lease.ip), Color::Error)
+                        });
+                    }
+                }
+                Key::Char('o') => self.lease_sort.cycle(LEASE_SORT_COLUMNS.len()),
+                Key::Char('O') => self.lease_sort.toggle_reverse(),
+                Key::Char('w') | Key::Char('W') => {
+                    if let Some(lease) = leases.get(list.selected) {
+                        status = Some(match net::wake_on_lan(&lease.mac) {
+                            Ok(()) => (format!("✓ Paquet wake-on-lan envoyé à {}", lease.mac), Color::Success),
+                            Err(e) => (format!("✗ Échec du wake-on-lan: {}", e), Color::Error),
+                        });
+                    }
+                }
+                _ => {}
+            }
+            selected_mac = leases.get(list.selected).map(|l| l.mac.clone());
+        }
+    }
+
+    /// Exécute des commandes arbitraires via l'exécuteur (sudo en mode Admin) et affiche leur sortie
+    fn diagnostic_console(&mut self) {
+        loop {
+            self.ui.clear_screen();
+            self.ui.draw_header("Console de diagnostic");
+            let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+            let y = box_y + 5;
+
+            self.ui.set_color(Color::Warning);
+            self.ui.draw_text(box_x + 2, y, "Les commandes sont exécutées avec sudo (mode Admin).");
+            self.ui.set_color(Color::Info);
+            self.ui.draw_text(box_x + 2, y + 2, "Commande à exécuter (Entrée vide ou Échap pour quitter) :");
+            let cmd = match read_line(&self.ui, &self.input_reader, box_x + 2, y + 4) {
+                Some(cmd) if !cmd.trim().is_empty() => cmd,
+                _ => break,
+            };
+
+            let mut viewer = TextViewer::new("Console de diagnostic");
+            viewer.push(format!("$ {}", cmd), Color::Info);
+            match self.executor.run_shell(&cmd, true) {
+                Ok(output) => {
+                    viewer.push_text(&output.stdout, Color::Fg);
+                    viewer.push_text(&output.stderr, Color::Error);
+                    let (label, color) = match output.exit_code {
+                        Some(0) => ("0".to_string(), Color::Success),
+                        Some(code) => (code.to_string(), Color::Error),
+                        None => ("interrompu par un signal".to_string(), Color::Error),
+                    };
+                    viewer.push(format!("Code de sortie: {}", label), color);
+                }
+                Err(e) => viewer.push(format!("Erreur: {}", e), Color::Error),
+            }
+            viewer.run(&self.ui, &self.input_reader);
+        }
+    }
+
+    fn show_connected_clients(&mut self) {
+        self.show_message("Clients Connectés", "Liste des clients connectés");
+    }
+
+    /// Affiche la configuration RMDB (version améliorée avec API)
+    ///
+    /// Le JSON complet défile dans la visionneuse, clés et valeurs colorées. Si l'API ne
+    /// répond pas, la configuration locale est affichée à la place.
+    fn show_config(&mut self) {
+        loop {
+            self.ui.clear_screen();
+            self.ui.draw_header("Configuration RMDB");
+            let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+            self.ui.set_color(Color::Info);
+            self.ui.draw_text(box_x + 2, box_y + 2, "Chargement de la configuration...");
+
+            let config = match self.api_client().get_config() {
+                Ok(config) => config,
+                Err(_) => {
+                    // Fallback vers méthode locale
+                    self.show_config_local();
+                    return;
+                }
+            };
+            let config_str = serde_json::to_string_pretty(&config)
+                .unwrap_or_else(|_| "Erreur de formatage".to_string());
+
+            let mut viewer = TextViewer::new("Configuration RMDB")
+                .with_hint("Flèches: Défiler | R: Actualiser | E: Exporter | Q: Retour")
+                .with_action_keys(&['r']);
+            viewer.push_json(&config_str);
+            if viewer.run(&self.ui, &self.input_reader) != Some('r') {
+                break;
+            }
+        }
+    }
+
+    /// Affiche la configuration (méthode locale de fallback)
+    fn show_config_local(&mut self) {
+        let config_path = "/etc/rmdbd/config.json";
+        let cmd = format!("cat {} 2>/dev/null || echo 'Fichier de configuration non trouvé'", config_path);
+        let output = self.executor.run_shell(&cmd, false);
+        let content = output.map(|o| o.stdout).unwrap_or_else(|_| "Erreur lors de la lecture de la configuration".to_string());
+
+        let mut viewer = TextViewer::new(config_path);
+        viewer.set_status("API non disponible, lecture du fichier local", Color::Warning);
+        match serde_json::from_str::<serde_json::Value>(&content) {
+            Ok(value) => viewer.push_json(&serde_json::to_string_pretty(&value).unwrap_or(content)),
+            Err(_) => viewer.push_text(&content, Color::Fg),
+        }
+        viewer.run(&self.ui, &self.input_reader);
+    }
+
+    /// Compare la configuration live (API) avec un fichier JSON local
+    fn diff_config(&mut self) {
+        if !self.ensure_api_available() {
+            return;
+        }
+        self.ui.clear_screen();
+        self.ui.draw_header("Comparer la Configuration");
+
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let mut y = box_y + 5;
+
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, y, "Fichier de configuration à comparer (ex: /etc/rmdbd/config.json) :");
+        y += 1;
+        let Some(path) = read_line(&self.ui, &self.input_reader, box_x + 4, y) else {
+            return;
+        };
+        let path = path.trim().to_string();
+        if path.is_empty() {
+            return;
+        }
+
+        let saved: serde_json::Value = match std::fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str(&content) {
+                Ok(value) => value,
+                Err(e) => {
+                    self.show_error_message("Erreur", &format!("JSON invalide dans {}: {}", path, e));
+                    return;
+                }
+            },
+            Err(e) => {
+                self.show_error_message("Erreur", &format!("Impossible de lire {}: {}", path, e));
+                return;
+            }
+        };
+
+        let api_client = self.api_client();
+        let Some(live) = self.with_retry_ui("Impossible de charger la configuration live", || api_client.get_config()) else {
+            return;
+        };
+
+        let diff = json_diff(&saved, &live);
+        let mut viewer = TextViewer::new("Différences de Configuration");
+        viewer.push(format!("Fichier: {}  →  Live: API", path), Color::Info);
+        viewer.push("", Color::Fg);
+        if diff.is_empty() {
+            viewer.push("✓ Aucune différence: la configuration live correspond au fichier.", Color::Success);
+        } else {
+            viewer.push(format!("{} différence(s):", diff.len()), Color::Warning);
+            for line in &diff {
+                let old = line.old.as_deref().unwrap_or("");
+                let new = line.new.as_deref().unwrap_or("");
+                match line.kind {
+                    DiffKind::Added => viewer.push(format!("+ {} = {}", line.key, new), Color::Success),
+                    DiffKind::Removed => viewer.push(format!("- {} = {}", line.key, old), Color::Error),
+                    DiffKind::Changed => viewer.push(format!("~ {}: {} → {}", line.key, old, new), Color::Warning),
+                }
+            }
+        }
+        viewer.run(&self.ui, &self.input_reader);
+    }
+
+    /// Édite la configuration live : navigation dans l'arbre JSON, puis enregistrement via l'API
+    fn edit_config(&mut self) {
+        if !self.ensure_api_available() {
+            return;
+        }
+        let api_client = self.api_client();
+        let Some(config) = self.with_retry_ui("Impossible de charger la configuration", || api_client.get_config()) else {
+            return;
+        };
+
+        let mut editor = ConfigEditor::new(config);
+        loop {
+            match editor.run(&self.ui, &self.input_reader) {
+                EditorOutcome::Save(config) => {
+                    if !editor.is_modified() {
+                        self.show_message("Éditer Configuration", "Aucune modification à enregistrer.");
+                        return;
+                    }
+                    if self.with_retry_ui("Impossible d'enregistrer la configuration", || api_client.update_config(&config)).is_some() {
+                        self.show_message("Éditer Configuration", "✓ Configuration enregistrée.");
+                        return;
+                    }
+                }
+                EditorOutcome::Cancelled => {
+                    if !editor.is_modified()
+                        || self.ask_yes_no_default("Éditer Configuration", "Abandonner les modifications non enregistrées ?", true)
+                    {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Édite la configuration live dans l'éditeur de l'utilisateur, puis l'enregistre via l'API
+    ///
+    /// Un JSON invalide est signalé et l'éditeur rouvert sur le texte saisi.
+    fn edit_config_external(&mut self) {
+        if !self.ensure_api_available() {
+            return;
+        }
+        let api_client = self.api_client();
+        let Some(config) = self.with_retry_ui("Impossible de charger la configuration", || api_client.get_config()) else {
+            return;
+        };
+
+        let mut text = serde_json::to_string_pretty(&config).unwrap_or_default();
+        loop {
+            let Some(edited) = self.edit_in_external_editor(&text, "config.json") else {
+                return;
+            };
+            match serde_json::from_str::<serde_json::Value>(&edited) {
+                Ok(value) if value == config => {
+                    self.show_message("Éditeur Externe", "Aucune modification à enregistrer.");
+                    return;
+                }
+                Ok(value) => {
+                    if self.with_retry_ui("Impossible d'enregistrer la configuration", || api_client.update_config(&value)).is_some() {
+                        self.show_message("Éditeur Externe", "✓ Configuration enregistrée.");
+                    }
+                    return;
+                }
+                Err(e) => {
+                    text = edited;
+                    let question = format!("JSON invalide: {}\nRouvrir l'éditeur pour corriger ?", e);
+                    if !self.ask_yes_no_default("Éditeur Externe", &question, false) {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Suspend le TUI, ouvre `initial` dans l'éditeur externe (fichier `file_name`) et renvoie
+    /// le texte enregistré
+    fn edit_in_external_editor(&mut self, initial: &str, file_name: &str) -> Option<String> {
+        let visual = std::env::var("VISUAL").ok();
+        let editor = std::env::var("EDITOR").ok();
+        let candidates = editor_candidates(self.tui_config.editor.as_deref(), visual.as_deref(), editor.as_deref());
+        let result = RawModeGuard::suspend(|| run_external_editor(initial, file_name, &candidates));
+        self.ui.hide_cursor();
+        self.needs_full_redraw = true;
+        match result {
+            Ok(text) => Some(text),
+            Err(e) => {
+                self.show_error_message("Éditeur Externe", &e);
+                None
+            }
+        }
+    }
+
+    /// Affiche la configuration réseau
+    fn show_network_config(&mut self) {
+        self.ui.clear_screen();
+        self.ui.draw_header("Configuration Réseau");
+
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let mut y = box_y + 3;
+
+        self.ui.set_color(Color::Fg);
+        self.ui.draw_text(box_x + 2, y, "Services réseau:");
+        y += 2;
+
+        let services = vec![
+            ("DHCP", "Port 67"),
+            ("DNS", "Port 53"),
+            ("TFTP", "Port 69"),
+            ("HTTP", "Port 8080"),
+            ("NBD", "Port 10809"),
+        ];
+
+        for (service, port) in services {
+            self.ui.set_color(Color::Info);
+            self.ui.draw_text(box_x + 4, y, &format!("{}: {}", service, port));
+            y += 1;
+        }
+
+        y += 2;
+        self.ui.set_color(Color::Reset);
+        self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
+        let _ = self.input_reader.read_key();
+    }
+
+    /// Affiche la configuration de sécurité
+    fn show_security_config(&mut self) {
         self.ui.clear_screen();
         self.ui.draw_header("Configuration Sécurité");
 
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 2;
 
-        let api_client = APIClient::new("http://localhost:8080".to_string());
+        let api_client = self.api_client();
 
         self.ui.set_color(Color::Info);
         self.ui.draw_text(box_x + 2, y, "Chargement des métriques de sécurité...");
@@ -1441,7 +2589,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("✗ Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ Erreur: {}", e), usize::MAX);
             }
         }
 
@@ -1451,13 +2599,27 @@ impl MainApp {
         let _ = self.input_reader.read_key();
     }
 
+    /// Suit les journaux du service RMDB selon le système d'init (journal systemd ou fichier)
+    fn follow_service_logs(&mut self) {
+        let (source, cmd) = if self.capabilities.has_systemctl {
+            ("journal systemd", format!("journalctl -u {} -n 50 -f --no-pager", RMDB_SERVICE_NAME))
+        } else if self.capabilities.has_rc_service {
+            // OpenRC redirige la sortie du service vers son fichier de log, sinon vers syslog
+            let log = if std::path::Path::new(RMDB_LOG_PATH).exists() { RMDB_LOG_PATH } else { "/var/log/messages" };
+            ("OpenRC", format!("tail -n 50 -F {}", log))
+        } else {
+            ("fichier", format!("tail -n 50 -F {}", RMDB_LOG_PATH))
+        };
+        self.follow_command(&format!("Journaux {} en direct ({})", RMDB_SERVICE_NAME, source), &cmd);
+    }
+
     fn show_logs(&mut self) {
         self.ui.clear_screen();
         self.ui.draw_header("Journaux RMDB");
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
-        let log_path = "/var/log/rmdbd.log";
+        let log_path = RMDB_LOG_PATH;
         let cmd = format!("tail -20 {} 2>/dev/null || echo 'Fichier de log non trouvé'", log_path);
         let output = self.executor.run_shell(&cmd, false);
         let content = output.map(|o| o.stdout).unwrap_or_else(|_| "Erreur lors de la lecture des logs".to_string());
@@ -1480,7 +2642,7 @@ impl MainApp {
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 3;
 
-        let api_client = APIClient::new("http://localhost:8080".to_string());
+        let api_client = self.api_client();
 
         // Vérifier les problèmes de réparation
         self.ui.set_color(Color::Info);
@@ -1516,7 +2678,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("✗ Erreur lors de la vérification: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ Erreur lors de la vérification: {}", e), usize::MAX);
             }
         }
 
@@ -1526,26 +2688,94 @@ impl MainApp {
         let _ = self.input_reader.read_key();
     }
 
+    /// Ouvre le journal du dernier déploiement, puis propose les journaux plus anciens
+    fn show_deployment_logs(&mut self) {
+        let logs = DeploymentLogger::list_logs();
+        if logs.is_empty() {
+            self.show_message("Journaux de déploiement", "Aucun journal de déploiement trouvé (/var/log/rmdb ou ./logs).");
+            return;
+        }
+
+        let mut selected = 0;
+        let mut min_level = self.log_level();
+        let mut open = true;
+        loop {
+            if open {
+                self.view_deployment_log(&logs[selected], min_level);
+            }
+
+            let title = format!("Journaux de déploiement ({}) - niveau ≥ {}", logs.len(), min_level.as_str());
+            let outcome = SelectList::new(&title, &logs, |path| {
+                let date = DeploymentLogger::log_timestamp(path)
+                    .and_then(|ts| chrono::DateTime::from_timestamp(ts as i64, 0))
+                    .map(|dt| dt.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M:%S").to_string())
+                    .unwrap_or_else(|| "-".to_string());
+                format!("{}  {}", date, path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default())
+            })
+                .with_detail(|path| path.display().to_string())
+                .with_hint("Flèches: Naviguer | Entrée: Ouvrir | N: Niveau | Q: Retour")
+                .with_action_keys(&['n'])
+                .with_selected(selected)
+                .run(&self.ui, &self.input_reader);
+            match outcome {
+                SelectOutcome::Selected(index) => {
+                    selected = index;
+                    open = true;
+                }
+                SelectOutcome::Action(_, index) => {
+                    // Niveau minimal suivant : DEBUG → INFO → WARN → ERROR → DEBUG
+                    let next = Level::ALL.iter().position(|l| *l == min_level).map_or(0, |i| (i + 1) % Level::ALL.len());
+                    min_level = Level::ALL[next];
+                    selected = index;
+                    open = false;
+                }
+                SelectOutcome::Cancelled => return,
+            }
+        }
+    }
+
+    /// Affiche un journal en masquant les lignes sous `min_level`
+    ///
+    /// Les lignes sans étiquette de niveau suivent le niveau de la ligne précédente.
+    fn view_deployment_log(&mut self, path: &std::path::Path, min_level: Level) {
+        let title = format!("Journal {} (≥ {})", path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default(), min_level.as_str());
+        let mut viewer = TextViewer::new(&title);
+        match std::fs::read_to_string(path) {
+            Ok(content) => {
+                let mut level = Level::Info;
+                for line in content.lines() {
+                    level = Level::of_line(line).unwrap_or(level);
+                    if level < min_level {
+                        continue;
+                    }
+                    let color = match level {
+                        Level::Error => Color::Error,
+                        Level::Warn => Color::Warning,
+                        _ => Color::Fg,
+                    };
+                    viewer.push(line, color);
+                }
+                if viewer.is_empty() {
+                    viewer.push(format!("(aucune ligne de niveau ≥ {})", min_level.as_str()), Color::Info);
+                }
+            }
+            Err(e) => viewer.push(format!("Impossible de lire {}: {}", path.display(), e), Color::Error),
+        }
+        viewer.run(&self.ui, &self.input_reader);
+    }
+
     fn show_system_info(&mut self) {
         self.ui.clear_screen();
         self.ui.draw_header("Informations Système");
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
-        let commands = vec![
-            ("Hostname", "hostname"),
-            ("Uptime", "uptime"),
-            ("CPU", "nproc"),
-            ("Memory", "free -h | head -2"),
-        ];
-
-        for (label, cmd) in commands {
-            let output = self.executor.run_shell(cmd, false);
-            let value = output.map(|o| o.stdout.trim().to_string()).unwrap_or_else(|_| "N/A".to_string());
+        let info = SystemInfo::collect(&self.executor);
+        for (label, value) in info.rows() {
             self.ui.set_color(Color::Warning);
             self.ui.draw_text(box_x + 2, y, &format!("{}: ", label));
             self.ui.set_color(Color::Fg);
-            self.ui.draw_text(box_x + 12, y, &value);
+            self.ui.draw_text_clipped(box_x + 12, y, &value, usize::MAX);
             y += 1;
         }
 
@@ -1554,6 +2784,19 @@ impl MainApp {
         let _ = self.input_reader.read_key();
     }
 
+    /// Raccourci du menu principal : ouvre la catégorie (ou lance l'action) depuis n'importe quel menu
+    ///
+    /// Faux si la touche n'est le raccourci d'aucune catégorie.
+    fn jump_to_category(&mut self, key: char) -> bool {
+        let Some(item) = get_main_menu().into_iter().find(|m| m.hotkey == Some(key)) else {
+            return false;
+        };
+        self.return_to_main_menu();
+        self.selected_menu = item.id;
+        self.execute_menu();
+        true
+    }
+
     fn return_to_main_menu(&mut self) {
         let menu = get_main_menu();
         self.menu_items = menu.iter().map(|m| m.label).collect();
@@ -1562,6 +2805,22 @@ impl MainApp {
         self.menu_offset = 0;
     }
 
+    /// Pied des écrans alimentés par l'API : `true` si R est pressé (relancer le chargement)
+    fn read_refresh_key(&mut self, x: u16, y: u16) -> bool {
+        self.ui.set_color(Color::Reset);
+        self.ui.draw_text(x, y, "R: Actualiser | Autre touche: Continuer");
+        matches!(self.input_reader.read_key(), Ok(Key::Char('r')) | Ok(Key::Char('R')))
+    }
+
+    /// Indicateur affiché au-dessus de l'aide d'une liste pendant son rechargement
+    fn show_refreshing(&self) {
+        let (box_x, _, _, box_h) = self.ui.get_box_dimensions();
+        self.ui.clear_line(box_h.saturating_sub(3));
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, box_h.saturating_sub(3), "Actualisation...");
+        self.ui.set_color(Color::Reset);
+    }
+
     fn show_message(&mut self, title: &str, message: &str) {
         self.ui.clear_screen();
         self.ui.draw_header(title);
@@ -1578,42 +2837,321 @@ impl MainApp {
         self.ui.draw_header(title);
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         self.ui.set_color(Color::Error);
-        self.ui.draw_text(box_x + 2, box_y + 5, message);
+        self.ui.draw_text_clipped(box_x + 2, box_y + 5, message, usize::MAX);
         self.ui.set_color(Color::Reset);
         self.ui.draw_text(box_x + 2, box_y + 7, "Appuyez sur une touche pour continuer...");
         let _ = self.input_reader.read_key();
     }
 
-    fn show_terminal_size_warning(&mut self) {
+    /// Appelle l'API ; sur une erreur transitoire, propose de réessayer (R) ou de revenir (Q)
+    ///
+    /// Retourne `None` si l'utilisateur abandonne, si l'erreur n'est pas transitoire
+    /// ou après `API_MAX_ATTEMPTS` échecs (l'erreur est alors affichée).
+    fn with_retry_ui<T>(&mut self, context: &str, fetch: impl Fn() -> Result<T, APIError>) -> Option<T> {
+        let mut attempt = 1;
+        loop {
+            match fetch() {
+                Ok(value) => return Some(value),
+                Err(e) if e.is_transient() && attempt < API_MAX_ATTEMPTS => {
+                    if !self.ask_api_retry(context, &e, attempt) {
+                        return None;
+                    }
+                    attempt += 1;
+                }
+                Err(e) if matches!(e.cause(), APIError::NotFound(_)) => {
+                    self.show_api_error("Introuvable", &format!("{}: élément introuvable sur le serveur (404).", context), context, &e);
+                    return None;
+                }
+                Err(e) if matches!(e.cause(), APIError::Unauthorized(_)) => {
+                    self.show_api_error("Authentification requise", &format!("{}: le serveur RMDB refuse l'accès (401/403).", context), context, &e);
+                    return None;
+                }
+                Err(e) => {
+                    self.show_api_error("Erreur", &format!("{}: {}", context, e), context, &e);
+                    return None;
+                }
+            }
+        }
+    }
+
+    /// Rapport de l'erreur API pour un ticket, avec la requête en cause
+    fn api_error_report(&self, context: &str, error: &APIError) -> String {
+        error_report(context, error, &self.tui_config.api_url())
+    }
+
+    /// Erreur API définitive : C copie le rapport (presse-papiers OSC 52), toute autre touche revient
+    fn show_api_error(&mut self, title: &str, message: &str, context: &str, error: &APIError) {
         self.ui.clear_screen();
-        self.ui.draw_header("Taille du Terminal");
+        self.ui.draw_header(title);
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
-        self.ui.set_color(Color::Warning);
-        self.ui.draw_text(box_x + 2, box_y + 5, &format!("Terminal trop petit: {}x{}", self.ui.terminal.width(), self.ui.terminal.height()));
-        self.ui.draw_text(box_x + 2, box_y + 6, &format!("Minimum requis: {}x{}", Self::MIN_WIDTH, Self::MIN_HEIGHT));
+        self.ui.set_color(Color::Error);
+        self.ui.draw_text_clipped(box_x + 2, box_y + 5, message, usize::MAX);
         self.ui.set_color(Color::Reset);
+        self.ui.draw_text(box_x + 2, box_y + 7, "C: Copier le rapport d'erreur | Autre touche: Continuer");
+        while let Ok(Key::Char('c' | 'C')) = self.input_reader.read_key() {
+            self.ui.copy_to_clipboard(&self.api_error_report(context, error));
+            self.ui.clear_line(box_y + 9);
+            self.ui.set_color(Color::Success);
+            self.ui.draw_text(box_x + 2, box_y + 9, "✓ Rapport copié si le terminal accepte OSC 52");
+            self.ui.set_color(Color::Reset);
+        }
     }
 
-    fn ask_yes_no(&mut self, title: &str, question: &str) -> bool {
+    /// Écran d'erreur API avec R: Réessayer | C: Copier le rapport | Q: Retour (vrai pour réessayer)
+    fn ask_api_retry(&mut self, context: &str, error: &APIError, attempt: u32) -> bool {
         self.ui.clear_screen();
-        self.ui.draw_header(title);
-        let (box_x, box_y, _box_w, box_h) = self.ui.get_box_dimensions();
+        self.ui.draw_header("Erreur API");
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
-        
+
+        self.ui.set_color(Color::Error);
+        self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ {}: {}", context, error), usize::MAX);
+        y += 2;
         self.ui.set_color(Color::Fg);
-        self.ui.draw_text(box_x + 2, y, question);
+        self.ui.draw_text(box_x + 4, y, "Assurez-vous que le serveur RMDB est démarré");
+        y += 1;
+        self.ui.draw_text_clipped(box_x + 4, y, &format!("et accessible sur {}", self.tui_config.api_url()), usize::MAX);
         y += 2;
-        
-        let mut selected = 0; // 0 = Oui, 1 = Non
-        let options = vec!["Oui", "Non"];
-        
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, y, &format!("Tentative {}/{}", attempt, API_MAX_ATTEMPTS));
+        y += 2;
+        self.ui.set_color(Color::Reset);
+        self.ui.draw_text(box_x + 2, y, "R: Réessayer | C: Copier le rapport d'erreur | Q: Retour");
+
         loop {
-            // Effacer la zone des options
-            for i in 0..options.len() {
-                self.ui.clear_line(y + i as u16);
-            }
-            
-            // Afficher les options
+            match self.input_reader.read_key() {
+                Ok(Key::Char('r')) | Ok(Key::Char('R')) => return true,
+                Ok(Key::Char('c')) | Ok(Key::Char('C')) => {
+                    self.ui.copy_to_clipboard(&self.api_error_report(context, error));
+                    self.ui.clear_line(y + 2);
+                    self.ui.set_color(Color::Success);
+                    self.ui.draw_text(box_x + 2, y + 2, "✓ Rapport copié si le terminal accepte OSC 52");
+                    self.ui.set_color(Color::Reset);
+                }
+                Ok(Key::Quit) | Ok(Key::Interrupt) | Err(_) => return false,
+                _ => {}
+            }
+        }
+    }
+
+    /// Vérifie rapidement que le serveur RMDB répond avant un écran alimenté par l'API
+    ///
+    /// Sans réponse, affiche immédiatement les consignes (R: Réessayer | Q: Retour) au lieu
+    /// d'attendre l'échec de chaque requête. Faux si l'utilisateur revient au menu.
+    fn ensure_api_available(&mut self) -> bool {
+        let api_client = self.api_client();
+        loop {
+            let Err(error) = api_client.ping() else {
+                return true;
+            };
+            self.ui.clear_screen();
+            self.ui.draw_header("Serveur non disponible");
+            let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+            let mut y = box_y + 5;
+
+            self.ui.set_color(Color::Error);
+            self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ Le serveur RMDB ne répond pas: {}", error), usize::MAX);
+            y += 2;
+            self.ui.set_color(Color::Fg);
+            self.ui.draw_text(box_x + 4, y, "Assurez-vous que le serveur RMDB est démarré");
+            y += 1;
+            self.ui.draw_text_clipped(box_x + 4, y, &format!("et accessible sur {}", self.tui_config.api_url()), usize::MAX);
+            y += 1;
+            self.ui.draw_text(box_x + 4, y, "(démarrez RMDB dans le container, ou définissez RMDB_API_URL)");
+            y += 2;
+            self.ui.set_color(Color::Reset);
+            self.ui.draw_text(box_x + 2, y, "R: Réessayer | Q: Retour");
+
+            loop {
+                match self.input_reader.read_key() {
+                    Ok(Key::Char('r')) | Ok(Key::Char('R')) => break,
+                    Ok(Key::Quit) | Ok(Key::Interrupt) | Err(_) => return false,
+                    _ => {}
+                }
+            }
+            self.show_refreshing();
+        }
+    }
+
+    fn show_terminal_size_warning(&mut self) {
+        self.ui.clear_screen();
+        self.ui.draw_header("Taille du Terminal");
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        self.ui.set_color(Color::Warning);
+        self.ui.draw_text(box_x + 2, box_y + 5, &format!("Terminal trop petit: {}x{}", self.ui.terminal.width(), self.ui.terminal.height()));
+        self.ui.draw_text(box_x + 2, box_y + 6, &format!("Minimum requis: {}x{} (affichage complet: {}x{})",
+            self.ui.terminal.compact_min_width, self.ui.terminal.compact_min_height,
+            self.ui.terminal.min_width, self.ui.terminal.min_height));
+        self.ui.set_color(Color::Reset);
+    }
+
+    /// Attend pendant une opération longue ; Échap propose de l'interrompre (`cancel_token`)
+    ///
+    /// Retourne `false` si l'utilisateur a confirmé l'interruption.
+    fn operation_pause(&mut self, duration: Duration) -> bool {
+        let deadline = std::time::Instant::now() + duration;
+        loop {
+            if self.executor.cancel_requested() {
+                return false;
+            }
+            if self.abort_declined.swap(false, Ordering::SeqCst) {
+                // La question a remplacé le pied de page : rappeler l'opération en cours
+                self.redraw_operation_log();
+            }
+            let remaining = deadline.saturating_duration_since(std::time::Instant::now());
+            if remaining.is_zero() {
+                return true;
+            }
+            std::thread::sleep(remaining.min(Duration::from_millis(100)));
+        }
+    }
+
+    /// Ouvre le journal d'opération : les lignes suivantes défilent sous l'étape en cours
+    fn begin_operation_log(&mut self, title: &'static str) {
+        self.operation_log = Some(OperationLog {
+            title,
+            step: String::new(),
+            lines: Vec::new(),
+            color: Color::Fg,
+        });
+        self.ui.clear_screen();
+        self.redraw_operation_log();
+    }
+
+    /// Redessine le journal d'opération ; efface aussi ce qu'un écran intermédiaire a laissé
+    fn redraw_operation_log(&mut self) {
+        let Some(log) = self.operation_log.as_ref() else { return };
+        let (box_x, box_y, _, box_h) = self.ui.get_box_dimensions();
+        let step_y = self.ui.menu_top();
+        let panel_y = step_y + 2;
+        let footer_y = (box_y + box_h).saturating_sub(2);
+
+        for row in (box_y..panel_y).chain(footer_y..box_y + box_h) {
+            self.ui.clear_line(row);
+        }
+        self.ui.draw_header(log.title);
+        self.ui.set_color(Color::Accent);
+        self.ui.draw_text_clipped(box_x + 2, step_y, &log.step, usize::MAX);
+        self.ui.draw_log_panel(box_x + 2, panel_y, footer_y.saturating_sub(panel_y + 1), &log.lines);
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, footer_y, "Échap: Interrompre");
+        self.ui.set_color(Color::Reset);
+    }
+
+    /// Couleur des lignes suivantes du journal
+    fn log_color(&mut self, color: Color) {
+        if let Some(log) = self.operation_log.as_mut() {
+            log.color = color;
+        }
+    }
+
+    fn log_line(&mut self, indent: usize, text: &str) {
+        if let Some(log) = self.operation_log.as_mut() {
+            log.lines.push((format!("{}{}", " ".repeat(indent), text), log.color));
+        }
+        self.redraw_operation_log();
+    }
+
+    fn log_blank(&mut self) {
+        if let Some(log) = self.operation_log.as_mut() {
+            log.lines.push((String::new(), Color::Fg));
+        }
+    }
+
+    /// Après un échec de création ou de démarrage : signale les refus SELinux/AppArmor récents
+    fn log_mac_policy_hint(&mut self) {
+        let policy = LXCDeployment::check_mac_policy(&self.executor);
+        let denials = LXCDeployment::mac_denials(&self.executor, policy, &self.default_container);
+        let hint = mac_policy_hint(policy, &denials);
+        if hint.is_empty() {
+            return;
+        }
+        self.log_blank();
+        self.log_color(if denials.is_empty() { Color::Info } else { Color::Warning });
+        self.log_line(0, &hint[0]);
+        self.log_color(Color::Fg);
+        for denial in &denials {
+            self.log_line(2, denial);
+        }
+        self.log_color(Color::Info);
+        for line in &hint[1..] {
+            self.log_line(0, line);
+        }
+        self.log_blank();
+    }
+
+    /// Met à jour la ligne de progression fixe au-dessus du journal
+    fn log_step(&mut self, step: usize, total: usize, label: &str) {
+        if let Some(log) = self.operation_log.as_mut() {
+            log.step = format!("Étape {}/{} : {}", step, total, label);
+        }
+        self.redraw_operation_log();
+    }
+
+    /// Termine le journal : attend une touche puis le referme
+    fn log_wait_key(&mut self) {
+        let (box_x, box_y, _, box_h) = self.ui.get_box_dimensions();
+        let footer_y = (box_y + box_h).saturating_sub(2);
+        self.ui.clear_line(footer_y);
+        self.ui.set_color(Color::Fg);
+        self.ui.draw_text(box_x + 2, footer_y, "Appuyez sur une touche pour continuer...");
+        self.ui.set_color(Color::Reset);
+        let _ = self.input_reader.read_key();
+        self.operation_log = None;
+    }
+
+    /// Vérifie l'espace libre avant une opération ; demande confirmation s'il manque de la place
+    ///
+    /// Retourne `true` si l'espace suffit (ou n'a pas pu être mesuré) ou si l'utilisateur passe outre.
+    fn confirm_free_space(&mut self, title: &str, needs: &[(&str, u64)]) -> bool {
+        let low: Vec<String> = check_free_space(&self.executor, needs)
+            .into_iter()
+            .filter(FreeSpace::is_low)
+            .map(|space| format!("{} : {} disponibles, environ {} nécessaires", space.mount, format_size(space.available), format_size(space.required)))
+            .collect();
+        if low.is_empty() {
+            return true;
+        }
+        let question = format!("Espace disque faible — {}. L'opération risque d'échouer en cours de route. Continuer quand même ?", low.join(" ; "));
+        self.ask_yes_no_default(title, &question, true)
+    }
+
+    fn show_operation_aborted(&mut self) {
+        self.show_error_message(
+            "Opération interrompue",
+            "Container peut-être partiellement créé : vérifiez-le depuis le menu Containers.",
+        );
+    }
+
+    fn ask_yes_no(&mut self, title: &str, question: &str) -> bool {
+        self.ask_yes_no_default(title, question, false)
+    }
+
+    /// Comme `ask_yes_no`, mais présélectionne "Non" si `default_no` est vrai
+    /// (confirmations destructives : un Entrée réflexe annule)
+    fn ask_yes_no_default(&mut self, title: &str, question: &str, default_no: bool) -> bool {
+        self.ui.clear_screen();
+        self.ui.draw_header(title);
+        let (box_x, box_y, _box_w, box_h) = self.ui.get_box_dimensions();
+        let mut y = box_y + 5;
+        
+        self.ui.set_color(Color::Fg);
+        for line in question.lines() {
+            self.ui.draw_text(box_x + 2, y, line);
+            y += 1;
+        }
+        y += 1;
+        
+        let mut selected = usize::from(default_no); // 0 = Oui, 1 = Non
+        let options = vec!["Oui", "Non"];
+        
+        loop {
+            // Effacer la zone des options
+            for i in 0..options.len() {
+                self.ui.clear_line(y + i as u16);
+            }
+            
+            // Afficher les options
             for (i, option) in options.iter().enumerate() {
                 let selected_char = if i == selected { "▶" } else { " " };
                 self.ui.set_color(if i == selected { Color::Selection } else { Color::Fg });
@@ -1645,7 +3183,7 @@ impl MainApp {
                 Ok(Key::Enter) => {
                     return selected == 0; // true si "Oui", false si "Non"
                 }
-                Ok(Key::Quit) => {
+                Ok(Key::Quit) | Ok(Key::Interrupt) => {
                     return false;
                 }
                 _ => {}
@@ -1667,10 +3205,16 @@ impl MainApp {
         }
         y += 2;
         
+        if !self.distribution.has_known_lxc_packages() {
+            self.ui.set_color(Color::Warning);
+            self.ui.draw_text(box_x + 2, y, "⚠ Distribution non reconnue : liste de paquets générique utilisée.");
+            y += 2;
+        }
+
         // Préparer les commandes d'installation
-        let packages = vec!["lxc-templates"];
-        let update_cmd = format!("sudo {}", self.distribution.update_command());
-        let install_cmd = format!("sudo {}", self.distribution.install_command(&packages.iter().map(|s| *s).collect::<Vec<_>>()));
+        let packages = self.distribution.lxc_template_packages();
+        let update_cmd = self.distribution.update_command();
+        let install_cmds = self.distribution.lxc_install_commands(&packages);
         
         // Mettre à jour les dépôts d'abord
         self.ui.set_color(Color::Info);
@@ -1685,7 +3229,7 @@ impl MainApp {
         self.ui.set_color(Color::Reset);
         
         // Installer les templates
-        let output = self.executor.run_shell(&install_cmd, true);
+        let output = self.run_admin_commands(&install_cmds);
         
         if output.is_ok() && output.as_ref().unwrap().exit_code == Some(0) {
             self.ui.set_color(Color::Success);
@@ -1725,10 +3269,16 @@ impl MainApp {
         }
         y += 2;
         
+        if !self.distribution.has_known_lxc_packages() {
+            self.ui.set_color(Color::Warning);
+            self.ui.draw_text(box_x + 2, y, "⚠ Distribution non reconnue : liste de paquets générique utilisée.");
+            y += 2;
+        }
+
         // Préparer les commandes d'installation
-        let packages = vec!["lxc", "lxc-templates"];
-        let update_cmd = format!("sudo {}", self.distribution.update_command());
-        let install_cmd = format!("sudo {}", self.distribution.install_command(&packages.iter().map(|s| *s).collect::<Vec<_>>()));
+        let packages = self.distribution.lxc_packages();
+        let update_cmd = self.distribution.update_command();
+        let install_cmds = self.distribution.lxc_install_commands(&packages);
         
         // Mettre à jour les dépôts d'abord
         self.ui.set_color(Color::Info);
@@ -1743,7 +3293,7 @@ impl MainApp {
         self.ui.set_color(Color::Reset);
         
         // Installer LXC
-        let output = self.executor.run_shell(&install_cmd, true);
+        let output = self.run_admin_commands(&install_cmds);
         
         if output.is_ok() && output.as_ref().unwrap().exit_code == Some(0) {
             self.ui.set_color(Color::Success);
@@ -1768,22 +3318,139 @@ impl MainApp {
         }
     }
 
+    /// Lance les commandes avec sudo, dans l'ordre, et s'arrête à la première en échec
+    fn run_admin_commands(&self, commands: &[String]) -> Result<CommandOutput, ExecError> {
+        let mut last = Ok(CommandOutput { exit_code: Some(0), stdout: String::new(), stderr: String::new() });
+        for cmd in commands {
+            last = self.executor.run_shell(cmd, true);
+            if !matches!(last, Ok(ref output) if output.exit_code == Some(0)) {
+                break;
+            }
+        }
+        last
+    }
+
     fn deploy_lxc_container(&mut self) {
-        self.ui.clear_screen();
-        self.ui.draw_header("Déploiement Container LXC Alpine");
-        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
-        let mut y = box_y + 5;
+        let container_name = self.default_container.clone();
+        self.forget_container_init(&container_name);
+        let existed = LXCDeployment::new(container_name.clone(), String::new())
+            .check_container_exists_with_executor(&self.executor);
+
+        let token = self.cancel_token();
+        self.executor.set_cancel_token(Some(token.clone()));
+        let started_at = chrono::Local::now();
+        let started = std::time::Instant::now();
+        let result = match self.deploy_lxc_container_steps(&container_name, started) {
+            DeployResult::Failed if token.is_cancelled() => DeployResult::Cancelled,
+            result => result,
+        };
+        self.executor.set_cancel_token(None);
+        self.operation_log = None;
+
+        self.record_history(DeployKind::LxcDeploy, &container_name, started_at, started, result);
+
+        if token.is_cancelled() && !existed {
+            self.rollback_partial_container(&container_name);
+        }
+    }
+
+    /// Jeton d'annulation des opérations longues : Échap pendant une commande demande
+    /// confirmation, puis arrête la commande en cours et les suivantes
+    fn cancel_token(&self) -> CancelToken {
+        let asciify = self.ui.asciify;
+        let abort_declined = Arc::clone(&self.abort_declined);
+        CancelToken::new().with_request_check(move || {
+            if !escape_pressed() {
+                return false;
+            }
+            let mut ui = UI::new();
+            ui.asciify = asciify;
+            let (box_x, box_y, _, box_h) = ui.get_box_dimensions();
+            let row = (box_y + box_h).saturating_sub(2);
+            ui.clear_line(row);
+            ui.set_color(Color::Warning);
+            ui.draw_text(box_x + 2, row, "⚠ Interrompre l'opération ? Elle peut laisser un container partiel (o/N)");
+            ui.set_color(Color::Reset);
+            let confirmed = matches!(InputReader::new().read_key(), Ok(Key::Char('o' | 'O')));
+            ui.clear_line(row);
+            abort_declined.store(!confirmed, Ordering::SeqCst);
+            confirmed
+        })
+    }
+
+    /// Propose les ponts détectés pour le réseau du container ; `None` garde le réseau LXC par défaut
+    fn select_bridge(&mut self) -> Option<String> {
+        let bridges = net::list_bridges();
+        if bridges.is_empty() {
+            return None;
+        }
+        let mut choices = vec!["Réseau LXC par défaut".to_string()];
+        choices.extend(bridges.iter().map(|b| format!("Pont {}", b)));
+        let index = SelectList::new("Réseau du container", &choices, |c| c.clone())
+            .with_prompt("Pont auquel rattacher le container (ex: br0 pour le PXE sur le LAN) :")
+            .with_hint("Flèches: Naviguer | Entrée: Choisir | Q: Réseau par défaut")
+            .select(&self.ui, &self.input_reader)?;
+        bridges.get(index.checked_sub(1)?).cloned()
+    }
+
+    /// Propose de limiter le débit du téléchargement du template, `configured` (Ko/s) présélectionné ; `None` : sans limite
+    fn select_rate_limit(&mut self, configured: u32) -> Option<u32> {
+        let mut limits = vec![256, 512, 1024, 2048, 5120];
+        if !limits.contains(&configured) {
+            limits.push(configured);
+            limits.sort_unstable();
+        }
+        let mut choices = vec!["Sans limite".to_string()];
+        choices.extend(limits.iter().map(|kbps| format!("{} Ko/s", kbps)));
+        let selected = limits.iter().position(|&kbps| kbps == configured).map_or(0, |i| i + 1);
+        let index = SelectList::new("Débit du téléchargement", &choices, |c| c.clone())
+            .with_prompt("Limiter le débit du téléchargement du template (nécessite trickle) :")
+            .with_hint("Flèches: Naviguer | Entrée: Choisir | Q: Sans limite")
+            .with_selected(selected)
+            .select(&self.ui, &self.input_reader)?;
+        limits.get(index.checked_sub(1)?).copied()
+    }
+
+    /// Après une annulation, propose de supprimer le container laissé à moitié créé
+    fn rollback_partial_container(&mut self, name: &str) {
+        let deploy = LXCDeployment::new(name.to_string(), String::new());
+        if !deploy.check_container_exists_with_executor(&self.executor) {
+            self.show_message("Opération annulée", "Opération annulée. Aucun container n'a été laissé sur le système.");
+            return;
+        }
+        let question = format!("Opération annulée : le container '{}' est peut-être incomplet.\nVoulez-vous le supprimer ?", name);
+        if !self.ask_yes_no("Opération annulée", &question) {
+            return;
+        }
+        self.forget_container_init(name);
+        match LXCDeployment::destroy_container_by_name(&self.executor, name) {
+            Ok(output) if output.exit_code == Some(0) => {
+                self.show_message("Opération annulée", &format!("✓ Container '{}' supprimé.", name));
+            }
+            Ok(output) => {
+                self.show_error_message("Opération annulée", &format!("✗ Suppression impossible: {}", output.stderr.trim()));
+            }
+            Err(e) => {
+                self.show_error_message("Opération annulée", &format!("✗ Suppression impossible: {}", e));
+            }
+        }
+    }
+
+    /// Étapes du déploiement de `container_name` ; `Failed` aussi pour une opération interrompue
+    fn deploy_lxc_container_steps(&mut self, container_name: &str, started: std::time::Instant) -> DeployResult {
+        let mut result = DeployResult::Failed;
+        self.begin_operation_log("Déploiement Container LXC Alpine");
 
         // Vérifier LXC
-        self.ui.set_color(Color::Info);
-        self.ui.draw_text(box_x + 2, y, "Vérification de LXC...");
-        y += 1;
+        self.log_step(1, DEPLOY_STEPS, "Vérification de LXC");
+        self.log_color(Color::Info);
+        self.log_line(0, "Vérification de LXC...");
 
         // Créer le logger de déploiement
-        let logger = match DeploymentLogger::new() {
+        let logger = match self.deployment_logger() {
             Ok(l) => {
                 l.info("=== Début du déploiement LXC ===");
-                l.info("Container: rmdb, Alpine: 3.20");
+                l.info(&format!("Container: {}, Alpine: 3.20", container_name));
                 Some(l)
             }
             Err(e) => {
@@ -1792,18 +3459,23 @@ impl MainApp {
             }
         };
         
-        let lxc_deploy = if let Some(logger) = logger {
-            LXCDeployment::new("rmdb".to_string(), "3.20".to_string())
+        let timings = self.tui_config.deploy_timings();
+        let mut lxc_deploy = if let Some(logger) = logger {
+            LXCDeployment::new(container_name.to_string(), "3.20".to_string())
                 .with_logger(logger)
         } else {
-            LXCDeployment::new("rmdb".to_string(), "3.20".to_string())
-        };
+            LXCDeployment::new(container_name.to_string(), "3.20".to_string())
+        }
+        .with_timings(timings);
+        // Miroir interne pour les réseaux sans accès au miroir Alpine par défaut
+        if let Some(mirror) = self.tui_config.alpine_mirror.clone() {
+            lxc_deploy = lxc_deploy.with_mirror(mirror);
+        }
         
         if !lxc_deploy.check_lxc_installed() {
-            self.ui.set_color(Color::Warning);
-            self.ui.draw_text(box_x + 2, y, "LXC n'est pas installé.");
-            y += 2;
-            self.ui.set_color(Color::Reset);
+            self.log_color(Color::Warning);
+            self.log_line(0, "LXC n'est pas installé.");
+            self.log_blank();
             
             // Proposer l'installation
             let install_lxc = self.ask_yes_no(
@@ -1813,32 +3485,112 @@ impl MainApp {
             
             if install_lxc {
                 if !self.install_lxc() {
-                    return; // Échec de l'installation
+                    return result; // Échec de l'installation
                 }
                 // Vérifier à nouveau après installation
                 if !lxc_deploy.check_lxc_installed() {
                     self.show_error_message("Erreur", "LXC n'a pas pu être installé ou détecté.");
-                    return;
+                    return result;
                 }
             } else {
                 // L'utilisateur a refusé l'installation
-                return;
+                return result;
             }
         }
 
-        self.ui.set_color(Color::Success);
-        self.ui.draw_text(box_x + 2, y, "LXC est installé.");
-        y += 2;
+        self.log_color(Color::Success);
+        self.log_line(0, "LXC est installé.");
+        let lxc_version = LXCDeployment::lxc_version(&self.executor);
+        lxc_deploy = lxc_deploy.with_lxc_version(lxc_version);
+        match lxc_version {
+            Some(version) if is_unsupported_lxc_version(version) => {
+                self.log_color(Color::Warning);
+                self.log_line(0, &format!("⚠ LXC {}.{} n'est pas pris en charge.", version.0, version.1));
+                let (min_major, min_minor) = LXC_MIN_SUPPORTED_VERSION;
+                let question = format!(
+                    "LXC {}.{} est trop ancien (minimum {}.{}) : la création du container risque d'échouer.\n\
+                     Mettez LXC à jour depuis les paquets de la distribution.\n\nContinuer quand même ?",
+                    version.0, version.1, min_major, min_minor
+                );
+                if !self.ask_yes_no_default("Version de LXC", &question, true) {
+                    return result;
+                }
+                self.redraw_operation_log();
+            }
+            Some(version) => {
+                self.log_color(Color::Fg);
+                self.log_line(0, &format!("Version de LXC: {}.{}", version.0, version.1));
+            }
+            None => {
+                self.log_color(Color::Warning);
+                self.log_line(0, "Version de LXC inconnue (syntaxe actuelle utilisée).");
+            }
+        }
+        if let Some(bridge) = self.select_bridge() {
+            lxc_deploy = lxc_deploy.with_network(bridge, NetMode::Veth);
+        }
+        self.redraw_operation_log();
+        match lxc_deploy.network() {
+            Some((bridge, mode)) => {
+                self.log_color(Color::Fg);
+                self.log_line(0, &format!("Réseau: {} sur le pont {}", mode.config_value(), bridge));
+            }
+            None => {
+                self.log_color(Color::Fg);
+                self.log_line(0, "Réseau: configuration LXC par défaut");
+            }
+        }
+        // Le choix du débit n'est proposé que si une limite est configurée (clé `rate_limit`, RMDB_RATE_LIMIT)
+        let rate_limit = self.tui_config.rate_limit.filter(|&kbps| kbps > 0);
+        if let Some(kbps) = rate_limit.and_then(|configured| self.select_rate_limit(configured)) {
+            if LXCDeployment::rate_limit_available(&self.executor) {
+                lxc_deploy = lxc_deploy.with_rate_limit(kbps);
+                self.log_color(Color::Fg);
+                self.log_line(0, &format!("Téléchargement limité à {} Ko/s", kbps));
+            } else {
+                self.log_color(Color::Warning);
+                self.log_line(0, "⚠ trickle n'est pas installé : le téléchargement ne sera pas limité.");
+                let sudo = if self.capabilities.is_root() { "" } else { "sudo " };
+                let install_hint = format!("  {}{}", sudo, self.distribution.install_command(&["trickle"]));
+                self.log_line(0, &install_hint);
+            }
+        }
+        self.redraw_operation_log();
+        self.log_blank();
 
         // Vérifier les templates LXC
-        self.ui.set_color(Color::Info);
-        self.ui.draw_text(box_x + 2, y, "Vérification des templates LXC...");
-        y += 1;
-        
-        if !lxc_deploy.check_lxc_templates() {
-            self.ui.set_color(Color::Warning);
-            self.ui.draw_text(box_x + 2, y, "Les templates LXC ne sont pas installés.");
-            self.ui.set_color(Color::Reset);
+        self.log_step(2, DEPLOY_STEPS, "Templates LXC");
+        self.log_color(Color::Info);
+        self.log_line(0, "Vérification des sources de téléchargement...");
+        let sources = lxc_deploy.check_template_sources(&self.executor);
+        if sources.is_offline() {
+            // Hors ligne : installer les templates ou télécharger l'image échouerait, on demande un cache local
+            self.log_color(Color::Warning);
+            self.log_line(0, &format!("⚠ Ni le miroir Alpine ({}) ni {} ne répondent.", sources.mirror, LXC_IMAGES_SERVER));
+            self.log_line(0, "Hôte hors ligne : un cache de template local est nécessaire.");
+            match self.prompt_local_template() {
+                Some(dir) => {
+                    self.log_color(Color::Fg);
+                    self.log_line(0, &format!("Template local: {}", dir.display()));
+                    lxc_deploy = lxc_deploy.with_local_template(dir);
+                }
+                None => {
+                    self.log_color(Color::Warning);
+                    self.log_line(0, "Aucun cache local fourni : déploiement annulé.");
+                    self.log_wait_key();
+                    return result;
+                }
+            }
+            self.log_blank();
+        } else {
+            self.log_line(0, "Vérification des templates LXC...");
+        }
+
+        if lxc_deploy.local_template().is_some() {
+            // Le template `local` est fourni par LXC lui-même : rien à installer
+        } else if !lxc_deploy.check_lxc_templates() {
+            self.log_color(Color::Warning);
+            self.log_line(0, "Les templates LXC ne sont pas installés.");
             
             // Proposer l'installation des templates
             let install_templates = self.ask_yes_no(
@@ -1848,21 +3600,26 @@ impl MainApp {
             
             if install_templates {
                 if !self.install_lxc_templates() {
-                    return; // Échec de l'installation
+                    return result; // Échec de l'installation
                 }
                 
                 // Attendre un peu pour que les fichiers soient écrits
-                std::thread::sleep(std::time::Duration::from_secs(1));
+                if !self.operation_pause(timings.recheck()) {
+                    self.show_operation_aborted();
+                    return result;
+                }
                 
                 // Vérifier à nouveau après installation avec plusieurs tentatives
                 let mut templates_ok = false;
-                for attempt in 1..=3 {
+                let attempts = timings.template_detect_attempts();
+                for attempt in 1..=attempts {
                     if lxc_deploy.check_lxc_templates() {
                         templates_ok = true;
                         break;
                     }
-                    if attempt < 3 {
-                        std::thread::sleep(std::time::Duration::from_secs(1));
+                    if attempt < attempts && !self.operation_pause(timings.template_detect_interval()) {
+                        self.show_operation_aborted();
+                        return result;
                     }
                 }
                 
@@ -1870,209 +3627,177 @@ impl MainApp {
                     // Afficher un avertissement mais continuer quand même
                     // Parfois les templates sont installés mais la détection échoue
                     // (surtout sur RHEL où les emplacements peuvent différer)
-                    self.ui.clear_screen();
-                    self.ui.draw_header("Déploiement Container LXC Alpine");
-                    y = box_y + 5;
-                    self.ui.set_color(Color::Warning);
-                    self.ui.draw_text(box_x + 2, y, "Les templates LXC ont été installés.");
-                    self.ui.draw_text(box_x + 2, y + 1, "La détection automatique a échoué, mais nous continuons.");
-                    self.ui.draw_text(box_x + 2, y + 2, "Si la création échoue, les templates peuvent être à un emplacement non standard.");
-                    y += 4;
-                    std::thread::sleep(std::time::Duration::from_secs(2));
+                    self.log_color(Color::Warning);
+                    self.log_line(0, "Les templates LXC ont été installés.");
+                    self.log_line(0, "La détection automatique a échoué, mais nous continuons.");
+                    self.log_line(0, "Si la création échoue, les templates peuvent être à un emplacement non standard.");
+                    self.log_blank();
+                    if !self.operation_pause(std::time::Duration::from_secs(2)) {
+                        self.show_operation_aborted();
+                        return result;
+                    }
                 } else {
                     // Continuer avec la création
-                    self.ui.clear_screen();
-                    self.ui.draw_header("Déploiement Container LXC Alpine");
-                    y = box_y + 5;
-                    self.ui.set_color(Color::Success);
-                    self.ui.draw_text(box_x + 2, y, "Templates LXC installés et détectés.");
-                    y += 2;
+                    self.log_color(Color::Success);
+                    self.log_line(0, "Templates LXC installés et détectés.");
+                    self.log_blank();
                 }
             } else {
                 // L'utilisateur a refusé l'installation
-                return;
+                return result;
             }
         } else {
-            self.ui.set_color(Color::Success);
-            self.ui.draw_text(box_x + 2, y, "Templates LXC disponibles.");
-            y += 2;
+            self.log_color(Color::Success);
+            self.log_line(0, "Templates LXC disponibles.");
+            self.log_blank();
         }
 
         // Vérifier si le container existe (avec executor pour utiliser sudo)
         if lxc_deploy.check_container_exists_with_executor(&self.executor) {
-            self.ui.set_color(Color::Warning);
-            self.ui.draw_text(box_x + 2, y, "Le container 'rmdb' existe déjà.");
-            self.ui.set_color(Color::Reset);
-            self.ui.draw_text(box_x + 2, y + 1, "Appuyez sur une touche pour continuer...");
-            let _ = self.input_reader.read_key();
-            return;
+            self.log_color(Color::Warning);
+            self.log_line(0, &format!("Le container '{}' existe déjà.", container_name));
+            self.log_wait_key();
+            return result;
+        }
+
+        // Un disque plein en cours de création laisserait un container à moitié créé
+        if !self.confirm_free_space("Déploiement LXC", &[("/var/lib/lxc", CONTAINER_SPACE_ESTIMATE)]) {
+            self.redraw_operation_log();
+            self.log_color(Color::Warning);
+            self.log_line(0, "Déploiement annulé : espace disque insuffisant.");
+            self.log_wait_key();
+            return result;
         }
+        self.redraw_operation_log();
 
         // Créer le container
-        self.ui.set_color(Color::Info);
-        self.ui.draw_text(box_x + 2, y, "Création du container Alpine Linux 3.20...");
-        y += 1;
-        self.ui.set_color(Color::Reset);
+        self.log_step(3, DEPLOY_STEPS, "Création du container");
+        self.log_color(Color::Info);
+        self.log_line(0, "Création du container Alpine Linux 3.20...");
 
         match lxc_deploy.create_container(&self.executor) {
             Ok(output) => {
                 if output.exit_code == Some(0) {
-                    self.ui.set_color(Color::Success);
-                    self.ui.draw_text(box_x + 2, y, "Container créé avec succès!");
-                    y += 2;
+                    self.log_color(Color::Success);
+                    self.log_line(0, "Container créé avec succès!");
+                    self.log_blank();
                     
                     // Vérifier immédiatement que le container existe avec diagnostic détaillé
-                    self.ui.set_color(Color::Info);
-                    self.ui.draw_text(box_x + 2, y, "Vérification détaillée de l'existence du container...");
-                    y += 1;
-                    io::stdout().flush().unwrap();
+                    self.log_color(Color::Info);
+                    self.log_line(0, "Vérification détaillée de l'existence du container...");
                     
                     // Attendre un peu pour que le système de fichiers soit à jour
-                    std::thread::sleep(std::time::Duration::from_secs(2));
+                    if !self.operation_pause(timings.settle()) {
+                        self.show_operation_aborted();
+                        return result;
+                    }
                     
                     // Vérification stricte : le container DOIT exister avant de continuer
                     let container_exists = lxc_deploy.check_container_exists_with_executor(&self.executor);
                     if !container_exists {
-                        self.ui.set_color(Color::Error);
-                        self.ui.draw_text(box_x + 2, y, "✗ ERREUR: Le container n'a pas été créé correctement!");
-                        y += 1;
-                        self.ui.set_color(Color::Fg);
-                        self.ui.draw_text(box_x + 2, y, "La commande lxc-create a réussi mais le container n'est pas détectable.");
-                        y += 1;
-                        self.ui.draw_text(box_x + 2, y, "Vérifiez les logs système et les permissions LXC.");
-                        y += 2;
-                        self.ui.set_color(Color::Info);
-                        self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
-                        self.ui.set_color(Color::Reset);
-                        io::stdout().flush().unwrap();
-                        let _ = self.input_reader.read_key();
-                        return;
+                        self.log_color(Color::Error);
+                        self.log_line(0, "✗ ERREUR: Le container n'a pas été créé correctement!");
+                        self.log_color(Color::Fg);
+                        self.log_line(0, "La commande lxc-create a réussi mais le container n'est pas détectable.");
+                        self.log_line(0, "Vérifiez les logs système et les permissions LXC.");
+                        self.log_blank();
+                        self.log_wait_key();
+                        return result;
                     }
                     
                     // Diagnostic détaillé
-                    self.show_container_diagnostic(&lxc_deploy, box_x, &mut y);
+                    self.show_container_diagnostic(&lxc_deploy);
                     
                     // Démarrer le container
-                    self.ui.set_color(Color::Info);
-                    self.ui.draw_text(box_x + 2, y, "Démarrage du container...");
-                    y += 1;
+                    self.log_step(4, DEPLOY_STEPS, "Démarrage du container");
+                    self.log_color(Color::Info);
+                    self.log_line(0, "Démarrage du container...");
                     
                     match lxc_deploy.start_container(&self.executor) {
                         Ok(_) => {
-                            self.ui.set_color(Color::Success);
-                            self.ui.draw_text(box_x + 2, y, "Container démarré!");
-                            y += 2;
+                            self.log_color(Color::Success);
+                            self.log_line(0, "Container démarré!");
+                            self.log_blank();
                             
                             // Attendre que le container soit prêt
-                            self.ui.set_color(Color::Info);
-                            self.ui.draw_text(box_x + 2, y, "Attente que le container soit prêt...");
-                            y += 1;
-                            std::thread::sleep(std::time::Duration::from_secs(3));
+                            self.log_color(Color::Info);
+                            self.log_line(0, "Attente que le container soit prêt...");
+                            if !self.operation_pause(timings.container_ready()) {
+                                self.show_operation_aborted();
+                                return result;
+                            }
                             
                             // Vérification complète du container
-                            self.ui.set_color(Color::Info);
-                            self.ui.draw_text(box_x + 2, y, "Vérification complète du container...");
-                            y += 1;
-                            io::stdout().flush().unwrap();
+                            self.log_step(5, DEPLOY_STEPS, "Vérification du container");
+                            self.log_color(Color::Info);
+                            self.log_line(0, "Vérification complète du container...");
                             
                             match lxc_deploy.verify_container(&self.executor) {
                                 Ok(verification) => {
-                                    let mut all_ok = true;
-                                    
-                                    // Vérifier chaque aspect
-                                    if !verification.exists {
-                                        self.ui.set_color(Color::Error);
-                                        self.ui.draw_text(box_x + 2, y, "✗ Container non détecté par check_container_exists()");
-                                        y += 1;
-                                        all_ok = false;
-                                    }
-                                    
-                                    if !verification.detectable_by_ls && !verification.detectable_by_list {
-                                        self.ui.set_color(Color::Warning);
-                                        self.ui.draw_text(box_x + 2, y, "⚠ Container non détecté par lxc-ls ou lxc list");
-                                        y += 1;
-                                        all_ok = false;
-                                    }
-                                    
-                                    if !verification.detectable_by_filesystem {
-                                        self.ui.set_color(Color::Warning);
-                                        self.ui.draw_text(box_x + 2, y, "⚠ Container non trouvé dans le système de fichiers");
-                                        y += 1;
-                                        all_ok = false;
-                                    }
-                                    
-                                    if !verification.can_get_status {
-                                        self.ui.set_color(Color::Error);
-                                        self.ui.draw_text(box_x + 2, y, "✗ Impossible d'obtenir le statut du container");
-                                        y += 1;
-                                        all_ok = false;
-                                    }
-                                    
-                                    if !verification.can_attach {
-                                        self.ui.set_color(Color::Error);
-                                        self.ui.draw_text(box_x + 2, y, "✗ Impossible d'accéder au container via lxc-attach");
-                                        y += 1;
-                                        all_ok = false;
+                                    // Afficher chaque problème selon sa gravité
+                                    for issue in verification.issues() {
+                                        match issue.severity {
+                                            IssueSeverity::Critical => {
+                                                self.log_color(Color::Error);
+                                                self.log_line(0, &format!("✗ {}", issue.message));
+                                            }
+                                            IssueSeverity::Warning => {
+                                                self.log_color(Color::Warning);
+                                                self.log_line(0, &format!("⚠ {}", issue.message));
+                                            }
+                                        }
                                     }
-                                    
+
                                     if !verification.is_running {
-                                        self.ui.set_color(Color::Warning);
-                                        self.ui.draw_text(box_x + 2, y, "⚠ Container créé mais n'est pas en cours d'exécution");
-                                        y += 1;
                                         // Essayer de le démarrer à nouveau
-                                        self.ui.set_color(Color::Info);
-                                        self.ui.draw_text(box_x + 2, y, "Tentative de démarrage...");
-                                        y += 1;
+                                        self.log_color(Color::Info);
+                                        self.log_line(0, "Tentative de démarrage...");
                                         let _ = lxc_deploy.start_container(&self.executor);
-                                        std::thread::sleep(std::time::Duration::from_secs(2));
+                                        if !self.operation_pause(timings.container_start()) {
+                                            self.show_operation_aborted();
+                                            return result;
+                                        }
                                     }
                                     
                                     // Afficher les erreurs détaillées si présentes
                                     if !verification.errors.is_empty() {
-                                        self.ui.set_color(Color::Warning);
-                                        self.ui.draw_text(box_x + 2, y, "Détails des problèmes:");
-                                        y += 1;
+                                        self.log_color(Color::Warning);
+                                        self.log_line(0, "Détails des problèmes:");
                                         for error in verification.errors.iter().take(5) {
-                                            self.ui.set_color(Color::Fg);
-                                            self.ui.draw_text(box_x + 4, y, &format!("- {}", error));
-                                            y += 1;
+                                            self.log_color(Color::Fg);
+                                            self.log_line(2, &format!("- {}", error));
                                         }
                                     }
                                     
-                                    if all_ok && verification.is_running && verification.can_attach {
-                                        self.ui.set_color(Color::Success);
-                                        self.ui.draw_text(box_x + 2, y, "✓ Container vérifié et opérationnel!");
-                                        y += 2;
-                                    } else {
-                                        // Si les vérifications critiques échouent, arrêter le processus
-                                        if !verification.exists || !verification.can_attach {
-                                            self.ui.set_color(Color::Error);
-                                            self.ui.draw_text(box_x + 2, y, "✗ ERREUR CRITIQUE: Le container n'est pas utilisable!");
-                                            y += 1;
-                                            self.ui.set_color(Color::Fg);
-                                            self.ui.draw_text(box_x + 2, y, "Le déploiement ne peut pas continuer.");
-                                            y += 1;
-                                            self.ui.draw_text(box_x + 2, y, "Veuillez vérifier la configuration LXC et réessayer.");
-                                            y += 2;
-                                            self.ui.set_color(Color::Info);
-                                            self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
-                                            self.ui.set_color(Color::Reset);
-                                            io::stdout().flush().unwrap();
-                                            let _ = self.input_reader.read_key();
-                                            return;
+                                    match verification.overall() {
+                                        VerificationStatus::Ok => {
+                                            self.log_color(Color::Success);
+                                            self.log_line(0, "✓ Container vérifié et opérationnel!");
+                                            self.log_blank();
+                                        }
+                                        VerificationStatus::Failed => {
+                                            // Les vérifications critiques ont échoué, arrêter le processus
+                                            self.log_color(Color::Error);
+                                            self.log_line(0, "✗ ERREUR CRITIQUE: Le container n'est pas utilisable!");
+                                            self.log_color(Color::Fg);
+                                            self.log_line(0, "Le déploiement ne peut pas continuer.");
+                                            self.log_line(0, "Veuillez vérifier la configuration LXC et réessayer.");
+                                            self.log_blank();
+                                            self.log_wait_key();
+                                            return result;
+                                        }
+                                        VerificationStatus::Degraded => {
+                                            self.log_color(Color::Warning);
+                                            self.log_line(0, "⚠ Container créé mais certains tests ont échoué");
+                                            self.log_line(0, "Le container peut ne pas apparaître dans la liste");
+                                            self.log_blank();
                                         }
-                                        
-                                        self.ui.set_color(Color::Warning);
-                                        self.ui.draw_text(box_x + 2, y, "⚠ Container créé mais certains tests ont échoué");
-                                        y += 1;
-                                        self.ui.draw_text(box_x + 2, y, "Le container peut ne pas apparaître dans la liste");
-                                        y += 2;
                                     }
                                 }
                                 Err(e) => {
-                                    self.ui.set_color(Color::Error);
-                                    self.ui.draw_text(box_x + 2, y, &format!("Erreur lors de la vérification: {}", e));
-                                    y += 2;
+                                    self.log_color(Color::Error);
+                                    self.log_line(0, &format!("Erreur lors de la vérification: {}", e));
+                                    self.log_blank();
                                 }
                             }
                             
@@ -2081,11 +3806,13 @@ impl MainApp {
                             let current_dir = std::env::current_dir().unwrap_or_default();
                             
                             let rmdb_source_str = {
-                                let mut found_path: Option<String> = None;
+                                // 0. Répertoire configuré (RMDB_SOURCE_DIR ou tui.json)
+                                let mut found_path: Option<String> = self.tui_config.source_dir.clone()
+                                    .filter(|dir| std::path::Path::new(dir).exists());
                                 
                                 // 1. Emplacement de développement (dossier courant)
                                 let dev_path = current_dir.join("rmdb_source");
-                                if dev_path.exists() {
+                                if found_path.is_none() && dev_path.exists() {
                                     found_path = Some(dev_path.to_string_lossy().to_string());
                                 }
                                 
@@ -2117,134 +3844,116 @@ impl MainApp {
                                 match found_path {
                                     Some(path) => path,
                                     None => {
-                                        self.ui.set_color(Color::Error);
-                                        self.ui.draw_text(box_x + 2, y, "rmdb_source introuvable.");
-                                        y += 1;
-                                        self.ui.set_color(Color::Info);
-                                        self.ui.draw_text(box_x + 2, y, "Emplacements vérifiés:");
-                                        y += 1;
-                                        self.ui.draw_text(box_x + 4, y, &format!("- {}", current_dir.join("rmdb_source").display()));
-                                        y += 1;
-                                        self.ui.draw_text(box_x + 4, y, "- /usr/local/share/rmdb/rmdb_source");
-                                        y += 1;
-                                        self.ui.draw_text(box_x + 4, y, "- /opt/rmdb/rmdb_source");
-                                        y += 2;
-                                        self.ui.set_color(Color::Reset);
-                                        self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
-                                        let _ = self.input_reader.read_key();
-                                        return;
+                                        self.log_color(Color::Error);
+                                        self.log_line(0, "rmdb_source introuvable.");
+                                        self.log_color(Color::Info);
+                                        self.log_line(0, "Emplacements vérifiés:");
+                                        self.log_line(2, &format!("- {}", current_dir.join("rmdb_source").display()));
+                                        self.log_line(2, "- /usr/local/share/rmdb/rmdb_source");
+                                        self.log_line(2, "- /opt/rmdb/rmdb_source");
+                                        self.log_blank();
+                                        self.log_wait_key();
+                                        return result;
                                     }
                                 }
                             };
                             
-                            self.ui.set_color(Color::Info);
-                            self.ui.draw_text(box_x + 2, y, &format!("Installation de RMDB depuis: {}", rmdb_source_str));
-                            y += 1;
+                            self.log_step(6, DEPLOY_STEPS, "Installation de RMDB");
+                            self.log_color(Color::Info);
+                            self.log_line(0, &format!("Installation de RMDB depuis: {}", rmdb_source_str));
                             
                             match lxc_deploy.install_rmdb_in_container(&self.executor, &rmdb_source_str) {
                                 Ok(_) => {
-                                    self.ui.set_color(Color::Success);
-                                    self.ui.draw_text(box_x + 2, y, "RMDB installé avec succès!");
-                                    y += 2;
+                                    self.log_color(Color::Success);
+                                    self.log_line(0, "RMDB installé avec succès!");
+                                    self.record_deploy(container_name, started, true);
+                                    result = DeployResult::Success;
+                                    self.log_blank();
                                     
                                     // Vérification finale : s'assurer que le container apparaît dans la liste
-                                    self.ui.set_color(Color::Info);
-                                    self.ui.draw_text(box_x + 2, y, "Vérification finale de la détection du container...");
-                                    y += 1;
-                                    io::stdout().flush().unwrap();
+                                    self.log_color(Color::Info);
+                                    self.log_line(0, "Vérification finale de la détection du container...");
                                     
                                     // Attendre un peu pour que le système soit à jour
-                                    std::thread::sleep(std::time::Duration::from_secs(1));
+                                    if !self.operation_pause(timings.recheck()) {
+                                        self.show_operation_aborted();
+                                        return result;
+                                    }
                                     
                                     match LXCDeployment::list_all_containers(&self.executor) {
                                         Ok(containers) => {
-                                            let found = containers.iter().any(|c| c.name == "rmdb");
+                                            let found = containers.iter().any(|c| c.name == container_name);
                                             if found {
-                                                self.ui.set_color(Color::Success);
-                                                self.ui.draw_text(box_x + 2, y, "✓ Container 'rmdb' détecté dans la liste des containers");
-                                                y += 2;
+                                                self.log_color(Color::Success);
+                                                self.log_line(0, &format!("✓ Container '{}' détecté dans la liste des containers", container_name));
+                                                self.log_blank();
                                             } else {
-                                                self.ui.set_color(Color::Error);
-                                                self.ui.draw_text(box_x + 2, y, "✗ ERREUR: Container 'rmdb' non trouvé dans la liste!");
-                                                y += 1;
-                                                self.ui.set_color(Color::Fg);
-                                                self.ui.draw_text(box_x + 2, y, &format!("Containers trouvés: {}", containers.len()));
-                                                y += 1;
+                                                self.log_color(Color::Error);
+                                                self.log_line(0, &format!("✗ ERREUR: Container '{}' non trouvé dans la liste!", container_name));
+                                                self.log_color(Color::Fg);
+                                                self.log_line(0, &format!("Containers trouvés: {}", containers.len()));
                                                 
                                                 // Afficher les containers trouvés pour debug
                                                 if !containers.is_empty() {
-                                                    self.ui.set_color(Color::Info);
-                                                    self.ui.draw_text(box_x + 2, y, "Containers détectés:");
-                                                    y += 1;
+                                                    self.log_color(Color::Info);
+                                                    self.log_line(0, "Containers détectés:");
                                                     for container in containers.iter().take(5) {
-                                                        self.ui.set_color(Color::Fg);
-                                                        self.ui.draw_text(box_x + 4, y, &format!("- {} ({})", container.name, container.status));
-                                                        y += 1;
+                                                        self.log_color(Color::Fg);
+                                                        self.log_line(2, &format!("- {} ({})", container.name, container.status));
                                                     }
                                                 } else {
-                                                    self.ui.set_color(Color::Warning);
-                                                    self.ui.draw_text(box_x + 2, y, "Aucun container détecté par list_all_containers()");
-                                                    y += 1;
+                                                    self.log_color(Color::Warning);
+                                                    self.log_line(0, "Aucun container détecté par list_all_containers()");
                                                 }
-                                                y += 1;
-                                                self.ui.set_color(Color::Warning);
-                                                self.ui.draw_text(box_x + 2, y, "Le container a été créé mais n'est pas détectable.");
-                                                y += 1;
-                                                self.ui.draw_text(box_x + 2, y, "Vérifiez les permissions LXC et la configuration système.");
-                                                y += 2;
+                                                self.log_color(Color::Warning);
+                                                self.log_line(0, "Le container a été créé mais n'est pas détectable.");
+                                                self.log_line(0, "Vérifiez les permissions LXC et la configuration système.");
+                                                self.log_blank();
                                             }
                                         }
                                         Err(e) => {
-                                            self.ui.set_color(Color::Warning);
-                                            self.ui.draw_text(box_x + 2, y, &format!("⚠ Impossible de lister les containers: {}", e));
-                                            y += 2;
+                                            self.log_color(Color::Warning);
+                                            self.log_line(0, &format!("⚠ Impossible de lister les containers: {}", e));
+                                            self.log_blank();
                                         }
                                     }
                                     
                                     // Afficher le chemin du log si disponible
                                     if let Some(ref logger) = lxc_deploy.logger {
-                                        self.ui.set_color(Color::Info);
-                                        self.ui.draw_text(box_x + 2, y, &format!("Logs disponibles dans: {}", logger.log_path().display()));
-                                        y += 1;
+                                        self.log_color(Color::Info);
+                                        self.log_line(0, &format!("Logs disponibles dans: {}", logger.log_path().display()));
                                     }
                                     
-                                    self.ui.set_color(Color::Info);
-                                    self.ui.draw_text(box_x + 2, y, "Déploiement terminé!");
-                                    y += 1;
-                                    self.ui.set_color(Color::Fg);
-                                    self.ui.draw_text(box_x + 2, y, "Pour démarrer RMDB:");
-                                    y += 1;
-                                    self.ui.draw_text(box_x + 4, y, "lxc-attach -n rmdb -- rc-service rmdbd start");
-                                    y += 1;
+                                    self.log_color(Color::Info);
+                                    self.log_line(0, "Déploiement terminé!");
+                                    self.log_color(Color::Fg);
+                                    self.log_line(0, "Pour démarrer RMDB:");
+                                    self.log_line(2, &format!("lxc-attach -n {} -- rc-service rmdbd start", container_name));
                                 }
                                 Err(e) => {
-                                    self.ui.set_color(Color::Error);
-                                    self.ui.draw_text(box_x + 2, y, &format!("Erreur lors de l'installation de RMDB: {}", e));
-                                    y += 1;
+                                    self.log_color(Color::Error);
+                                    self.log_line(0, &format!("Erreur lors de l'installation de RMDB: {}", e));
                                     
                                     // Afficher le chemin du log pour le débogage
                                     if let Some(ref logger) = lxc_deploy.logger {
-                                        self.ui.set_color(Color::Warning);
-                                        self.ui.draw_text(box_x + 2, y, &format!("Consultez les logs dans: {}", logger.log_path().display()));
-                                        y += 1;
+                                        self.log_color(Color::Warning);
+                                        self.log_line(0, &format!("Consultez les logs dans: {}", logger.log_path().display()));
                                     }
-                                    y += 1;
                                 }
                             }
                         }
                         Err(e) => {
-                            self.ui.set_color(Color::Error);
-                            self.ui.draw_text(box_x + 2, y, &format!("Erreur: {}", e));
-                            y += 2;
+                            self.log_color(Color::Error);
+                            self.log_line(0, &format!("Erreur: {}", e));
+                            self.log_blank();
+                            self.log_mac_policy_hint();
                         }
                     }
                 } else {
-                    self.ui.set_color(Color::Error);
-                    self.ui.draw_text(box_x + 2, y, "Erreur lors de la création du container.");
-                    y += 1;
+                    self.log_color(Color::Error);
+                    self.log_line(0, "Erreur lors de la création du container.");
                     
                     // Afficher les détails de l'erreur
-                    let (_, _, _, box_h) = self.ui.get_box_dimensions();
                     let error_text = format!("{}{}", output.stderr, output.stdout);
                     
                     // Détecter les erreurs spécifiques à RHEL
@@ -2255,75 +3964,75 @@ impl MainApp {
                                          error_text.contains("default.conf");
                     
                     if is_rhel_permission_error {
-                        self.ui.set_color(Color::Warning);
-                        self.ui.draw_text(box_x + 2, y, "Erreur de permissions détectée (RHEL/CentOS).");
-                        y += 1;
-                        self.ui.set_color(Color::Info);
-                        self.ui.draw_text(box_x + 2, y, "Sur RHEL, LXC nécessite souvent:");
-                        y += 1;
-                        self.ui.set_color(Color::Fg);
-                        self.ui.draw_text(box_x + 4, y, "1. Exécution en tant que root, OU");
-                        y += 1;
-                        self.ui.draw_text(box_x + 4, y, "2. Configuration de mappings UID/GID");
-                        y += 2;
-                        self.ui.set_color(Color::Warning);
-                        self.ui.draw_text(box_x + 2, y, "Solution recommandée: Exécutez le TUI avec sudo");
-                        y += 2;
+                        self.log_color(Color::Warning);
+                        self.log_line(0, "Erreur de permissions détectée (RHEL/CentOS).");
+                        self.log_color(Color::Info);
+                        self.log_line(0, "Sur RHEL, LXC nécessite souvent:");
+                        self.log_color(Color::Fg);
+                        self.log_line(2, "1. Exécution en tant que root, OU");
+                        self.log_line(2, "2. Configuration de mappings UID/GID");
+                        self.log_blank();
+                        self.log_color(Color::Warning);
+                        self.log_line(0, "Solution recommandée: Exécutez le TUI avec sudo");
+                        self.log_blank();
                     } else if is_config_error {
-                        self.ui.set_color(Color::Warning);
-                        self.ui.draw_text(box_x + 2, y, "Configuration LXC manquante.");
-                        y += 1;
-                        self.ui.set_color(Color::Info);
-                        self.ui.draw_text(box_x + 2, y, "La configuration a été créée automatiquement.");
-                        y += 1;
-                        self.ui.draw_text(box_x + 2, y, "Réessayez la création du container.");
-                        y += 2;
+                        self.log_color(Color::Warning);
+                        self.log_line(0, "Configuration LXC manquante.");
+                        self.log_color(Color::Info);
+                        self.log_line(0, "Utilisez Containers LXC › Réparer la configuration LXC,");
+                        self.log_line(0, "puis réessayez la création du container.");
+                        self.log_blank();
                     } else {
                         // Afficher les erreurs standard
                         if !output.stderr.is_empty() {
-                            let error_lines: Vec<&str> = output.stderr.lines().collect();
-                            for (i, line) in error_lines.iter().take(5).enumerate() {
-                                if (y + i as u16) < (box_y + box_h - 5) {
-                                    self.ui.set_color(Color::Error);
-                                    self.ui.draw_text(box_x + 2, y + i as u16, line);
-                                }
+                            self.log_color(Color::Error);
+                            for line in output.stderr.lines().take(5) {
+                                self.log_line(0, line);
                             }
-                            y += error_lines.len().min(5) as u16;
                         }
                         
                         if !output.stdout.is_empty() {
-                            let output_lines: Vec<&str> = output.stdout.lines().collect();
-                            for (i, line) in output_lines.iter().take(3).enumerate() {
-                                if (y + i as u16) < (box_y + box_h - 5) {
-                                    self.ui.set_color(Color::Warning);
-                                    self.ui.draw_text(box_x + 2, y + i as u16, line);
-                                }
+                            self.log_color(Color::Warning);
+                            for line in output.stdout.lines().take(3) {
+                                self.log_line(0, line);
                             }
-                            y += output_lines.len().min(3) as u16;
                         }
                         
-                        y += 1;
-                        self.ui.set_color(Color::Info);
-                        self.ui.draw_text(box_x + 2, y, "Vérifiez que les templates LXC sont installés:");
-                        y += 1;
-                        self.ui.set_color(Color::Fg);
-                        let packages = vec!["lxc-templates"];
-                        let install_hint = format!("  sudo {}", self.distribution.install_command(&packages.iter().map(|s| *s).collect::<Vec<_>>()));
-                        self.ui.draw_text(box_x + 2, y, &install_hint);
-                        y += 1;
+                        if is_network_failure(&error_text) {
+                            self.log_blank();
+                            self.log_color(Color::Warning);
+                            match lxc_deploy.mirror() {
+                                Some(mirror) => {
+                                    self.log_line(0, &format!("Téléchargement impossible depuis le miroir {}", mirror));
+                                    self.log_line(0, "Vérifiez que ce miroir est joignable depuis cet hôte.");
+                                }
+                                None => {
+                                    self.log_line(0, "Le téléchargement d'Alpine a échoué (réseau ou miroir injoignable).");
+                                    self.log_line(0, "Utilisez un miroir interne: RMDB_ALPINE_MIRROR=http://miroir.local/alpine");
+                                }
+                            }
+                            self.log_blank();
+                        }
+                        self.log_color(Color::Info);
+                        self.log_line(0, "Vérifiez que les templates LXC sont installés:");
+                        self.log_color(Color::Fg);
+                        for install_cmd in self.distribution.lxc_install_commands(&self.distribution.lxc_template_packages()) {
+                            self.log_line(0, &format!("  sudo {}", install_cmd));
+                        }
                     }
+                    self.log_mac_policy_hint();
                 }
             }
             Err(e) => {
-                self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("Erreur: {}", e));
-                y += 2;
+                self.log_color(Color::Error);
+                self.log_line(0, &format!("Erreur: {}", e));
+                self.log_blank();
                 
                 // Si c'est un outil manquant, proposer l'installation
                 if let ExecError::MissingTool(_) = e {
-                    self.ui.set_color(Color::Info);
-                    self.ui.draw_text(box_x + 2, y, "Voulez-vous installer les templates LXC ?");
-                    y += 2;
+                    self.log_color(Color::Info);
+                    self.log_line(0, "Voulez-vous installer les templates LXC ?");
+                    self.log_blank();
                     
                     let install_templates = self.ask_yes_no(
                         "Installation des Templates LXC",
@@ -2333,29 +4042,23 @@ impl MainApp {
                     if install_templates {
                         if self.install_lxc_templates() {
                             // Réessayer la création
-                            self.ui.clear_screen();
-                            self.ui.draw_header("Déploiement Container LXC Alpine");
-                            y = box_y + 5;
-                            self.ui.set_color(Color::Info);
-                            self.ui.draw_text(box_x + 2, y, "Nouvelle tentative de création du container...");
-                            y += 1;
+                            self.log_color(Color::Info);
+                            self.log_line(0, "Nouvelle tentative de création du container...");
                             
                             match lxc_deploy.create_container(&self.executor) {
                                 Ok(output) => {
                                     if output.exit_code == Some(0) {
-                                        self.ui.set_color(Color::Success);
-                                        self.ui.draw_text(box_x + 2, y, "Container créé avec succès!");
-                                        y += 2;
+                                        self.log_color(Color::Success);
+                                        self.log_line(0, "Container créé avec succès!");
+                                        self.log_blank();
                                     } else {
-                                        self.ui.set_color(Color::Error);
-                                        self.ui.draw_text(box_x + 2, y, "Échec de la création après installation des templates.");
-                                        y += 1;
+                                        self.log_color(Color::Error);
+                                        self.log_line(0, "Échec de la création après installation des templates.");
                                     }
                                 }
                                 Err(e2) => {
-                                    self.ui.set_color(Color::Error);
-                                    self.ui.draw_text(box_x + 2, y, &format!("Erreur: {}", e2));
-                                    y += 1;
+                                    self.log_color(Color::Error);
+                                    self.log_line(0, &format!("Erreur: {}", e2));
                                 }
                             }
                         }
@@ -2363,19 +4066,17 @@ impl MainApp {
                 }
             }
         }
-
-        self.ui.set_color(Color::Reset);
-        self.ui.draw_text(box_x + 2, y + 1, "Appuyez sur une touche pour continuer...");
-        let _ = self.input_reader.read_key();
+        self.log_wait_key();
+        result
     }
 
     fn show_deployment_status(&mut self) {
         self.ui.clear_screen();
         self.ui.draw_header("Statut des Containers LXC");
-        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let (box_x, box_y, box_w, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
-        let lxc_deploy = LXCDeployment::new("rmdb".to_string(), "3.20".to_string());
+        let lxc_deploy = LXCDeployment::new(self.default_container.clone(), "3.20".to_string());
 
         // Vérifier LXC
         if !lxc_deploy.check_lxc_installed() {
@@ -2409,21 +4110,36 @@ impl MainApp {
         }
 
         // Lister les containers
-        let cmd = "lxc-ls -f 2>/dev/null || echo 'Aucun container trouvé'";
-        let output = self.executor.run_shell(cmd, false);
-        let content = output.map(|o| o.stdout).unwrap_or_else(|_| "Erreur".to_string());
-
-        for line in content.lines().take(20) {
+        let containers = LXCDeployment::list_all_containers_detailed(&self.executor);
+        if containers.is_empty() {
             self.ui.set_color(Color::Fg);
-            self.ui.draw_text(box_x + 2, y, line);
+            self.ui.draw_text(box_x + 2, y, "Aucun container trouvé");
+            y += 1;
+        } else {
+            // IPv4 en dernière colonne, tronquée par le bord de la boîte
+            let name_width = container_name_width(&containers, box_w, 40);
+            self.ui.set_color(Color::Info);
+            self.ui.draw_text(box_x + 2, y, &format!("{} {:<10} {:<10} {}", pad_to_width("NOM", name_width), "ÉTAT", "AUTO", "IPV4"));
             y += 1;
+            for container in containers.iter().take(20) {
+                let autostart = match container.autostart {
+                    Some(true) => "oui",
+                    Some(false) => "non",
+                    None => "?",
+                };
+                let ipv4 = if container.ipv4.is_empty() { "-".to_string() } else { container.ipv4.join(", ") };
+                self.ui.set_color(container_color(container));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("{} {:<10} {:<10} {}",
+                    pad_to_width(&container.name, name_width), container.status, autostart, ipv4), usize::MAX);
+                y += 1;
+            }
         }
 
         // Vérifier le statut du container rmdb
         if lxc_deploy.check_container_exists_with_executor(&self.executor) {
             y += 1;
             self.ui.set_color(Color::Info);
-            self.ui.draw_text(box_x + 2, y, "Container 'rmdb':");
+            self.ui.draw_text(box_x + 2, y, &format!("Container '{}':", self.default_container));
             y += 1;
             
             match lxc_deploy.get_container_status(&self.executor) {
@@ -2451,11 +4167,11 @@ impl MainApp {
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
-        let lxc_deploy = LXCDeployment::new("rmdb".to_string(), "3.20".to_string());
+        let lxc_deploy = LXCDeployment::new(self.default_container.clone(), "3.20".to_string());
 
         if !lxc_deploy.check_container_exists() {
             self.ui.set_color(Color::Error);
-            self.ui.draw_text(box_x + 2, y, "Le container 'rmdb' n'existe pas.");
+            self.ui.draw_text(box_x + 2, y, &format!("Le container '{}' n'existe pas.", self.default_container));
             self.ui.set_color(Color::Reset);
             self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
             let _ = self.input_reader.read_key();
@@ -2463,7 +4179,7 @@ impl MainApp {
         }
 
         self.ui.set_color(Color::Info);
-        self.ui.draw_text(box_x + 2, y, "Démarrage du container 'rmdb'...");
+        self.ui.draw_text(box_x + 2, y, &format!("Démarrage du container '{}'...", self.default_container));
         y += 1;
 
         match lxc_deploy.start_container(&self.executor) {
@@ -2481,7 +4197,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("Erreur: {}", e), usize::MAX);
             }
         }
 
@@ -2496,11 +4212,11 @@ impl MainApp {
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
-        let lxc_deploy = LXCDeployment::new("rmdb".to_string(), "3.20".to_string());
+        let lxc_deploy = LXCDeployment::new(self.default_container.clone(), "3.20".to_string());
 
         if !lxc_deploy.check_container_exists() {
             self.ui.set_color(Color::Error);
-            self.ui.draw_text(box_x + 2, y, "Le container 'rmdb' n'existe pas.");
+            self.ui.draw_text(box_x + 2, y, &format!("Le container '{}' n'existe pas.", self.default_container));
             self.ui.set_color(Color::Reset);
             self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
             let _ = self.input_reader.read_key();
@@ -2508,7 +4224,7 @@ impl MainApp {
         }
 
         self.ui.set_color(Color::Info);
-        self.ui.draw_text(box_x + 2, y, "Arrêt du container 'rmdb'...");
+        self.ui.draw_text(box_x + 2, y, &format!("Arrêt du container '{}'...", self.default_container));
         y += 1;
 
         match lxc_deploy.stop_container(&self.executor) {
@@ -2526,7 +4242,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("Erreur: {}", e), usize::MAX);
             }
         }
 
@@ -2541,11 +4257,11 @@ impl MainApp {
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
-        let lxc_deploy = LXCDeployment::new("rmdb".to_string(), "3.20".to_string());
+        let lxc_deploy = LXCDeployment::new(self.default_container.clone(), "3.20".to_string());
 
         if !lxc_deploy.check_container_exists() {
             self.ui.set_color(Color::Error);
-            self.ui.draw_text(box_x + 2, y, "Le container 'rmdb' n'existe pas.");
+            self.ui.draw_text(box_x + 2, y, &format!("Le container '{}' n'existe pas.", self.default_container));
             self.ui.set_color(Color::Reset);
             self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
             let _ = self.input_reader.read_key();
@@ -2553,7 +4269,7 @@ impl MainApp {
         }
 
         self.ui.set_color(Color::Info);
-        self.ui.draw_text(box_x + 2, y, "Redémarrage du container 'rmdb'...");
+        self.ui.draw_text(box_x + 2, y, &format!("Redémarrage du container '{}'...", self.default_container));
         y += 1;
 
         // Arrêter
@@ -2573,13 +4289,13 @@ impl MainApp {
                     }
                     Err(e) => {
                         self.ui.set_color(Color::Error);
-                        self.ui.draw_text(box_x + 2, y, &format!("Erreur au démarrage: {}", e));
+                        self.ui.draw_text_clipped(box_x + 2, y, &format!("Erreur au démarrage: {}", e), usize::MAX);
                     }
                 }
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("Erreur à l'arrêt: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("Erreur à l'arrêt: {}", e), usize::MAX);
             }
         }
 
@@ -2591,45 +4307,34 @@ impl MainApp {
     fn lxc_show_logs(&mut self) {
         self.ui.clear_screen();
         self.ui.draw_header("Logs Container LXC");
-        let (box_x, box_y, _, box_h) = self.ui.get_box_dimensions();
-        let mut y = box_y + 5;
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let y = box_y + 5;
 
-        let lxc_deploy = LXCDeployment::new("rmdb".to_string(), "3.20".to_string());
+        let lxc_deploy = LXCDeployment::new(self.default_container.clone(), "3.20".to_string());
 
         if !lxc_deploy.check_container_exists() {
             self.ui.set_color(Color::Error);
-            self.ui.draw_text(box_x + 2, y, "Le container 'rmdb' n'existe pas.");
+            self.ui.draw_text(box_x + 2, y, &format!("Le container '{}' n'existe pas.", self.default_container));
             self.ui.set_color(Color::Reset);
             self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
             let _ = self.input_reader.read_key();
             return;
         }
 
-        let cmd = format!("lxc-info -n rmdb -S 2>/dev/null || echo 'Container non démarré'");
+        let cmd = format!("lxc-info -n {} -S 2>/dev/null || echo 'Container non démarré'", self.default_container);
         let output = self.executor.run_shell(&cmd, false);
         let status = output.map(|o| o.stdout.trim().to_string()).unwrap_or_else(|_| "Inconnu".to_string());
 
-        self.ui.set_color(Color::Info);
-        self.ui.draw_text(box_x + 2, y, &format!("Statut: {}", status));
-        y += 2;
-
-        // Afficher les dernières lignes des logs système
-        let logs_cmd = format!("journalctl -u lxc@rmdb.service -n 50 --no-pager 2>/dev/null || dmesg | grep -i lxc | tail -20 || echo 'Logs non disponibles'");
+        // Dernières lignes des logs système, en entier dans la visionneuse (E: Exporter)
+        let logs_cmd = format!("journalctl -u lxc@{}.service -n 500 --no-pager 2>/dev/null || dmesg | grep -i lxc | tail -200 || echo 'Logs non disponibles'", self.default_container);
         let logs_output = self.executor.run_shell(&logs_cmd, false);
         let logs = logs_output.map(|o| o.stdout).unwrap_or_else(|_| "Erreur".to_string());
 
-        self.ui.set_color(Color::Fg);
-        for line in logs.lines().take((box_h - y - 5) as usize) {
-            if y >= box_h - 5 {
-                break;
-            }
-            self.ui.draw_text(box_x + 2, y, line);
-            y += 1;
-        }
-
-        self.ui.set_color(Color::Reset);
-        self.ui.draw_text(box_x + 2, box_h - 2, "Appuyez sur une touche pour continuer...");
-        let _ = self.input_reader.read_key();
+        let mut viewer = TextViewer::new("Logs Container LXC");
+        viewer.push_text(&format!("Statut: {}", status), Color::Info);
+        viewer.push("", Color::Fg);
+        viewer.push_text(&logs, Color::Fg);
+        viewer.run(&self.ui, &self.input_reader);
     }
 
     fn lxc_access_shell(&mut self) {
@@ -2638,11 +4343,11 @@ impl MainApp {
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
-        let lxc_deploy = LXCDeployment::new("rmdb".to_string(), "3.20".to_string());
+        let lxc_deploy = LXCDeployment::new(self.default_container.clone(), "3.20".to_string());
 
         if !lxc_deploy.check_container_exists() {
             self.ui.set_color(Color::Error);
-            self.ui.draw_text(box_x + 2, y, "Le container 'rmdb' n'existe pas.");
+            self.ui.draw_text(box_x + 2, y, &format!("Le container '{}' n'existe pas.", self.default_container));
             self.ui.set_color(Color::Reset);
             self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
             let _ = self.input_reader.read_key();
@@ -2653,111 +4358,228 @@ impl MainApp {
         self.ui.draw_text(box_x + 2, y, "Pour accéder au shell du container, utilisez:");
         y += 2;
         self.ui.set_color(Color::Fg);
-        self.ui.draw_text(box_x + 2, y, "  lxc-attach -n rmdb");
+        self.ui.draw_text(box_x + 2, y, &format!("  lxc-attach -n {}", self.default_container));
         y += 2;
         self.ui.set_color(Color::Warning);
         self.ui.draw_text(box_x + 2, y, "Ou depuis le TUI, exécutez cette commande:");
         y += 1;
         self.ui.set_color(Color::Fg);
-        self.ui.draw_text(box_x + 2, y, "  lxc-attach -n rmdb -- sh");
+        self.ui.draw_text(box_x + 2, y, &format!("  lxc-attach -n {} -- sh", self.default_container));
         y += 3;
         self.ui.set_color(Color::Info);
-        self.ui.draw_text(box_x + 2, y, "Note: L'accès shell interactif nécessite de quitter le TUI.");
+        self.ui.draw_text(box_x + 2, y, "Entrée ouvre le shell ici (mode Admin) : le TUI reprend à la sortie du shell.");
         y += 2;
         self.ui.set_color(Color::Reset);
-        self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
-        let _ = self.input_reader.read_key();
+        self.ui.draw_text(box_x + 2, y, "Entrée: Ouvrir le shell | Autre touche: Retour");
+        if !matches!(self.input_reader.read_key(), Ok(Key::Enter)) || !self.ensure_admin() {
+            return;
+        }
+
+        let cmd = format!("lxc-attach -n {}", self.default_container);
+        let result = RawModeGuard::suspend(|| self.executor.run_interactive(&cmd, true));
+        self.ui.hide_cursor();
+        self.needs_full_redraw = true;
+        match result {
+            Ok(Some(0)) => {}
+            Ok(code) => {
+                let state = LXCDeployment::info_fields(&self.executor, &self.default_container).remove("State");
+                let code = code.map(|c| c.to_string()).unwrap_or_else(|| "signal".to_string());
+                self.show_error_message(
+                    "Accès Shell Container",
+                    &format!("{} s'est terminé en erreur ({}) : {}", cmd, code, attach_failure_hint(&code, state.as_deref())),
+                );
+            }
+            Err(e) => self.show_error_message("Accès Shell Container", &format!("{}: {}", cmd, e)),
+        }
     }
 
     fn lxc_show_stats(&mut self) {
         self.ui.clear_screen();
         self.ui.draw_header("Statistiques Container LXC");
-        let (box_x, box_y, _, box_h) = self.ui.get_box_dimensions();
+        let (box_x, box_y, box_w, box_h) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
-        let lxc_deploy = LXCDeployment::new("rmdb".to_string(), "3.20".to_string());
+        let lxc_deploy = LXCDeployment::new(self.default_container.clone(), "3.20".to_string());
 
         if !lxc_deploy.check_container_exists() {
             self.ui.set_color(Color::Error);
-            self.ui.draw_text(box_x + 2, y, "Le container 'rmdb' n'existe pas.");
+            self.ui.draw_text(box_x + 2, y, &format!("Le container '{}' n'existe pas.", self.default_container));
             self.ui.set_color(Color::Reset);
             self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
             let _ = self.input_reader.read_key();
             return;
         }
 
-        // Informations du container
-        let info_cmd = format!("lxc-info -n rmdb 2>/dev/null || echo 'Container non démarré'");
-        let info_output = self.executor.run_shell(&info_cmd, false);
-        let info = info_output.map(|o| o.stdout).unwrap_or_else(|_| "Erreur".to_string());
-
-        self.ui.set_color(Color::Fg);
-        for line in info.lines().take((box_h - y - 5) as usize) {
-            if y >= box_h - 5 {
-                break;
+        let host = SystemInfo::collect(&self.executor);
+        loop {
+            // Informations du container
+            let mut info = LXCDeployment::info_fields(&self.executor, &self.default_container);
+            if info.is_empty() {
+                self.ui.set_color(Color::Warning);
+                self.ui.draw_text(box_x + 2, y, "lxc-info ne renvoie rien (container inaccessible ?)");
+                y += 1;
+            }
+            // Identité et état d'abord, puis les compteurs par ordre alphabétique
+            let mut rows: Vec<(String, String)> = ["Name", "State", "PID", "IP"]
+                .into_iter()
+                .filter_map(|key| info.remove_entry(key))
+                .collect();
+            rows.extend(info);
+            self.ui.set_color(Color::Fg);
+            for (key, value) in &rows {
+                if y + 10 >= box_h {
+                    break;
+                }
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("{:<14}{}", format!("{}:", key), value), box_w.saturating_sub(4) as usize);
+                y += 1;
             }
-            self.ui.draw_text(box_x + 2, y, line);
             y += 1;
-        }
 
-        y += 1;
+            // Consommation réelle, depuis les cgroups du container
+            let usage = LXCDeployment::resource_usage(&self.executor, &self.default_container);
+            self.ui.set_color(Color::Warning);
+            let title = match usage.cgroup {
+                Some(CgroupVersion::V2) => "Ressources (cgroup v2):",
+                Some(CgroupVersion::V1) => "Ressources (cgroup v1):",
+                None => "Ressources:",
+            };
+            self.ui.draw_text(box_x + 2, y, title);
+            y += 1;
+            let bar_width = box_w.saturating_sub(8).min(50);
+
+            match usage.memory_used {
+                Some(used) => {
+                    let (label, reference) = match usage.memory_limit {
+                        Some(limit) => (format!("Mémoire: {} / {}", format_size(used), format_size(limit)), Some(limit)),
+                        None => (format!("Mémoire: {} (sans limite)", format_size(used)), host.mem_total),
+                    };
+                    self.ui.set_color(Color::Fg);
+                    self.ui.draw_text(box_x + 4, y, &label);
+                    if let Some(reference) = reference.filter(|&r| r > 0) {
+                        self.ui.draw_progress_bar(box_x + 4, y + 1, bar_width, used as f64 / reference as f64);
+                    }
+                    y += 2;
+                }
+                None => {
+                    self.ui.set_color(Color::Info);
+                    self.ui.draw_text(box_x + 4, y, "Mémoire: non disponible (container arrêté ?)");
+                    y += 1;
+                }
+            }
 
-        // Statistiques CPU/Mémoire depuis le container
-        let stats_cmd = format!("lxc-attach -n rmdb -- sh -c 'top -bn1 | head -5' 2>/dev/null || echo 'Statistiques non disponibles'");
-        let stats_output = self.executor.run_shell(&stats_cmd, false);
-        let stats = stats_output.map(|o| o.stdout).unwrap_or_else(|_| "Erreur".to_string());
+            match usage.cpu_percent {
+                Some(percent) => {
+                    let (label, cores) = match usage.cpu_limit {
+                        Some(limit) => (format!("CPU: {:.1} % (limite: {:.2} cœur(s))", percent, limit), Some(limit)),
+                        None => (format!("CPU: {:.1} % (sans limite)", percent), host.cpu_count.map(f64::from)),
+                    };
+                    self.ui.set_color(Color::Fg);
+                    self.ui.draw_text(box_x + 4, y, &label);
+                    if let Some(cores) = cores.filter(|&c| c > 0.0) {
+                        self.ui.draw_progress_bar(box_x + 4, y + 1, bar_width, percent / 100.0 / cores);
+                    }
+                }
+                None => {
+                    self.ui.set_color(Color::Info);
+                    self.ui.draw_text(box_x + 4, y, "CPU: non disponible");
+                }
+            }
 
-        self.ui.set_color(Color::Warning);
-        self.ui.draw_text(box_x + 2, y, "Statistiques du container:");
-        y += 1;
-        self.ui.set_color(Color::Fg);
-        for line in stats.lines().take((box_h - y - 5) as usize) {
-            if y >= box_h - 5 {
+            if !self.read_refresh_key(box_x + 2, box_h - 2) {
                 break;
             }
-            self.ui.draw_text(box_x + 2, y, line);
-            y += 1;
+            self.show_refreshing();
+            self.ui.clear_screen();
+            self.ui.draw_header("Statistiques Container LXC");
+            y = box_y + 5;
         }
-
-        self.ui.set_color(Color::Reset);
-        self.ui.draw_text(box_x + 2, box_h - 2, "Appuyez sur une touche pour continuer...");
-        let _ = self.input_reader.read_key();
     }
 
     fn lxc_show_config(&mut self) {
         self.ui.clear_screen();
         self.ui.draw_header("Configuration Container LXC");
-        let (box_x, box_y, _, box_h) = self.ui.get_box_dimensions();
-        let mut y = box_y + 5;
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let y = box_y + 5;
 
-        let lxc_deploy = LXCDeployment::new("rmdb".to_string(), "3.20".to_string());
+        let lxc_deploy = LXCDeployment::new(self.default_container.clone(), "3.20".to_string());
 
         if !lxc_deploy.check_container_exists() {
             self.ui.set_color(Color::Error);
-            self.ui.draw_text(box_x + 2, y, "Le container 'rmdb' n'existe pas.");
+            self.ui.draw_text(box_x + 2, y, &format!("Le container '{}' n'existe pas.", self.default_container));
             self.ui.set_color(Color::Reset);
             self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
             let _ = self.input_reader.read_key();
             return;
         }
 
-        let config_path = "/var/lib/lxc/rmdb/config";
+        let config_path = format!("/var/lib/lxc/{}/config", self.default_container);
         let config_cmd = format!("cat {} 2>/dev/null || echo 'Configuration non trouvée'", config_path);
         let config_output = self.executor.run_shell(&config_cmd, false);
         let config = config_output.map(|o| o.stdout).unwrap_or_else(|_| "Erreur".to_string());
 
-        self.ui.set_color(Color::Fg);
-        for line in config.lines().take((box_h - y - 5) as usize) {
-            if y >= box_h - 5 {
-                break;
+        let mut viewer = TextViewer::new(&config_path)
+            .with_hint("Flèches: Défiler | E: Exporter | M: Modifier | Q: Retour")
+            .with_action_keys(&['m']);
+        viewer.push_text(&config, Color::Fg);
+        if viewer.run(&self.ui, &self.input_reader) == Some('m') {
+            self.lxc_edit_config();
+        }
+    }
+
+    /// Modifie la configuration LXC du container dans l'éditeur externe
+    ///
+    /// Une configuration sans `lxc.rootfs.path`/`lxc.uts.name` est refusée et l'éditeur rouvert
+    /// sur le texte saisi ; une fois écrite, propose de redémarrer le container pour l'appliquer.
+    fn lxc_edit_config(&mut self) {
+        let title = "Configuration Container LXC";
+        let name = self.default_container.clone();
+        if !self.ensure_admin() {
+            return;
+        }
+        let original = match LXCDeployment::read_config(&self.executor, &name) {
+            Ok(content) => content,
+            Err(e) => {
+                self.show_error_message(title, &e.to_string());
+                return;
+            }
+        };
+
+        let mut text = original.clone();
+        loop {
+            let Some(edited) = self.edit_in_external_editor(&text, "lxc.conf") else {
+                return;
+            };
+            if edited.trim_end() == original.trim_end() {
+                self.show_message(title, "Aucune modification à enregistrer.");
+                return;
+            }
+            match LXCDeployment::write_config(&self.executor, &name, &edited) {
+                Ok(()) => break,
+                Err(e) => {
+                    text = edited;
+                    let question = format!("{}\nRouvrir l'éditeur pour corriger ?", e);
+                    if !self.ask_yes_no_default(title, &question, false) {
+                        return;
+                    }
+                }
             }
-            self.ui.draw_text(box_x + 2, y, line);
-            y += 1;
         }
 
-        self.ui.set_color(Color::Reset);
-        self.ui.draw_text(box_x + 2, box_h - 2, "Appuyez sur une touche pour continuer...");
-        let _ = self.input_reader.read_key();
+        let running = LXCDeployment::info_fields(&self.executor, &name).get("State").map(String::as_str) == Some("RUNNING");
+        if !running {
+            self.show_message(title, "✓ Configuration enregistrée (ancienne version dans config.bak). Elle s'appliquera au prochain démarrage.");
+            return;
+        }
+        let question = format!("✓ Configuration enregistrée (ancienne version dans config.bak).\nRedémarrer '{}' pour l'appliquer ?", name);
+        if !self.ask_yes_no_default(title, &question, false) {
+            return;
+        }
+        let _ = LXCDeployment::stop_container_by_name(&self.executor, &name);
+        match LXCDeployment::start_container_by_name(&self.executor, &name) {
+            Ok(output) if output.exit_code == Some(0) => self.show_message(title, &format!("✓ Container '{}' redémarré.", name)),
+            Ok(output) => self.show_error_message(title, &format!("Redémarrage impossible: {}", output.stderr.trim())),
+            Err(e) => self.show_error_message(title, &format!("Redémarrage impossible: {}", e)),
+        }
     }
 
     fn lxc_rmdb_start(&mut self) {
@@ -2766,11 +4588,11 @@ impl MainApp {
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
-        let lxc_deploy = LXCDeployment::new("rmdb".to_string(), "3.20".to_string());
+        let lxc_deploy = LXCDeployment::new(self.default_container.clone(), "3.20".to_string());
 
         if !lxc_deploy.check_container_exists() {
             self.ui.set_color(Color::Error);
-            self.ui.draw_text(box_x + 2, y, "Le container 'rmdb' n'existe pas.");
+            self.ui.draw_text(box_x + 2, y, &format!("Le container '{}' n'existe pas.", self.default_container));
             self.ui.set_color(Color::Reset);
             self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
             let _ = self.input_reader.read_key();
@@ -2778,11 +4600,13 @@ impl MainApp {
         }
 
         self.ui.set_color(Color::Info);
-        self.ui.draw_text(box_x + 2, y, "Démarrage de RMDB dans le container...");
+        let container = self.default_container.clone();
+        let init = self.container_init(&container);
+        self.ui.draw_text(box_x + 2, y, &format!("Démarrage de RMDB dans le container ({})...", init.name()));
         y += 1;
 
-        let cmd = "lxc-attach -n rmdb -- rc-service rmdbd start 2>&1 || lxc-attach -n rmdb -- /usr/local/bin/rmdbd -config /etc/rmdbd/config.json &";
-        let output = self.executor.run_shell(cmd, true);
+        let cmd = init.service_command(&container, "start");
+        let output = self.executor.run_shell(&cmd, true);
 
         match output {
             Ok(o) => {
@@ -2799,7 +4623,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("Erreur: {}", e), usize::MAX);
             }
         }
 
@@ -2814,11 +4638,11 @@ impl MainApp {
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
-        let lxc_deploy = LXCDeployment::new("rmdb".to_string(), "3.20".to_string());
+        let lxc_deploy = LXCDeployment::new(self.default_container.clone(), "3.20".to_string());
 
         if !lxc_deploy.check_container_exists() {
             self.ui.set_color(Color::Error);
-            self.ui.draw_text(box_x + 2, y, "Le container 'rmdb' n'existe pas.");
+            self.ui.draw_text(box_x + 2, y, &format!("Le container '{}' n'existe pas.", self.default_container));
             self.ui.set_color(Color::Reset);
             self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
             let _ = self.input_reader.read_key();
@@ -2826,11 +4650,13 @@ impl MainApp {
         }
 
         self.ui.set_color(Color::Info);
-        self.ui.draw_text(box_x + 2, y, "Arrêt de RMDB dans le container...");
+        let container = self.default_container.clone();
+        let init = self.container_init(&container);
+        self.ui.draw_text(box_x + 2, y, &format!("Arrêt de RMDB dans le container ({})...", init.name()));
         y += 1;
 
-        let cmd = "lxc-attach -n rmdb -- rc-service rmdbd stop 2>&1 || lxc-attach -n rmdb -- pkill rmdbd";
-        let output = self.executor.run_shell(cmd, true);
+        let cmd = init.service_command(&container, "stop");
+        let output = self.executor.run_shell(&cmd, true);
 
         match output {
             Ok(o) => {
@@ -2844,7 +4670,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("Erreur: {}", e), usize::MAX);
             }
         }
 
@@ -2859,11 +4685,11 @@ impl MainApp {
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
-        let lxc_deploy = LXCDeployment::new("rmdb".to_string(), "3.20".to_string());
+        let lxc_deploy = LXCDeployment::new(self.default_container.clone(), "3.20".to_string());
 
         if !lxc_deploy.check_container_exists() {
             self.ui.set_color(Color::Error);
-            self.ui.draw_text(box_x + 2, y, "Le container 'rmdb' n'existe pas.");
+            self.ui.draw_text(box_x + 2, y, &format!("Le container '{}' n'existe pas.", self.default_container));
             self.ui.set_color(Color::Reset);
             self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
             let _ = self.input_reader.read_key();
@@ -2871,11 +4697,13 @@ impl MainApp {
         }
 
         self.ui.set_color(Color::Info);
-        self.ui.draw_text(box_x + 2, y, "Redémarrage de RMDB dans le container...");
+        let container = self.default_container.clone();
+        let init = self.container_init(&container);
+        self.ui.draw_text(box_x + 2, y, &format!("Redémarrage de RMDB dans le container ({})...", init.name()));
         y += 1;
 
-        let cmd = "lxc-attach -n rmdb -- rc-service rmdbd restart 2>&1 || (lxc-attach -n rmdb -- pkill rmdbd && sleep 1 && lxc-attach -n rmdb -- /usr/local/bin/rmdbd -config /etc/rmdbd/config.json &)";
-        let output = self.executor.run_shell(cmd, true);
+        let cmd = init.service_command(&container, "restart");
+        let output = self.executor.run_shell(&cmd, true);
 
         match output {
             Ok(o) => {
@@ -2889,7 +4717,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("Erreur: {}", e), usize::MAX);
             }
         }
 
@@ -2901,42 +4729,59 @@ impl MainApp {
     fn lxc_rmdb_logs(&mut self) {
         self.ui.clear_screen();
         self.ui.draw_header("Logs RMDB dans Container");
-        let (box_x, box_y, _, box_h) = self.ui.get_box_dimensions();
-        let mut y = box_y + 5;
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let y = box_y + 5;
 
-        let lxc_deploy = LXCDeployment::new("rmdb".to_string(), "3.20".to_string());
+        let lxc_deploy = LXCDeployment::new(self.default_container.clone(), "3.20".to_string());
 
         if !lxc_deploy.check_container_exists() {
             self.ui.set_color(Color::Error);
-            self.ui.draw_text(box_x + 2, y, "Le container 'rmdb' n'existe pas.");
+            self.ui.draw_text(box_x + 2, y, &format!("Le container '{}' n'existe pas.", self.default_container));
             self.ui.set_color(Color::Reset);
             self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
             let _ = self.input_reader.read_key();
             return;
         }
 
-        let cmd = "lxc-attach -n rmdb -- tail -50 /var/log/rmdbd.log 2>/dev/null || lxc-attach -n rmdb -- journalctl -u rmdbd -n 50 --no-pager 2>/dev/null || echo 'Logs non disponibles'";
-        let output = self.executor.run_shell(cmd, false);
+        let container = self.default_container.clone();
+        let cmd = self.container_init(&container).logs_command(&container, 500);
+        let output = self.executor.run_shell(&cmd, false);
         let logs = output.map(|o| o.stdout).unwrap_or_else(|_| "Erreur".to_string());
 
-        self.ui.set_color(Color::Fg);
-        for line in logs.lines().take((box_h - y - 5) as usize) {
-            if y >= box_h - 5 {
-                break;
-            }
-            self.ui.draw_text(box_x + 2, y, line);
-            y += 1;
+        let title = format!("Logs RMDB dans {}", self.default_container);
+        let mut viewer = TextViewer::new(&title)
+            .with_hint("Flèches: Défiler | E: Exporter | F: Suivre en direct | Q: Retour")
+            .with_action_keys(&['f']);
+        viewer.push_text(&logs, Color::Fg);
+        if viewer.run(&self.ui, &self.input_reader) == Some('f') {
+            self.lxc_rmdb_logs_follow(&self.default_container.clone());
         }
+    }
 
-        self.ui.set_color(Color::Reset);
-        self.ui.draw_text(box_x + 2, box_h - 2, "Appuyez sur une touche pour continuer...");
-        let _ = self.input_reader.read_key();
+    /// Suit les logs RMDB du container en direct (tail -f)
+    fn lxc_rmdb_logs_follow(&mut self, container_name: &str) {
+        let cmd = self.container_init(container_name).follow_logs_command(container_name);
+        self.follow_command(&format!("Logs RMDB en direct ({})", container_name), &cmd);
+    }
+
+    /// Exécute une commande continue et affiche sa sortie dans la visionneuse jusqu'à Q
+    fn follow_command(&mut self, title: &str, cmd: &str) {
+        match self.executor.spawn_stream(cmd, false) {
+            Ok(mut stream) => {
+                let mut viewer = TextViewer::new(title);
+                viewer.run_stream(&self.ui, &self.input_reader, &mut stream);
+            }
+            Err(e) => {
+                self.show_error_message("Erreur", &format!("Impossible de suivre les logs: {}", e));
+            }
+        }
     }
 
     fn lxc_destroy_container(&mut self) {
-        let confirm = self.ask_yes_no(
+        let confirm = self.ask_yes_no_default(
             "Suppression Container",
-            "Êtes-vous sûr de vouloir supprimer le container 'rmdb' ? Cette action est irréversible."
+            &format!("Êtes-vous sûr de vouloir supprimer le container '{}' ? Cette action est irréversible.", self.default_container),
+            true
         );
 
         if !confirm {
@@ -2948,11 +4793,11 @@ impl MainApp {
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
-        let lxc_deploy = LXCDeployment::new("rmdb".to_string(), "3.20".to_string());
+        let lxc_deploy = LXCDeployment::new(self.default_container.clone(), "3.20".to_string());
 
         if !lxc_deploy.check_container_exists() {
             self.ui.set_color(Color::Error);
-            self.ui.draw_text(box_x + 2, y, "Le container 'rmdb' n'existe pas.");
+            self.ui.draw_text(box_x + 2, y, &format!("Le container '{}' n'existe pas.", self.default_container));
             self.ui.set_color(Color::Reset);
             self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
             let _ = self.input_reader.read_key();
@@ -2967,6 +4812,7 @@ impl MainApp {
         self.ui.draw_text(box_x + 2, y, "Suppression du container...");
         y += 1;
 
+        self.forget_container_init(&self.default_container.clone());
         match lxc_deploy.destroy_container(&self.executor) {
             Ok(output) => {
                 if output.exit_code == Some(0) {
@@ -2982,7 +4828,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("Erreur: {}", e), usize::MAX);
             }
         }
 
@@ -2996,7 +4842,7 @@ impl MainApp {
     fn show_containers_list(&mut self) {
         self.ui.clear_screen();
         self.ui.draw_header("Liste des Containers LXC");
-        let (box_x, box_y, _box_w, box_h) = self.ui.get_box_dimensions();
+        let (box_x, box_y, box_w, box_h) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
         self.ui.set_color(Color::Info);
@@ -3016,12 +4862,13 @@ impl MainApp {
                     self.ui.draw_text(box_x + 2, y, &format!("{} container(s) trouvé(s):", containers.len()));
                     y += 2;
 
-                    // En-tête
+                    // En-tête ; la colonne des noms s'adapte au plus long nom
+                    let name_width = container_name_width(&containers, box_w, 27);
                     self.ui.set_color(Color::Info);
-                    let header = format!("{:<20} {:<15}", "Nom", "Statut");
+                    let header = format!("{} {:<15} {:<10}", pad_to_width("Nom", name_width), "Statut", "Auto");
                     self.ui.draw_text(box_x + 2, y, &header);
                     y += 1;
-                    self.ui.draw_text(box_x + 2, y, &"-".repeat(35));
+                    self.ui.draw_text(box_x + 2, y, &"-".repeat(name_width + 27));
                     y += 1;
 
                     // Liste des containers
@@ -3034,9 +4881,14 @@ impl MainApp {
                             _ => Color::Fg,
                         };
                         self.ui.set_color(status_color);
-                        let line = format!("{:<20} {:<15}", container.name, container.status);
+                        let autostart = match container.autostart {
+                            Some(true) => "oui",
+                            Some(false) => "non",
+                            None => "-",
+                        };
+                        let line = format!("{} {:<15} {:<10}", pad_to_width(&container.name, name_width), container.status, autostart);
                         if y < box_y + box_h - 5 {
-                            self.ui.draw_text(box_x + 2, y, &line);
+                            self.ui.draw_text_clipped(box_x + 2, y, &line, usize::MAX);
                             y += 1;
                         }
                     }
@@ -3044,7 +4896,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("Erreur lors de la récupération de la liste: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("Erreur lors de la récupération de la liste: {}", e), usize::MAX);
                 y += 2;
             }
         }
@@ -3054,10 +4906,11 @@ impl MainApp {
         let _ = self.input_reader.read_key();
     }
 
-    fn select_container(&mut self, title: &str) -> Option<String> {
+    /// Charge les containers pour une sélection ; `None` (après message) si erreur ou liste vide
+    fn load_containers_for_selection(&mut self, title: &str) -> Option<Vec<ContainerInfo>> {
         self.ui.clear_screen();
         self.ui.draw_header(title);
-        let (box_x, box_y, _box_w, box_h) = self.ui.get_box_dimensions();
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
         self.ui.set_color(Color::Info);
@@ -3070,7 +4923,7 @@ impl MainApp {
             Err(e) => {
                 self.ui.clear_line(y);
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("Erreur: {}", e), usize::MAX);
                 y += 2;
                 self.ui.set_color(Color::Reset);
                 self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
@@ -3090,262 +4943,448 @@ impl MainApp {
             return None;
         }
 
-        // Afficher la liste avec sélection
-        let mut selected = 0;
-        loop {
-            self.ui.clear_screen();
-            self.ui.draw_header(title);
-            y = box_y + 5;
+        Some(containers)
+    }
 
-            self.ui.set_color(Color::Info);
-            self.ui.draw_text(box_x + 2, y, "Sélectionnez un container:");
-            y += 2;
+    fn select_container(&mut self, title: &str) -> Option<String> {
+        let containers = self.load_containers_for_selection(title)?;
+        let name_width = container_name_width(&containers, self.ui.get_box_dimensions().2, 30);
+        SelectList::new(title, &containers, |c| container_label(c, name_width))
+            .with_prompt("Sélectionnez un container:")
+            .with_color(container_color)
+            .with_hint("Flèches: Sélectionner | Entrée: Valider | Q: Annuler")
+            .with_sort(CONTAINER_SORT_COLUMNS, &mut self.container_sort)
+            .select(&self.ui, &self.input_reader)
+            .map(|i| containers[i].name.clone())
+    }
 
-            for (i, container) in containers.iter().enumerate() {
-                let selected_char = if i == selected { "▶" } else { " " };
-                let status_color = match container.status.as_str() {
-                    "RUNNING" => Color::Success,
-                    "STOPPED" => Color::Warning,
-                    "FROZEN" => Color::Info,
-                    _ => Color::Fg,
-                };
-                self.ui.set_color(if i == selected { Color::Selection } else { Color::Fg });
-                self.ui.draw_text(box_x + 2, y, &format!("{} {}", selected_char, container.name));
-                self.ui.set_color(status_color);
-                self.ui.draw_text(box_x + 25, y, &format!("({})", container.status));
-                y += 1;
-            }
+    /// Sélection d'un ou plusieurs containers : Espace coche, Entrée valide les containers cochés
+    /// (ou celui sous le curseur si aucun n'est coché)
+    fn select_containers(&mut self, title: &str) -> Vec<String> {
+        let Some(containers) = self.load_containers_for_selection(title) else {
+            return Vec::new();
+        };
+        let mut checked = HashSet::new();
+        let name_width = container_name_width(&containers, self.ui.get_box_dimensions().2, 34);
+        let selected = SelectList::new(title, &containers, |c| container_label(c, name_width))
+            .with_prompt("Sélectionnez un ou plusieurs containers:")
+            .with_color(container_color)
+            .with_hint("Flèches: Sélectionner | Entrée: Valider | Q: Annuler")
+            .with_sort(CONTAINER_SORT_COLUMNS, &mut self.container_sort)
+            .with_multi_select(&mut checked)
+            .select(&self.ui, &self.input_reader);
+        match selected {
+            Some(_) if !checked.is_empty() => containers
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| checked.contains(i))
+                .map(|(_, c)| c.name.clone())
+                .collect(),
+            Some(i) => vec![containers[i].name.clone()],
+            None => Vec::new(),
+        }
+    }
 
-            self.ui.set_color(Color::Reset);
-            self.ui.draw_text(box_x + 2, box_h - 2, "Flèches: Sélectionner | Entrée: Valider | Q: Annuler");
+    /// Applique `operation` à chaque container et affiche le résultat ligne par ligne
+    fn containers_batch(
+        &mut self,
+        title: &str,
+        verb: &str,
+        names: &[String],
+        operation: fn(&CommandExecutor, &str) -> Result<CommandOutput, ExecError>,
+    ) {
+        self.ui.clear_screen();
+        self.ui.draw_header(title);
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let mut y = box_y + 5;
 
-            match self.input_reader.read_key() {
-                Ok(Key::Up) => {
-                    if selected > 0 {
-                        selected -= 1;
-                    } else {
-                        selected = containers.len() - 1;
-                    }
-                }
-                Ok(Key::Down) => {
-                    if selected < containers.len() - 1 {
-                        selected += 1;
-                    } else {
-                        selected = 0;
-                    }
+        let mut failures = 0;
+        for name in names {
+            self.ui.set_color(Color::Info);
+            self.ui.draw_text_clipped(box_x + 2, y, &format!("{} de '{}'...", verb, name), usize::MAX);
+            io::stdout().flush().unwrap();
+            let result = operation(&self.executor, name);
+            self.ui.clear_line(y);
+            match result {
+                Ok(output) if output.exit_code == Some(0) => {
+                    self.ui.set_color(Color::Success);
+                    self.ui.draw_text_clipped(box_x + 2, y, &format!("✓ {}", name), usize::MAX);
                 }
-                Ok(Key::Enter) => {
-                    return Some(containers[selected].name.clone());
+                Ok(output) => {
+                    failures += 1;
+                    self.ui.set_color(Color::Error);
+                    let reason = output.stderr.lines().next().unwrap_or("Erreur inconnue").to_string();
+                    self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ {}: {}", name, reason), usize::MAX);
                 }
-                Ok(Key::Quit) => {
-                    return None;
+                Err(e) => {
+                    failures += 1;
+                    self.ui.set_color(Color::Error);
+                    self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ {}: {}", name, e), usize::MAX);
                 }
-                _ => {}
             }
+            y += 1;
+        }
+        y += 1;
+
+        if failures == 0 {
+            self.ui.set_color(Color::Success);
+            self.ui.draw_text(box_x + 2, y, &format!("{} container(s) traité(s) avec succès.", names.len()));
+        } else {
+            self.ui.set_color(Color::Warning);
+            self.ui.draw_text(box_x + 2, y, &format!("{} échec(s) sur {} container(s).", failures, names.len()));
         }
+        y += 2;
+        self.ui.set_color(Color::Reset);
+        self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
+        let _ = self.input_reader.read_key();
     }
 
     fn containers_start(&mut self) {
-        if let Some(container_name) = self.select_container("Démarrer Container") {
-            self.ui.clear_screen();
-            self.ui.draw_header("Démarrer Container LXC");
-            let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
-            let mut y = box_y + 5;
-
-            // Vérifier d'abord si le container a une configuration valide
-            self.ui.set_color(Color::Info);
-            self.ui.draw_text(box_x + 2, y, &format!("Vérification de la configuration du container '{}'...", container_name));
-            y += 1;
-            io::stdout().flush().unwrap();
+        let names = self.select_containers("Démarrer Container");
 
-            let config_check = format!("sudo -n test -f /var/lib/lxc/{}/config && echo 'found' || echo 'not found'", container_name);
-            let has_config = self.executor.run_shell(&config_check, true)
-                .map(|o| o.stdout.contains("found"))
-                .unwrap_or(false);
-
-            if !has_config {
-                self.ui.clear_line(y - 1);
-                self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y - 1, "✗ Configuration du container introuvable!");
-                y += 1;
-                self.ui.set_color(Color::Warning);
-                self.ui.draw_text(box_x + 2, y, "Le container semble être corrompu ou incomplet.");
-                y += 1;
-                self.ui.set_color(Color::Fg);
-                self.ui.draw_text(box_x + 2, y, "Solutions possibles:");
-                y += 1;
-                self.ui.draw_text(box_x + 4, y, "1. Réinstaller le container (menu: Réinstaller)");
-                y += 1;
-                self.ui.draw_text(box_x + 4, y, "2. Supprimer et recréer le container");
-                y += 2;
-                self.ui.set_color(Color::Reset);
-                self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
-                let _ = self.input_reader.read_key();
-                return;
+        // Un container gelé tourne déjà : on propose de le dégeler, lxc-start échouerait
+        let (frozen, names): (Vec<String>, Vec<String>) = names.into_iter().partition(|name| LXCDeployment::is_frozen(&self.executor, name));
+        if !frozen.is_empty() {
+            let question = format!("Gelé(s) (FROZEN) : {}. Dégeler au lieu de démarrer ?", frozen.join(", "));
+            if self.ask_yes_no_default("Démarrer Container", &question, false) {
+                self.containers_batch("Dégeler les containers", "Dégel", &frozen, |executor, name| LXCDeployment::unfreeze_container(executor, name));
             }
+        }
 
-            self.ui.clear_line(y - 1);
-            self.ui.set_color(Color::Info);
-            self.ui.draw_text(box_x + 2, y - 1, &format!("Démarrage du container '{}'...", container_name));
+        match names.as_slice() {
+            [] => {}
+            [name] => self.container_start(name),
+            _ => self.containers_batch("Démarrer les containers", "Démarrage", &names, LXCDeployment::start_container_by_name),
+        }
+    }
+
+    fn container_start(&mut self, container_name: &str) {
+        self.ui.clear_screen();
+        self.ui.draw_header("Démarrer Container LXC");
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let mut y = box_y + 5;
+
+        // Vérifier d'abord si le container a une configuration valide
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, y, &format!("Vérification de la configuration du container '{}'...", container_name));
+        y += 1;
+        io::stdout().flush().unwrap();
+
+        let config_check = format!("test -f /var/lib/lxc/{}/config && echo 'found' || echo 'not found'", container_name);
+        let has_config = self.executor.run_shell(&config_check, true)
+            .map(|o| o.stdout.contains("found"))
+            .unwrap_or(false);
+
+        if !has_config {
+            self.ui.clear_line(y - 1);
+            self.ui.set_color(Color::Error);
+            self.ui.draw_text(box_x + 2, y - 1, "✗ Configuration du container introuvable!");
+            y += 1;
+            self.ui.set_color(Color::Warning);
+            self.ui.draw_text(box_x + 2, y, "Le container semble être corrompu ou incomplet.");
+            y += 1;
+            self.ui.set_color(Color::Fg);
+            self.ui.draw_text(box_x + 2, y, "Solutions possibles:");
+            y += 1;
+            self.ui.draw_text(box_x + 4, y, "1. Réinstaller le container (menu: Réinstaller)");
+            y += 1;
+            self.ui.draw_text(box_x + 4, y, "2. Supprimer et recréer le container");
             y += 2;
+            self.ui.set_color(Color::Reset);
+            self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
+            let _ = self.input_reader.read_key();
+            return;
+        }
 
-            match LXCDeployment::start_container_by_name(&self.executor, &container_name) {
-                Ok(output) => {
-                    if output.exit_code == Some(0) {
-                        self.ui.set_color(Color::Success);
-                        self.ui.draw_text(box_x + 2, y, "Container démarré avec succès!");
-                        
-                        // Vérifier si RMDB est installé dans le container
-                        y += 2;
+        self.ui.clear_line(y - 1);
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, y - 1, &format!("Démarrage du container '{}'...", container_name));
+        y += 2;
+
+        match LXCDeployment::start_container_by_name(&self.executor, container_name) {
+            Ok(output) => {
+                if output.exit_code == Some(0) {
+                    self.ui.set_color(Color::Success);
+                    self.ui.draw_text(box_x + 2, y, "Container démarré avec succès!");
+                    
+                    // Vérifier si RMDB est installé dans le container
+                    y += 2;
+                    self.ui.set_color(Color::Info);
+                    self.ui.draw_text(box_x + 2, y, "Vérification de l'installation de RMDB...");
+                    y += 1;
+                    io::stdout().flush().unwrap();
+                    
+                    let rmdb_check = format!("lxc-attach -n {} -- test -f /usr/local/bin/rmdbd && echo 'installed' || echo 'not installed'", container_name);
+                    let rmdb_installed = self.executor.run_shell(&rmdb_check, true)
+                        .map(|o| o.stdout.contains("installed"))
+                        .unwrap_or(false);
+                    
+                    if !rmdb_installed && container_name == self.default_container {
+                        self.ui.clear_line(y - 1);
+                        self.ui.set_color(Color::Warning);
+                        self.ui.draw_text(box_x + 2, y - 1, "⚠ RMDB n'est pas installé dans le container.");
+                        y += 1;
+                        self.ui.set_color(Color::Fg);
+                        self.ui.draw_text(box_x + 2, y, "Pour installer RMDB:");
+                        y += 1;
+                        self.ui.draw_text(box_x + 4, y, "1. Utilisez le menu 'Ajouter' pour créer un nouveau container avec RMDB");
+                        y += 1;
+                        self.ui.draw_text(box_x + 4, y, &format!("2. Ou réinstallez le container '{}' (menu: Réinstaller)", self.default_container));
+                        y += 1;
                         self.ui.set_color(Color::Info);
-                        self.ui.draw_text(box_x + 2, y, "Vérification de l'installation de RMDB...");
+                        self.ui.draw_text(box_x + 2, y, "Note: L'installation de RMDB nécessite rmdb_source.");
                         y += 1;
-                        io::stdout().flush().unwrap();
-                        
-                        let rmdb_check = format!("lxc-attach -n {} -- test -f /usr/local/bin/rmdbd && echo 'installed' || echo 'not installed'", container_name);
-                        let rmdb_installed = self.executor.run_shell(&rmdb_check, true)
-                            .map(|o| o.stdout.contains("installed"))
-                            .unwrap_or(false);
-                        
-                        if !rmdb_installed && container_name == "rmdb" {
-                            self.ui.clear_line(y - 1);
-                            self.ui.set_color(Color::Warning);
-                            self.ui.draw_text(box_x + 2, y - 1, "⚠ RMDB n'est pas installé dans le container.");
-                            y += 1;
-                            self.ui.set_color(Color::Fg);
-                            self.ui.draw_text(box_x + 2, y, "Pour installer RMDB:");
-                            y += 1;
-                            self.ui.draw_text(box_x + 4, y, "1. Utilisez le menu 'Ajouter' pour créer un nouveau container avec RMDB");
-                            y += 1;
-                            self.ui.draw_text(box_x + 4, y, "2. Ou réinstallez le container 'rmdb' (menu: Réinstaller)");
-                            y += 1;
-                            self.ui.set_color(Color::Info);
-                            self.ui.draw_text(box_x + 2, y, "Note: L'installation de RMDB nécessite rmdb_source.");
-                            y += 1;
-                        } else if rmdb_installed {
-                            self.ui.clear_line(y - 1);
-                            self.ui.set_color(Color::Success);
-                            self.ui.draw_text(box_x + 2, y - 1, "✓ RMDB est installé dans le container.");
-                        }
-                    } else {
-                        self.ui.set_color(Color::Error);
-                        self.ui.draw_text(box_x + 2, y, "Échec du démarrage du container.");
+                    } else if rmdb_installed {
+                        self.ui.clear_line(y - 1);
+                        self.ui.set_color(Color::Success);
+                        self.ui.draw_text(box_x + 2, y - 1, "✓ RMDB est installé dans le container.");
+                    }
+                } else {
+                    self.ui.set_color(Color::Error);
+                    self.ui.draw_text(box_x + 2, y, "Échec du démarrage du container.");
+                    y += 1;
+                    
+                    // Analyser l'erreur pour donner des conseils
+                    let stderr = output.stderr.to_lowercase();
+                    if stderr.contains("no container config") || stderr.contains("config specified") {
+                        self.ui.set_color(Color::Warning);
+                        self.ui.draw_text(box_x + 2, y, "Le container n'a pas de configuration valide.");
                         y += 1;
-                        
-                        // Analyser l'erreur pour donner des conseils
-                        let stderr = output.stderr.to_lowercase();
-                        if stderr.contains("no container config") || stderr.contains("config specified") {
-                            self.ui.set_color(Color::Warning);
-                            self.ui.draw_text(box_x + 2, y, "Le container n'a pas de configuration valide.");
-                            y += 1;
+                        self.ui.set_color(Color::Fg);
+                        self.ui.draw_text(box_x + 2, y, "Solutions:");
+                        y += 1;
+                        self.ui.draw_text(box_x + 4, y, "1. Réinstaller le container (menu: Réinstaller)");
+                        y += 1;
+                        self.ui.draw_text(box_x + 4, y, "2. Vérifier que le container a été créé correctement");
+                        y += 1;
+                    } else {
+                        if !output.stderr.is_empty() {
                             self.ui.set_color(Color::Fg);
-                            self.ui.draw_text(box_x + 2, y, "Solutions:");
+                            let error_line = output.stderr.lines().next().unwrap_or("Erreur inconnue");
+                            self.ui.draw_text(box_x + 2, y, error_line);
                             y += 1;
-                            self.ui.draw_text(box_x + 4, y, "1. Réinstaller le container (menu: Réinstaller)");
-                            y += 1;
-                            self.ui.draw_text(box_x + 4, y, "2. Vérifier que le container a été créé correctement");
-                            y += 1;
-                        } else {
-                            if !output.stderr.is_empty() {
-                                self.ui.set_color(Color::Fg);
-                                let error_line = output.stderr.lines().next().unwrap_or("Erreur inconnue");
-                                self.ui.draw_text(box_x + 2, y, error_line);
-                                y += 1;
-                            }
                         }
                     }
                 }
-                Err(e) => {
-                    self.ui.set_color(Color::Error);
-                    self.ui.draw_text(box_x + 2, y, &format!("Erreur: {}", e));
-                }
             }
+            Err(e) => {
+                self.ui.set_color(Color::Error);
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("Erreur: {}", e), usize::MAX);
+            }
+        }
 
-            self.ui.set_color(Color::Reset);
-            self.ui.draw_text(box_x + 2, y + 3, "Appuyez sur une touche pour continuer...");
-            let _ = self.input_reader.read_key();
+        self.ui.set_color(Color::Reset);
+        self.ui.draw_text(box_x + 2, y + 3, "Appuyez sur une touche pour continuer...");
+        let _ = self.input_reader.read_key();
+    }
+
+    /// Gèle (`freeze`) ou dégèle les containers choisis
+    fn containers_freeze(&mut self, freeze: bool) {
+        let (title, verb) = if freeze { ("Geler les containers", "Gel") } else { ("Dégeler les containers", "Dégel") };
+        let names = self.select_containers(title);
+        if names.is_empty() {
+            return;
+        }
+        if freeze {
+            self.containers_batch(title, verb, &names, |executor, name| LXCDeployment::freeze_container(executor, name));
+        } else {
+            self.containers_batch(title, verb, &names, |executor, name| LXCDeployment::unfreeze_container(executor, name));
         }
     }
 
     fn containers_stop(&mut self) {
-        if let Some(container_name) = self.select_container("Stopper Container") {
-            self.ui.clear_screen();
-            self.ui.draw_header("Arrêter Container LXC");
-            let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
-            let mut y = box_y + 5;
+        let names = self.select_containers("Stopper Container");
+        match names.as_slice() {
+            [] => {}
+            [name] => self.container_stop(name),
+            _ => self.containers_batch("Arrêter les containers", "Arrêt", &names, LXCDeployment::stop_container_by_name),
+        }
+    }
 
-            self.ui.set_color(Color::Info);
-            self.ui.draw_text(box_x + 2, y, &format!("Arrêt du container '{}'...", container_name));
-            y += 2;
+    fn container_stop(&mut self, container_name: &str) {
+        self.ui.clear_screen();
+        self.ui.draw_header("Arrêter Container LXC");
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let mut y = box_y + 5;
 
-            match LXCDeployment::stop_container_by_name(&self.executor, &container_name) {
-                Ok(output) => {
-                    if output.exit_code == Some(0) {
-                        self.ui.set_color(Color::Success);
-                        self.ui.draw_text(box_x + 2, y, "Container arrêté avec succès!");
-                    } else {
-                        self.ui.set_color(Color::Error);
-                        self.ui.draw_text(box_x + 2, y, "Échec de l'arrêt du container.");
-                        if !output.stderr.is_empty() {
-                            self.ui.draw_text(box_x + 2, y + 1, &output.stderr.lines().next().unwrap_or("Erreur inconnue"));
-                        }
-                    }
-                }
-                Err(e) => {
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, y, &format!("Arrêt du container '{}'...", container_name));
+        y += 2;
+
+        match LXCDeployment::stop_container_by_name(&self.executor, container_name) {
+            Ok(output) => {
+                if output.exit_code == Some(0) {
+                    self.ui.set_color(Color::Success);
+                    self.ui.draw_text(box_x + 2, y, "Container arrêté avec succès!");
+                } else {
                     self.ui.set_color(Color::Error);
-                    self.ui.draw_text(box_x + 2, y, &format!("Erreur: {}", e));
+                    self.ui.draw_text(box_x + 2, y, "Échec de l'arrêt du container.");
+                    if !output.stderr.is_empty() {
+                        self.ui.draw_text(box_x + 2, y + 1, output.stderr.lines().next().unwrap_or("Erreur inconnue"));
+                    }
                 }
             }
-
-            self.ui.set_color(Color::Reset);
-            self.ui.draw_text(box_x + 2, y + 3, "Appuyez sur une touche pour continuer...");
-            let _ = self.input_reader.read_key();
+            Err(e) => {
+                self.ui.set_color(Color::Error);
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("Erreur: {}", e), usize::MAX);
+            }
         }
+
+        self.ui.set_color(Color::Reset);
+        self.ui.draw_text(box_x + 2, y + 3, "Appuyez sur une touche pour continuer...");
+        let _ = self.input_reader.read_key();
     }
 
-    fn containers_destroy(&mut self) {
-        if let Some(container_name) = self.select_container("Supprimer Container") {
-            let confirm = self.ask_yes_no(
-                "Suppression Container",
-                &format!("Êtes-vous sûr de vouloir supprimer le container '{}' ? Cette action est irréversible.", container_name)
-            );
+    /// Clone un container existant sous un nouveau nom (`lxc-copy`)
+    fn containers_clone(&mut self) {
+        let Some(source) = self.select_container("Cloner Container") else {
+            return;
+        };
 
-            if !confirm {
+        self.ui.clear_screen();
+        self.ui.draw_header("Cloner Container");
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let mut y = box_y + 5;
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text_clipped(box_x + 2, y, &format!("Nom du clone de '{}' (vide pour annuler) :", source), usize::MAX);
+        y += 1;
+        let Some(target) = read_line(&self.ui, &self.input_reader, box_x + 4, y) else {
+            return;
+        };
+        let target = target.trim();
+        if target.is_empty() {
+            return;
+        }
+        let target = match validate_container_name(target) {
+            Ok(target) => target,
+            Err(e) => {
+                self.show_error_message("Cloner Container", &format!("Nom invalide: {}", e));
                 return;
             }
+        };
+        if !LXCDeployment::check_container_fully_removed(&self.executor, &target) {
+            self.show_error_message(
+                "Cloner Container",
+                &format!("Le container '{}' existe déjà. Choisissez un autre nom ou supprimez-le d'abord.", target),
+            );
+            return;
+        }
 
-            self.ui.clear_screen();
-            self.ui.draw_header("Supprimer Container LXC");
-            let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
-            let mut y = box_y + 5;
+        let Some(mode) = SelectList::new("Mode de clonage", &CloneMode::ALL, |mode| mode.label().to_string())
+            .with_prompt(&format!("Cloner '{}' vers '{}' :", source, target))
+            .with_hint("Flèches: Sélectionner | Entrée: Valider | Q: Annuler")
+            .select(&self.ui, &self.input_reader)
+            .map(|i| CloneMode::ALL[i])
+        else {
+            return;
+        };
 
-            self.ui.set_color(Color::Warning);
-            self.ui.draw_text(box_x + 2, y, &format!("Suppression du container '{}'...", container_name));
-            y += 2;
+        let running = LXCDeployment::info_fields(&self.executor, &source).get("State").is_some_and(|state| state == "RUNNING");
+        if running
+            && !self.ask_yes_no_default(
+                "Cloner Container",
+                &format!("Le container '{}' est démarré : il sera arrêté pendant la copie, puis redémarré.\n\nContinuer ?", source),
+                true,
+            )
+        {
+            return;
+        }
 
-            match LXCDeployment::destroy_container_by_name(&self.executor, &container_name) {
-                Ok(output) => {
-                    if output.exit_code == Some(0) {
-                        self.ui.set_color(Color::Success);
-                        self.ui.draw_text(box_x + 2, y, "Container supprimé avec succès!");
-                    } else {
-                        self.ui.set_color(Color::Error);
-                        self.ui.draw_text(box_x + 2, y, "Échec de la suppression du container.");
-                        if !output.stderr.is_empty() {
-                            self.ui.draw_text(box_x + 2, y + 1, &output.stderr.lines().next().unwrap_or("Erreur inconnue"));
-                        }
+        self.ui.clear_screen();
+        self.ui.draw_header("Cloner Container");
+        let mut y = box_y + 5;
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text_clipped(box_x + 2, y, &format!("Clonage de '{}' vers '{}' ({})...", source, target, mode.label()), usize::MAX);
+        y += 2;
+        io::stdout().flush().unwrap();
+
+        self.forget_container_init(&target);
+        match LXCDeployment::clone_container(&self.executor, &source, &target, mode) {
+            Ok(()) => {
+                self.ui.set_color(Color::Success);
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("✓ Container '{}' créé.", target), usize::MAX);
+                if mode == CloneMode::Overlay {
+                    y += 1;
+                    self.ui.set_color(Color::Warning);
+                    self.ui.draw_text_clipped(box_x + 2, y, &format!("Le clone dépend de '{}' : ne supprimez pas la source.", source), usize::MAX);
+                }
+            }
+            Err(e) => {
+                self.ui.set_color(Color::Error);
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ {}", e), usize::MAX);
+            }
+        }
+
+        self.ui.set_color(Color::Reset);
+        self.ui.draw_text(box_x + 2, y + 3, "Appuyez sur une touche pour continuer...");
+        let _ = self.input_reader.read_key();
+    }
+
+    fn containers_destroy(&mut self) {
+        let names = self.select_containers("Supprimer Container");
+        match names.as_slice() {
+            [] => {}
+            [name] => self.container_destroy(name),
+            _ => {
+                // Une seule confirmation, avec la liste complète des containers visés
+                let confirm = self.ask_yes_no_default(
+                    "Suppression de containers",
+                    &format!("Supprimer {} containers ({}) ? Cette action est irréversible.", names.len(), names.join(", ")),
+                    true
+                );
+                if confirm {
+                    for name in &names {
+                        self.forget_container_init(name);
                     }
+                    self.containers_batch("Supprimer les containers", "Suppression", &names, LXCDeployment::destroy_container_by_name);
                 }
-                Err(e) => {
+            }
+        }
+    }
+
+    fn container_destroy(&mut self, container_name: &str) {
+        let confirm = self.ask_yes_no_default(
+            "Suppression Container",
+            &format!("Êtes-vous sûr de vouloir supprimer le container '{}' ? Cette action est irréversible.", container_name),
+            true
+        );
+
+        if !confirm {
+            return;
+        }
+
+        self.ui.clear_screen();
+        self.ui.draw_header("Supprimer Container LXC");
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let mut y = box_y + 5;
+
+        self.ui.set_color(Color::Warning);
+        self.ui.draw_text(box_x + 2, y, &format!("Suppression du container '{}'...", container_name));
+        y += 2;
+
+        self.forget_container_init(container_name);
+        match LXCDeployment::destroy_container_by_name(&self.executor, container_name) {
+            Ok(output) => {
+                if output.exit_code == Some(0) {
+                    self.ui.set_color(Color::Success);
+                    self.ui.draw_text(box_x + 2, y, "Container supprimé avec succès!");
+                } else {
                     self.ui.set_color(Color::Error);
-                    self.ui.draw_text(box_x + 2, y, &format!("Erreur: {}", e));
+                    self.ui.draw_text(box_x + 2, y, "Échec de la suppression du container.");
+                    if !output.stderr.is_empty() {
+                        self.ui.draw_text(box_x + 2, y + 1, output.stderr.lines().next().unwrap_or("Erreur inconnue"));
+                    }
                 }
             }
-
-            self.ui.set_color(Color::Reset);
-            self.ui.draw_text(box_x + 2, y + 3, "Appuyez sur une touche pour continuer...");
-            let _ = self.input_reader.read_key();
+            Err(e) => {
+                self.ui.set_color(Color::Error);
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("Erreur: {}", e), usize::MAX);
+            }
         }
+
+        self.ui.set_color(Color::Reset);
+        self.ui.draw_text(box_x + 2, y + 3, "Appuyez sur une touche pour continuer...");
+        let _ = self.input_reader.read_key();
     }
 
     fn containers_restart(&mut self) {
@@ -3385,7 +5424,7 @@ impl MainApp {
                 }
                 Err(e) => {
                     self.ui.set_color(Color::Error);
-                    self.ui.draw_text(box_x + 2, y, &format!("Erreur: {}", e));
+                    self.ui.draw_text_clipped(box_x + 2, y, &format!("Erreur: {}", e), usize::MAX);
                 }
             }
 
@@ -3408,7 +5447,7 @@ impl MainApp {
 
         // Demander le nom du container
         self.ui.set_color(Color::Fg);
-        self.ui.draw_text(box_x + 2, y, "Nom du container (ou Entrée pour 'rmdb'):");
+        self.ui.draw_text(box_x + 2, y, &format!("Nom du container (ou Entrée pour '{}'):", self.default_container));
         y += 1;
         self.ui.set_color(Color::Reset);
         io::stdout().flush().unwrap();
@@ -3418,42 +5457,52 @@ impl MainApp {
             match self.input_reader.read_key() {
                 Ok(Key::Enter) => {
                     if container_name.trim().is_empty() {
-                        container_name = "rmdb".to_string();
+                        container_name = self.default_container.clone();
+                    }
+                    match validate_container_name(&container_name) {
+                        Ok(name) => container_name = name,
+                        Err(e) => {
+                            self.show_error_message("Ajouter Container", &format!("Nom invalide: {}", e));
+                            return;
+                        }
                     }
                     break;
                 }
-                Ok(Key::Backspace) => {
-                    if !container_name.is_empty() {
-                        container_name.pop();
-                        self.ui.set_cursor(box_x + 2 + container_name.len() as u16, y);
-                        print!(" ");
-                        self.ui.set_cursor(box_x + 2 + container_name.len() as u16, y);
-                        io::stdout().flush().unwrap();
-                    }
+                Ok(Key::Backspace) if !container_name.is_empty() => {
+                    container_name.pop();
+                    self.ui.set_cursor(box_x + 2 + container_name.len() as u16, y);
+                    print!(" ");
+                    self.ui.set_cursor(box_x + 2 + container_name.len() as u16, y);
+                    io::stdout().flush().unwrap();
                 }
-                Ok(Key::Char(c)) => {
-                    if c.is_alphanumeric() || c == '-' || c == '_' {
-                        container_name.push(c);
-                        print!("{}", c);
-                        io::stdout().flush().unwrap();
-                    }
+                Ok(Key::Char(c)) if c.is_ascii_alphanumeric() || c == '-' || c == '_' => {
+                    container_name.push(c);
+                    print!("{}", c);
+                    io::stdout().flush().unwrap();
                 }
-                Ok(Key::Quit) => {
+                Ok(Key::Quit) | Ok(Key::Interrupt) => {
                     return;
                 }
                 _ => {}
             }
         }
 
+        if !self.confirm_no_vm_collision(&container_name) {
+            return;
+        }
+
         // Vérifier si le container existe déjà en utilisant check_container_exists_with_executor
         // Cette méthode vérifie réellement via LXC, pas seulement via list_all_containers qui peut avoir des placeholders
         let lxc_deploy = LXCDeployment::new(container_name.clone(), "3.20".to_string());
         if lxc_deploy.check_container_exists_with_executor(&self.executor) {
             // Vérifier aussi via lxc-ls pour confirmer
-            let cmd_verify = format!("sudo -n lxc-ls -1 2>/dev/null | grep -q '^{}$' && echo 'found' || echo 'not found'", container_name);
-            let is_really_managed = self.executor.run_shell(&cmd_verify, true)
-                .map(|o| o.stdout.contains("found"))
-                .unwrap_or(false);
+            let is_really_managed = match LXCDeployment::managed_container_names(&self.executor) {
+                Ok(names) => names.contains(&container_name),
+                Err(e) => {
+                    self.show_error_message("Ajouter Container", &format!("Impossible de vérifier les containers gérés: {}", e));
+                    return;
+                }
+            };
             
             if is_really_managed {
                 self.ui.set_color(Color::Warning);
@@ -3474,33 +5523,29 @@ impl MainApp {
                 
                 let should_clean = self.ask_yes_no("Nettoyage", "Supprimer le répertoire existant ?");
                 if should_clean {
-                    let cmd_clean = format!("sudo -n rm -rf /var/lib/lxc/{} 2>&1", container_name);
-                    if let Ok(output) = self.executor.run_shell(&cmd_clean, true) {
-                        if output.exit_code == Some(0) || output.stdout.is_empty() {
+                    // Le répertoire est déplacé dans la corbeille plutôt que supprimé
+                    match LXCDeployment::backup_before_remove(&self.executor, &container_name) {
+                        Ok(backup) => {
                             self.ui.set_color(Color::Success);
-                            self.ui.draw_text(box_x + 2, y + 2, "✓ Répertoire supprimé. Vous pouvez maintenant créer le container.");
+                            self.ui.draw_text(box_x + 2, y + 2, "✓ Répertoire déplacé. Vous pouvez maintenant créer le container.");
+                            y += 1;
+                            self.ui.set_color(Color::Fg);
+                            self.ui.draw_text(box_x + 2, y + 2, &format!("Sauvegarde: {}", backup.display()));
                             y += 2;
                             self.ui.set_color(Color::Reset);
                             self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
                             let _ = self.input_reader.read_key();
                             // Continuer avec la création
-                        } else {
+                        }
+                        Err(e) => {
                             self.ui.set_color(Color::Error);
-                            self.ui.draw_text(box_x + 2, y + 2, &format!("✗ Erreur lors de la suppression: {}", output.stdout));
+                            self.ui.draw_text(box_x + 2, y + 2, &format!("✗ Erreur lors de la suppression: {}", e));
                             y += 2;
                             self.ui.set_color(Color::Reset);
                             self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
                             let _ = self.input_reader.read_key();
                             return;
                         }
-                    } else {
-                        self.ui.set_color(Color::Error);
-                        self.ui.draw_text(box_x + 2, y + 2, "✗ Impossible de supprimer le répertoire.");
-                        y += 2;
-                        self.ui.set_color(Color::Reset);
-                        self.ui.draw_text(box_x + 2, y + 2, "Appuyez sur une touche pour continuer...");
-                        let _ = self.input_reader.read_key();
-                        return;
                     }
                 } else {
                     // L'utilisateur a refusé le nettoyage
@@ -3517,7 +5562,9 @@ impl MainApp {
         io::stdout().flush().unwrap();
 
         // Utiliser la logique de création existante mais avec le nom personnalisé
-        let lxc_deploy = LXCDeployment::new(container_name.clone(), "3.20".to_string());
+        let timings = self.tui_config.deploy_timings();
+        self.forget_container_init(&container_name);
+        let lxc_deploy = LXCDeployment::new(container_name.clone(), "3.20".to_string()).with_timings(timings);
         match lxc_deploy.create_container(&self.executor) {
             Ok(output) => {
                 if output.exit_code == Some(0) {
@@ -3530,7 +5577,7 @@ impl MainApp {
                     self.ui.draw_text(box_x + 2, y, "Démarrage du container...");
                     y += 1;
                     let _ = lxc_deploy.start_container(&self.executor);
-                    std::thread::sleep(std::time::Duration::from_secs(2));
+                    std::thread::sleep(timings.container_start());
 
                     self.ui.set_color(Color::Success);
                     self.ui.draw_text(box_x + 2, y, "Container démarré!");
@@ -3544,7 +5591,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("Erreur: {}", e), usize::MAX);
             }
         }
 
@@ -3553,387 +5600,919 @@ impl MainApp {
         let _ = self.input_reader.read_key();
     }
 
-    fn containers_reinstall(&mut self) {
-        if let Some(container_name) = self.select_container("Réinstaller Container") {
-            let confirm = self.ask_yes_no(
-                "Réinstallation Container",
-                &format!("Êtes-vous sûr de vouloir réinstaller le container '{}' ?\nCette action va supprimer complètement le container et le recréer.", container_name)
-            );
+    /// Avertit si une VM de l'API porte déjà ce nom ; renvoie `false` si l'utilisateur renonce
+    ///
+    /// Containers et VMs sont des espaces de noms distincts : ce n'est qu'un avertissement.
+    /// Sans API joignable, la vérification est ignorée.
+    fn confirm_no_vm_collision(&mut self, container_name: &str) -> bool {
+        let Ok(vms) = self.api_client().get_vms(None) else {
+            return true;
+        };
+        let collisions = vm_name_collisions(vms.iter().map(|vm| vm.name.as_str()), container_name);
+        if collisions.is_empty() {
+            return true;
+        }
+        let proceed = self.ask_yes_no_default(
+            "Nom déjà utilisé par une VM",
+            &format!(
+                "⚠ Une VM s'appelle déjà '{}'.\n\
+                 Container et VM ne se gênent pas, mais deux éléments RMDB du même nom prêtent à confusion.\n\n\
+                 Créer quand même le container '{}' ?",
+                collisions[0], container_name
+            ),
+            true,
+        );
+        if proceed {
+            // ask_yes_no a effacé l'écran de création
+            self.ui.clear_screen();
+            self.ui.draw_header("Ajouter Container Alpine Linux");
+        }
+        proceed
+    }
 
-            if !confirm {
+    /// Résumé affiché avant une réinstallation : dernier déploiement et taille du template
+    fn reinstall_summary(&self) -> String {
+        let mut lines = Vec::new();
+        let last = deploy_cache::load_last_deploy();
+        match &last {
+            Some(record) => lines.push(format!(
+                "Dernier déploiement ({}) : {} le {}",
+                record.container,
+                format_duration(record.duration_secs),
+                record.finished_at.get(..16).unwrap_or(&record.finished_at).replace('T', " ")
+            )),
+            None => lines.push("Aucun déploiement précédent enregistré.".to_string()),
+        }
+        // Le template déjà en cache évite le téléchargement ; sinon la dernière taille connue sert d'estimation
+        lines.push(match LXCDeployment::template_cache_size(&self.executor) {
+            Some(bytes) => format!("Template Alpine : {} en cache, pas de téléchargement", format_size(bytes)),
+            None => match last.and_then(|r| r.template_size_bytes) {
+                Some(bytes) => format!("Template Alpine : ~{} à télécharger", format_size(bytes)),
+                None => "Template Alpine : absent du cache, taille inconnue".to_string(),
+            },
+        });
+        lines.push("RMDB ne sera pas réinstallé automatiquement (container seul).".to_string());
+        lines.join("\n")
+    }
+
+    /// Enregistre la durée du déploiement et la taille du template dans le cache
+    fn record_deploy(&mut self, container: &str, started: std::time::Instant, rmdb_installed: bool) {
+        let record = DeployRecord {
+            container: container.to_string(),
+            finished_at: chrono::Local::now().to_rfc3339(),
+            duration_secs: started.elapsed().as_secs(),
+            template_size_bytes: LXCDeployment::template_cache_size(&self.executor),
+            rmdb_installed,
+        };
+        // Le cache est indicatif : un échec d'écriture ne doit pas interrompre le déploiement
+        let _ = deploy_cache::save_last_deploy(&record);
+    }
+
+    /// Ajoute une tentative à l'historique des déploiements
+    ///
+    /// Le journal associé est le plus récent créé depuis le début de la tentative.
+    fn record_history(
+        &self,
+        kind: DeployKind,
+        target: &str,
+        started_at: chrono::DateTime<chrono::Local>,
+        started: std::time::Instant,
+        result: DeployResult,
+    ) {
+        let since = started_at.timestamp().max(0) as u64;
+        let log_path = DeploymentLogger::list_logs()
+            .into_iter()
+            .find(|path| DeploymentLogger::log_timestamp(path).is_some_and(|ts| ts >= since));
+        let entry = HistoryEntry {
+            started_at: started_at.to_rfc3339(),
+            kind,
+            target: target.to_string(),
+            result,
+            duration_secs: started.elapsed().as_secs(),
+            log_path,
+        };
+        // Historique indicatif : un échec d'écriture est seulement signalé dans tui.log
+        if let Err(e) = DeployHistory::new().record(entry) {
+            log_warning(&format!("Historique des déploiements non enregistré: {}", e));
+        }
+    }
+
+    /// Historique des déploiements : Entrée ouvre le journal de la tentative choisie
+    fn show_deploy_history(&mut self) {
+        let entries = DeployHistory::new().list();
+        if entries.is_empty() {
+            self.show_message("Historique des déploiements", "Aucun déploiement ni installation enregistré.");
+            return;
+        }
+
+        let mut selected = 0;
+        loop {
+            let title = format!("Historique des déploiements ({})", entries.len());
+            let outcome = SelectList::new(&title, &entries, |entry| {
+                let date = chrono::DateTime::parse_from_rfc3339(&entry.started_at)
+                    .map(|dt| dt.with_timezone(&chrono::Local).format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_else(|_| entry.started_at.clone());
+                format!(
+                    "{}  {:<25} {:<12} {:<8} {}",
+                    date,
+                    entry.kind.label(),
+                    truncate_string(&entry.target, 12),
+                    entry.result.label(),
+                    format_duration(entry.duration_secs)
+                )
+            })
+                .with_detail(|entry| match &entry.log_path {
+                    Some(path) => path.display().to_string(),
+                    None => "(pas de journal)".to_string(),
+                })
+                .with_color(|entry| match entry.result {
+                    DeployResult::Success => Color::Success,
+                    DeployResult::Failed => Color::Error,
+                    DeployResult::Cancelled => Color::Warning,
+                })
+                .with_hint("Flèches: Naviguer | Entrée: Ouvrir le journal | Q: Retour")
+                .with_selected(selected)
+                .run(&self.ui, &self.input_reader);
+            match outcome {
+                SelectOutcome::Selected(index) => {
+                    selected = index;
+                    match entries[index].log_path.clone() {
+                        Some(path) => self.view_deployment_log(&path, self.log_level()),
+                        None => self.show_message("Historique des déploiements", "Cette tentative n'a pas de journal de déploiement."),
+                    }
+                }
+                SelectOutcome::Action(..) => {}
+                SelectOutcome::Cancelled => return,
+            }
+        }
+    }
+
+    /// Containers fantômes : répertoires non gérés, containers listés sans répertoire, doublons LXC/LXD
+    fn containers_ghosts(&mut self) {
+        let title = "Containers fantômes";
+        let mut selected = 0;
+        loop {
+            let ghosts = match LXCDeployment::find_ghost_containers(&self.executor) {
+                Ok(ghosts) => ghosts,
+                Err(e) => {
+                    self.show_error_message(title, &format!("Analyse annulée, la liste des containers LXC est illisible: {}", e));
+                    return;
+                }
+            };
+            if ghosts.is_empty() {
+                self.show_message(title, "Aucun container fantôme : le disque et LXC concordent.");
                 return;
             }
+            let name_width = column_width("Nom", ghosts.iter().map(|g| g.name.as_str()), 30);
+            let outcome = SelectList::new(title, &ghosts, |ghost| {
+                format!("{}  {:<22} {}", pad_to_width(&ghost.name, name_width), ghost.kind.label(), ghost.paths.join(", "))
+            })
+                .with_prompt("Incohérences entre le disque et LXC :")
+                .with_color(|ghost| if ghost.can_clean() { Color::Warning } else { Color::Error })
+                .with_hint("Flèches: Naviguer | Entrée: Nettoyer | Q: Retour")
+                .with_selected(selected)
+                .run(&self.ui, &self.input_reader);
+            let index = match outcome {
+                SelectOutcome::Selected(index) => index,
+                SelectOutcome::Action(..) => continue,
+                SelectOutcome::Cancelled => return,
+            };
+            selected = index;
+            let ghost = &ghosts[index];
+
+            match ghost.kind {
+                GhostKind::OnDiskOnly if ghost.can_clean() => {
+                    let question = format!("Déplacer {} dans la corbeille ({}) ?", ghost.paths.join(", "), LXC_TRASH_DIR);
+                    if !self.ask_yes_no_default(title, &question, true) {
+                        continue;
+                    }
+                    match LXCDeployment::backup_before_remove(&self.executor, &ghost.name) {
+                        Ok(backup) => self.show_message(title, &format!("Répertoire déplacé : {}", backup.display())),
+                        Err(e) => self.show_error_message(title, &format!("Échec du déplacement: {}", e)),
+                    }
+                }
+                GhostKind::ListedOnly => {
+                    let question = format!("Supprimer les verrous LXC restants de '{}' ?", ghost.name);
+                    if !self.ask_yes_no_default(title, &question, true) {
+                        continue;
+                    }
+                    match LXCDeployment::cleanup_ghost_container(&self.executor, &ghost.name) {
+                        Ok(_) => self.show_message(title, &format!("Verrous de '{}' supprimés.", ghost.name)),
+                        Err(e) => self.show_error_message(title, &format!("Échec du nettoyage: {}", e)),
+                    }
+                }
+                GhostKind::OnDiskOnly => self.show_message(
+                    title,
+                    &format!("{} relève de LXD : vérifiez-le avec `lxc list` avant de le supprimer à la main.", ghost.paths.join(", ")),
+                ),
+                GhostKind::Duplicate => self.show_message(
+                    title,
+                    &format!("'{}' existe sous LXC et LXD ({}) : supprimez à la main le répertoire inutile.", ghost.name, ghost.paths.join(", ")),
+                ),
+            }
+        }
+    }
 
-            self.ui.clear_screen();
-            self.ui.draw_header("Réinstaller Container");
-            let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
-            let mut y = box_y + 5;
+    /// Bascule le démarrage automatique d'un container au boot
+    fn containers_autostart(&mut self) {
+        let container_name = match self.select_container("Démarrage automatique") {
+            Some(name) => name,
+            None => return,
+        };
+        let current = LXCDeployment::list_all_containers_detailed(&self.executor)
+            .into_iter()
+            .find(|c| c.name == container_name)
+            .and_then(|c| c.autostart)
+            .unwrap_or(false);
 
-            // Étape 1: Arrêter le container
-            self.ui.set_color(Color::Info);
-            self.ui.draw_text(box_x + 2, y, &format!("Arrêt du container '{}'...", container_name));
+        let question = if current {
+            format!("Le démarrage automatique est activé pour '{}'.\nVoulez-vous le désactiver ?", container_name)
+        } else {
+            format!("Le démarrage automatique est désactivé pour '{}'.\nVoulez-vous l'activer (délai de 5 s) ?", container_name)
+        };
+        if !self.ask_yes_no("Démarrage automatique", &question) {
+            return;
+        }
+
+        match LXCDeployment::set_autostart(&self.executor, &container_name, !current, Some(5)) {
+            Ok(()) => {
+                let state = if current { "désactivé" } else { "activé" };
+                self.show_message("Démarrage automatique", &format!("✓ Démarrage automatique {} pour '{}'.", state, container_name));
+            }
+            Err(e) => {
+                self.show_error_message("Démarrage automatique", &format!("✗ {}", e));
+            }
+        }
+    }
+
+    /// Vérifie que RMDB sert bien ses requêtes à l'intérieur du container choisi
+    /// Liste de contrôle de l'installation sur l'hôte (binaire, configuration, service, répertoires)
+    fn host_verify_install(&mut self) {
+        self.ui.clear_screen();
+        self.ui.draw_header("Vérification de l'installation");
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let mut y = box_y + 5;
+
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, y, "Vérification en cours...");
+        io::stdout().flush().unwrap();
+        let checks = HostDeployment::new().verify_install(&self.executor);
+        self.ui.clear_line(y);
+
+        for check in &checks {
+            if check.passed {
+                self.ui.set_color(Color::Success);
+                self.ui.draw_text(box_x + 2, y, &format!("✓ {}", check.label));
+            } else {
+                self.ui.set_color(Color::Error);
+                self.ui.draw_text(box_x + 2, y, &format!("✗ {}", check.label));
+            }
             y += 1;
-            io::stdout().flush().unwrap();
-            let _ = LXCDeployment::stop_container_by_name(&self.executor, &container_name);
-            std::thread::sleep(std::time::Duration::from_secs(2));
+            self.ui.set_color(Color::Fg);
+            self.ui.draw_text_clipped(box_x + 6, y, &check.detail, usize::MAX);
+            y += 1;
+        }
+        y += 1;
 
-            // Étape 2: Supprimer le container
+        if checks.iter().all(|c| c.passed) {
+            self.ui.set_color(Color::Success);
+            self.ui.draw_text(box_x + 2, y, "L'installation est complète.");
+        } else {
             self.ui.set_color(Color::Warning);
-            self.ui.draw_text(box_x + 2, y, &format!("Suppression du container '{}'...", container_name));
+            self.ui.draw_text(box_x + 2, y, "Installation incomplète : relancez « Installer RMDB » pour la réparer.");
+        }
+        y += 2;
+
+        self.ui.set_color(Color::Reset);
+        self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
+        let _ = self.input_reader.read_key();
+    }
+
+    fn containers_health(&mut self) {
+        let container_name = match self.select_container("Santé RMDB (container)") {
+            Some(name) => name,
+            None => return,
+        };
+
+        self.ui.clear_screen();
+        self.ui.draw_header(&format!("Santé RMDB - {}", container_name));
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let mut y = box_y + 5;
+
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, y, "Vérification en cours...");
+        io::stdout().flush().unwrap();
+        let health = LXCDeployment::check_rmdb_health(&self.executor, &container_name);
+        self.ui.clear_line(y);
+
+        for check in &health.checks {
+            if check.passed {
+                self.ui.set_color(Color::Success);
+                self.ui.draw_text(box_x + 2, y, &format!("✓ {}", check.label));
+            } else {
+                self.ui.set_color(Color::Error);
+                self.ui.draw_text(box_x + 2, y, &format!("✗ {}", check.label));
+            }
             y += 1;
-            io::stdout().flush().unwrap();
+            self.ui.set_color(Color::Fg);
+            self.ui.draw_text_clipped(box_x + 6, y, &check.detail, usize::MAX);
+            y += 1;
+        }
+        y += 1;
 
-            match LXCDeployment::destroy_container_by_name(&self.executor, &container_name) {
-                Ok(output) => {
-                    if output.exit_code == Some(0) || output.stderr.contains("does not exist") || output.stderr.contains("not found") {
-                        self.ui.set_color(Color::Success);
-                        self.ui.draw_text(box_x + 2, y, "Container supprimé.");
-                    } else {
-                        self.ui.set_color(Color::Warning);
-                        self.ui.draw_text(box_x + 2, y, "Avertissement: Erreur lors de la suppression.");
+        if health.is_healthy() {
+            self.ui.set_color(Color::Success);
+            self.ui.draw_text(box_x + 2, y, "RMDB est opérationnel dans le container.");
+        } else {
+            self.ui.set_color(Color::Warning);
+            self.ui.draw_text(box_x + 2, y, &format!("Consultez les logs: lxc-attach -n {} -- tail {}", container_name, RMDB_LOG_PATH));
+        }
+        y += 2;
+
+        self.ui.set_color(Color::Reset);
+        self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
+        let _ = self.input_reader.read_key();
+    }
+
+    /// Profils de connexion API : activer, ajouter ou supprimer un profil
+    ///
+    /// Les changements sont enregistrés aussitôt dans tui.json (profils seulement)
+    /// et s'appliquent aux écrans API suivants.
+    fn manage_api_profiles(&mut self) {
+        loop {
+            let profiles = self.tui_config.profiles.clone();
+            let fallback_url = self.tui_config.api_url.clone().unwrap_or_else(|| tui_config::DEFAULT_API_URL.to_string());
+            // Première ligne : sans profil, l'URL de tui.json, RMDB_API_URL ou la valeur par défaut
+            let mut rows: Vec<Option<&ApiProfile>> = vec![None];
+            rows.extend(profiles.entries.iter().map(Some));
+            let active = profiles.active_index().map(|i| i + 1).unwrap_or(0);
+            let label = |row: &Option<&ApiProfile>| {
+                let (marker, name, url) = match row {
+                    Some(profile) => (profiles.active.as_deref() == Some(&profile.name), profile.name.as_str(), profile.url.as_str()),
+                    None => (profiles.active().is_none(), "(sans profil)", fallback_url.as_str()),
+                };
+                format!("{} {:<20} {}", if marker { "●" } else { " " }, name, url)
+            };
+
+            let outcome = SelectList::new("Profils de Connexion API", &rows, label)
+                .with_hint("Flèches: Naviguer | Entrée: Activer | A: Ajouter | S: Supprimer | Q: Retour")
+                .with_action_keys(&['a', 's'])
+                .with_selected(active)
+                .run(&self.ui, &self.input_reader);
+
+            // La modification est rejouée sur le fichier : son profil actif n'est pas écrasé par
+            // celui de la session, vidé par RMDB_API_URL ou --api-url
+            let change: Box<dyn Fn(&mut Profiles)> = match outcome {
+                SelectOutcome::Cancelled => return,
+                SelectOutcome::Selected(index) => {
+                    let name = rows[index].map(|profile| profile.name.clone());
+                    Box::new(move |profiles| {
+                        profiles.select(name.as_deref());
+                    })
+                }
+                SelectOutcome::Action('a', _) => match self.prompt_api_profile() {
+                    Some(profile) => Box::new(move |profiles| profiles.add(profile.clone())),
+                    None => continue,
+                },
+                SelectOutcome::Action('s', index) => {
+                    let Some(profile) = rows[index] else {
+                        continue;
+                    };
+                    let question = format!("Supprimer le profil '{}' ({}) ?", profile.name, profile.url);
+                    if !self.ask_yes_no_default("Profils de Connexion API", &question, true) {
+                        continue;
+                    }
+                    let name = profile.name.clone();
+                    Box::new(move |profiles| profiles.remove(&name))
+                }
+                SelectOutcome::Action(..) => continue,
+            };
+            change(&mut self.tui_config.profiles);
+
+            // Seuls les profils sont écrits : les valeurs venues de l'environnement restent hors du fichier
+            let mut file = TuiConfig::load_file();
+            change(&mut file.profiles);
+            if let Err(e) = file.save() {
+                self.show_error_message("Profils de Connexion API", &format!("Écriture de {} impossible: {}", tui_config::config_path().display(), e));
+            }
+        }
+    }
+
+    /// Saisie d'un nouveau profil (nom, URL, token facultatif) ; `None` si annulé
+    fn prompt_api_profile(&mut self) -> Option<ApiProfile> {
+        self.ui.clear_screen();
+        self.ui.draw_header("Nouveau Profil de Connexion");
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let mut y = box_y + 5;
+
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, y, "Nom du profil (vide pour annuler) :");
+        y += 1;
+        let name = read_line(&self.ui, &self.input_reader, box_x + 4, y)?.trim().to_string();
+        if name.is_empty() {
+            return None;
+        }
+        y += 2;
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, y, "URL de l'API (ex: http://pxe-01:8080) :");
+        y += 1;
+        let url = read_line(&self.ui, &self.input_reader, box_x + 4, y)?.trim().to_string();
+        if let Err(message) = validate_profile_url(&url) {
+            self.show_error_message("Nouveau Profil de Connexion", &message);
+            return None;
+        }
+        y += 2;
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, y, "Token (facultatif, masqué, stocké en clair dans tui.json) :");
+        y += 1;
+        let token = read_secret(&self.ui, &self.input_reader, box_x + 4, y)?.trim().to_string();
+
+        Some(ApiProfile {
+            name,
+            url: url.trim_end_matches('/').to_string(),
+            token: Some(token).filter(|t| !t.is_empty()),
+        })
+    }
+
+    /// Enregistre les préférences effectives dans tui.json
+    fn save_tui_config(&mut self) {
+        match self.tui_config.save() {
+            Ok(path) => self.show_message("Préférences TUI", &format!("Préférences enregistrées dans {}", path.display())),
+            Err(e) => self.show_error_message("Préférences TUI", &format!("Écriture de {} impossible: {}", tui_config::config_path().display(), e)),
+        }
+    }
+
+    /// Vérifie les `default.conf` de LXC et crée ceux qui manquent
+    fn containers_repair_lxc_config(&mut self) {
+        self.ui.clear_screen();
+        self.ui.draw_header("Réparer la configuration LXC");
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let mut y = box_y + 5;
+
+        let lxc_deploy = LXCDeployment::new(self.default_container.clone(), "3.20".to_string())
+            .with_lxc_version(LXCDeployment::lxc_version(&self.executor));
+        // Configuration root, puis celle de l'utilisateur s'il n'est pas root
+        let mut modes = vec![LxcPrivilege::Privileged];
+        if std::env::var("USER").is_ok_and(|user| user != "root") {
+            modes.push(LxcPrivilege::Unprivileged);
+        }
+
+        for mode in modes {
+            let path = mode.default_config_path();
+            match lxc_deploy.ensure_default_config(&self.executor, mode) {
+                Ok(DefaultConfigStatus::Present(_)) => {
+                    self.ui.set_color(Color::Success);
+                    self.ui.draw_text_clipped(box_x + 2, y, &format!("✓ {} présent (inchangé)", path.display()), usize::MAX);
+                    y += 1;
+                }
+                Ok(DefaultConfigStatus::Created(_, contents)) => {
+                    self.ui.set_color(Color::Success);
+                    self.ui.draw_text_clipped(box_x + 2, y, &format!("✓ {} créé:", path.display()), usize::MAX);
+                    y += 1;
+                    self.ui.set_color(Color::Fg);
+                    for line in contents.lines() {
+                        self.ui.draw_text_clipped(box_x + 6, y, line, usize::MAX);
                         y += 1;
-                        if !output.stderr.is_empty() {
-                            self.ui.set_color(Color::Fg);
-                            let error_preview = output.stderr.lines().next().unwrap_or("Erreur inconnue");
-                            self.ui.draw_text(box_x + 4, y, error_preview);
-                            y += 1;
-                        }
                     }
                 }
                 Err(e) => {
-                    self.ui.set_color(Color::Warning);
-                    self.ui.draw_text(box_x + 2, y, &format!("Erreur lors de la suppression: {}", e));
+                    let color = if mode == LxcPrivilege::Unprivileged { Color::Warning } else { Color::Error };
+                    self.ui.set_color(color);
+                    self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ {}", path.display()), usize::MAX);
+                    y += 1;
+                    self.ui.set_color(Color::Fg);
+                    self.ui.draw_text_clipped(box_x + 6, y, &e.to_string(), usize::MAX);
                     y += 1;
                 }
             }
-            
-            // Attendre un peu et vérifier que le container a bien été supprimé
-            std::thread::sleep(std::time::Duration::from_secs(2));
-            
-            // Vérification stricte : utiliser check_container_fully_removed qui vérifie uniquement le système de fichiers
-            let mut fully_removed = LXCDeployment::check_container_fully_removed(&self.executor, &container_name);
-            
-            if !fully_removed {
-                self.ui.set_color(Color::Warning);
-                self.ui.draw_text(box_x + 2, y, "Le répertoire du container existe encore. Suppression forcée...");
+            y += 1;
+        }
+
+        self.ui.set_color(Color::Reset);
+        self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
+        let _ = self.input_reader.read_key();
+    }
+
+    fn containers_reinstall(&mut self) {
+        if let Some(container_name) = self.select_container("Réinstaller Container") {
+            let confirm = self.ask_yes_no_default(
+                "Réinstallation Container",
+                &format!(
+                    "Êtes-vous sûr de vouloir réinstaller le container '{}' ?\nCette action va supprimer complètement le container et le recréer.\n\n{}",
+                    container_name,
+                    self.reinstall_summary()
+                ),
+                true
+            );
+
+            if !confirm {
+                return;
+            }
+            let started_at = chrono::Local::now();
+            let started = std::time::Instant::now();
+            let result = self.containers_reinstall_steps(container_name.clone(), started);
+            self.record_history(DeployKind::ContainerReinstall, &container_name, started_at, started, result);
+        }
+    }
+
+    fn containers_reinstall_steps(&mut self, container_name: String, started: std::time::Instant) -> DeployResult {
+        let mut result = DeployResult::Failed;
+        self.ui.clear_screen();
+        self.ui.draw_header("Réinstaller Container");
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let mut y = box_y + 5;
+        let timings = self.tui_config.deploy_timings();
+
+        // Étape 1: Arrêter le container
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, y, &format!("Arrêt du container '{}'...", container_name));
+        y += 1;
+        io::stdout().flush().unwrap();
+        let _ = LXCDeployment::stop_container_by_name(&self.executor, &container_name);
+        std::thread::sleep(timings.settle());
+
+        // Étape 2: Sauvegarder le répertoire LXC dans la corbeille, la réinstallation s'arrête si c'est impossible
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, y, &format!("Sauvegarde du container '{}'...", container_name));
+        y += 1;
+        io::stdout().flush().unwrap();
+        match LXCDeployment::backup_before_remove(&self.executor, &container_name) {
+            Ok(backup) => {
+                self.ui.set_color(Color::Fg);
+                self.ui.draw_text_clipped(box_x + 4, y, &format!("Sauvegarde: {}", backup.display()), usize::MAX);
                 y += 1;
-                io::stdout().flush().unwrap();
-                
-                // Essayer de supprimer directement tous les répertoires possibles
-                let paths_to_remove = vec![
-                    format!("/var/lib/lxc/{}", container_name),
-                    format!("/var/lib/lxd/containers/{}", container_name),
-                ];
-                
-                for path in &paths_to_remove {
-                    let force_remove_cmd = format!("sudo -n rm -rf {} 2>&1", path);
-                    let _ = self.executor.run_shell(&force_remove_cmd, true);
-                }
-                
-                // Attendre un peu après suppression
-                std::thread::sleep(std::time::Duration::from_secs(1));
-                
-                // Vérifier à nouveau
-                fully_removed = LXCDeployment::check_container_fully_removed(&self.executor, &container_name);
-                
-                if fully_removed {
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => {
+                self.ui.set_color(Color::Error);
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ Sauvegarde impossible, réinstallation annulée: {}", e), usize::MAX);
+                y += 2;
+                self.ui.set_color(Color::Reset);
+                self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
+                let _ = self.input_reader.read_key();
+                return result;
+            }
+        }
+
+        // Étape 3: Supprimer le container
+        self.ui.set_color(Color::Warning);
+        self.ui.draw_text(box_x + 2, y, &format!("Suppression du container '{}'...", container_name));
+        y += 1;
+        io::stdout().flush().unwrap();
+
+        self.forget_container_init(&container_name);
+        match LXCDeployment::destroy_container_by_name(&self.executor, &container_name) {
+            Ok(output) => {
+                if output.exit_code == Some(0) || output.stderr.contains("does not exist") || output.stderr.contains("not found") {
                     self.ui.set_color(Color::Success);
-                    self.ui.draw_text(box_x + 2, y, "Répertoires supprimés avec succès.");
-                    y += 1;
+                    self.ui.draw_text(box_x + 2, y, "Container supprimé.");
                 } else {
-                    self.ui.set_color(Color::Error);
-                    self.ui.draw_text(box_x + 2, y, "Impossible de supprimer les répertoires.");
-                    y += 1;
-                    self.ui.set_color(Color::Fg);
-                    self.ui.draw_text(box_x + 2, y, "Veuillez supprimer manuellement:");
+                    self.ui.set_color(Color::Warning);
+                    self.ui.draw_text(box_x + 2, y, "Avertissement: Erreur lors de la suppression.");
                     y += 1;
-                    for path in &paths_to_remove {
-                        self.ui.draw_text(box_x + 4, y, &format!("sudo rm -rf {}", path));
+                    if !output.stderr.is_empty() {
+                        self.ui.set_color(Color::Fg);
+                        let error_preview = output.stderr.lines().next().unwrap_or("Erreur inconnue");
+                        self.ui.draw_text(box_x + 4, y, error_preview);
                         y += 1;
                     }
-                    y += 1;
-                    self.ui.set_color(Color::Reset);
-                    self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
-                    let _ = self.input_reader.read_key();
-                    return;
                 }
             }
-            
-            // Nettoyer les entrées fantômes (si le container est détecté par lxc-ls mais n'existe pas dans le système de fichiers)
-            self.ui.set_color(Color::Info);
-            self.ui.draw_text(box_x + 2, y, "Nettoyage des entrées fantômes...");
+            Err(e) => {
+                self.ui.set_color(Color::Warning);
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("Erreur lors de la suppression: {}", e), usize::MAX);
+                y += 1;
+            }
+        }
+        
+        // Attendre un peu et vérifier que le container a bien été supprimé
+        std::thread::sleep(timings.settle());
+        
+        // Vérification stricte : utiliser check_container_fully_removed qui vérifie uniquement le système de fichiers
+        let mut fully_removed = LXCDeployment::check_container_fully_removed(&self.executor, &container_name);
+        
+        if !fully_removed {
+            self.ui.set_color(Color::Warning);
+            self.ui.draw_text(box_x + 2, y, "Le répertoire du container existe encore. Suppression forcée...");
             y += 1;
             io::stdout().flush().unwrap();
-            let _ = LXCDeployment::cleanup_ghost_container(&self.executor, &container_name);
-            std::thread::sleep(std::time::Duration::from_secs(1));
             
-            // Vérification finale : s'assurer que le container n'est plus détecté nulle part
-            let lxc_deploy_check = LXCDeployment::new(container_name.clone(), "3.20".to_string());
-            let still_detected = lxc_deploy_check.check_container_exists_with_executor(&self.executor);
+            // Supprimer les répertoires restants
+            let paths_to_remove = vec![
+                format!("/var/lib/lxc/{}", container_name),
+                format!("/var/lib/lxd/containers/{}", container_name),
+            ];
             
-            if still_detected {
-                self.ui.set_color(Color::Warning);
-                self.ui.draw_text(box_x + 2, y, "⚠ Le container est encore détecté par certaines commandes LXC.");
+            for path in &paths_to_remove {
+                let force_remove_cmd = format!("rm -rf {} 2>&1", path);
+                let _ = self.executor.run_shell(&force_remove_cmd, true);
+            }
+            
+            // Attendre un peu après suppression
+            std::thread::sleep(timings.recheck());
+            
+            // Vérifier à nouveau
+            fully_removed = LXCDeployment::check_container_fully_removed(&self.executor, &container_name);
+            
+            if fully_removed {
+                self.ui.set_color(Color::Success);
+                self.ui.draw_text(box_x + 2, y, "Répertoires supprimés avec succès.");
                 y += 1;
-                self.ui.set_color(Color::Fg);
-                self.ui.draw_text(box_x + 2, y, "Tentative de nettoyage supplémentaire...");
+            } else {
+                self.ui.set_color(Color::Error);
+                self.ui.draw_text(box_x + 2, y, "Impossible de supprimer les répertoires.");
                 y += 1;
-                io::stdout().flush().unwrap();
-                
-                // Attendre un peu plus et réessayer
-                std::thread::sleep(std::time::Duration::from_secs(2));
-                
-                // Vérifier à nouveau
-                let still_detected_after = lxc_deploy_check.check_container_exists_with_executor(&self.executor);
-                if still_detected_after {
-                    self.ui.set_color(Color::Error);
-                    self.ui.draw_text(box_x + 2, y, "✗ Le container est toujours détecté. Réinstallation impossible.");
+                self.ui.set_color(Color::Fg);
+                self.ui.draw_text(box_x + 2, y, "Veuillez supprimer manuellement:");
+                y += 1;
+                for path in &paths_to_remove {
+                    self.ui.draw_text(box_x + 4, y, &format!("sudo rm -rf {}", path));
                     y += 1;
-                    self.ui.set_color(Color::Fg);
-                    self.ui.draw_text(box_x + 2, y, "Veuillez redémarrer le système ou nettoyer manuellement les caches LXC.");
-                    y += 2;
-                    self.ui.set_color(Color::Reset);
-                    self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
-                    let _ = self.input_reader.read_key();
-                    return;
                 }
+                y += 1;
+                self.ui.set_color(Color::Reset);
+                self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
+                let _ = self.input_reader.read_key();
+                return result;
             }
-            
-            self.ui.clear_line(y - 1);
-            self.ui.set_color(Color::Success);
-            self.ui.draw_text(box_x + 2, y - 1, "✓ Container complètement supprimé et nettoyé.");
-            y += 1;
-
-            // Étape 3: Vérification finale avant création
+        }
+        
+        // Nettoyer les entrées fantômes (si le container est détecté par lxc-ls mais n'existe pas dans le système de fichiers)
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, y, "Nettoyage des entrées fantômes...");
+        y += 1;
+        io::stdout().flush().unwrap();
+        let _ = LXCDeployment::cleanup_ghost_container(&self.executor, &container_name);
+        std::thread::sleep(timings.recheck());
+        
+        // Vérification finale : s'assurer que le container n'est plus détecté nulle part
+        let lxc_deploy_check = LXCDeployment::new(container_name.clone(), "3.20".to_string());
+        let still_detected = lxc_deploy_check.check_container_exists_with_executor(&self.executor);
+        
+        if still_detected {
+            self.ui.set_color(Color::Warning);
+            self.ui.draw_text(box_x + 2, y, "⚠ Le container est encore détecté par certaines commandes LXC.");
             y += 1;
-            self.ui.set_color(Color::Info);
-            self.ui.draw_text(box_x + 2, y, "Vérification finale avant création...");
+            self.ui.set_color(Color::Fg);
+            self.ui.draw_text(box_x + 2, y, "Tentative de nettoyage supplémentaire...");
             y += 1;
             io::stdout().flush().unwrap();
             
-            // Vérifier une dernière fois que le container n'existe vraiment plus (système de fichiers uniquement)
-            let final_check = LXCDeployment::check_container_fully_removed(&self.executor, &container_name);
-            if !final_check {
+            // Attendre un peu plus et réessayer
+            std::thread::sleep(timings.settle());
+            
+            // Vérifier à nouveau
+            let still_detected_after = lxc_deploy_check.check_container_exists_with_executor(&self.executor);
+            if still_detected_after {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, "✗ ERREUR: Le container existe encore dans le système de fichiers.");
+                self.ui.draw_text(box_x + 2, y, "✗ Le container est toujours détecté. Réinstallation impossible.");
                 y += 1;
                 self.ui.set_color(Color::Fg);
-                self.ui.draw_text(box_x + 2, y, "Veuillez supprimer manuellement le répertoire:");
-                y += 1;
-                self.ui.draw_text(box_x + 4, y, &format!("sudo rm -rf /var/lib/lxc/{}", container_name));
+                self.ui.draw_text(box_x + 2, y, "Veuillez redémarrer le système ou nettoyer manuellement les caches LXC.");
                 y += 2;
                 self.ui.set_color(Color::Reset);
                 self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
                 let _ = self.input_reader.read_key();
-                return;
-            }
-            
-            self.ui.clear_line(y - 1);
-            self.ui.set_color(Color::Success);
-            self.ui.draw_text(box_x + 2, y - 1, "✓ Vérification OK. Le container peut être recréé.");
-            y += 2;
-            
-            // Étape 4: Recréer le container
-            self.ui.set_color(Color::Info);
-            self.ui.draw_text(box_x + 2, y, &format!("Création du container '{}'...", container_name));
-            y += 1;
-            io::stdout().flush().unwrap();
-
-            let lxc_deploy = LXCDeployment::new(container_name.clone(), "3.20".to_string());
-            match lxc_deploy.create_container(&self.executor) {
-                Ok(output) => {
-                    if output.exit_code == Some(0) {
-                        self.ui.set_color(Color::Success);
-                        self.ui.draw_text(box_x + 2, y, "Container créé avec succès!");
-                        y += 2;
-
-                        // Étape 5: Démarrer le container
-                        self.ui.set_color(Color::Info);
-                        self.ui.draw_text(box_x + 2, y, "Démarrage du container...");
-                        y += 1;
-                        let _ = lxc_deploy.start_container(&self.executor);
-                        std::thread::sleep(std::time::Duration::from_secs(2));
-
-                        self.ui.set_color(Color::Success);
-                        self.ui.draw_text(box_x + 2, y, "Container réinstallé et démarré avec succès!");
-                    } else {
-                        self.ui.set_color(Color::Error);
-                        self.ui.draw_text(box_x + 2, y, "Erreur lors de la création du container.");
-                        if !output.stderr.is_empty() {
-                            self.ui.draw_text(box_x + 2, y + 1, &output.stderr.lines().next().unwrap_or("Erreur inconnue"));
-                        }
-                    }
-                }
-                Err(e) => {
-                    self.ui.set_color(Color::Error);
-                    self.ui.draw_text(box_x + 2, y, &format!("Erreur: {}", e));
-                }
+                return result;
             }
+        }
+        
+        self.ui.clear_line(y - 1);
+        self.ui.set_color(Color::Success);
+        self.ui.draw_text(box_x + 2, y - 1, "✓ Container complètement supprimé et nettoyé.");
+        y += 1;
 
+        // Étape 4: Vérification finale avant création
+        y += 1;
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, y, "Vérification finale avant création...");
+        y += 1;
+        io::stdout().flush().unwrap();
+        
+        // Vérifier une dernière fois que le container n'existe vraiment plus (système de fichiers uniquement)
+        let final_check = LXCDeployment::check_container_fully_removed(&self.executor, &container_name);
+        if !final_check {
+            self.ui.set_color(Color::Error);
+            self.ui.draw_text(box_x + 2, y, "✗ ERREUR: Le container existe encore dans le système de fichiers.");
+            y += 1;
+            self.ui.set_color(Color::Fg);
+            self.ui.draw_text(box_x + 2, y, "Veuillez supprimer manuellement le répertoire:");
+            y += 1;
+            self.ui.draw_text(box_x + 4, y, &format!("sudo rm -rf /var/lib/lxc/{}", container_name));
+            y += 2;
             self.ui.set_color(Color::Reset);
-            self.ui.draw_text(box_x + 2, y + 3, "Appuyez sur une touche pour continuer...");
+            self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
             let _ = self.input_reader.read_key();
+            return result;
         }
-    }
-
-
-    fn show_container_diagnostic(&mut self, _lxc_deploy: &LXCDeployment, box_x: u16, y: &mut u16) {
+        
+        self.ui.clear_line(y - 1);
+        self.ui.set_color(Color::Success);
+        self.ui.draw_text(box_x + 2, y - 1, "✓ Vérification OK. Le container peut être recréé.");
+        y += 2;
+        
+        // Étape 5: Recréer le container
         self.ui.set_color(Color::Info);
-        self.ui.draw_text(box_x + 2, *y, "Diagnostic détaillé du container 'rmdb':");
-        *y += 2;
+        self.ui.draw_text(box_x + 2, y, &format!("Création du container '{}'...", container_name));
+        y += 1;
+        io::stdout().flush().unwrap();
 
-        // Test 1: lxc-ls avec sudo
-        self.ui.set_color(Color::Fg);
-        self.ui.draw_text(box_x + 4, *y, "1. Test lxc-ls (avec sudo):");
-        *y += 1;
-        let cmd1 = "sudo -n lxc-ls -1 2>&1";
-        match self.executor.run_shell(cmd1, true) {
+        self.forget_container_init(&container_name);
+        let lxc_deploy = LXCDeployment::new(container_name.clone(), "3.20".to_string()).with_timings(timings);
+        match lxc_deploy.create_container(&self.executor) {
             Ok(output) => {
-                let found = output.stdout.lines().any(|line| line.trim() == "rmdb");
-                if found {
+                if output.exit_code == Some(0) {
+                    self.ui.set_color(Color::Success);
+                    self.ui.draw_text(box_x + 2, y, "Container créé avec succès!");
+                    y += 2;
+
+                    // Étape 6: Démarrer le container
+                    self.ui.set_color(Color::Info);
+                    self.ui.draw_text(box_x + 2, y, "Démarrage du container...");
+                    y += 1;
+                    let _ = lxc_deploy.start_container(&self.executor);
+                    std::thread::sleep(timings.container_start());
+
                     self.ui.set_color(Color::Success);
-                    self.ui.draw_text(box_x + 6, *y, "✓ Container trouvé");
+                    self.ui.draw_text(box_x + 2, y, "Container réinstallé et démarré avec succès!");
+                    self.record_deploy(&container_name, started, false);
+                    result = DeployResult::Success;
                 } else {
                     self.ui.set_color(Color::Error);
-                    self.ui.draw_text(box_x + 6, *y, "✗ Container non trouvé");
-                    if !output.stdout.is_empty() {
-                        self.ui.set_color(Color::Fg);
-                        let preview = output.stdout.lines().take(3).collect::<Vec<_>>().join(", ");
-                        self.ui.draw_text(box_x + 6, *y + 1, &format!("Containers vus: {}", preview));
-                        *y += 1;
+                    self.ui.draw_text(box_x + 2, y, "Erreur lors de la création du container.");
+                    if !output.stderr.is_empty() {
+                        self.ui.draw_text(box_x + 2, y + 1, output.stderr.lines().next().unwrap_or("Erreur inconnue"));
                     }
                 }
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 6, *y, &format!("✗ Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("Erreur: {}", e), usize::MAX);
             }
         }
-        *y += 2;
 
-        // Test 2: lxc-ls sans sudo
-        self.ui.set_color(Color::Fg);
-        self.ui.draw_text(box_x + 4, *y, "2. Test lxc-ls (sans sudo):");
-        *y += 1;
-        let cmd2 = "lxc-ls -1 2>&1";
-        match self.executor.run_shell(cmd2, false) {
-            Ok(output) => {
-                let found = output.stdout.lines().any(|line| line.trim() == "rmdb");
-                if found {
-                    self.ui.set_color(Color::Success);
-                    self.ui.draw_text(box_x + 6, *y, "✓ Container trouvé");
-                } else {
-                    self.ui.set_color(Color::Warning);
-                    self.ui.draw_text(box_x + 6, *y, "⚠ Container non trouvé (normal si permissions requises)");
-                }
-            }
-            Err(e) => {
-                self.ui.set_color(Color::Warning);
-                self.ui.draw_text(box_x + 6, *y, &format!("⚠ Erreur (attendu): {}", e));
+        self.ui.set_color(Color::Reset);
+        self.ui.draw_text(box_x + 2, y + 3, "Appuyez sur une touche pour continuer...");
+        let _ = self.input_reader.read_key();
+        result
+    }
+
+
+    fn show_container_diagnostic(&mut self, lxc_deploy: &LXCDeployment) {
+        let results = LXCDeployment::diagnose_detection(&self.executor, &self.default_container, lxc_deploy.detected_lxc_version());
+        self.log_color(Color::Info);
+        self.log_line(0, &format!("Diagnostic détaillé du container '{}':", self.default_container));
+        self.log_blank();
+
+        for result in &results {
+            self.log_color(Color::Fg);
+            self.log_line(2, &format!("{}:", result.title));
+            let (symbol, color) = diagnostic_style(result.status);
+            self.log_color(color);
+            self.log_line(4, &format!("{} {}", symbol, result.summary));
+            self.log_color(Color::Fg);
+            for detail in &result.details {
+                self.log_line(4, detail);
             }
+            self.log_blank();
         }
-        *y += 2;
 
-        // Test 3: lxc list
-        self.ui.set_color(Color::Fg);
-        self.ui.draw_text(box_x + 4, *y, "3. Test lxc list:");
-        *y += 1;
-        let cmd3 = "lxc list --format csv -c n 2>&1";
-        match self.executor.run_shell(cmd3, false) {
-            Ok(output) => {
-                let found = output.stdout.lines().any(|line| line.trim() == "rmdb" || line.contains("rmdb"));
-                if found {
-                    self.ui.set_color(Color::Success);
-                    self.ui.draw_text(box_x + 6, *y, "✓ Container trouvé");
-                } else {
-                    self.ui.set_color(Color::Error);
-                    self.ui.draw_text(box_x + 6, *y, "✗ Container non trouvé");
-                }
-            }
-            Err(e) => {
-                self.ui.set_color(Color::Warning);
-                self.ui.draw_text(box_x + 6, *y, &format!("⚠ Erreur: {}", e));
-            }
+        // Résumé
+        self.log_color(Color::Info);
+        self.log_line(0, "Résumé:");
+        self.log_color(Color::Fg);
+        self.log_line(2, "Si le container n'est pas trouvé par list_all_containers()");
+        self.log_line(2, "mais existe dans le système de fichiers, il y a probablement");
+        self.log_line(2, "un problème de permissions ou de configuration LXC.");
+        self.log_blank();
+
+        if results.iter().any(|result| result.status != DiagnosticStatus::Ok) {
+            self.offer_diagnostic_report(&diagnostic_report(&self.default_container, &results));
         }
-        *y += 2;
+    }
 
-        // Test 4: Système de fichiers
-        self.ui.set_color(Color::Fg);
-        self.ui.draw_text(box_x + 4, *y, "4. Test système de fichiers:");
-        *y += 1;
-        let paths = vec!["/var/lib/lxc/rmdb", "/var/lib/lxd/containers/rmdb"];
-        let mut found_fs = false;
-        for path in &paths {
-            let cmd4 = format!("test -d {} && echo 'found' || echo 'not found'", path);
-            match self.executor.run_shell(&cmd4, true) {
-                Ok(output) => {
-                    if output.stdout.contains("found") {
-                        self.ui.set_color(Color::Success);
-                        self.ui.draw_text(box_x + 6, *y, &format!("✓ Trouvé: {}", path));
-                        found_fs = true;
-                        *y += 1;
+    /// En pied du journal : C copie le rapport de diagnostic, E l'enregistre, autre touche continue
+    fn offer_diagnostic_report(&mut self, report: &str) {
+        let (box_x, box_y, _, box_h) = self.ui.get_box_dimensions();
+        let footer_y = (box_y + box_h).saturating_sub(2);
+        let mut status: Option<(String, Color)> = None;
+        loop {
+            self.redraw_operation_log();
+            if let Some((message, color)) = &status {
+                self.ui.clear_line(footer_y.saturating_sub(1));
+                self.ui.set_color(*color);
+                self.ui.draw_text_clipped(box_x + 2, footer_y.saturating_sub(1), message, usize::MAX);
+            }
+            self.ui.clear_line(footer_y);
+            self.ui.set_color(Color::Info);
+            self.ui.draw_text(box_x + 2, footer_y, "C: Copier le rapport | E: Exporter | Autre touche: Continuer");
+            self.ui.set_color(Color::Reset);
+            match self.input_reader.read_key() {
+                Ok(Key::Char('c' | 'C')) => {
+                    self.ui.copy_to_clipboard(report);
+                    status = Some((format!("✓ Rapport copié ({} lignes) si le terminal accepte OSC 52", report.lines().count()), Color::Success));
+                }
+                Ok(Key::Char('e' | 'E')) => {
+                    if let Some(result) = prompt_export(&self.ui, &self.input_reader, report) {
+                        status = Some(result);
                     }
                 }
-                Err(_) => {}
+                _ => break,
             }
         }
-        if !found_fs {
-            self.ui.set_color(Color::Error);
-            self.ui.draw_text(box_x + 6, *y, "✗ Container non trouvé dans le système de fichiers");
-            *y += 1;
-        }
-        *y += 1;
+        self.redraw_operation_log();
+    }
 
-        // Test 5: list_all_containers
-        self.ui.set_color(Color::Fg);
-        self.ui.draw_text(box_x + 4, *y, "5. Test list_all_containers():");
-        *y += 1;
-        match LXCDeployment::list_all_containers(&self.executor) {
-            Ok(containers) => {
-                let found = containers.iter().any(|c| c.name == "rmdb");
-                if found {
-                    self.ui.set_color(Color::Success);
-                    self.ui.draw_text(box_x + 6, *y, &format!("✓ Container trouvé ({} containers au total)", containers.len()));
-                } else {
-                    self.ui.set_color(Color::Error);
-                    self.ui.draw_text(box_x + 6, *y, &format!("✗ Container non trouvé ({} autres containers vus)", containers.len()));
-                    if !containers.is_empty() {
-                        self.ui.set_color(Color::Info);
-                        self.ui.draw_text(box_x + 6, *y + 1, "Containers détectés:");
-                        *y += 1;
-                        for container in containers.iter().take(3) {
-                            self.ui.draw_text(box_x + 8, *y, &format!("- {} ({})", container.name, container.status));
-                            *y += 1;
-                        }
-                    }
-                }
-            }
-            Err(e) => {
-                self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 6, *y, &format!("✗ Erreur: {}", e));
+    /// Outils attendus, présents ou non, avec la commande d'installation adaptée à la distribution ;
+    /// C copie la commande qui installe tous les outils manquants
+    fn show_requirements(&mut self) {
+        let sudo = if self.capabilities.is_root() { "" } else { "sudo " };
+        let requirements = self.capabilities.requirements();
+        let mut packages: Vec<String> = Vec::new();
+        for package in requirements.iter().filter(|r| !r.present).filter_map(|r| r.package) {
+            let package = self.distribution.package_name(package);
+            if !packages.contains(&package) {
+                packages.push(package);
+            }
+        }
+        let full_command = format!("{}{}", sudo, self.distribution.install_command(&packages.iter().map(String::as_str).collect::<Vec<_>>()));
+
+        let width = requirements.iter().map(|r| display_width(r.tool)).max().unwrap_or(0);
+        let mut viewer = TextViewer::new("Vérifier les prérequis");
+        if !packages.is_empty() {
+            viewer = viewer
+                .with_hint("Flèches: Défiler | C: Copier la commande d'installation | E: Exporter | Q: Retour")
+                .with_action_keys(&['c']);
+        }
+        viewer.push(format!("Distribution: {}", self.distribution.distro), Color::Info);
+        viewer.push("", Color::Fg);
+        for requirement in &requirements {
+            let (symbol, color) = if requirement.present { ("✓", Color::Success) } else { ("✗", Color::Error) };
+            viewer.push(format!("{} {}  {}", symbol, pad_to_width(requirement.tool, width), requirement.purpose), color);
+            if !requirement.present {
+                let install = match requirement.package {
+                    Some(package) => format!("{}{}", sudo, self.distribution.install_command(&[&self.distribution.package_name(package)])),
+                    None => "Fourni par le système d'init (systemd ou OpenRC)".to_string(),
+                };
+                viewer.push(format!("    → {}", install), Color::Fg);
             }
         }
-        *y += 2;
+        viewer.push("", Color::Fg);
+        if packages.is_empty() {
+            viewer.push("Aucun paquet à installer.", Color::Success);
+        } else {
+            viewer.push("Pour tout installer :", Color::Info);
+            viewer.push(format!("  {}", full_command), Color::Fg);
+        }
 
-        // Résumé
+        while viewer.run(&self.ui, &self.input_reader) == Some('c') {
+            self.ui.copy_to_clipboard(&full_command);
+            viewer.set_status("✓ Commande copiée si le terminal accepte OSC 52", Color::Success);
+        }
+    }
+
+    /// Diagnostic de détection du container RMDB dans la visionneuse : C copie le rapport
+    /// (presse-papiers OSC 52), E l'enregistre dans un fichier
+    fn lxc_detection_diagnostic(&mut self) {
+        self.ui.clear_screen();
+        self.ui.draw_header("Diagnostic de détection");
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         self.ui.set_color(Color::Info);
-        self.ui.draw_text(box_x + 2, *y, "Résumé:");
-        *y += 1;
-        self.ui.set_color(Color::Fg);
-        self.ui.draw_text(box_x + 4, *y, "Si le container n'est pas trouvé par list_all_containers()");
-        *y += 1;
-        self.ui.draw_text(box_x + 4, *y, "mais existe dans le système de fichiers, il y a probablement");
-        *y += 1;
-        self.ui.draw_text(box_x + 4, *y, "un problème de permissions ou de configuration LXC.");
-        *y += 2;
+        self.ui.draw_text(box_x + 2, box_y + 5, "Diagnostic en cours...");
+
+        let version = LXCDeployment::lxc_version(&self.executor);
+        let results = LXCDeployment::diagnose_detection(&self.executor, &self.default_container, version);
+        let report = diagnostic_report(&self.default_container, &results);
+
+        let title = format!("Diagnostic de détection: {}", self.default_container);
+        let mut viewer = TextViewer::new(&title)
+            .with_hint("Flèches: Défiler | C: Copier le rapport | E: Exporter | Q: Retour")
+            .with_action_keys(&['c'])
+            .with_export_text(&report);
+        for result in &results {
+            viewer.push(format!("{}:", result.title), Color::Fg);
+            let (symbol, color) = diagnostic_style(result.status);
+            viewer.push(format!("  {} {}", symbol, result.summary), color);
+            for detail in &result.details {
+                viewer.push(format!("  {}", detail), Color::Fg);
+            }
+        }
+        while viewer.run(&self.ui, &self.input_reader) == Some('c') {
+            self.ui.copy_to_clipboard(&report);
+            viewer.set_status(format!("✓ Rapport copié ({} lignes) si le terminal accepte OSC 52", report.lines().count()), Color::Success);
+        }
     }
 
     // ========== Fonctions de gestion RMDB sur le système hôte ==========
 
     fn host_install(&mut self) {
+        let needs = [("/usr/local", HOST_BUILD_SPACE_ESTIMATE), ("/var/lib/rmdb", HOST_DATA_SPACE_ESTIMATE)];
+        if !self.confirm_free_space("Installation RMDB", &needs) {
+            return;
+        }
         self.ui.clear_screen();
         self.ui.draw_header("Installation RMDB sur le système hôte");
-        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let (box_x, box_y, box_w, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
         // Vérifier si RMDB est déjà installé
@@ -3984,9 +6563,31 @@ impl MainApp {
         y += 2;
 
         // Installation
-        match host_deploy.install_rmdb(&self.executor, &rmdb_source_path) {
+        let mut installed = false;
+        let mut estimator = ProgressEstimator::new();
+        let progress_y = y;
+        self.ui.draw_progress_bar(box_x + 2, progress_y, box_w.saturating_sub(6), 0.0);
+        self.ui.set_color(Color::Info);
+        self.ui.draw_text(box_x + 2, progress_y + 1, "Échap: Interrompre");
+        let token = self.cancel_token();
+        self.executor.set_cancel_token(Some(token.clone()));
+        let started_at = chrono::Local::now();
+        let started = std::time::Instant::now();
+        let result = host_deploy.install_rmdb_with_progress(&self.executor, &rmdb_source_path, &mut |line| {
+            if let Some(fraction) = estimator.feed(line) {
+                self.ui.draw_progress_bar(box_x + 2, progress_y, box_w.saturating_sub(6), fraction);
+            }
+        });
+        self.executor.set_cancel_token(None);
+        self.ui.clear_line(progress_y + 1);
+        if result.is_ok() {
+            self.ui.draw_progress_bar(box_x + 2, progress_y, box_w.saturating_sub(6), 1.0);
+        }
+        y += 2;
+        match result {
             Ok(output) => {
                 if output.exit_code == Some(0) {
+                    installed = true;
                     self.ui.set_color(Color::Success);
                     self.ui.draw_text(box_x + 2, y, "✓ RMDB installé avec succès sur le système hôte!");
                     y += 2;
@@ -3999,108 +6600,129 @@ impl MainApp {
                 } else {
                     self.ui.set_color(Color::Error);
                     self.ui.draw_text(box_x + 2, y, "✗ Erreur lors de l'installation.");
-                    if !output.stderr.is_empty() {
+                    // La compilation fusionne stdout et stderr : sa dernière ligne porte l'erreur
+                    let detail = output.stderr.lines().next().or_else(|| output.stdout.lines().last());
+                    if let Some(detail) = detail {
                         y += 1;
                         self.ui.set_color(Color::Fg);
-                        self.ui.draw_text(box_x + 4, y, &output.stderr.lines().next().unwrap_or("Erreur inconnue"));
+                        self.ui.draw_text_clipped(box_x + 4, y, detail, usize::MAX);
                     }
                 }
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("✗ Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ Erreur: {}", e), usize::MAX);
             }
         }
 
         y += 2;
+        let outcome = match (installed, token.is_cancelled()) {
+            (true, _) => DeployResult::Success,
+            (false, true) => DeployResult::Cancelled,
+            (false, false) => DeployResult::Failed,
+        };
+        self.record_history(DeployKind::HostInstall, "hôte", started_at, started, outcome);
+
         self.ui.set_color(Color::Reset);
         self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
         let _ = self.input_reader.read_key();
-    }
-
-    /// Affiche le menu d'installation
-    fn show_install_menu(&mut self) {
-        use crate::pres::install_menu::get_install_menu;
-        let install_menu = get_install_menu();
-        let mut selected = 0;
-        let mut menu_offset = 0;
-
-        loop {
-            self.ui.clear_screen();
-            self.ui.draw_header("Installation RMDB");
 
-            let (box_x, box_y, box_w, box_h) = self.ui.get_box_dimensions();
-            let mut y = box_y + 2;
+        // Étape optionnelle post-installation: ouverture des ports du pare-feu
+        if installed {
+            self.offer_firewall_ports(&host_deploy);
+        }
+    }
 
-            self.ui.set_color(Color::Fg);
-            self.ui.draw_text(box_x + 2, y, "Choisissez une option :");
-            y += 2;
+    /// Propose d'ouvrir les ports requis par RMDB dans le pare-feu actif
+    fn offer_firewall_ports(&mut self, host_deploy: &HostDeployment) {
+        let backend = match host_deploy.detect_firewall(&self.executor) {
+            Some(backend) => backend,
+            None => return,
+        };
 
-            // Afficher les options du menu
-            let visible_items = (box_h as usize).saturating_sub(6).min(install_menu.len());
-            let start = menu_offset.min(install_menu.len().saturating_sub(visible_items));
+        let ports = RMDB_REQUIRED_PORTS.iter()
+            .map(|(port, proto)| format!("{}/{}", port, proto))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let question = format!("Pare-feu {} actif. Ouvrir les ports RMDB ({}) ?", backend.display_name(), ports);
+        if !self.ask_yes_no("Pare-feu", &question) {
+            return;
+        }
 
-            for i in start..(start + visible_items).min(install_menu.len()) {
-                let item = &install_menu[i];
-                let prefix = if i == selected { "> " } else { "  " };
-                let color = if i == selected { Color::Selection } else { Color::Fg };
+        self.ui.clear_screen();
+        self.ui.draw_header("Ouverture des ports du pare-feu");
+        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let mut y = box_y + 5;
 
-                self.ui.set_color(color);
-                self.ui.draw_text(box_x + 2, y, &format!("{}{}", prefix, item.label));
+        match host_deploy.open_firewall_ports(&self.executor, RMDB_REQUIRED_PORTS) {
+            Ok(report) => {
+                self.ui.set_color(Color::Info);
+                self.ui.draw_text(box_x + 2, y, &format!("Pare-feu: {}", report.backend.display_name()));
+                y += 2;
+                if !report.opened.is_empty() {
+                    self.ui.set_color(Color::Success);
+                    self.ui.draw_text(box_x + 2, y, &format!("✓ Ports ouverts: {}", report.opened.join(", ")));
+                    y += 1;
+                }
+                if !report.already_open.is_empty() {
+                    self.ui.set_color(Color::Fg);
+                    self.ui.draw_text(box_x + 2, y, &format!("= Déjà ouverts: {}", report.already_open.join(", ")));
+                    y += 1;
+                }
+                if !report.failed.is_empty() {
+                    self.ui.set_color(Color::Error);
+                    self.ui.draw_text(box_x + 2, y, &format!("✗ Échec: {}", report.failed.join(", ")));
+                    y += 1;
+                }
+            }
+            Err(e) => {
+                self.ui.set_color(Color::Error);
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ Erreur: {}", e), usize::MAX);
                 y += 1;
             }
+        }
 
-            // Instructions
-            y = box_y + box_h - 3;
-            self.ui.set_color(Color::Reset);
-            self.ui.draw_text(box_x + 2, y, "Flèches: Naviguer | Entrée: Sélectionner | Q: Retour");
+        y += 2;
+        self.ui.set_color(Color::Reset);
+        self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
+        let _ = self.input_reader.read_key();
+    }
 
-            match self.input_reader.read_key() {
-                Ok(Key::Quit) => break,
-                Ok(Key::Up) => {
-                    if selected > 0 {
-                        selected -= 1;
-                        if selected < menu_offset {
-                            menu_offset = selected;
-                        }
-                    } else {
-                        selected = install_menu.len() - 1;
-                        menu_offset = selected.saturating_sub(visible_items - 1);
-                    }
+    /// Affiche le menu d'installation
+    fn show_install_menu(&mut self) {
+        use crate::pres::install_menu::{get_install_menu, InstallMenuAction};
+        let install_menu = get_install_menu();
+        let mut selected = 0;
+
+        loop {
+            let choice = SelectList::new("Installation RMDB", &install_menu, |item| item.label.to_string())
+                .with_prompt("Choisissez une option :")
+                .with_selected(selected)
+                .select(&self.ui, &self.input_reader);
+
+            let index = match choice {
+                Some(index) => index,
+                None => break,
+            };
+            selected = index;
+
+            match install_menu[index].action {
+                InstallMenuAction::SelectInstallationMode => {
+                    self.select_installation_mode();
                 }
-                Ok(Key::Down) => {
-                    if selected < install_menu.len() - 1 {
-                        selected += 1;
-                        if selected >= menu_offset + visible_items {
-                            menu_offset = selected - visible_items + 1;
-                        }
-                    } else {
-                        selected = 0;
-                        menu_offset = 0;
-                    }
+                InstallMenuAction::InstallOnHost => {
+                    self.install_on_host();
                 }
-                Ok(Key::Enter) => {
-                    use crate::pres::install_menu::InstallMenuAction;
-                    match install_menu[selected].action {
-                        InstallMenuAction::SelectInstallationMode => {
-                            self.select_installation_mode();
-                        }
-                        InstallMenuAction::InstallOnHost => {
-                            self.install_on_host();
-                        }
-                        InstallMenuAction::InstallInContainer => {
-                            self.install_in_container();
-                        }
-                        InstallMenuAction::InstallInVM => {
-                            self.install_in_vm();
-                        }
-                        InstallMenuAction::ConfigureInstallation => {
-                            self.configure_installation();
-                        }
-                        InstallMenuAction::Back => break,
-                    }
+                InstallMenuAction::InstallInContainer => {
+                    self.install_in_container();
+                }
+                InstallMenuAction::InstallInVM => {
+                    self.install_in_vm();
+                }
+                InstallMenuAction::ConfigureInstallation => {
+                    self.configure_installation();
                 }
-                _ => {}
+                InstallMenuAction::Back => break,
             }
         }
     }
@@ -4115,7 +6737,7 @@ impl MainApp {
         self.ui.clear_screen();
         self.ui.draw_header("Installation RMDB dans un container Alpine");
 
-        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let (box_x, box_y, box_w, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 3;
 
         // Vérifier les privilèges
@@ -4125,9 +6747,9 @@ impl MainApp {
 
         // Demander le nom du container
         self.ui.set_color(Color::Fg);
-        self.ui.draw_text(box_x + 2, y, "Nom du container (par défaut: rmdb) :");
+        self.ui.draw_text(box_x + 2, y, &format!("Nom du container (par défaut: {}) :", self.default_container));
         y += 1;
-        let container_name = "rmdb".to_string(); // TODO: Implémenter saisie interactive
+        let container_name = self.default_container.clone(); // TODO: Implémenter saisie interactive
         let alpine_version = "3.19".to_string();
 
         // Trouver le répertoire source
@@ -4156,6 +6778,11 @@ impl MainApp {
             return;
         }
 
+        let Some(extra_packages) = self.prompt_extra_packages(box_x + 2, y) else {
+            return;
+        };
+        y += 3;
+
         self.ui.set_color(Color::Fg);
         self.ui.draw_text(box_x + 2, y, &format!("Container : {}", container_name));
         y += 1;
@@ -4163,13 +6790,14 @@ impl MainApp {
         y += 2;
 
         // Créer la configuration
-        let logger = DeploymentLogger::new().unwrap_or_else(|_| DeploymentLogger::default());
+        let logger = self.deployment_logger().unwrap_or_else(|_| DeploymentLogger::default());
         let mut config = InstallationConfig::new(InstallationType::ContainerAlpine, rmdb_source.clone())
             .with_logger(logger)
             .with_container_name(container_name.clone())
             .with_alpine_version(alpine_version)
             .with_rust_install(true)
-            .with_go_install(true);
+            .with_go_install(true)
+            .with_extra_packages(extra_packages);
         
         if let Some(m) = mode {
             config = config.with_installation_mode(m);
@@ -4184,37 +6812,82 @@ impl MainApp {
             Ok(Key::Char('o')) | Ok(Key::Char('O')) | Ok(Key::Enter) => {
                 self.ui.clear_screen();
                 self.ui.draw_header("Installation en cours...");
-
-                let installer = RMDBInstaller::new(config);
-                match installer.install(&self.executor) {
+                self.ui.set_color(Color::Info);
+                self.ui.draw_text(box_x + 2, box_y + 13, "Échap: Interrompre");
+
+                let ui = &self.ui;
+                let installer = RMDBInstaller::new(config).with_progress(|step, fraction| {
+                    ui.clear_line(box_y + 5);
+                    ui.set_color(Color::Info);
+                    ui.draw_text(box_x + 2, box_y + 5, step);
+                    ui.draw_progress_bar(box_x + 2, box_y + 6, box_w.saturating_sub(6), fraction);
+                });
+                let existed = LXCDeployment::new(container_name.clone(), String::new())
+                    .check_container_exists_with_executor(&self.executor);
+                let token = self.cancel_token();
+                self.executor.set_cancel_token(Some(token.clone()));
+                let started_at = chrono::Local::now();
+                let started = std::time::Instant::now();
+                let result = installer.install(&self.executor);
+                drop(installer);
+                self.executor.set_cancel_token(None);
+                let outcome = match (&result, token.is_cancelled()) {
+                    (Ok(_), _) => DeployResult::Success,
+                    (Err(_), true) => DeployResult::Cancelled,
+                    (Err(_), false) => DeployResult::Failed,
+                };
+                self.record_history(DeployKind::ContainerInstall, &container_name, started_at, started, outcome);
+                match result {
                     Ok(output) => {
                         self.ui.set_color(Color::Success);
-                        self.ui.draw_text(box_x + 2, box_y + 5, "✓ Installation terminée avec succès !");
-                        if !output.stdout.is_empty() {
-                            self.ui.set_color(Color::Fg);
-                            self.ui.draw_text(box_x + 2, box_y + 7, &output.stdout);
+                        self.ui.draw_text(box_x + 2, box_y + 8, "✓ Installation terminée avec succès !");
+                        // Bilan sur les lignes 10 à 12 : stdout, puis paquets supplémentaires en échec
+                        let lines = output.stdout.lines().map(|l| (l, Color::Fg)).chain(output.stderr.lines().map(|l| (l, Color::Warning)));
+                        for (row, (line, color)) in lines.take(3).enumerate() {
+                            self.ui.set_color(color);
+                            self.ui.draw_text_clipped(box_x + 2, box_y + 10 + row as u16, line, box_w.saturating_sub(4) as usize);
                         }
                     }
                     Err(e) => {
                         self.ui.set_color(Color::Error);
-                        self.ui.draw_text(box_x + 2, box_y + 5, &format!("✗ Erreur : {}", e));
+                        self.ui.draw_text(box_x + 2, box_y + 8, &format!("✗ Erreur : {}", e));
                     }
                 }
 
+                self.ui.clear_line(box_y + 13);
                 self.ui.set_color(Color::Reset);
-                self.ui.draw_text(box_x + 2, box_y + 10, "Appuyez sur une touche pour continuer...");
+                self.ui.draw_text(box_x + 2, box_y + 13, "Appuyez sur une touche pour continuer...");
                 let _ = self.input_reader.read_key();
+
+                if token.is_cancelled() && !existed {
+                    self.rollback_partial_container(&container_name);
+                }
             }
             _ => {}
         }
     }
 
+    /// Saisie des paquets supplémentaires (séparés par des espaces, vide pour aucun) ;
+    /// `None` si la saisie est annulée (Échap) ou la liste invalide
+    fn prompt_extra_packages(&mut self, x: u16, y: u16) -> Option<Vec<String>> {
+        self.ui.set_color(Color::Fg);
+        self.ui.draw_text(x, y, "Paquets supplémentaires (séparés par des espaces, Entrée pour aucun, Échap: annuler) :");
+        let input = read_line(&self.ui, &self.input_reader, x + 2, y + 1)?;
+        match parse_package_list(&input) {
+            Ok(packages) => Some(packages),
+            Err(message) => {
+                self.show_error_message("Paquets supplémentaires", &message);
+                None
+            }
+        }
+    }
+
     /// Installation dans une VM
     fn install_in_vm(&mut self) {
         self.ui.clear_screen();
         self.ui.draw_header("Installation RMDB dans une VM Rocky Linux");
 
-        let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+        let (box_x, box_y, box_w, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 3;
 
         // Vérifier les privilèges
@@ -4252,6 +6925,11 @@ impl MainApp {
             return;
         }
 
+        let Some(extra_packages) = self.prompt_extra_packages(box_x + 2, y) else {
+            return;
+        };
+        y += 3;
+
         self.ui.set_color(Color::Fg);
         self.ui.draw_text(box_x + 2, y, &format!("VM : {}", vm_name));
         y += 1;
@@ -4259,13 +6937,14 @@ impl MainApp {
         y += 2;
 
         // Créer la configuration
-        let logger = DeploymentLogger::new().unwrap_or_else(|_| DeploymentLogger::default());
+        let logger = self.deployment_logger().unwrap_or_else(|_| DeploymentLogger::default());
         let mut config = InstallationConfig::new(InstallationType::VMRocky, rmdb_source.clone())
             .with_logger(logger)
             .with_vm_name(vm_name.clone())
             .with_rocky_version(rocky_version)
             .with_rust_install(true)
-            .with_go_install(true);
+            .with_go_install(true)
+            .with_extra_packages(extra_packages);
         
         if let Some(m) = mode {
             config = config.with_installation_mode(m);
@@ -4280,25 +6959,41 @@ impl MainApp {
             Ok(Key::Char('o')) | Ok(Key::Char('O')) | Ok(Key::Enter) => {
                 self.ui.clear_screen();
                 self.ui.draw_header("Installation en cours...");
-
-                let installer = RMDBInstaller::new(config);
-                match installer.install(&self.executor) {
+                self.ui.set_color(Color::Info);
+                self.ui.draw_text(box_x + 2, box_y + 13, "Échap: Interrompre");
+
+                let ui = &self.ui;
+                let installer = RMDBInstaller::new(config).with_progress(|step, fraction| {
+                    ui.clear_line(box_y + 5);
+                    ui.set_color(Color::Info);
+                    ui.draw_text(box_x + 2, box_y + 5, step);
+                    ui.draw_progress_bar(box_x + 2, box_y + 6, box_w.saturating_sub(6), fraction);
+                });
+                let token = self.cancel_token();
+                self.executor.set_cancel_token(Some(token.clone()));
+                let result = installer.install(&self.executor);
+                drop(installer);
+                self.executor.set_cancel_token(None);
+                match result {
                     Ok(output) => {
                         self.ui.set_color(Color::Success);
-                        self.ui.draw_text(box_x + 2, box_y + 5, "✓ Installation terminée avec succès !");
-                        if !output.stdout.is_empty() {
-                            self.ui.set_color(Color::Fg);
-                            self.ui.draw_text(box_x + 2, box_y + 7, &output.stdout);
+                        self.ui.draw_text(box_x + 2, box_y + 8, "✓ Installation terminée avec succès !");
+                        // Bilan sur les lignes 10 à 12 : stdout, puis paquets supplémentaires en échec
+                        let lines = output.stdout.lines().map(|l| (l, Color::Fg)).chain(output.stderr.lines().map(|l| (l, Color::Warning)));
+                        for (row, (line, color)) in lines.take(3).enumerate() {
+                            self.ui.set_color(color);
+                            self.ui.draw_text_clipped(box_x + 2, box_y + 10 + row as u16, line, box_w.saturating_sub(4) as usize);
                         }
                     }
                     Err(e) => {
                         self.ui.set_color(Color::Error);
-                        self.ui.draw_text(box_x + 2, box_y + 5, &format!("✗ Erreur : {}", e));
+                        self.ui.draw_text(box_x + 2, box_y + 8, &format!("✗ Erreur : {}", e));
                     }
                 }
 
+                self.ui.clear_line(box_y + 13);
                 self.ui.set_color(Color::Reset);
-                self.ui.draw_text(box_x + 2, box_y + 10, "Appuyez sur une touche pour continuer...");
+                self.ui.draw_text(box_x + 2, box_y + 13, "Appuyez sur une touche pour continuer...");
                 let _ = self.input_reader.read_key();
             }
             _ => {}
@@ -4347,7 +7042,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 4, y, &format!("✗ Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 4, y, &format!("✗ Erreur: {}", e), usize::MAX);
             }
         }
         y += 2;
@@ -4381,7 +7076,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("✗ Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ Erreur: {}", e), usize::MAX);
             }
         }
 
@@ -4415,7 +7110,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("✗ Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ Erreur: {}", e), usize::MAX);
             }
         }
 
@@ -4449,7 +7144,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("✗ Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ Erreur: {}", e), usize::MAX);
             }
         }
 
@@ -4459,6 +7154,52 @@ impl MainApp {
         let _ = self.input_reader.read_key();
     }
 
+    /// Change le mode d'utilisation d'une installation existante puis propose de redémarrer le service
+    fn host_change_mode(&mut self) {
+        let host_deploy = HostDeployment::new();
+        if !host_deploy.check_rmdb_installed(&self.executor) {
+            self.show_error_message("Mode d'utilisation", "RMDB n'est pas installé sur ce système.");
+            return;
+        }
+
+        let current = self.tui_config.installation_mode.as_deref().and_then(InstallationMode::from_short_name);
+        let modes = get_mode_selection_menu();
+        let prompt = match current {
+            Some(mode) => format!("Mode actuel : {}", mode.display_name()),
+            None => "Mode actuel : non enregistré".to_string(),
+        };
+        let initial = modes.iter().position(|(mode, _)| Some(*mode) == current).unwrap_or(0);
+        let Some(index) = SelectList::new("Changer le mode d'utilisation", &modes, |(_, label)| label.to_string())
+            .with_prompt(&prompt)
+            .with_selected(initial)
+            .select(&self.ui, &self.input_reader)
+        else {
+            return;
+        };
+        let mode = modes[index].0;
+        if Some(mode) == current {
+            self.show_message("Mode d'utilisation", "Ce mode est déjà configuré.");
+            return;
+        }
+
+        // Le mode est conservé dans tui.json : la configuration de rmdbd n'a pas de clé pour lui
+        self.tui_config.installation_mode = Some(mode.short_name().to_string());
+        let mut file = TuiConfig::load_file();
+        file.installation_mode = Some(mode.short_name().to_string());
+        if let Err(e) = file.save() {
+            self.show_error_message("Mode d'utilisation", &format!("Écriture de {} impossible: {}", tui_config::config_path().display(), e));
+            return;
+        }
+        if self.ask_yes_no(
+            "Mode d'utilisation",
+            &format!("Mode enregistré : {}.\nRedémarrer RMDB maintenant pour l'appliquer ?", mode.display_name()),
+        ) {
+            self.host_restart();
+        } else {
+            self.show_message("Mode d'utilisation", "Le nouveau mode s'appliquera au prochain redémarrage de RMDB.");
+        }
+    }
+
     fn host_enable(&mut self) {
         self.ui.clear_screen();
         self.ui.draw_header("Activer RMDB au démarrage");
@@ -4478,7 +7219,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("✗ Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ Erreur: {}", e), usize::MAX);
             }
         }
 
@@ -4507,7 +7248,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("✗ Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ Erreur: {}", e), usize::MAX);
             }
         }
 
@@ -4523,7 +7264,7 @@ impl MainApp {
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 5;
 
-        let confirm = self.ask_yes_no("Désinstallation", "Êtes-vous sûr de vouloir désinstaller RMDB du système hôte ? Cette action est irréversible.");
+        let confirm = self.ask_yes_no_default("Désinstallation", "Êtes-vous sûr de vouloir désinstaller RMDB du système hôte ? Cette action est irréversible.", true);
         if !confirm {
             return;
         }
@@ -4541,7 +7282,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("✗ Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ Erreur: {}", e), usize::MAX);
             }
         }
 
@@ -4553,6 +7294,9 @@ impl MainApp {
 
     /// Trouve le répertoire source RMDB
     fn find_rmdb_source(&self) -> Option<String> {
+        if let Some(dir) = self.tui_config.source_dir.as_deref().filter(|dir| std::path::Path::new(dir).exists()) {
+            return Some(dir.to_string());
+        }
         let possible_paths = vec![
             "./rmdb_source",
             "../rmdb_source",
@@ -4573,160 +7317,50 @@ impl MainApp {
     fn get_installation_mode(&mut self) -> Option<InstallationMode> {
         // Pour l'instant, on demande toujours à l'utilisateur
         // En production, on pourrait lire depuis un fichier de configuration
-        use crate::pres::install_menu::get_mode_selection_menu;
         let modes = get_mode_selection_menu();
-        let mut selected = 0;
-
-        self.ui.clear_screen();
-        self.ui.draw_header("Sélection du mode d'utilisation");
-
-        let (box_x, box_y, box_w, box_h) = self.ui.get_box_dimensions();
-        let mut y = box_y + 2;
-
-        self.ui.set_color(Color::Fg);
-        self.ui.draw_text(box_x + 2, y, "Choisissez le mode d'utilisation de RMDB :");
-        y += 2;
-
-        loop {
-            // Afficher les options
-            let visible_items = (box_h as usize).saturating_sub(6).min(modes.len());
-            let start = 0.min(modes.len().saturating_sub(visible_items));
-            let mut display_y = box_y + 4;
-
-            for i in start..(start + visible_items).min(modes.len()) {
-                let (mode, label) = &modes[i];
-                let prefix = if i == selected { "> " } else { "  " };
-                let color = if i == selected { Color::Selection } else { Color::Fg };
-
-                self.ui.set_color(color);
-                self.ui.draw_text(box_x + 2, display_y, &format!("{}{}", prefix, label));
-                display_y += 1;
-            }
 
-            // Instructions
-            let inst_y = box_y + box_h - 3;
-            self.ui.set_color(Color::Reset);
-            self.ui.draw_text(box_x + 2, inst_y, "Flèches: Naviguer | Entrée: Sélectionner");
-
-            match self.input_reader.read_key() {
-                Ok(Key::Up) => {
-                    if selected > 0 {
-                        selected -= 1;
-                    } else {
-                        selected = modes.len() - 1;
-                    }
-                }
-                Ok(Key::Down) => {
-                    if selected < modes.len() - 1 {
-                        selected += 1;
-                    } else {
-                        selected = 0;
-                    }
-                }
-                Ok(Key::Enter) => {
-                    let (selected_mode, _) = &modes[selected];
-                    return Some(*selected_mode);
-                }
-                Ok(Key::Quit) => {
-                    return None;
-                }
-                _ => {}
-            }
-        }
+        SelectList::new("Sélection du mode d'utilisation", &modes, |(_, label)| label.to_string())
+            .with_prompt("Choisissez le mode d'utilisation de RMDB :")
+            .with_hint("Flèches: Naviguer | Entrée: Sélectionner")
+            .select(&self.ui, &self.input_reader)
+            .map(|i| modes[i].0)
     }
 
     /// Sélection du mode d'installation
     fn select_installation_mode(&mut self) {
-        use crate::pres::install_menu::get_mode_selection_menu;
         let modes = get_mode_selection_menu();
-        let mut selected = 0;
-        let mut menu_offset = 0;
 
-        loop {
+        let choice = SelectList::new("Sélection du mode d'utilisation", &modes, |(_, label)| label.to_string())
+            .with_prompt("Choisissez le mode d'utilisation de RMDB :")
+            .select(&self.ui, &self.input_reader);
+
+        if let Some(index) = choice {
+            let (selected_mode, _) = &modes[index];
+
+            // Afficher confirmation
             self.ui.clear_screen();
-            self.ui.draw_header("Sélection du mode d'utilisation");
+            self.ui.draw_header("Mode sélectionné");
 
-            let (box_x, box_y, _box_w, box_h) = self.ui.get_box_dimensions();
-            let mut y = box_y + 2;
+            let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+            let mut y = box_y + 3;
 
-            self.ui.set_color(Color::Fg);
-            self.ui.draw_text(box_x + 2, y, "Choisissez le mode d'utilisation de RMDB :");
+            self.ui.set_color(Color::Success);
+            self.ui.draw_text(box_x + 2, y, &format!("✓ Mode sélectionné : {}", selected_mode.display_name()));
             y += 2;
 
-            // Afficher les options du menu
-            let visible_items = (box_h as usize).saturating_sub(6).min(modes.len());
-            let start = menu_offset.min(modes.len().saturating_sub(visible_items));
-
-            for i in start..(start + visible_items).min(modes.len()) {
-                let (_mode, label) = &modes[i];
-                let prefix = if i == selected { "> " } else { "  " };
-                let color = if i == selected { Color::Selection } else { Color::Fg };
+            self.ui.set_color(Color::Fg);
+            self.ui.draw_text(box_x + 2, y, "Ce mode sera utilisé lors de l'installation.");
+            y += 1;
+            self.ui.draw_text(box_x + 2, y, "Vous pourrez le modifier dans la configuration.");
+            y += 2;
 
-                self.ui.set_color(color);
-                self.ui.draw_text(box_x + 2, y, &format!("{}{}", prefix, label));
-                y += 1;
-            }
+            self.ui.set_color(Color::Info);
+            self.ui.draw_text(box_x + 2, y, "Le mode sera appliqué lors de l'installation de RMDB.");
+            y += 2;
 
-            // Instructions
-            y = box_y + box_h - 3;
             self.ui.set_color(Color::Reset);
-            self.ui.draw_text(box_x + 2, y, "Flèches: Naviguer | Entrée: Sélectionner | Q: Retour");
-
-            match self.input_reader.read_key() {
-                Ok(Key::Quit) => break,
-                Ok(Key::Up) => {
-                    if selected > 0 {
-                        selected -= 1;
-                        if selected < menu_offset {
-                            menu_offset = selected;
-                        }
-                    } else {
-                        selected = modes.len() - 1;
-                        menu_offset = selected.saturating_sub(visible_items - 1);
-                    }
-                }
-                Ok(Key::Down) => {
-                    if selected < modes.len() - 1 {
-                        selected += 1;
-                        if selected >= menu_offset + visible_items {
-                            menu_offset = selected - visible_items + 1;
-                        }
-                    } else {
-                        selected = 0;
-                        menu_offset = 0;
-                    }
-                }
-                Ok(Key::Enter) => {
-                    let (selected_mode, _) = &modes[selected];
-                    
-                    // Afficher confirmation
-                    self.ui.clear_screen();
-                    self.ui.draw_header("Mode sélectionné");
-
-                    let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
-                    let mut y = box_y + 3;
-
-                    self.ui.set_color(Color::Success);
-                    self.ui.draw_text(box_x + 2, y, &format!("✓ Mode sélectionné : {}", selected_mode.display_name()));
-                    y += 2;
-
-                    self.ui.set_color(Color::Fg);
-                    self.ui.draw_text(box_x + 2, y, "Ce mode sera utilisé lors de l'installation.");
-                    y += 1;
-                    self.ui.draw_text(box_x + 2, y, "Vous pourrez le modifier dans la configuration.");
-                    y += 2;
-
-                    self.ui.set_color(Color::Info);
-                    self.ui.draw_text(box_x + 2, y, "Le mode sera appliqué lors de l'installation de RMDB.");
-                    y += 2;
-
-                    self.ui.set_color(Color::Reset);
-                    self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
-                    let _ = self.input_reader.read_key();
-                    break;
-                }
-                _ => {}
-            }
+            self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
+            let _ = self.input_reader.read_key();
         }
     }
 
@@ -4755,74 +7389,68 @@ impl MainApp {
 
     /// Affiche la liste des VMs
     fn show_vms_list(&mut self) {
+        if !self.ensure_api_available() {
+            return;
+        }
         self.ui.clear_screen();
         self.ui.draw_header("Liste des Machines Virtuelles");
 
-        let (box_x, box_y, _box_w, box_h) = self.ui.get_box_dimensions();
-        let mut y = box_y + 2;
+        let (box_x, box_y, _box_w, _box_h) = self.ui.get_box_dimensions();
 
-        // Créer le client API (par défaut localhost:8080)
-        let api_client = APIClient::new("http://localhost:8080".to_string());
+        // Créer le client API (par défaut localhost:8080, voir tui.json)
+        let api_client = self.api_client();
 
         self.ui.set_color(Color::Info);
-        self.ui.draw_text(box_x + 2, y, "Chargement des VMs...");
-        y += 1;
-
-        match api_client.get_vms(None) {
-            Ok(vms) => {
-                if vms.is_empty() {
-                    self.ui.set_color(Color::Warning);
-                    self.ui.draw_text(box_x + 2, y, "Aucune VM trouvée.");
-                    y += 2;
-                } else {
-                    self.ui.set_color(Color::Fg);
-                    self.ui.draw_text(box_x + 2, y, &format!("Total: {} VM(s)", vms.len()));
-                    y += 2;
+        self.ui.draw_text(box_x + 2, box_y + 2, "Chargement des VMs...");
 
-                    // Afficher les VMs (limité à la taille de l'écran)
-                    let max_items = (box_h as usize).saturating_sub(8).min(vms.len());
-                    for (i, vm) in vms.iter().take(max_items).enumerate() {
-                        self.ui.set_color(Color::Fg);
-                        self.ui.draw_text(box_x + 2, y, &format!("{}. {}", i + 1, vm.name));
-                        y += 1;
-                        self.ui.set_color(Color::Info);
-                        self.ui.draw_text(box_x + 4, y, &format!("ID: {} | Catégorie: {} | Format: {}", 
-                            vm.id, vm.category, vm.format));
-                        y += 1;
-                        if !vm.description.is_empty() {
-                            self.ui.set_color(Color::Fg);
-                            self.ui.draw_text(box_x + 4, y, &format!("Description: {}", vm.description));
-                            y += 1;
-                        }
-                        y += 1;
-                    }
+        let mut selected = 0;
+        loop {
+            let Some(vms) = self.with_retry_ui("Erreur lors du chargement", || api_client.get_vms(None)) else {
+                return;
+            };
+            if vms.is_empty() {
+                // Position recalculée à chaque actualisation : l'écran est redessiné depuis le haut
+                let y = box_y + 3;
+                self.ui.clear_screen();
+                self.ui.draw_header("Liste des Machines Virtuelles");
+                self.ui.set_color(Color::Warning);
+                self.ui.draw_text(box_x + 2, y, "Aucune VM trouvée.");
+                if self.read_refresh_key(box_x + 2, y + 2) {
+                    continue;
+                }
+                return;
+            }
 
-                    if vms.len() > max_items {
-                        self.ui.set_color(Color::Warning);
-                        self.ui.draw_text(box_x + 2, y, &format!("... et {} VM(s) supplémentaire(s)", vms.len() - max_items));
-                        y += 1;
+            let title = format!("Liste des Machines Virtuelles ({} VM(s))", vms.len());
+            let outcome = SelectList::new(&title, &vms, |vm| vm.name.clone())
+                .with_detail(|vm| {
+                    let mut detail = format!("ID: {} | Catégorie: {} | Format: {} | Taille: {}",
+                        vm.id, vm.category, vm.format, format_size(vm.size));
+                    if !vm.description.is_empty() {
+                        detail.push_str(&format!(" | {}", vm.description));
                     }
+                    detail
+                })
+                .with_hint("Flèches: Naviguer | R: Actualiser | Entrée/Q: Retour")
+                .with_action_keys(&['r'])
+                .with_selected(selected)
+                .with_sort(VM_SORT_COLUMNS, &mut self.vm_sort)
+                .run(&self.ui, &self.input_reader);
+            match outcome {
+                SelectOutcome::Action(_, index) => {
+                    selected = index;
+                    self.show_refreshing();
                 }
-            }
-            Err(e) => {
-                self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("✗ Erreur lors du chargement: {}", e));
-                y += 1;
-                self.ui.set_color(Color::Fg);
-                self.ui.draw_text(box_x + 4, y, "Assurez-vous que le serveur RMDB est démarré");
-                y += 1;
-                self.ui.draw_text(box_x + 4, y, "et accessible sur http://localhost:8080");
+                _ => return,
             }
         }
-
-        y += 2;
-        self.ui.set_color(Color::Reset);
-        self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
-        let _ = self.input_reader.read_key();
     }
 
     /// Affiche le formulaire de création de VM
     fn show_vm_create(&mut self) {
+        if !self.ensure_api_available() {
+            return;
+        }
         self.ui.clear_screen();
         self.ui.draw_header("Créer une Machine Virtuelle");
 
@@ -4830,7 +7458,7 @@ impl MainApp {
         let mut y = box_y + 3;
 
         // Créer le client API
-        let api_client = APIClient::new("http://localhost:8080".to_string());
+        let api_client = self.api_client();
 
         // Récupérer les catégories disponibles
         let categories = match api_client.get_vm_categories() {
@@ -4920,38 +7548,106 @@ impl MainApp {
         }
         y += 2;
 
-        // Confirmation
+        // Le disque n'est vérifiable que si le serveur tourne sur cet hôte
+        let local_server = net::is_local_url(&self.tui_config.api_url());
+        let status = if local_server { check_disk_path(&self.executor, &disk_path) } else { None };
+        let warnings = validate_disk_path(&disk_path, &format, status);
+        if !local_server {
+            self.ui.set_color(Color::Info);
+            self.ui.draw_text(box_x + 2, y, "Serveur distant : existence du disque non vérifiée.");
+            y += 1;
+        }
+        self.ui.set_color(Color::Warning);
+        for warning in &warnings {
+            self.ui.draw_text_clipped(box_x + 2, y, &format!("⚠ {}", warning), usize::MAX);
+            y += 1;
+        }
+        if !local_server || !warnings.is_empty() {
+            y += 1;
+        }
+
+        // Confirmation ; avec des avertissements, Entrée annule
         self.ui.set_color(Color::Warning);
-        self.ui.draw_text(box_x + 2, y, "Créer cette VM ? (O/n)");
+        if warnings.is_empty() {
+            self.ui.draw_text(box_x + 2, y, "Créer cette VM ? (O/n)");
+        } else {
+            self.ui.draw_text(box_x + 2, y, "Créer cette VM malgré les avertissements ? (o/N)");
+        }
         y += 1;
         self.ui.set_color(Color::Reset);
 
-        match self.input_reader.read_key() {
-            Ok(Key::Char('o')) | Ok(Key::Char('O')) | Ok(Key::Enter) => {
-                self.ui.clear_screen();
-                self.ui.draw_header("Création en cours...");
+        let confirmed = match self.input_reader.read_key() {
+            Ok(Key::Char('o')) | Ok(Key::Char('O')) => true,
+            Ok(Key::Enter) => warnings.is_empty(),
+            _ => false,
+        };
+        if confirmed {
+            self.ui.clear_screen();
+            self.ui.draw_header("Création en cours...");
 
-                match api_client.create_vm(&vm_name, &description, &category, &format, &disk_path) {
-                    Ok(vm) => {
-                        self.ui.set_color(Color::Success);
-                        self.ui.draw_text(box_x + 2, box_y + 5, &format!("✓ VM '{}' créée avec succès !", vm.name));
-                        self.ui.set_color(Color::Info);
-                        self.ui.draw_text(box_x + 2, box_y + 7, &format!("ID: {}", vm.id));
-                    }
-                    Err(e) => {
-                        self.ui.set_color(Color::Error);
-                        self.ui.draw_text(box_x + 2, box_y + 5, &format!("✗ Erreur lors de la création: {}", e));
-                    }
+            match api_client.create_vm(&vm_name, &description, &category, &format, &disk_path) {
+                Ok(vm) => {
+                    self.ui.set_color(Color::Success);
+                    self.ui.draw_text(box_x + 2, box_y + 5, &format!("✓ VM '{}' créée avec succès !", vm.name));
+                    self.ui.set_color(Color::Info);
+                    self.ui.draw_text(box_x + 2, box_y + 7, &format!("ID: {}", vm.id));
+                }
+                Err(e) => {
+                    self.ui.set_color(Color::Error);
+                    self.ui.draw_text(box_x + 2, box_y + 5, &format!("✗ Erreur lors de la création: {}", e));
                 }
-
-                self.ui.set_color(Color::Reset);
-                self.ui.draw_text(box_x + 2, box_y + 10, "Appuyez sur une touche pour continuer...");
-                let _ = self.input_reader.read_key();
             }
-            _ => {}
+
+            self.ui.set_color(Color::Reset);
+            self.ui.draw_text(box_x + 2, box_y + 10, "Appuyez sur une touche pour continuer...");
+            let _ = self.input_reader.read_key();
         }
     }
 
+    /// Demande un répertoire de cache de template pour un déploiement hors ligne
+    ///
+    /// Redemande tant que le répertoire ne contient pas `rootfs.tar.xz` et `meta.tar.xz` ;
+    /// une saisie vide annule.
+    fn prompt_local_template(&mut self) -> Option<std::path::PathBuf> {
+        let mut error: Option<String> = None;
+        let result = loop {
+            self.ui.clear_screen();
+            self.ui.draw_header("Déploiement Hors Ligne");
+
+            let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
+            let mut y = box_y + 3;
+            self.ui.set_color(Color::Warning);
+            self.ui.draw_text(box_x + 2, y, "Aucune source de template n'est joignable.");
+            y += 2;
+            self.ui.set_color(Color::Fg);
+            self.ui.draw_text(box_x + 2, y, "Copiez depuis une machine connectée le cache du template download :");
+            y += 1;
+            self.ui.draw_text(box_x + 4, y, "/var/cache/lxc/download/alpine/<version>/amd64/default");
+            y += 1;
+            self.ui.draw_text(box_x + 2, y, "(fichiers rootfs.tar.xz et meta.tar.xz). Laissez vide pour annuler.");
+            y += 2;
+            if let Some(message) = error.take() {
+                self.ui.set_color(Color::Error);
+                self.ui.draw_text(box_x + 2, y, &message);
+            }
+            y += 2;
+            self.ui.set_color(Color::Info);
+            self.ui.draw_text(box_x + 2, y, "Répertoire du cache de template :");
+            y += 1;
+            let path = self.read_text_input(box_x + 4, y, 60);
+            if path.is_empty() {
+                break None;
+            }
+            let dir = std::path::PathBuf::from(path);
+            match validate_local_template(&dir) {
+                Ok(()) => break Some(dir),
+                Err(message) => error = Some(message),
+            }
+        };
+        self.redraw_operation_log();
+        result
+    }
+
     /// Lit une entrée texte de l'utilisateur (version simplifiée)
     fn read_text_input(&mut self, x: u16, y: u16, _max_len: usize) -> String {
         // Version simplifiée : afficher un prompt et lire ligne par ligne
@@ -4978,7 +7674,7 @@ impl MainApp {
                 Ok(Key::Enter) => {
                     done = true;
                 }
-                Ok(Key::Quit) => {
+                Ok(Key::Quit) | Ok(Key::Interrupt) => {
                     buffer.clear();
                     done = true;
                 }
@@ -5006,117 +7702,134 @@ impl MainApp {
         self.ui.set_color(Color::Reset);
         self.ui.draw_text(box_x + 2, y, "Appuyez sur une touche pour continuer...");
         let _ = self.input_reader.read_key();
-    }
-
-    /// Affiche la gestion des overlays de VMs
-    fn show_vm_overlays(&mut self) {
-        let api_client = APIClient::new("http://localhost:8080".to_string());
-        
-        // Charger la liste des overlays
-        let overlays = match api_client.get_overlays() {
-            Ok(overlays) => overlays,
-            Err(e) => {
-                self.show_error_message("Erreur", &format!("Impossible de charger les overlays: {}", e));
-                return;
-            }
-        };
+    }
 
-        if overlays.is_empty() {
-            self.show_message("Overlays", "Aucun overlay disponible.");
+    /// Affiche la gestion des overlays de VMs
+    fn show_vm_overlays(&mut self) {
+        if !self.ensure_api_available() {
             return;
         }
-
-        // Menu de sélection d'overlay
+        let api_client = self.api_client();
         let mut selected = 0;
-        let mut menu_offset = 0;
-
-        loop {
-            self.ui.clear_screen();
-            self.ui.draw_header("Gestion des Overlays de VMs");
-
-            let (box_x, box_y, _box_w, box_h) = self.ui.get_box_dimensions();
-            let mut y = box_y + 2;
-
-            self.ui.set_color(Color::Fg);
-            self.ui.draw_text(box_x + 2, y, &format!("Total: {} overlay(s)", overlays.len()));
-            y += 2;
-
-            // Afficher les overlays
-            let visible_items = (box_h as usize).saturating_sub(8).min(overlays.len());
-            let start = menu_offset.min(overlays.len().saturating_sub(visible_items));
 
-            for i in start..(start + visible_items).min(overlays.len()) {
-                let overlay = &overlays[i];
-                let prefix = if i == selected { "> " } else { "  " };
-                let color = if i == selected { Color::Selection } else { Color::Fg };
+        // Chaque passage recharge la liste (R: Actualiser)
+        'reload: loop {
+            let Some(overlays) = self.with_retry_ui("Impossible de charger les overlays", || api_client.get_overlays()) else {
+                return;
+            };
 
-                self.ui.set_color(color);
-                self.ui.draw_text(box_x + 2, y, &format!("{}MAC: {} | VM ID: {}", prefix, overlay.mac_address, overlay.vm_id));
-                y += 1;
-                self.ui.set_color(Color::Info);
-                self.ui.draw_text(box_x + 4, y, &format!("Chemin: {} | Taille: {} octets", overlay.overlay_path, overlay.size));
-                y += 1;
+            if overlays.is_empty() {
+                self.show_message("Overlays", "Aucun overlay disponible.");
+                return;
             }
 
-            // Instructions
-            y = box_y + box_h - 4;
-            self.ui.set_color(Color::Reset);
-            self.ui.draw_text(box_x + 2, y, "Flèches: Naviguer | Entrée: Détails | S=Supprimer | Q=Retour");
-            y += 1;
-            self.ui.draw_text(box_x + 2, y, "C=Créer overlay | M=Rechercher par MAC");
+            // Menu de sélection d'overlay
+            let title = format!("Gestion des Overlays de VMs ({} overlay(s))", overlays.len());
 
-            match self.input_reader.read_key() {
-                Ok(Key::Quit) => break,
-                Ok(Key::Up) => {
-                    if selected > 0 {
-                        selected -= 1;
-                        if selected < menu_offset {
-                            menu_offset = selected;
-                        }
-                    } else {
-                        selected = overlays.len() - 1;
-                        menu_offset = selected.saturating_sub(visible_items - 1);
-                    }
-                }
-                Ok(Key::Down) => {
-                    if selected < overlays.len() - 1 {
-                        selected += 1;
-                        if selected >= menu_offset + visible_items {
-                            menu_offset = selected - visible_items + 1;
-                        }
-                    } else {
-                        selected = 0;
-                        menu_offset = 0;
+            loop {
+                let outcome = SelectList::new(&title, &overlays, |o| format!("MAC: {} | VM ID: {}", o.mac_address, o.vm_id))
+                    .with_detail(|o| format!("Chemin: {} | Taille: {} octets", o.overlay_path, o.size))
+                    .with_hint("Flèches: Naviguer | Entrée: Détails | S=Supprimer | C=Créer | M=Rechercher par MAC | R=Actualiser | Q=Retour")
+                    .with_action_keys(&['s', 'c', 'm', 'r'])
+                    .with_selected(selected)
+                    .run(&self.ui, &self.input_reader);
+
+                match outcome {
+                    SelectOutcome::Cancelled => break 'reload,
+                    SelectOutcome::Selected(index) => {
+                        selected = index;
+                        self.show_overlay_details(&overlays[index]);
                     }
-                }
-                Ok(Key::Enter) => {
-                    self.show_overlay_details(&overlays[selected]);
-                }
-                Ok(Key::Char('s')) | Ok(Key::Char('S')) => {
-                    if self.ask_yes_no("Suppression", &format!("Supprimer l'overlay pour MAC '{}' ?", overlays[selected].mac_address)) {
-                        match api_client.delete_overlay(&overlays[selected].id) {
-                            Ok(_) => {
-                                self.show_message("Succès", &format!("Overlay supprimé avec succès."));
-                                break; // Retour au menu principal
-                            }
-                            Err(e) => {
-                                self.show_error_message("Erreur", &format!("Impossible de supprimer: {}", e));
+                    SelectOutcome::Action('s', index) => {
+                        selected = index;
+                        if self.ask_yes_no("Suppression", &format!("Supprimer l'overlay pour MAC '{}' ?", overlays[index].mac_address)) {
+                            match api_client.delete_overlay(&overlays[index].id) {
+                                Ok(_) => {
+                                    self.show_message("Succès", "Overlay supprimé avec succès.");
+                                    break 'reload; // Retour au menu principal
+                                }
+                                Err(e) => {
+                                    self.show_error_message("Erreur", &format!("Impossible de supprimer: {}", e));
+                                }
                             }
                         }
                     }
+                    SelectOutcome::Action('c', _) => {
+                        self.create_overlay_interactive();
+                        break 'reload; // Retour au menu principal après création
+                    }
+                    SelectOutcome::Action('m', index) => {
+                        selected = index;
+                        self.search_overlay_by_mac();
+                    }
+                    SelectOutcome::Action('r', index) => {
+                        selected = index;
+                        self.show_refreshing();
+                        continue 'reload;
+                    }
+                    SelectOutcome::Action(_, _) => {}
                 }
-                Ok(Key::Char('c')) | Ok(Key::Char('C')) => {
-                    self.create_overlay_interactive();
-                    break; // Retour au menu principal après création
-                }
-                Ok(Key::Char('m')) | Ok(Key::Char('M')) => {
-                    self.search_overlay_by_mac();
-                }
-                _ => {}
             }
         }
     }
 
+    /// Supprime en lot les overlays dont la VM n'existe plus ou dont le client n'a plus de lease
+    fn cleanup_orphan_overlays(&mut self) {
+        if !self.ensure_api_available() {
+            return;
+        }
+        let api_client = self.api_client();
+
+        let Some((overlays, vms)) = self.with_retry_ui("Impossible de charger les overlays ou les VMs", || {
+            Ok((api_client.get_overlays()?, api_client.get_vms(None)?))
+        }) else {
+            return;
+        };
+        // Sans leases disponibles, seul le critère de la VM supprimée est appliqué
+        let leases = api_client.get_dhcp_leases().unwrap_or_default();
+
+        let now = chrono::Utc::now();
+        let orphans = find_orphan_overlays(&overlays, &vms, &leases, now);
+        if orphans.is_empty() {
+            self.show_message("Overlays orphelins", "Aucun overlay orphelin trouvé.");
+            return;
+        }
+
+        let total_size: u64 = orphans.iter().map(|o| o.size).sum();
+        let mut viewer = TextViewer::new("Overlays orphelins");
+        viewer.push(format!("{} overlay(s) orphelin(s), {} au total", orphans.len(), format_size(total_size)), Color::Warning);
+        viewer.push("", Color::Fg);
+        for overlay in &orphans {
+            let reason = orphan_reason(overlay, &vms, &leases, now).map(|r| r.label()).unwrap_or("-");
+            viewer.push(format!("{:<18} VM {:<12} {:>10}  ({})",
+                overlay.mac_address, overlay.vm_id, format_size(overlay.size), reason), Color::Fg);
+        }
+        viewer.run(&self.ui, &self.input_reader);
+
+        if !self.ask_yes_no_default("Overlays orphelins", &format!("Supprimer ces {} overlay(s) ?", orphans.len()), true) {
+            return;
+        }
+
+        let mut failures = Vec::new();
+        for overlay in &orphans {
+            if let Err(e) = api_client.delete_overlay(&overlay.id) {
+                failures.push(format!("{}: {}", overlay.mac_address, e));
+            }
+        }
+
+        let deleted = orphans.len() - failures.len();
+        if failures.is_empty() {
+            self.show_message("Overlays orphelins", &format!("✓ {} overlay(s) supprimé(s).", deleted));
+        } else {
+            let mut viewer = TextViewer::new("Overlays orphelins");
+            viewer.push(format!("{} supprimé(s), {} échec(s) :", deleted, failures.len()), Color::Warning);
+            for failure in &failures {
+                viewer.push(failure.as_str(), Color::Error);
+            }
+            viewer.run(&self.ui, &self.input_reader);
+        }
+    }
+
     /// Affiche les détails d'un overlay
     fn show_overlay_details(&mut self, overlay: &VMOverlay) {
         self.ui.clear_screen();
@@ -5148,21 +7861,20 @@ impl MainApp {
 
     /// Crée un overlay de manière interactive
     fn create_overlay_interactive(&mut self) {
+        if !self.ensure_api_available() {
+            return;
+        }
         self.ui.clear_screen();
         self.ui.draw_header("Créer un Overlay de VM");
 
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 3;
 
-        let api_client = APIClient::new("http://localhost:8080".to_string());
+        let api_client = self.api_client();
 
         // Charger les VMs pour sélection
-        let vms = match api_client.get_vms(None) {
-            Ok(vms) => vms,
-            Err(e) => {
-                self.show_error_message("Erreur", &format!("Impossible de charger les VMs: {}", e));
-                return;
-            }
+        let Some(vms) = self.with_retry_ui("Impossible de charger les VMs", || api_client.get_vms(None)) else {
+            return;
         };
 
         if vms.is_empty() {
@@ -5171,41 +7883,19 @@ impl MainApp {
         }
 
         // Sélection de la VM
-        self.ui.set_color(Color::Info);
-        self.ui.draw_text(box_x + 2, y, "Sélectionnez une VM:");
-        y += 2;
-
-        let mut selected_vm = 0;
-        loop {
-            for (i, vm) in vms.iter().enumerate() {
-                let prefix = if i == selected_vm { "> " } else { "  " };
-                let color = if i == selected_vm { Color::Selection } else { Color::Fg };
-                self.ui.set_color(color);
-                self.ui.draw_text(box_x + 2, y + i as u16, &format!("{}{}", prefix, vm.name));
-            }
-
-            match self.input_reader.read_key() {
-                Ok(Key::Up) => {
-                    if selected_vm > 0 {
-                        selected_vm -= 1;
-                    } else {
-                        selected_vm = vms.len() - 1;
-                    }
-                }
-                Ok(Key::Down) => {
-                    if selected_vm < vms.len() - 1 {
-                        selected_vm += 1;
-                    } else {
-                        selected_vm = 0;
-                    }
-                }
-                Ok(Key::Enter) => break,
-                Ok(Key::Quit) => return,
-                _ => {}
-            }
-        }
+        let selected_vm = match SelectList::new("Créer un Overlay de VM", &vms, |vm| vm.name.clone())
+            .with_prompt("Sélectionnez une VM:")
+            .select(&self.ui, &self.input_reader)
+        {
+            Some(index) => index,
+            None => return,
+        };
 
-        y += vms.len() as u16 + 2;
+        self.ui.clear_screen();
+        self.ui.draw_header("Créer un Overlay de VM");
+        self.ui.set_color(Color::Fg);
+        self.ui.draw_text(box_x + 2, y, &format!("VM: {}", vms[selected_vm].name));
+        y += 2;
 
         // Saisie de l'adresse MAC
         self.ui.set_color(Color::Info);
@@ -5253,6 +7943,9 @@ impl MainApp {
 
     /// Recherche un overlay par MAC address
     fn search_overlay_by_mac(&mut self) {
+        if !self.ensure_api_available() {
+            return;
+        }
         self.ui.clear_screen();
         self.ui.draw_header("Rechercher Overlay par MAC");
 
@@ -5269,7 +7962,7 @@ impl MainApp {
             return;
         }
 
-        let api_client = APIClient::new("http://localhost:8080".to_string());
+        let api_client = self.api_client();
         match api_client.get_overlay_by_mac(&mac) {
             Ok(overlay) => {
                 self.show_overlay_details(&overlay);
@@ -5282,57 +7975,28 @@ impl MainApp {
 
     /// Menu pour les modules avancés (Repair, Test, Security)
     fn show_advanced_modules_menu(&mut self) {
-        let mut selected = 0;
         let modules = vec![
             ("Réparation Système", "Repair"),
             ("Tests Système", "Test"),
             ("Sécurité", "Security"),
         ];
+        let mut selected = 0;
 
         loop {
-            self.ui.clear_screen();
-            self.ui.draw_header("Modules Avancés");
-
-            let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
-            let mut y = box_y + 3;
-
-            for (i, (name, _)) in modules.iter().enumerate() {
-                let prefix = if i == selected { "> " } else { "  " };
-                let color = if i == selected { Color::Selection } else { Color::Fg };
-
-                self.ui.set_color(color);
-                self.ui.draw_text(box_x + 2, y, &format!("{}{}", prefix, name));
-                y += 1;
-            }
+            let choice = SelectList::new("Modules Avancés", &modules, |(name, _)| name.to_string())
+                .with_selected(selected)
+                .select(&self.ui, &self.input_reader);
 
-            y += 2;
-            self.ui.set_color(Color::Reset);
-            self.ui.draw_text(box_x + 2, y, "Flèches: Naviguer | Entrée: Sélectionner | Q: Retour");
+            let index = match choice {
+                Some(index) => index,
+                None => break,
+            };
+            selected = index;
 
-            match self.input_reader.read_key() {
-                Ok(Key::Quit) => break,
-                Ok(Key::Up) => {
-                    if selected > 0 {
-                        selected -= 1;
-                    } else {
-                        selected = modules.len() - 1;
-                    }
-                }
-                Ok(Key::Down) => {
-                    if selected < modules.len() - 1 {
-                        selected += 1;
-                    } else {
-                        selected = 0;
-                    }
-                }
-                Ok(Key::Enter) => {
-                    match modules[selected].1 {
-                        "Repair" => self.show_repair_module(),
-                        "Test" => self.show_test_module(),
-                        "Security" => self.show_security_module(),
-                        _ => {}
-                    }
-                }
+            match modules[index].1 {
+                "Repair" => self.show_repair_module(),
+                "Test" => self.show_test_module(),
+                "Security" => self.show_security_module(),
                 _ => {}
             }
         }
@@ -5340,185 +8004,134 @@ impl MainApp {
 
     /// Affiche le module de réparation
     fn show_repair_module(&mut self) {
-        let mut selected = 0;
+        if !self.ensure_api_available() {
+            return;
+        }
         let repair_types = vec![
             ("DNS Resolution", "dns"),
             ("Network Connectivity", "network"),
             ("Configuration", "config"),
             ("Services", "services"),
         ];
+        let mut selected = 0;
 
         loop {
-            self.ui.clear_screen();
-            self.ui.draw_header("Module de Réparation");
+            let choice = SelectList::new("Module de Réparation", &repair_types, |(name, _)| name.to_string())
+                .with_prompt("Sélectionnez le type de réparation:")
+                .with_hint("Flèches: Naviguer | Entrée: Exécuter | Q: Retour")
+                .with_selected(selected)
+                .select(&self.ui, &self.input_reader);
+
+            let index = match choice {
+                Some(index) => index,
+                None => break,
+            };
+            selected = index;
 
             let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
-            let mut y = box_y + 3;
-
-            self.ui.set_color(Color::Fg);
-            self.ui.draw_text(box_x + 2, y, "Sélectionnez le type de réparation:");
-            y += 2;
-
-            for (i, (name, _)) in repair_types.iter().enumerate() {
-                let prefix = if i == selected { "> " } else { "  " };
-                let color = if i == selected { Color::Selection } else { Color::Fg };
-
-                self.ui.set_color(color);
-                self.ui.draw_text(box_x + 2, y, &format!("{}{}", prefix, name));
-                y += 1;
-            }
-
-            y += 2;
-            self.ui.set_color(Color::Reset);
-            self.ui.draw_text(box_x + 2, y, "Flèches: Naviguer | Entrée: Exécuter | Q: Retour");
+            let api_client = self.api_client();
+            self.ui.clear_screen();
+            self.ui.draw_header("Réparation en cours...");
 
-            match self.input_reader.read_key() {
-                Ok(Key::Quit) => break,
-                Ok(Key::Up) => {
-                    if selected > 0 {
-                        selected -= 1;
+            match api_client.run_repair(repair_types[index].1) {
+                Ok(result) => {
+                    if result.success {
+                        self.ui.set_color(Color::Success);
+                        self.ui.draw_text(box_x + 2, box_y + 5, &format!("✓ {}", result.message));
                     } else {
-                        selected = repair_types.len() - 1;
+                        self.ui.set_color(Color::Error);
+                        self.ui.draw_text(box_x + 2, box_y + 5, &format!("✗ {}", result.message));
                     }
-                }
-                Ok(Key::Down) => {
-                    if selected < repair_types.len() - 1 {
-                        selected += 1;
-                    } else {
-                        selected = 0;
+                    if let Some(ref details) = result.details {
+                        self.ui.set_color(Color::Fg);
+                        self.ui.draw_text(box_x + 2, box_y + 7, details);
                     }
                 }
-                Ok(Key::Enter) => {
-                    let api_client = APIClient::new("http://localhost:8080".to_string());
-                    self.ui.clear_screen();
-                    self.ui.draw_header("Réparation en cours...");
-
-                    match api_client.run_repair(repair_types[selected].1) {
-                        Ok(result) => {
-                            if result.success {
-                                self.ui.set_color(Color::Success);
-                                self.ui.draw_text(box_x + 2, box_y + 5, &format!("✓ {}", result.message));
-                            } else {
-                                self.ui.set_color(Color::Error);
-                                self.ui.draw_text(box_x + 2, box_y + 5, &format!("✗ {}", result.message));
-                            }
-                            if let Some(ref details) = result.details {
-                                self.ui.set_color(Color::Fg);
-                                self.ui.draw_text(box_x + 2, box_y + 7, details);
-                            }
-                        }
-                        Err(e) => {
-                            self.ui.set_color(Color::Error);
-                            self.ui.draw_text(box_x + 2, box_y + 5, &format!("✗ Erreur: {}", e));
-                        }
-                    }
-
-                    self.ui.set_color(Color::Reset);
-                    self.ui.draw_text(box_x + 2, box_y + 10, "Appuyez sur une touche pour continuer...");
-                    let _ = self.input_reader.read_key();
+                Err(e) => {
+                    self.ui.set_color(Color::Error);
+                    self.ui.draw_text(box_x + 2, box_y + 5, &format!("✗ Erreur: {}", e));
                 }
-                _ => {}
             }
+
+            self.ui.set_color(Color::Reset);
+            self.ui.draw_text(box_x + 2, box_y + 10, "Appuyez sur une touche pour continuer...");
+            let _ = self.input_reader.read_key();
         }
     }
 
     /// Affiche le module de test
     fn show_test_module(&mut self) {
-        let mut selected = 0;
+        if !self.ensure_api_available() {
+            return;
+        }
         let test_types = vec![
             ("Test Unitaires", "unit"),
             ("Test Intégration", "integration"),
             ("Test Connectivité", "connectivity"),
             ("Test Performance", "performance"),
         ];
+        let mut selected = 0;
 
         loop {
-            self.ui.clear_screen();
-            self.ui.draw_header("Module de Test");
+            let choice = SelectList::new("Module de Test", &test_types, |(name, _)| name.to_string())
+                .with_prompt("Sélectionnez le type de test:")
+                .with_hint("Flèches: Naviguer | Entrée: Exécuter | Q: Retour")
+                .with_selected(selected)
+                .select(&self.ui, &self.input_reader);
+
+            let index = match choice {
+                Some(index) => index,
+                None => break,
+            };
+            selected = index;
 
             let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
-            let mut y = box_y + 3;
-
-            self.ui.set_color(Color::Fg);
-            self.ui.draw_text(box_x + 2, y, "Sélectionnez le type de test:");
-            y += 2;
-
-            for (i, (name, _)) in test_types.iter().enumerate() {
-                let prefix = if i == selected { "> " } else { "  " };
-                let color = if i == selected { Color::Selection } else { Color::Fg };
-
-                self.ui.set_color(color);
-                self.ui.draw_text(box_x + 2, y, &format!("{}{}", prefix, name));
-                y += 1;
-            }
-
-            y += 2;
-            self.ui.set_color(Color::Reset);
-            self.ui.draw_text(box_x + 2, y, "Flèches: Naviguer | Entrée: Exécuter | Q: Retour");
+            let api_client = self.api_client();
+            self.ui.clear_screen();
+            self.ui.draw_header("Test en cours...");
 
-            match self.input_reader.read_key() {
-                Ok(Key::Quit) => break,
-                Ok(Key::Up) => {
-                    if selected > 0 {
-                        selected -= 1;
+            match api_client.run_test(test_types[index].1) {
+                Ok(result) => {
+                    if result.success {
+                        self.ui.set_color(Color::Success);
+                        self.ui.draw_text(box_x + 2, box_y + 5, &format!("✓ {}", result.message));
                     } else {
-                        selected = test_types.len() - 1;
+                        self.ui.set_color(Color::Error);
+                        self.ui.draw_text(box_x + 2, box_y + 5, &format!("✗ {}", result.message));
                     }
-                }
-                Ok(Key::Down) => {
-                    if selected < test_types.len() - 1 {
-                        selected += 1;
-                    } else {
-                        selected = 0;
+                    if let Some(ref details) = result.details {
+                        self.ui.set_color(Color::Fg);
+                        self.ui.draw_text(box_x + 2, box_y + 7, details);
                     }
-                }
-                Ok(Key::Enter) => {
-                    let api_client = APIClient::new("http://localhost:8080".to_string());
-                    self.ui.clear_screen();
-                    self.ui.draw_header("Test en cours...");
-
-                    match api_client.run_test(test_types[selected].1) {
-                        Ok(result) => {
-                            if result.success {
-                                self.ui.set_color(Color::Success);
-                                self.ui.draw_text(box_x + 2, box_y + 5, &format!("✓ {}", result.message));
-                            } else {
-                                self.ui.set_color(Color::Error);
-                                self.ui.draw_text(box_x + 2, box_y + 5, &format!("✗ {}", result.message));
-                            }
-                            if let Some(ref details) = result.details {
-                                self.ui.set_color(Color::Fg);
-                                self.ui.draw_text(box_x + 2, box_y + 7, details);
-                            }
-                            if let Some(duration) = result.duration_ms {
-                                self.ui.set_color(Color::Info);
-                                self.ui.draw_text(box_x + 2, box_y + 9, &format!("Durée: {} ms", duration));
-                            }
-                        }
-                        Err(e) => {
-                            self.ui.set_color(Color::Error);
-                            self.ui.draw_text(box_x + 2, box_y + 5, &format!("✗ Erreur: {}", e));
-                        }
+                    if let Some(duration) = result.duration_ms {
+                        self.ui.set_color(Color::Info);
+                        self.ui.draw_text(box_x + 2, box_y + 9, &format!("Durée: {} ms", duration));
                     }
-
-                    self.ui.set_color(Color::Reset);
-                    self.ui.draw_text(box_x + 2, box_y + 12, "Appuyez sur une touche pour continuer...");
-                    let _ = self.input_reader.read_key();
                 }
-                _ => {}
+                Err(e) => {
+                    self.ui.set_color(Color::Error);
+                    self.ui.draw_text(box_x + 2, box_y + 5, &format!("✗ Erreur: {}", e));
+                }
             }
+
+            self.ui.set_color(Color::Reset);
+            self.ui.draw_text(box_x + 2, box_y + 12, "Appuyez sur une touche pour continuer...");
+            let _ = self.input_reader.read_key();
         }
     }
 
     /// Affiche le module de sécurité
     fn show_security_module(&mut self) {
+        if !self.ensure_api_available() {
+            return;
+        }
         self.ui.clear_screen();
         self.ui.draw_header("Module de Sécurité");
 
         let (box_x, box_y, _, _) = self.ui.get_box_dimensions();
         let mut y = box_y + 2;
 
-        let api_client = APIClient::new("http://localhost:8080".to_string());
+        let api_client = self.api_client();
 
         self.ui.set_color(Color::Info);
         self.ui.draw_text(box_x + 2, y, "Chargement des métriques de sécurité...");
@@ -5541,7 +8154,7 @@ impl MainApp {
             }
             Err(e) => {
                 self.ui.set_color(Color::Error);
-                self.ui.draw_text(box_x + 2, y, &format!("✗ Erreur: {}", e));
+                self.ui.draw_text_clipped(box_x + 2, y, &format!("✗ Erreur: {}", e), usize::MAX);
             }
         }
 
@@ -5552,3 +8165,102 @@ impl MainApp {
     }
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pres::executor::MockExecutor;
+
+    #[test]
+    fn test_query_service_status() {
+        let systemd = Capabilities { has_systemctl: true, ..Capabilities::default() };
+        let mock = MockExecutor::new().on("is-active dhcp", "inactive\ninactive\n").on("is-active rmdbd", "active\n");
+        assert_eq!(query_service_status(&mock, &systemd, "rmdbd"), "active");
+        assert_eq!(query_service_status(&mock, &systemd, "dhcp"), "inactive");
+        assert_eq!(mock.issued()[0], ("systemctl is-active rmdbd 2>/dev/null || echo inactive".to_string(), false));
+
+        let openrc = Capabilities { has_rc_service: true, ..Capabilities::default() };
+        let mock = MockExecutor::new().on_error("rc-service", ExecError::Failed("absent".to_string()));
+        assert_eq!(query_service_status(&mock, &openrc, "tftp"), "inconnu");
+
+        // Sans init : fichier PID puis /proc/<pid>/comm
+        let mock = MockExecutor::new().on("cat /run/rmdbd.pid", "4242\n").on("/proc/4242/comm", "alive\n");
+        assert_eq!(query_service_status(&mock, &Capabilities::default(), RMDB_SERVICE_NAME), "active");
+    }
+
+    #[test]
+    fn test_vm_name_collisions() {
+        let vms = ["rmdb", "Debian-12", "win11"];
+        assert_eq!(vm_name_collisions(vms, "RMDB "), ["rmdb"]);
+        assert_eq!(vm_name_collisions(vms, "debian-12"), ["Debian-12"]);
+        assert!(vm_name_collisions(vms, "rmdb-labo").is_empty());
+    }
+
+    #[test]
+    fn test_attach_failure_hint() {
+        assert!(attach_failure_hint("1", Some("STOPPED")).contains("arrêté"));
+        assert!(attach_failure_hint("1", Some("FROZEN")).contains("Dégeler"));
+        assert!(attach_failure_hint("127", Some("RUNNING")).contains("introuvable"));
+        assert!(attach_failure_hint("1", None).contains("sudo"));
+    }
+
+    #[test]
+    fn test_next_menu_state() {
+        assert_eq!(next_menu_state(&MenuState::Main, &MainMenuAction::ServicesTheme), MenuTransition::Push("Services"));
+        assert_eq!(next_menu_state(&MenuState::Main, &MainMenuAction::Quit), MenuTransition::Quit);
+        assert_eq!(next_menu_state(&MenuState::Main, &MainMenuAction::ServiceStatus), MenuTransition::Run);
+
+        let services = MenuState::SubMenu("Services".to_string(), submenu_for("Services", false));
+        assert_eq!(next_menu_state(&services, &MainMenuAction::ServicesTheme), MenuTransition::Pop);
+        assert_eq!(next_menu_state(&services, &MainMenuAction::ServiceStatus), MenuTransition::Run);
+
+        // Chaque sous-menu se termine par un retour vers le menu principal
+        for item in get_main_menu() {
+            if let MenuTransition::Push(category) = next_menu_state(&MenuState::Main, &item.action) {
+                let submenu = submenu_for(category, true);
+                let state = MenuState::SubMenu(category.to_string(), submenu.clone());
+                let last = submenu.last().expect("sous-menu vide");
+                assert_eq!(next_menu_state(&state, &last.action), MenuTransition::Pop, "{}", category);
+            }
+        }
+    }
+
+    #[test]
+    fn test_main_menu_hotkeys() {
+        let menu = get_main_menu();
+        let keys: Vec<char> = menu.iter().filter_map(|m| m.hotkey).collect();
+        let mut unique = keys.clone();
+        unique.sort();
+        unique.dedup();
+        assert_eq!(unique.len(), keys.len());
+        // Ni navigation vim, ni Quitter, ni palette
+        assert!(keys.iter().all(|k| !"jkgGqQ:".contains(*k)));
+        assert!(menu.iter().enumerate().all(|(i, m)| m.id == i));
+        assert!(menu.iter().filter(|m| theme_category(&m.action).is_some()).all(|m| m.hotkey.is_some()));
+    }
+
+    #[test]
+    fn test_quick_actions() {
+        let actions = quick_actions(false);
+        assert!(actions.iter().all(|a| !a.label.ends_with("Retour") && !a.label.contains("---")));
+        assert!(!actions.iter().any(|a| a.label.contains("Console de diagnostic")));
+        assert!(quick_actions(true).iter().any(|a| a.label.contains("Console de diagnostic")));
+        assert!(quick_actions(true).iter().any(|a| matches!(a.action, MainMenuAction::SystemDisplayTest)));
+
+        assert!(!actions.iter().any(|a| matches!(a.action, MainMenuAction::Favorites)));
+        let edit = find_quick_action(&actions, &MainMenuAction::ConfigEdit).expect("action de la palette");
+        assert_eq!(edit.label, "Configuration › Éditer Configuration");
+
+        let found = filter_quick_actions(&actions, "CONFIG édit");
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].label, "Configuration › Éditer Configuration");
+        assert_eq!(filter_quick_actions(&actions, "").len(), actions.len());
+    }
+
+    #[test]
+    fn test_describe_mode() {
+        for mode in [ExecActionMode::ReadOnly, ExecActionMode::Safe, ExecActionMode::Admin] {
+            assert!(!describe_mode(mode).is_empty());
+        }
+        assert!(describe_mode(ExecActionMode::Admin).iter().any(|line| line.contains("sudo")));
+    }
+}
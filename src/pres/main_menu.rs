@@ -4,10 +4,14 @@ pub struct MainMenuItem {
     pub label: &'static str,
     pub category: MainMenuCategory,
     pub action: MainMenuAction,
+    /// Raccourci du menu principal, actif depuis n'importe quel menu (attribué à la main :
+    /// les initiales en conflit, les touches vim et `q` sont écartées)
+    pub hotkey: Option<char>,
 }
 
 #[derive(Clone)]
 pub enum MainMenuCategory {
+    Favorites,
     Services,
     IPXE,
     Clients,
@@ -22,6 +26,8 @@ pub enum MainMenuCategory {
 #[derive(Clone)]
 pub enum MainMenuAction {
     // Actions principales (thématiques)
+    Favorites,
+    QuickStatus,
     ServicesTheme,
     IPXETheme,
     ClientsTheme,
@@ -42,9 +48,11 @@ pub enum MainMenuAction {
     IPXEMenu,
     IPXEEntries,
     IPXEGenerate,
+    IPXESave,
     IPXEConfig,
     // Actions Clients
     ClientsLeases,
+    ClientsLeasesWatch,
     ClientsConnected,
     ClientsHistory,
     // Actions VMs
@@ -52,20 +60,32 @@ pub enum MainMenuAction {
     VMsCreate,
     VMsManage,
     VMsOverlays,
+    VMsOverlaysCleanup,
     // Actions Configuration
     ConfigView,
+    ConfigDiff,
     ConfigEdit,
+    ConfigEditExternal,
     ConfigNetwork,
     ConfigSecurity,
+    ConfigProfiles,
+    ConfigSaveTui,
     // Actions Monitoring
     MonitoringLogs,
+    MonitoringLogsFollow,
     MonitoringMetrics,
     MonitoringHealth,
     MonitoringDashboard,
+    MonitoringDeployLogs,
+    MonitoringDeployHistory,
     // Actions Système
     SystemInfo,
     SystemServices,
     SystemProcesses,
+    SystemDiagnosticConsole,
+    SystemDisplayTest,
+    SystemSudoReset,
+    SystemRequirements,
     // Actions Déploiement
     DeployLXC,
     DeployStatus,
@@ -82,25 +102,36 @@ pub enum MainMenuAction {
     LXCRmdbRestart,
     LXCRmdbLogs,
     LXCConfig,
+    LXCConfigEdit,
+    LXCDiagnostic,
     LXCDestroy,
     // Actions Gestion Générale Containers LXC
     ContainersTheme,
     ContainersList,
     ContainersStart,
     ContainersStop,
+    ContainersFreeze,
+    ContainersUnfreeze,
     ContainersRestart,
     ContainersAdd,
+    ContainersClone,
     ContainersDestroy,
     ContainersReinstall,
+    ContainersAutostart,
+    ContainersHealth,
+    ContainersRepairConfig,
+    ContainersGhosts,
     // Actions RMDB sur Système Hôte
     HostTheme,
     HostInstall,
     HostStatus,
+    HostVerify,
     HostStart,
     HostStop,
     HostRestart,
     HostEnable,
     HostDisable,
+    HostChangeMode,
     HostUninstall,
     // Actions Installation
     InstallMenu,
@@ -114,69 +145,94 @@ pub fn get_main_menu() -> Vec<MainMenuItem> {
     vec![
         MainMenuItem {
             id: 0,
+            label: "Favoris / Récents",
+            category: MainMenuCategory::Favorites,
+            action: MainMenuAction::Favorites,
+            hotkey: Some('f'),
+        },
+        MainMenuItem {
+            id: 1,
+            label: "État rapide",
+            category: MainMenuCategory::Services,
+            action: MainMenuAction::QuickStatus,
+            hotkey: Some('e'),
+        },
+        MainMenuItem {
+            id: 2,
             label: "Services",
             category: MainMenuCategory::Services,
             action: MainMenuAction::ServicesTheme,
+            hotkey: Some('s'),
         },
         MainMenuItem {
-            id: 1,
+            id: 3,
             label: "IPXE",
             category: MainMenuCategory::IPXE,
             action: MainMenuAction::IPXETheme,
+            hotkey: Some('i'),
         },
         MainMenuItem {
-            id: 2,
+            id: 4,
             label: "Clients",
             category: MainMenuCategory::Clients,
             action: MainMenuAction::ClientsTheme,
+            hotkey: Some('c'),
         },
         MainMenuItem {
-            id: 3,
+            id: 5,
             label: "VMs",
             category: MainMenuCategory::VMs,
             action: MainMenuAction::VMsTheme,
+            hotkey: Some('v'),
         },
         MainMenuItem {
-            id: 4,
+            id: 6,
             label: "Configuration",
             category: MainMenuCategory::Configuration,
             action: MainMenuAction::ConfigurationTheme,
+            hotkey: Some('o'),
         },
         MainMenuItem {
-            id: 5,
+            id: 7,
             label: "Monitoring",
             category: MainMenuCategory::Monitoring,
             action: MainMenuAction::MonitoringTheme,
+            hotkey: Some('m'),
         },
         MainMenuItem {
-            id: 6,
+            id: 8,
             label: "Système",
             category: MainMenuCategory::System,
             action: MainMenuAction::SystemTheme,
+            hotkey: Some('y'),
         },
         MainMenuItem {
-            id: 7,
+            id: 9,
             label: "Containers LXC",
             category: MainMenuCategory::Containers,
             action: MainMenuAction::ContainersTheme,
+            hotkey: Some('l'),
         },
         MainMenuItem {
-            id: 8,
+            id: 10,
             label: "RMDB Hôte",
             category: MainMenuCategory::Host,
             action: MainMenuAction::HostTheme,
+            hotkey: Some('h'),
         },
         MainMenuItem {
-            id: 9,
+            id: 11,
             label: "Installation RMDB",
             category: MainMenuCategory::System,
             action: MainMenuAction::InstallMenu,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 10,
+            id: 12,
             label: "Quitter",
             category: MainMenuCategory::System,
             action: MainMenuAction::Quit,
+            hotkey: None,
         },
     ]
 }
@@ -188,54 +244,63 @@ pub fn get_services_submenu() -> Vec<MainMenuItem> {
             label: "Statut des Services",
             category: MainMenuCategory::Services,
             action: MainMenuAction::ServiceStatus,
+            hotkey: None,
         },
         MainMenuItem {
             id: 1,
             label: "Service DHCP",
             category: MainMenuCategory::Services,
             action: MainMenuAction::ServiceDHCP,
+            hotkey: None,
         },
         MainMenuItem {
             id: 2,
             label: "Service DNS",
             category: MainMenuCategory::Services,
             action: MainMenuAction::ServiceDNS,
+            hotkey: None,
         },
         MainMenuItem {
             id: 3,
             label: "Service TFTP",
             category: MainMenuCategory::Services,
             action: MainMenuAction::ServiceTFTP,
+            hotkey: None,
         },
         MainMenuItem {
             id: 4,
             label: "Service HTTP/HTTPS",
             category: MainMenuCategory::Services,
             action: MainMenuAction::ServiceHTTP,
+            hotkey: None,
         },
         MainMenuItem {
             id: 5,
             label: "Démarrer Services",
             category: MainMenuCategory::Services,
             action: MainMenuAction::ServiceStart,
+            hotkey: None,
         },
         MainMenuItem {
             id: 6,
             label: "Arrêter Services",
             category: MainMenuCategory::Services,
             action: MainMenuAction::ServiceStop,
+            hotkey: None,
         },
         MainMenuItem {
             id: 7,
             label: "Redémarrer Services",
             category: MainMenuCategory::Services,
             action: MainMenuAction::ServiceRestart,
+            hotkey: None,
         },
         MainMenuItem {
             id: 8,
             label: "Retour",
             category: MainMenuCategory::Services,
             action: MainMenuAction::ServicesTheme,
+            hotkey: None,
         },
     ]
 }
@@ -247,30 +312,42 @@ pub fn get_ipxe_submenu() -> Vec<MainMenuItem> {
             label: "Menu iPXE",
             category: MainMenuCategory::IPXE,
             action: MainMenuAction::IPXEMenu,
+            hotkey: None,
         },
         MainMenuItem {
             id: 1,
             label: "Entrées de Menu",
             category: MainMenuCategory::IPXE,
             action: MainMenuAction::IPXEEntries,
+            hotkey: None,
         },
         MainMenuItem {
             id: 2,
             label: "Générer Menu",
             category: MainMenuCategory::IPXE,
             action: MainMenuAction::IPXEGenerate,
+            hotkey: None,
         },
         MainMenuItem {
             id: 3,
+            label: "Exporter Menu vers Fichier",
+            category: MainMenuCategory::IPXE,
+            action: MainMenuAction::IPXESave,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 4,
             label: "Configuration iPXE",
             category: MainMenuCategory::IPXE,
             action: MainMenuAction::IPXEConfig,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 4,
+            id: 5,
             label: "Retour",
             category: MainMenuCategory::IPXE,
             action: MainMenuAction::IPXETheme,
+            hotkey: None,
         },
     ]
 }
@@ -282,24 +359,35 @@ pub fn get_clients_submenu() -> Vec<MainMenuItem> {
             label: "Leases DHCP",
             category: MainMenuCategory::Clients,
             action: MainMenuAction::ClientsLeases,
+            hotkey: None,
         },
         MainMenuItem {
             id: 1,
+            label: "Surveiller les Leases",
+            category: MainMenuCategory::Clients,
+            action: MainMenuAction::ClientsLeasesWatch,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 2,
             label: "Clients Connectés",
             category: MainMenuCategory::Clients,
             action: MainMenuAction::ClientsConnected,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 2,
+            id: 3,
             label: "Historique",
             category: MainMenuCategory::Clients,
             action: MainMenuAction::ClientsHistory,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 3,
+            id: 4,
             label: "Retour",
             category: MainMenuCategory::Clients,
             action: MainMenuAction::ClientsTheme,
+            hotkey: None,
         },
     ]
 }
@@ -311,30 +399,42 @@ pub fn get_vms_submenu() -> Vec<MainMenuItem> {
             label: "Liste des VMs",
             category: MainMenuCategory::VMs,
             action: MainMenuAction::VMsList,
+            hotkey: None,
         },
         MainMenuItem {
             id: 1,
             label: "Créer VM",
             category: MainMenuCategory::VMs,
             action: MainMenuAction::VMsCreate,
+            hotkey: None,
         },
         MainMenuItem {
             id: 2,
             label: "Gérer VM",
             category: MainMenuCategory::VMs,
             action: MainMenuAction::VMsManage,
+            hotkey: None,
         },
         MainMenuItem {
             id: 3,
             label: "Overlays",
             category: MainMenuCategory::VMs,
             action: MainMenuAction::VMsOverlays,
+            hotkey: None,
         },
         MainMenuItem {
             id: 4,
+            label: "Nettoyer les overlays orphelins",
+            category: MainMenuCategory::VMs,
+            action: MainMenuAction::VMsOverlaysCleanup,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 5,
             label: "Retour",
             category: MainMenuCategory::VMs,
             action: MainMenuAction::VMsTheme,
+            hotkey: None,
         },
     ]
 }
@@ -346,30 +446,63 @@ pub fn get_configuration_submenu() -> Vec<MainMenuItem> {
             label: "Voir Configuration",
             category: MainMenuCategory::Configuration,
             action: MainMenuAction::ConfigView,
+            hotkey: None,
         },
         MainMenuItem {
             id: 1,
+            label: "Comparer avec un Fichier",
+            category: MainMenuCategory::Configuration,
+            action: MainMenuAction::ConfigDiff,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 2,
             label: "Éditer Configuration",
             category: MainMenuCategory::Configuration,
             action: MainMenuAction::ConfigEdit,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 2,
+            id: 3,
+            label: "Modifier avec $EDITOR",
+            category: MainMenuCategory::Configuration,
+            action: MainMenuAction::ConfigEditExternal,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 4,
             label: "Configuration Réseau",
             category: MainMenuCategory::Configuration,
             action: MainMenuAction::ConfigNetwork,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 3,
+            id: 5,
             label: "Configuration Sécurité",
             category: MainMenuCategory::Configuration,
             action: MainMenuAction::ConfigSecurity,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 4,
+            id: 6,
+            label: "Profils de Connexion API",
+            category: MainMenuCategory::Configuration,
+            action: MainMenuAction::ConfigProfiles,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 7,
+            label: "Enregistrer les préférences TUI",
+            category: MainMenuCategory::Configuration,
+            action: MainMenuAction::ConfigSaveTui,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 8,
             label: "Retour",
             category: MainMenuCategory::Configuration,
             action: MainMenuAction::ConfigurationTheme,
+            hotkey: None,
         },
     ]
 }
@@ -381,61 +514,123 @@ pub fn get_monitoring_submenu() -> Vec<MainMenuItem> {
             label: "Journaux",
             category: MainMenuCategory::Monitoring,
             action: MainMenuAction::MonitoringLogs,
+            hotkey: None,
         },
         MainMenuItem {
             id: 1,
+            label: "Suivre les Journaux du Service",
+            category: MainMenuCategory::Monitoring,
+            action: MainMenuAction::MonitoringLogsFollow,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 2,
             label: "Métriques",
             category: MainMenuCategory::Monitoring,
             action: MainMenuAction::MonitoringMetrics,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 2,
+            id: 3,
             label: "Santé du Système",
             category: MainMenuCategory::Monitoring,
             action: MainMenuAction::MonitoringHealth,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 3,
+            id: 4,
             label: "Dashboard",
             category: MainMenuCategory::Monitoring,
             action: MainMenuAction::MonitoringDashboard,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 4,
+            id: 5,
+            label: "Derniers journaux de déploiement",
+            category: MainMenuCategory::Monitoring,
+            action: MainMenuAction::MonitoringDeployLogs,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 6,
+            label: "Historique des déploiements",
+            category: MainMenuCategory::Monitoring,
+            action: MainMenuAction::MonitoringDeployHistory,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 7,
             label: "Retour",
             category: MainMenuCategory::Monitoring,
             action: MainMenuAction::MonitoringTheme,
+            hotkey: None,
         },
     ]
 }
 
-pub fn get_system_submenu() -> Vec<MainMenuItem> {
-    vec![
+/// Sous-menu Système ; la console de diagnostic et le test d'affichage n'apparaissent qu'en mode expert (et Admin)
+pub fn get_system_submenu(expert_tools: bool) -> Vec<MainMenuItem> {
+    let mut items = vec![
         MainMenuItem {
             id: 0,
             label: "Informations Système",
             category: MainMenuCategory::System,
             action: MainMenuAction::SystemInfo,
+            hotkey: None,
         },
         MainMenuItem {
             id: 1,
             label: "Services Système",
             category: MainMenuCategory::System,
             action: MainMenuAction::SystemServices,
+            hotkey: None,
         },
         MainMenuItem {
             id: 2,
             label: "Processus",
             category: MainMenuCategory::System,
             action: MainMenuAction::SystemProcesses,
+            hotkey: None,
         },
         MainMenuItem {
             id: 3,
-            label: "Retour",
+            label: "Invalider la session sudo",
             category: MainMenuCategory::System,
-            action: MainMenuAction::SystemTheme,
+            action: MainMenuAction::SystemSudoReset,
+            hotkey: None,
         },
-    ]
+        MainMenuItem {
+            id: 4,
+            label: "Vérifier les prérequis",
+            category: MainMenuCategory::System,
+            action: MainMenuAction::SystemRequirements,
+            hotkey: None,
+        },
+    ];
+    if expert_tools {
+        items.push(MainMenuItem {
+            id: items.len(),
+            label: "Console de diagnostic",
+            category: MainMenuCategory::System,
+            action: MainMenuAction::SystemDiagnosticConsole,
+            hotkey: None,
+        });
+        items.push(MainMenuItem {
+            id: items.len(),
+            label: "Test d'affichage",
+            category: MainMenuCategory::System,
+            action: MainMenuAction::SystemDisplayTest,
+            hotkey: None,
+        });
+    }
+    items.push(MainMenuItem {
+        id: items.len(),
+        label: "Retour",
+        category: MainMenuCategory::System,
+        action: MainMenuAction::SystemTheme,
+        hotkey: None,
+    });
+    items
 }
 
 pub fn get_lxc_manage_submenu() -> Vec<MainMenuItem> {
@@ -445,90 +640,119 @@ pub fn get_lxc_manage_submenu() -> Vec<MainMenuItem> {
             label: "Statut du Container",
             category: MainMenuCategory::System,
             action: MainMenuAction::DeployStatus,
+            hotkey: None,
         },
         MainMenuItem {
             id: 1,
             label: "Démarrer Container",
             category: MainMenuCategory::System,
             action: MainMenuAction::LXCStart,
+            hotkey: None,
         },
         MainMenuItem {
             id: 2,
             label: "Arrêter Container",
             category: MainMenuCategory::System,
             action: MainMenuAction::LXCStop,
+            hotkey: None,
         },
         MainMenuItem {
             id: 3,
             label: "Redémarrer Container",
             category: MainMenuCategory::System,
             action: MainMenuAction::LXCRestart,
+            hotkey: None,
         },
         MainMenuItem {
             id: 4,
             label: "Logs du Container",
             category: MainMenuCategory::System,
             action: MainMenuAction::LXCLogs,
+            hotkey: None,
         },
         MainMenuItem {
             id: 5,
             label: "Accès Shell",
             category: MainMenuCategory::System,
             action: MainMenuAction::LXCShell,
+            hotkey: None,
         },
         MainMenuItem {
             id: 6,
             label: "Statistiques",
             category: MainMenuCategory::System,
             action: MainMenuAction::LXCStats,
+            hotkey: None,
         },
         MainMenuItem {
             id: 7,
             label: "Configuration",
             category: MainMenuCategory::System,
             action: MainMenuAction::LXCConfig,
+            hotkey: None,
         },
         MainMenuItem {
             id: 8,
+            label: "Modifier la configuration",
+            category: MainMenuCategory::System,
+            action: MainMenuAction::LXCConfigEdit,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 9,
+            label: "Diagnostic de détection",
+            category: MainMenuCategory::System,
+            action: MainMenuAction::LXCDiagnostic,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 10,
             label: "--- Gestion RMDB ---",
             category: MainMenuCategory::System,
             action: MainMenuAction::SystemInfo, // Action placeholder
+            hotkey: None,
         },
         MainMenuItem {
-            id: 9,
+            id: 11,
             label: "Démarrer RMDB",
             category: MainMenuCategory::System,
             action: MainMenuAction::LXCRmdbStart,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 10,
+            id: 12,
             label: "Arrêter RMDB",
             category: MainMenuCategory::System,
             action: MainMenuAction::LXCRmdbStop,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 11,
+            id: 13,
             label: "Redémarrer RMDB",
             category: MainMenuCategory::System,
             action: MainMenuAction::LXCRmdbRestart,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 12,
+            id: 14,
             label: "Logs RMDB",
             category: MainMenuCategory::System,
             action: MainMenuAction::LXCRmdbLogs,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 13,
+            id: 15,
             label: "Supprimer Container",
             category: MainMenuCategory::System,
             action: MainMenuAction::LXCDestroy,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 14,
+            id: 16,
             label: "Retour",
             category: MainMenuCategory::System,
             action: MainMenuAction::SystemTheme,
+            hotkey: None,
         },
     ]
 }
@@ -540,48 +764,105 @@ pub fn get_containers_submenu() -> Vec<MainMenuItem> {
             label: "Lister",
             category: MainMenuCategory::Containers,
             action: MainMenuAction::ContainersList,
+            hotkey: None,
         },
         MainMenuItem {
             id: 1,
             label: "Démarrer",
             category: MainMenuCategory::Containers,
             action: MainMenuAction::ContainersStart,
+            hotkey: None,
         },
         MainMenuItem {
             id: 2,
             label: "Redémarrer",
             category: MainMenuCategory::Containers,
             action: MainMenuAction::ContainersRestart,
+            hotkey: None,
         },
         MainMenuItem {
             id: 3,
             label: "Stopper",
             category: MainMenuCategory::Containers,
             action: MainMenuAction::ContainersStop,
+            hotkey: None,
         },
         MainMenuItem {
             id: 4,
+            label: "Geler",
+            category: MainMenuCategory::Containers,
+            action: MainMenuAction::ContainersFreeze,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 5,
+            label: "Dégeler",
+            category: MainMenuCategory::Containers,
+            action: MainMenuAction::ContainersUnfreeze,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 6,
             label: "Ajouter",
             category: MainMenuCategory::Containers,
             action: MainMenuAction::ContainersAdd,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 5,
+            id: 7,
+            label: "Cloner",
+            category: MainMenuCategory::Containers,
+            action: MainMenuAction::ContainersClone,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 8,
             label: "Supprimer",
             category: MainMenuCategory::Containers,
             action: MainMenuAction::ContainersDestroy,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 6,
+            id: 9,
             label: "Réinstaller",
             category: MainMenuCategory::Containers,
             action: MainMenuAction::ContainersReinstall,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 7,
+            id: 10,
+            label: "Démarrage auto (activer/désactiver)",
+            category: MainMenuCategory::Containers,
+            action: MainMenuAction::ContainersAutostart,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 11,
+            label: "Santé RMDB (container)",
+            category: MainMenuCategory::Containers,
+            action: MainMenuAction::ContainersHealth,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 12,
+            label: "Réparer la configuration LXC",
+            category: MainMenuCategory::Containers,
+            action: MainMenuAction::ContainersRepairConfig,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 13,
+            label: "Diagnostiquer les containers fantômes",
+            category: MainMenuCategory::Containers,
+            action: MainMenuAction::ContainersGhosts,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 14,
             label: "Retour",
             category: MainMenuCategory::Containers,
             action: MainMenuAction::ContainersTheme,
+            hotkey: None,
         },
     ]
 }
@@ -593,54 +874,77 @@ pub fn get_host_submenu() -> Vec<MainMenuItem> {
             label: "Installer RMDB",
             category: MainMenuCategory::Host,
             action: MainMenuAction::HostInstall,
+            hotkey: None,
         },
         MainMenuItem {
             id: 1,
             label: "Statut",
             category: MainMenuCategory::Host,
             action: MainMenuAction::HostStatus,
+            hotkey: None,
         },
         MainMenuItem {
             id: 2,
+            label: "Vérifier l'installation",
+            category: MainMenuCategory::Host,
+            action: MainMenuAction::HostVerify,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 3,
             label: "Démarrer",
             category: MainMenuCategory::Host,
             action: MainMenuAction::HostStart,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 3,
+            id: 4,
             label: "Arrêter",
             category: MainMenuCategory::Host,
             action: MainMenuAction::HostStop,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 4,
+            id: 5,
             label: "Redémarrer",
             category: MainMenuCategory::Host,
             action: MainMenuAction::HostRestart,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 5,
+            id: 6,
             label: "Activer au démarrage",
             category: MainMenuCategory::Host,
             action: MainMenuAction::HostEnable,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 6,
+            id: 7,
             label: "Désactiver au démarrage",
             category: MainMenuCategory::Host,
             action: MainMenuAction::HostDisable,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 7,
+            id: 8,
+            label: "Changer le mode d'utilisation",
+            category: MainMenuCategory::Host,
+            action: MainMenuAction::HostChangeMode,
+            hotkey: None,
+        },
+        MainMenuItem {
+            id: 9,
             label: "Désinstaller",
             category: MainMenuCategory::Host,
             action: MainMenuAction::HostUninstall,
+            hotkey: None,
         },
         MainMenuItem {
-            id: 8,
+            id: 10,
             label: "Retour",
             category: MainMenuCategory::Host,
             action: MainMenuAction::HostTheme,
+            hotkey: None,
         },
     ]
 }
@@ -5,7 +5,12 @@ pub mod sudo;
 pub mod executor;
 pub mod loading;
 pub mod utils;
+pub mod select_list;
+pub mod text_viewer;
+pub mod display_test;
+pub mod config_editor;
 pub mod main_menu;
+pub mod action_preview;
 pub mod install_menu;
 pub mod main_app;
 
@@ -0,0 +1,413 @@
+use crate::pres::ui::{UI, Color};
+use crate::pres::input::{InputReader, Key};
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+/// État de sélection et de défilement d'une liste
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ListState {
+    pub selected: usize,
+    pub offset: usize,
+}
+
+impl ListState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remonte d'un élément (retour à la fin après le premier)
+    pub fn up(&mut self, len: usize, visible: usize) {
+        if len == 0 {
+            return;
+        }
+        self.selected = if self.selected > 0 { self.selected - 1 } else { len - 1 };
+        self.scroll_to_selection(visible);
+    }
+
+    /// Descend d'un élément (retour au début après le dernier)
+    pub fn down(&mut self, len: usize, visible: usize) {
+        if len == 0 {
+            return;
+        }
+        self.selected = if self.selected + 1 < len { self.selected + 1 } else { 0 };
+        self.scroll_to_selection(visible);
+    }
+
+    /// Va au premier élément
+    pub fn home(&mut self, visible: usize) {
+        self.selected = 0;
+        self.scroll_to_selection(visible);
+    }
+
+    /// Va au dernier élément
+    pub fn end(&mut self, len: usize, visible: usize) {
+        self.selected = len.saturating_sub(1);
+        self.scroll_to_selection(visible);
+    }
+
+    /// Remonte d'un écran (sans retour circulaire)
+    pub fn page_up(&mut self, visible: usize) {
+        self.selected = self.selected.saturating_sub(visible.max(1));
+        self.scroll_to_selection(visible);
+    }
+
+    /// Descend d'un écran (sans retour circulaire)
+    pub fn page_down(&mut self, len: usize, visible: usize) {
+        self.selected = (self.selected + visible.max(1)).min(len.saturating_sub(1));
+        self.scroll_to_selection(visible);
+    }
+
+    /// Position « Élément X/Y », seulement quand la liste dépasse la zone visible
+    pub fn position_label(&self, len: usize, visible: usize) -> Option<String> {
+        (len > visible && len > 0).then(|| format!("Élément {}/{}", self.selected.min(len - 1) + 1, len))
+    }
+
+    /// Ajuste le décalage pour que l'élément sélectionné reste visible
+    pub fn scroll_to_selection(&mut self, visible: usize) {
+        let visible = visible.max(1);
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + visible {
+            self.offset = self.selected + 1 - visible;
+        }
+    }
+}
+
+/// Colonne de tri d'une liste : libellé et fonction de comparaison
+pub type SortColumn<T> = (&'static str, fn(&T, &T) -> Ordering);
+
+/// Colonne de tri courante d'une liste, conservée pendant la session
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SortState {
+    pub column: usize,
+    pub reversed: bool,
+}
+
+impl SortState {
+    /// Passe à la colonne suivante (ordre croissant)
+    pub fn cycle(&mut self, columns: usize) {
+        self.column = if columns == 0 { 0 } else { (self.column + 1) % columns };
+        self.reversed = false;
+    }
+
+    /// Inverse l'ordre de tri
+    pub fn toggle_reverse(&mut self) {
+        self.reversed = !self.reversed;
+    }
+
+    /// Indices des éléments dans l'ordre de tri (tri stable)
+    pub fn order<T>(&self, items: &[T], columns: &[SortColumn<T>]) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..items.len()).collect();
+        if let Some((_, compare)) = columns.get(self.column) {
+            order.sort_by(|&a, &b| compare(&items[a], &items[b]));
+            if self.reversed {
+                order.reverse();
+            }
+        }
+        order
+    }
+
+    /// Libellé du tri courant, ex: `Nom ↑`
+    pub fn label<T>(&self, columns: &[SortColumn<T>]) -> String {
+        match columns.get(self.column) {
+            Some((name, _)) => format!("{} {}", name, if self.reversed { "↓" } else { "↑" }),
+            None => String::new(),
+        }
+    }
+}
+
+/// Résultat d'une sélection dans une `SelectList`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SelectOutcome {
+    /// Entrée pressée sur l'élément donné
+    Selected(usize),
+    /// Touche d'action (déclarée via `with_action_keys`) pressée sur l'élément donné
+    Action(char, usize),
+    /// Q pressé
+    Cancelled,
+}
+
+type LabelFn<'a, T> = Box<dyn Fn(&T) -> String + 'a>;
+type ColorFn<'a, T> = Box<dyn Fn(&T) -> Color + 'a>;
+
+/// Liste sélectionnable générique : rendu, défilement, retour circulaire et scrollbar
+pub struct SelectList<'a, T> {
+    title: &'a str,
+    prompt: Option<&'a str>,
+    items: &'a [T],
+    label: LabelFn<'a, T>,
+    detail: Option<LabelFn<'a, T>>,
+    color: Option<ColorFn<'a, T>>,
+    hint: &'a str,
+    action_keys: &'a [char],
+    state: ListState,
+    sort: Option<(&'a [SortColumn<T>], &'a mut SortState)>,
+    /// Ordre d'affichage : indices dans `items`
+    order: Vec<usize>,
+    /// Éléments cochés avec Espace (indices dans `items`)
+    checked: Option<&'a mut HashSet<usize>>,
+}
+
+impl<'a, T> SelectList<'a, T> {
+    pub fn new(title: &'a str, items: &'a [T], label: impl Fn(&T) -> String + 'a) -> Self {
+        Self {
+            title,
+            prompt: None,
+            items,
+            label: Box::new(label),
+            detail: None,
+            color: None,
+            hint: "Flèches: Naviguer | Entrée: Sélectionner | Q: Retour",
+            action_keys: &[],
+            state: ListState::new(),
+            sort: None,
+            order: (0..items.len()).collect(),
+            checked: None,
+        }
+    }
+
+    /// Texte affiché au-dessus de la liste
+    pub fn with_prompt(mut self, prompt: &'a str) -> Self {
+        self.prompt = Some(prompt);
+        self
+    }
+
+    /// Seconde ligne affichée sous chaque élément
+    pub fn with_detail(mut self, detail: impl Fn(&T) -> String + 'a) -> Self {
+        self.detail = Some(Box::new(detail));
+        self
+    }
+
+    /// Couleur des éléments non sélectionnés
+    pub fn with_color(mut self, color: impl Fn(&T) -> Color + 'a) -> Self {
+        self.color = Some(Box::new(color));
+        self
+    }
+
+    /// Ligne d'aide affichée en bas de l'écran
+    pub fn with_hint(mut self, hint: &'a str) -> Self {
+        self.hint = hint;
+        self
+    }
+
+    /// Touches (en minuscules) renvoyées comme `SelectOutcome::Action`
+    pub fn with_action_keys(mut self, keys: &'a [char]) -> Self {
+        self.action_keys = keys;
+        self
+    }
+
+    /// Élément sélectionné au départ (indice dans `items`)
+    pub fn with_selected(mut self, index: usize) -> Self {
+        self.state.selected = self.order.iter().position(|&i| i == index).unwrap_or(0);
+        self
+    }
+
+    /// Active le tri au clavier : `o` change de colonne, `O` inverse l'ordre
+    pub fn with_sort(mut self, columns: &'a [SortColumn<T>], state: &'a mut SortState) -> Self {
+        let selected = self.order.get(self.state.selected).copied().unwrap_or(0);
+        self.order = state.order(self.items, columns);
+        self.sort = Some((columns, state));
+        self.with_selected(selected)
+    }
+
+    /// Active la sélection multiple : Espace coche ou décoche l'élément courant dans `checked`
+    pub fn with_multi_select(mut self, checked: &'a mut HashSet<usize>) -> Self {
+        self.checked = Some(checked);
+        self
+    }
+
+    /// Coche ou décoche l'élément courant ; faux sans sélection multiple
+    fn toggle_checked(&mut self) -> bool {
+        let index = self.order[self.state.selected];
+        match self.checked.as_mut() {
+            Some(checked) => {
+                if !checked.remove(&index) {
+                    checked.insert(index);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Applique une touche de tri en conservant l'élément sélectionné
+    fn handle_sort_key(&mut self, c: char) -> bool {
+        let (columns, state) = match self.sort.as_mut() {
+            Some((columns, state)) => (*columns, state),
+            None => return false,
+        };
+        match c {
+            'o' => state.cycle(columns.len()),
+            'O' => state.toggle_reverse(),
+            _ => return false,
+        }
+        let selected = self.order.get(self.state.selected).copied().unwrap_or(0);
+        self.order = state.order(self.items, columns);
+        self.state.selected = self.order.iter().position(|&i| i == selected).unwrap_or(0);
+        true
+    }
+
+    /// Affiche la liste et attend un choix de l'utilisateur
+    pub fn run(&mut self, ui: &UI, input: &InputReader) -> SelectOutcome {
+        if self.items.is_empty() {
+            return SelectOutcome::Cancelled;
+        }
+
+        loop {
+            let visible = self.render(ui);
+
+            // Les touches d'action de l'écran sont prioritaires sur les touches vim
+            let key = match input.read_key() {
+                Ok(Key::Char(c)) if self.action_keys.contains(&c.to_ascii_lowercase()) => {
+                    return SelectOutcome::Action(c.to_ascii_lowercase(), self.order[self.state.selected]);
+                }
+                Ok(Key::Char(c)) if self.handle_sort_key(c) => continue,
+                Ok(Key::Char(' ')) if self.toggle_checked() => continue,
+                Ok(key) => Ok(input.navigation_key(key)),
+                Err(e) => Err(e),
+            };
+
+            match key {
+                Ok(Key::Up) => self.state.up(self.items.len(), visible),
+                Ok(Key::Down) => self.state.down(self.items.len(), visible),
+                Ok(Key::Home) => self.state.home(visible),
+                Ok(Key::End) => self.state.end(self.items.len(), visible),
+                Ok(Key::PageUp) => self.state.page_up(visible),
+                Ok(Key::PageDown) => self.state.page_down(self.items.len(), visible),
+                Ok(Key::Enter) => return SelectOutcome::Selected(self.order[self.state.selected]),
+                Ok(Key::Quit) | Ok(Key::Interrupt) => return SelectOutcome::Cancelled,
+                _ => {}
+            }
+        }
+    }
+
+    /// Raccourci pour les listes sans touches d'action
+    pub fn select(mut self, ui: &UI, input: &InputReader) -> Option<usize> {
+        match self.run(ui, input) {
+            SelectOutcome::Selected(index) => Some(index),
+            _ => None,
+        }
+    }
+
+    /// Dessine la liste et retourne le nombre d'éléments visibles
+    fn render(&mut self, ui: &UI) -> usize {
+        ui.clear_screen();
+        ui.draw_header(self.title);
+        let (box_x, box_y, box_w, box_h) = ui.get_box_dimensions();
+        let mut y = box_y + 4;
+
+        if let Some(prompt) = self.prompt {
+            ui.set_color(Color::Info);
+            ui.draw_text(box_x + 2, y, prompt);
+            y += 2;
+        }
+
+        let lines_per_item: u16 = if self.detail.is_some() { 2 } else { 1 };
+        let list_height = box_h.saturating_sub(y + 3);
+        let visible = ((list_height / lines_per_item) as usize).max(1);
+        self.state.scroll_to_selection(visible);
+
+        let start = self.state.offset;
+        let end = (start + visible).min(self.items.len());
+        let list_y = y;
+
+        for (i, &item_index) in self.order[start..end].iter().enumerate() {
+            let item = &self.items[item_index];
+            let index = start + i;
+            let is_selected = index == self.state.selected;
+            let mut marker = if is_selected { "▶" } else { " " }.to_string();
+            if let Some(ref checked) = self.checked {
+                marker.push_str(if checked.contains(&item_index) { " [x]" } else { " [ ]" });
+            }
+            let color = if is_selected {
+                Color::Selection
+            } else {
+                self.color.as_ref().map(|f| f(item)).unwrap_or(Color::Fg)
+            };
+
+            ui.set_color(color);
+            ui.draw_text_clipped(box_x + 2, y, &format!("{} {}", marker, (self.label)(item)), usize::MAX);
+            y += 1;
+
+            if let Some(ref detail) = self.detail {
+                ui.set_color(Color::Info);
+                ui.draw_text_clipped(box_x + 4, y, &detail(item), usize::MAX);
+                y += 1;
+            }
+        }
+
+        ui.draw_scrollbar(box_w.saturating_sub(3), list_y, list_height, self.items.len(), visible, self.state.offset);
+
+        ui.set_color(Color::Reset);
+        let mut hint = self.hint.to_string();
+        if let Some(ref checked) = self.checked {
+            hint.push_str(&format!(" | Espace: Cocher ({})", checked.len()));
+        }
+        if let Some((columns, state)) = &self.sort {
+            hint.push_str(&format!(" | o/O: Tri ({})", state.label(columns)));
+        }
+        ui.draw_text(box_x + 2, box_h.saturating_sub(2), &hint);
+        visible
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_state_wraparound() {
+        let mut state = ListState::new();
+        state.up(5, 3);
+        assert_eq!(state, ListState { selected: 4, offset: 2 });
+        state.down(5, 3);
+        assert_eq!(state, ListState { selected: 0, offset: 0 });
+        assert_eq!(state.position_label(5, 3).as_deref(), Some("Élément 1/5"));
+        assert_eq!(state.position_label(3, 3), None);
+    }
+
+    #[test]
+    fn test_sort_state_order() {
+        let items = vec![("b", 2), ("a", 3), ("c", 1)];
+        let columns: [SortColumn<(&str, i32)>; 2] = [
+            ("Nom", |a, b| a.0.cmp(b.0)),
+            ("Valeur", |a, b| a.1.cmp(&b.1)),
+        ];
+        let mut state = SortState::default();
+        assert_eq!(state.order(&items, &columns), vec![1, 0, 2]);
+        state.toggle_reverse();
+        assert_eq!(state.order(&items, &columns), vec![2, 0, 1]);
+        state.cycle(columns.len());
+        assert_eq!(state.order(&items, &columns), vec![2, 0, 1]);
+        assert_eq!(state.label(&columns), "Valeur ↑");
+    }
+
+    #[test]
+    fn test_list_state_paging() {
+        let mut state = ListState::new();
+        state.page_down(10, 4);
+        assert_eq!(state, ListState { selected: 4, offset: 1 });
+        state.end(10, 4);
+        assert_eq!(state, ListState { selected: 9, offset: 6 });
+        state.page_down(10, 4);
+        assert_eq!(state.selected, 9);
+        state.home(4);
+        assert_eq!(state, ListState { selected: 0, offset: 0 });
+    }
+
+    #[test]
+    fn test_multi_select_toggle() {
+        let items = vec!["a", "b", "c"];
+        let mut checked = HashSet::new();
+        let mut list = SelectList::new("t", &items, |s| s.to_string()).with_multi_select(&mut checked).with_selected(2);
+        assert!(list.toggle_checked());
+        list.state.selected = 0;
+        assert!(list.toggle_checked());
+        assert!(list.toggle_checked());
+        drop(list);
+        assert_eq!(checked, HashSet::from([2]));
+
+        let mut single = SelectList::new("t", &items, |s| s.to_string());
+        assert!(!single.toggle_checked());
+    }
+}
@@ -7,11 +7,22 @@ use std::thread::{self, JoinHandle};
 use std::time::Duration;
 
 /// Maintient la session sudo vivante (timestamp) sans stocker de mot de passe.
-/// Le keep-alive est stoppé automatiquement au drop.
+/// Le keep-alive est stoppé automatiquement au drop, qui invalide aussi la session
+/// si `with_invalidate_on_drop(true)`.
 pub struct SudoKeepAliveGuard {
     stop: Arc<AtomicBool>,
     needs_reauth: Arc<AtomicBool>,
     handle: Option<JoinHandle<()>>,
+    invalidate_on_drop: bool,
+}
+
+/// Oublie les identifiants sudo en cache (`sudo -k`) ; ne demande jamais de mot de passe
+pub fn invalidate_sudo_session() -> bool {
+    Command::new("sudo")
+        .arg("-k")
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
 }
 
 impl SudoKeepAliveGuard {
@@ -47,9 +58,22 @@ impl SudoKeepAliveGuard {
             stop,
             needs_reauth,
             handle: Some(handle),
+            invalidate_on_drop: false,
         }
     }
 
+    /// `sudo -k` à l'arrêt du keep-alive : le timestamp ne survit pas à l'application
+    pub fn with_invalidate_on_drop(mut self, enabled: bool) -> Self {
+        self.invalidate_on_drop = enabled;
+        self
+    }
+
+    /// Invalide la session sudo tout de suite ; la prochaine action Admin redemandera le mot de passe
+    pub fn invalidate(&self) -> bool {
+        self.needs_reauth.store(true, Ordering::Relaxed);
+        invalidate_sudo_session()
+    }
+
     pub fn needs_reauth(&self) -> bool {
         self.needs_reauth.load(Ordering::Relaxed)
     }
@@ -69,6 +93,9 @@ impl SudoKeepAliveGuard {
 impl Drop for SudoKeepAliveGuard {
     fn drop(&mut self) {
         self.stop();
+        if self.invalidate_on_drop {
+            invalidate_sudo_session();
+        }
     }
 }
 
@@ -1,20 +1,55 @@
+use std::io::{self, Write};
 use std::process::Command;
 use std::str;
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::{Mutex, Once};
+
+/// État stty à restaurer, présent tant que le mode TUI est actif
+static SAVED_STTY: Mutex<Option<Option<String>>> = Mutex::new(None);
+/// Extrémité d'écriture du pipe par lequel le handler signale SIGINT/SIGTERM/SIGHUP
+static SIGNAL_PIPE_WRITE: AtomicI32 = AtomicI32::new(-1);
+/// Terminal rendu à un programme interactif : son Ctrl-C ne doit pas fermer le TUI
+static SUSPENDED: AtomicBool = AtomicBool::new(false);
 
 pub struct Terminal {
     width: u16,
     height: u16,
     pub min_width: u16,
     pub min_height: u16,
+    /// Taille minimale acceptée en disposition compacte (en dessous de `min_width`/`min_height`)
+    pub compact_min_width: u16,
+    pub compact_min_height: u16,
     pub optimal_width: u16,
     pub optimal_height: u16,
 }
 
-/// Active un mode terminal adapté au TUI (équivalent de `stty -echo -icanon min 1 time 0`).
-/// Le mode est restauré automatiquement au drop.
+/// Indique si la locale courante (LC_ALL, LC_CTYPE puis LANG) annonce un encodage UTF-8
+pub fn locale_supports_utf8() -> bool {
+    let var = |name: &str| std::env::var(name).ok();
+    is_utf8_locale(var("LC_ALL"), var("LC_CTYPE"), var("LANG"))
+}
+
+/// La première variable non vide l'emporte, comme pour setlocale(3)
+fn is_utf8_locale(lc_all: Option<String>, lc_ctype: Option<String>, lang: Option<String>) -> bool {
+    [lc_all, lc_ctype, lang]
+        .into_iter()
+        .flatten()
+        .find(|value| !value.is_empty())
+        .map(|value| {
+            let value = value.to_lowercase();
+            value.contains("utf-8") || value.contains("utf8")
+        })
+        .unwrap_or(false)
+}
+
+/// Active un mode terminal adapté au TUI (équivalent de `stty -echo -icanon -isig min 1 time 0`).
+/// Le mode est restauré automatiquement au drop, ou à la réception de SIGINT/SIGTERM/SIGHUP.
+///
+/// Sans `isig`, Ctrl-C est lu comme une touche (`Key::Interrupt`) ; SIGINT reste traité
+/// s'il vient d'ailleurs (`kill -INT`).
 pub struct RawModeGuard {
-    original: Option<String>,
+    _private: (),
 }
 
 impl RawModeGuard {
@@ -38,6 +73,15 @@ impl RawModeGuard {
         false
     }
 
+    /// Règle le délai de lecture de stdin en dixièmes de seconde (0 = lecture bloquante)
+    pub fn set_read_timeout(deciseconds: u8) -> bool {
+        if deciseconds == 0 {
+            Self::run_stty(&["min", "1", "time", "0"])
+        } else {
+            Self::run_stty(&["min", "0", "time", &deciseconds.to_string()])
+        }
+    }
+
     fn get_stty_state() -> Option<String> {
         let candidates = ["/usr/bin/stty", "/bin/stty", "stty"];
         for cmd in candidates {
@@ -58,6 +102,11 @@ impl RawModeGuard {
     }
 
     pub fn enable() -> Self {
+        Self::activate();
+        Self { _private: () }
+    }
+
+    fn activate() {
         // Sauvegarder l’état courant (best-effort)
         let original = Self::get_stty_state();
 
@@ -65,25 +114,107 @@ impl RawModeGuard {
         // 1) Essayer `stty raw -echo` (le plus direct)
         // 2) Fallback: équivalent run.sh
         if !Self::run_stty(&["raw", "-echo"]) {
-            let _ = Self::run_stty(&["-echo", "-icanon", "min", "1", "time", "0"]);
+            let _ = Self::run_stty(&["-echo", "-icanon", "-isig", "min", "1", "time", "0"]);
+        }
+
+        if let Ok(mut saved) = SAVED_STTY.lock() {
+            *saved = Some(original);
         }
+        install_signal_handlers();
+    }
 
-        Self { original }
+    /// Rend le terminal à un programme interactif (éditeur, shell) le temps de `f`,
+    /// puis réactive le mode TUI. L'écran est à redessiner ensuite.
+    ///
+    /// Pendant `f`, SIGINT (Ctrl-C dans l'éditeur ou le shell) est laissé au programme.
+    pub fn suspend<T>(f: impl FnOnce() -> T) -> T {
+        Self::restore();
+        print!("\x1B[2J\x1B[H");
+        let _ = io::stdout().flush();
+        SUSPENDED.store(true, Ordering::Relaxed);
+        let result = f();
+        SUSPENDED.store(false, Ordering::Relaxed);
+        Self::activate();
+        result
     }
-}
 
-impl Drop for RawModeGuard {
-    fn drop(&mut self) {
-        if let Some(orig) = self.original.as_deref() {
+    /// Restaure le terminal (mode stty, curseur, couleurs).
+    /// Sans effet si le terminal a déjà été restauré (drop après un signal, ou l'inverse).
+    pub fn restore() {
+        let Some(original) = SAVED_STTY.lock().ok().and_then(|mut saved| saved.take()) else {
+            return;
+        };
+
+        if let Some(orig) = original.as_deref() {
             // Restaurer exactement l’état initial
             let _ = Self::run_stty(&[orig]);
         } else {
             // fallback : au moins réactiver un mode sane
             let _ = Self::run_stty(&["sane"]);
         }
+        print!("\x1B[0m\x1B[?25h");
+        let _ = io::stdout().flush();
+    }
+}
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        Self::restore();
+    }
+}
+
+/// Handler de signal : se contente d'écrire le numéro du signal dans le pipe
+/// (seule opération sûre ici), la restauration se fait dans le thread de surveillance.
+extern "C" fn on_terminate_signal(signum: libc::c_int) {
+    if signum == libc::SIGINT && SUSPENDED.load(Ordering::Relaxed) {
+        return;
+    }
+    let fd = SIGNAL_PIPE_WRITE.load(Ordering::Relaxed);
+    if fd >= 0 {
+        let byte = signum as u8;
+        unsafe {
+            libc::write(fd, &byte as *const u8 as *const libc::c_void, 1);
+        }
     }
 }
 
+/// Installe une seule fois les handlers SIGINT/SIGTERM/SIGHUP qui restaurent le terminal avant de quitter
+fn install_signal_handlers() {
+    static INSTALLED: Once = Once::new();
+    INSTALLED.call_once(|| {
+        let mut fds = [0 as libc::c_int; 2];
+        if unsafe { libc::pipe(fds.as_mut_ptr()) } != 0 {
+            return;
+        }
+        let read_fd = fds[0];
+        SIGNAL_PIPE_WRITE.store(fds[1], Ordering::Relaxed);
+
+        std::thread::spawn(move || {
+            let mut byte = 0u8;
+            loop {
+                let n = unsafe { libc::read(read_fd, &mut byte as *mut u8 as *mut libc::c_void, 1) };
+                if n == 1 {
+                    break;
+                }
+                if n < 0 && io::Error::last_os_error().kind() == io::ErrorKind::Interrupted {
+                    continue;
+                }
+                return;
+            }
+            RawModeGuard::restore();
+            println!();
+            std::process::exit(128 + byte as i32);
+        });
+
+        let handler = on_terminate_signal as extern "C" fn(libc::c_int) as libc::sighandler_t;
+        for signum in [libc::SIGINT, libc::SIGTERM, libc::SIGHUP] {
+            unsafe {
+                libc::signal(signum, handler);
+            }
+        }
+    });
+}
+
 impl Terminal {
     pub fn new() -> Self {
         let (width, height) = Self::get_size();
@@ -92,6 +223,8 @@ impl Terminal {
             height,
             min_width: 80,
             min_height: 24,
+            compact_min_width: 60,
+            compact_min_height: 20,
             optimal_width: 100,
             optimal_height: 30,
         }
@@ -135,7 +268,12 @@ impl Terminal {
     }
 
     pub fn is_valid_size(&self) -> bool {
-        self.width >= self.min_width && self.height >= self.min_height
+        self.width >= self.compact_min_width && self.height >= self.compact_min_height
+    }
+
+    /// Vrai si le terminal est utilisable mais plus petit que la taille de la disposition complète
+    pub fn is_compact(&self) -> bool {
+        self.is_valid_size() && (self.width < self.min_width || self.height < self.min_height)
     }
 
     pub fn is_optimal_size(&self) -> bool {
@@ -167,7 +305,12 @@ impl Terminal {
 
     pub fn get_max_visible_items(&self) -> usize {
         let (_, _, _, box_height) = self.get_box_dimensions();
-        (box_height - 10).max(5) as usize
+        if self.is_compact() {
+            // En-tête sur une ligne : le menu commence plus haut
+            box_height.saturating_sub(6).max(3) as usize
+        } else {
+            box_height.saturating_sub(10).max(5) as usize
+        }
     }
     
     pub fn calculate_required_height(&self, content_lines: usize, header_lines: usize, footer_lines: usize) -> u16 {
@@ -199,4 +342,28 @@ mod tests {
         assert!(term.width > 0);
         assert!(term.height > 0);
     }
+
+    #[test]
+    fn test_compact_layout_range() {
+        let mut term = Terminal::new();
+        term.width = 70;
+        term.height = 22;
+        assert!(term.is_valid_size());
+        assert!(term.is_compact());
+        assert_eq!(term.get_max_visible_items(), 16);
+        term.width = 80;
+        term.height = 24;
+        assert!(!term.is_compact());
+        term.width = 50;
+        assert!(!term.is_valid_size());
+    }
+
+    #[test]
+    fn test_is_utf8_locale() {
+        let some = |v: &str| Some(v.to_string());
+        assert!(is_utf8_locale(None, None, some("fr_FR.UTF-8")));
+        assert!(is_utf8_locale(some(""), some("C.utf8"), some("C")));
+        assert!(!is_utf8_locale(some("C"), None, some("fr_FR.UTF-8")));
+        assert!(!is_utf8_locale(None, None, None));
+    }
 }
@@ -0,0 +1,364 @@
+use crate::pres::ui::{UI, Color};
+use crate::pres::utils::display_width;
+use crate::pres::input::{InputReader, Key};
+use crate::pres::executor::StreamingCommand;
+use crate::pres::config_editor::read_line;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const DEFAULT_HINT: &str = "Flèches/PagePréc/PageSuiv: Défiler | E: Exporter | Q: Retour";
+/// Indentation maximale affichée (en colonnes) ; au-delà, la profondeur est signalée par `…`
+const MAX_INDENT: usize = 24;
+
+/// Visionneuse de texte défilante (journaux, diffs, configuration)
+///
+/// `E` enregistre tout le texte (pas seulement la partie visible) dans un fichier.
+pub struct TextViewer<'a> {
+    title: &'a str,
+    /// Chaque ligne est une suite de segments colorés, affichés bout à bout
+    lines: Vec<Vec<(String, Color)>>,
+    offset: usize,
+    hint: &'a str,
+    action_keys: &'a [char],
+    /// Texte écrit par `E` à la place des lignes affichées (rapport brut)
+    export_text: Option<&'a str>,
+    /// Résultat de la dernière exportation, affiché au-dessus de l'aide
+    status: Option<(String, Color)>,
+}
+
+impl<'a> TextViewer<'a> {
+    pub fn new(title: &'a str) -> Self {
+        Self {
+            title,
+            lines: Vec::new(),
+            offset: 0,
+            hint: DEFAULT_HINT,
+            action_keys: &[],
+            export_text: None,
+            status: None,
+        }
+    }
+
+    pub fn with_hint(mut self, hint: &'a str) -> Self {
+        self.hint = hint;
+        self
+    }
+
+    /// Touches (minuscules) qui ferment la visionneuse et sont renvoyées par `run`
+    pub fn with_action_keys(mut self, keys: &'a [char]) -> Self {
+        self.action_keys = keys;
+        self
+    }
+
+    /// Texte enregistré par `E` à la place des lignes affichées
+    pub fn with_export_text(mut self, text: &'a str) -> Self {
+        self.export_text = Some(text);
+        self
+    }
+
+    /// Ajoute une ligne avec sa couleur
+    pub fn push(&mut self, line: impl Into<String>, color: Color) {
+        self.lines.push(vec![(line.into(), color)]);
+    }
+
+    /// Ajoute une ligne composée de segments de couleurs différentes
+    pub fn push_segments(&mut self, segments: Vec<(String, Color)>) {
+        self.lines.push(segments);
+    }
+
+    /// Ajoute un JSON indenté (`to_string_pretty`), clés et valeurs colorées différemment
+    pub fn push_json(&mut self, text: &str) {
+        for line in text.lines() {
+            self.push_segments(json_line_segments(line));
+        }
+    }
+
+    /// Ajoute chaque ligne d'un texte avec la même couleur
+    pub fn push_text(&mut self, text: &str, color: Color) {
+        for line in text.lines() {
+            self.push(line, color);
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Message affiché au-dessus de l'aide (résultat d'une action)
+    pub fn set_status(&mut self, message: impl Into<String>, color: Color) {
+        self.status = Some((message.into(), color));
+    }
+
+    /// Texte complet, une ligne par entrée
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        for line in &self.lines {
+            for (segment, _) in line {
+                text.push_str(segment);
+            }
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Affiche le texte et gère le défilement jusqu'à Q ; renvoie la touche d'action pressée
+    pub fn run(&mut self, ui: &UI, input: &InputReader) -> Option<char> {
+        loop {
+            let visible = self.render(ui);
+            let max_offset = self.lines.len().saturating_sub(visible);
+
+            match input.read_nav_key() {
+                Ok(Key::Up) => self.offset = self.offset.saturating_sub(1),
+                Ok(Key::Down) => self.offset = (self.offset + 1).min(max_offset),
+                Ok(Key::PageUp) => self.offset = self.offset.saturating_sub(visible),
+                Ok(Key::PageDown) => self.offset = (self.offset + visible).min(max_offset),
+                Ok(Key::Home) => self.offset = 0,
+                Ok(Key::End) => self.offset = max_offset,
+                Ok(Key::Char('e')) | Ok(Key::Char('E')) => self.export(ui, input),
+                Ok(Key::Char(c)) if self.action_keys.contains(&c.to_ascii_lowercase()) => {
+                    return Some(c.to_ascii_lowercase());
+                }
+                Ok(Key::Quit) | Ok(Key::Interrupt) | Ok(Key::Enter) | Ok(Key::Backspace) => break,
+                Err(_) => break,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// Demande un chemin (Entrée seule : nom horodaté dans le répertoire courant) et y écrit le texte
+    fn export(&mut self, ui: &UI, input: &InputReader) {
+        let text = match self.export_text {
+            Some(text) => text.to_string(),
+            None => self.text(),
+        };
+        if let Some(status) = prompt_export(ui, input, &text) {
+            self.status = Some(status);
+        }
+    }
+
+    /// Affiche la sortie d'une commande en continu jusqu'à Q
+    ///
+    /// La vue suit la fin du texte sauf si l'utilisateur a remonté le défilement.
+    pub fn run_stream(&mut self, ui: &UI, input: &InputReader, stream: &mut StreamingCommand) {
+        let mut visible = self.render(ui);
+        let mut follow = true;
+        let mut finished = false;
+
+        loop {
+            let new_lines = stream.try_lines();
+            let mut dirty = !new_lines.is_empty();
+            for line in new_lines {
+                self.push(line, Color::Fg);
+            }
+            if !finished && !stream.is_running() {
+                finished = true;
+                self.push("— Fin du flux —", Color::Warning);
+                dirty = true;
+            }
+
+            let max_offset = self.lines.len().saturating_sub(visible);
+            if dirty {
+                if follow {
+                    self.offset = max_offset;
+                }
+                visible = self.render(ui);
+            }
+
+            match input.poll_key(Duration::from_millis(200)) {
+                Ok(Some(key)) => {
+                    match input.navigation_key(key) {
+                        Key::Up => self.offset = self.offset.saturating_sub(1),
+                        Key::Down => self.offset = (self.offset + 1).min(max_offset),
+                        Key::PageUp => self.offset = self.offset.saturating_sub(visible),
+                        Key::PageDown => self.offset = (self.offset + visible).min(max_offset),
+                        Key::Home => self.offset = 0,
+                        Key::End => self.offset = max_offset,
+                        Key::Char('e') | Key::Char('E') => self.export(ui, input),
+                        // Ctrl-G n'arrête pas une commande en cours : le retour attend la fin du flux
+                        Key::Quit if input.main_menu_requested() && !finished => continue,
+                        Key::Quit | Key::Interrupt | Key::Enter | Key::Backspace => break,
+                        _ => continue,
+                    }
+                    follow = self.offset >= max_offset;
+                    visible = self.render(ui);
+                }
+                Ok(None) => {}
+                Err(_) => break,
+            }
+        }
+
+        stream.stop();
+    }
+
+    /// Dessine la portion visible et retourne le nombre de lignes affichables
+    fn render(&mut self, ui: &UI) -> usize {
+        ui.clear_screen();
+        ui.draw_header(self.title);
+        let (box_x, box_y, box_w, box_h) = ui.get_box_dimensions();
+        let top = box_y + 4;
+        let height = box_h.saturating_sub(top + 3);
+        let visible = (height as usize).max(1);
+        self.offset = self.offset.min(self.lines.len().saturating_sub(visible));
+
+        let end = (self.offset + visible).min(self.lines.len());
+        for (i, line) in self.lines[self.offset..end].iter().enumerate() {
+            let mut x = box_x + 2;
+            for (segment, color) in line {
+                // Indentation trop profonde (JSON très imbriqué) : bornée à l'affichage seulement,
+                // `text()` garde la ligne d'origine
+                let (segment, color) = if display_width(segment) > MAX_INDENT && segment.trim().is_empty() {
+                    (format!("{}… ", " ".repeat(MAX_INDENT - 2)), Color::Warning)
+                } else {
+                    (segment.clone(), *color)
+                };
+                ui.set_color(color);
+                ui.draw_text(x, top + i as u16, &segment);
+                x = x.saturating_add(display_width(&segment) as u16);
+            }
+        }
+
+        ui.draw_scrollbar(box_w.saturating_sub(3), top, height, self.lines.len(), visible, self.offset);
+
+        if let Some((message, color)) = &self.status {
+            ui.set_color(*color);
+            ui.draw_text_clipped(box_x + 2, box_h.saturating_sub(3), message, usize::MAX);
+        }
+        ui.set_color(Color::Reset);
+        ui.draw_text(box_x + 2, box_h.saturating_sub(2), self.hint);
+        visible
+    }
+}
+
+/// Découpe une ligne de JSON indenté sans la modifier : indentation, clé en `Accent`,
+/// valeur en `Info`, ponctuation (`{`, `]`, `,`, `:`) en `Fg`
+fn json_line_segments(line: &str) -> Vec<(String, Color)> {
+    let body = line.trim_start();
+    let mut segments = vec![(line[..line.len() - body.len()].to_string(), Color::Fg)];
+
+    let mut rest = body;
+    if let Some(end) = json_string_end(rest) {
+        if rest[end..].starts_with(':') {
+            segments.push((rest[..end].to_string(), Color::Accent));
+            segments.push((":".to_string(), Color::Fg));
+            rest = &rest[end + 1..];
+        }
+    }
+    let value = rest.trim_end_matches(',');
+    let (lead, value) = value.split_at(value.len() - value.trim_start().len());
+    if !lead.is_empty() {
+        segments.push((lead.to_string(), Color::Fg));
+    }
+    if !value.is_empty() {
+        let is_bracket = matches!(value, "{" | "}" | "[" | "]" | "{}" | "[]");
+        segments.push((value.to_string(), if is_bracket { Color::Fg } else { Color::Info }));
+    }
+    if rest.len() > lead.len() + value.len() {
+        segments.push((",".to_string(), Color::Fg));
+    }
+    segments
+}
+
+/// Position juste après la chaîne JSON qui ouvre `text` (guillemets échappés compris)
+fn json_string_end(text: &str) -> Option<usize> {
+    let mut chars = text.char_indices();
+    if chars.next()?.1 != '"' {
+        return None;
+    }
+    let mut escaped = false;
+    for (i, c) in chars {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => return Some(i + 1),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Demande un chemin en bas de l'écran et y écrit `text` ; message de résultat, `None` si annulé
+pub fn prompt_export(ui: &UI, input: &InputReader, text: &str) -> Option<(String, Color)> {
+    let (box_x, _, _, box_h) = ui.get_box_dimensions();
+    let y = box_h.saturating_sub(3);
+    ui.clear_line(y.saturating_sub(1));
+    ui.set_color(Color::Info);
+    ui.draw_text(box_x + 2, y.saturating_sub(1), "Exporter vers (Entrée: fichier horodaté, Échap: annuler) :");
+    ui.clear_line(y);
+    let path = read_line(ui, input, box_x + 2, y)?;
+    let path = export_path(path.trim());
+    Some(match export_text(&path, text) {
+        Ok(bytes) => (format!("✓ {} octets écrits dans {}", bytes, path.display()), Color::Success),
+        Err(message) => (format!("✗ {}", message), Color::Error),
+    })
+}
+
+/// Chemin d'exportation : `~/` développé, nom horodaté si la saisie est vide
+fn export_path(input: &str) -> PathBuf {
+    if input.is_empty() {
+        return PathBuf::from(format!("rmdb-export-{}.log", chrono::Local::now().format("%Y%m%d-%H%M%S")));
+    }
+    match input.strip_prefix("~/") {
+        Some(rest) => PathBuf::from(std::env::var("HOME").unwrap_or_default()).join(rest),
+        None => PathBuf::from(input),
+    }
+}
+
+/// Écrit le texte et renvoie le nombre d'octets, ou un message d'erreur lisible
+pub fn export_text(path: &Path, text: &str) -> Result<usize, String> {
+    match fs::write(path, text) {
+        Ok(()) => Ok(text.len()),
+        Err(e) => Err(match e.kind() {
+            io::ErrorKind::PermissionDenied => format!("Permission refusée: {}", path.display()),
+            io::ErrorKind::NotFound => format!(
+                "Répertoire inexistant: {}",
+                path.parent().map(|p| p.display().to_string()).unwrap_or_default()
+            ),
+            _ if path.is_dir() => format!("{} est un répertoire", path.display()),
+            _ => format!("Écriture impossible dans {}: {}", path.display(), e),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_full_text() {
+        let mut viewer = TextViewer::new("Journal");
+        for i in 0..500 {
+            viewer.push(format!("ligne {}", i), Color::Fg);
+        }
+        let dir = std::env::temp_dir().join(format!("rmdb-export-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let path = dir.join("logs.txt");
+        let text = viewer.text();
+        assert_eq!(export_text(&path, &text), Ok(text.len()));
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 500);
+
+        let missing = export_text(&dir.join("absent/logs.txt"), &text).unwrap_err();
+        assert!(missing.starts_with("Répertoire inexistant"), "{}", missing);
+        assert!(export_text(&dir, &text).is_err());
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_json_line_segments() {
+        let key = json_line_segments(r#"    "name\"x": "rmdb","#);
+        assert_eq!(key[1], (r#""name\"x""#.to_string(), Color::Accent));
+        assert_eq!(key.last().unwrap(), &(",".to_string(), Color::Fg));
+        assert!(key.contains(&(r#""rmdb""#.to_string(), Color::Info)));
+
+        // L'indentation profonde n'est bornée qu'à l'affichage : l'export reste du JSON valide
+        let deep = format!("{{\n{}\"a\": 1\n}}", " ".repeat(80));
+        let mut viewer = TextViewer::new("Configuration");
+        viewer.push_json(&deep);
+        assert_eq!(viewer.text(), format!("{}\n", deep));
+        assert!(serde_json::from_str::<serde_json::Value>(&viewer.text()).is_ok());
+    }
+}
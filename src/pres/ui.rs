@@ -1,15 +1,95 @@
+use std::borrow::Cow;
 use std::io::{self, Write};
-use crate::pres::terminal::Terminal;
+use crate::pres::terminal::{self, Terminal};
+use crate::pres::utils::{base64_encode, center_in_width, display_width, truncate_with_ellipsis};
+
+/// Colonnes laissées libres à droite du contenu (barre de défilement et marge)
+const CONTENT_RIGHT_MARGIN: u16 = 3;
+
+/// Disposition de l'écran selon la taille du terminal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LayoutMode {
+    /// En-tête encadré sur trois lignes, marges complètes
+    Full,
+    /// En-tête sur une ligne et marges réduites (petits terminaux, SSH mobile)
+    Compact,
+}
+
+/// Glyphes de l'interface : (nom, rendu UTF-8, rendu ASCII)
+const GLYPHS: &[(&str, &str, &str)] = &[
+    ("top_left", "┌", "+"),
+    ("top_right", "┐", "+"),
+    ("bottom_left", "└", "+"),
+    ("bottom_right", "┘", "+"),
+    ("horizontal", "─", "-"),
+    ("vertical", "│", "|"),
+    ("selected", "▶", ">"),
+    ("selected_end", "◀", "<"),
+    ("thumb", "█", "#"),
+    ("progress_empty", "░", "."),
+    ("ok", "✓", "[OK]"),
+    ("error", "✗", "[X]"),
+    ("warning", "⚠", "[!]"),
+];
+
+/// Équivalents ASCII des caractères présents dans les textes affichés
+const ASCII_FALLBACKS: &[(char, &str)] = &[
+    ('┌', "+"), ('┐', "+"), ('└', "+"), ('┘', "+"), ('├', "+"), ('┤', "+"), ('┬', "+"), ('┴', "+"), ('┼', "+"),
+    ('╔', "+"), ('╗', "+"), ('╚', "+"), ('╝', "+"),
+    ('─', "-"), ('━', "-"), ('═', "="), ('│', "|"), ('║', "|"),
+    ('▶', ">"), ('◀', "<"), ('→', "->"), ('←', "<-"), ('•', "*"), ('█', "#"), ('░', "."), ('…', "..."),
+    ('✓', "[OK]"), ('✔', "[OK]"), ('✅', "[OK]"), ('✗', "[X]"), ('✘', "[X]"), ('❌', "[X]"), ('⚠', "[!]"),
+    ('—', "-"), ('–', "-"), ('«', "\""), ('»', "\""),
+];
 
 pub struct UI {
     pub terminal: Terminal,
+    /// Remplace les caractères de dessin et symboles par de l'ASCII (locale non UTF-8 ou `--ascii`)
+    pub asciify: bool,
 }
 
 impl UI {
     pub fn new() -> Self {
         Self {
             terminal: Terminal::new(),
+            asciify: !terminal::locale_supports_utf8(),
+        }
+    }
+
+    /// Tous les glyphes (nom, rendu actuel), pour le test d'affichage
+    pub fn glyphs(&self) -> Vec<(&'static str, &'static str)> {
+        GLYPHS.iter().map(|(name, _, _)| (*name, self.glyph(name))).collect()
+    }
+
+    /// Retourne le glyphe nommé, en ASCII si l'affichage UTF-8 n'est pas disponible
+    pub fn glyph(&self, name: &str) -> &'static str {
+        GLYPHS
+            .iter()
+            .find(|(glyph_name, _, _)| *glyph_name == name)
+            .map(|(_, unicode, ascii)| if self.asciify { *ascii } else { *unicode })
+            .unwrap_or("?")
+    }
+
+    /// Convertit un texte pour l'affichage (translittération ASCII si nécessaire)
+    fn render_text<'t>(&self, text: &'t str) -> Cow<'t, str> {
+        if !self.asciify || text.is_ascii() {
+            return Cow::Borrowed(text);
         }
+        let mut result = String::with_capacity(text.len());
+        for c in text.chars() {
+            match ASCII_FALLBACKS.iter().find(|(unicode, _)| *unicode == c) {
+                Some((_, ascii)) => result.push_str(ascii),
+                None => result.push(c),
+            }
+        }
+        Cow::Owned(result)
+    }
+
+    /// Texte converti pour l'affichage puis tronqué à `max_width` colonnes, avec une ellipse
+    /// ASCII (`...`) si l'affichage UTF-8 n'est pas disponible
+    fn fit_text(&self, text: &str, max_width: usize) -> String {
+        let ellipsis = if self.asciify { "..." } else { "…" };
+        truncate_with_ellipsis(&self.render_text(text), max_width, ellipsis)
     }
 
     pub fn update_terminal_size(&mut self) {
@@ -24,6 +104,23 @@ impl UI {
         self.terminal.get_max_visible_items()
     }
 
+    pub fn layout_mode(&self) -> LayoutMode {
+        if self.terminal.is_compact() {
+            LayoutMode::Compact
+        } else {
+            LayoutMode::Full
+        }
+    }
+
+    /// Première ligne disponible pour le menu sous l'en-tête
+    pub fn menu_top(&self) -> u16 {
+        let (_, box_y, _, _) = self.get_box_dimensions();
+        match self.layout_mode() {
+            LayoutMode::Full => box_y + 5,
+            LayoutMode::Compact => box_y + 2,
+        }
+    }
+
     pub fn is_valid_size(&self) -> bool {
         self.terminal.is_valid_size()
     }
@@ -42,6 +139,14 @@ impl UI {
         io::stdout().flush().unwrap();
     }
 
+    /// Copie un texte dans le presse-papiers du terminal (séquence OSC 52)
+    ///
+    /// Fonctionne aussi en SSH si le terminal l'accepte ; sinon la séquence est ignorée sans erreur.
+    pub fn copy_to_clipboard(&self, text: &str) {
+        print!("\x1B]52;c;{}\x07", base64_encode(text.as_bytes()));
+        io::stdout().flush().unwrap();
+    }
+
     pub fn hide_cursor(&self) {
         print!("\x1B[?25l");
         io::stdout().flush().unwrap();
@@ -80,27 +185,29 @@ impl UI {
 
     pub fn draw_box(&self, x: u16, y: u16, w: u16, h: u16) {
         self.set_color(Color::Accent);
+        let horizontal = self.glyph("horizontal");
+        let vertical = self.glyph("vertical");
         
         self.set_cursor(x, y);
-        print!("┌");
+        print!("{}", self.glyph("top_left"));
         for _ in 0..(w - 2) {
-            print!("─");
+            print!("{}", horizontal);
         }
-        print!("┐");
+        print!("{}", self.glyph("top_right"));
         
         for i in 1..(h - 1) {
             self.set_cursor(x, y + i);
-            print!("│");
+            print!("{}", vertical);
             self.set_cursor(x + w - 1, y + i);
-            print!("│");
+            print!("{}", vertical);
         }
         
         self.set_cursor(x, y + h - 1);
-        print!("└");
+        print!("{}", self.glyph("bottom_left"));
         for _ in 0..(w - 2) {
-            print!("─");
+            print!("{}", horizontal);
         }
-        print!("┘");
+        print!("{}", self.glyph("bottom_right"));
         
         self.set_color(Color::Reset);
         io::stdout().flush().unwrap();
@@ -109,12 +216,16 @@ impl UI {
     pub fn draw_text(&self, x: u16, y: u16, text: &str) {
         self.set_cursor(x, y);
         let max_width = (self.terminal.width().saturating_sub(x)) as usize;
-        let display_text = if text.chars().count() > max_width {
-            text.chars().take(max_width).collect::<String>()
-        } else {
-            text.to_string()
-        };
-        print!("{}", display_text);
+        print!("{}", self.fit_text(text, max_width));
+        io::stdout().flush().unwrap();
+    }
+
+    /// Affiche un texte tronqué avec `…` à `max_width` colonnes, sans dépasser le bord
+    /// droit de la zone de contenu (colonnes réservées à la barre de défilement).
+    pub fn draw_text_clipped(&self, x: u16, y: u16, text: &str, max_width: usize) {
+        let available = self.terminal.width().saturating_sub(x).saturating_sub(CONTENT_RIGHT_MARGIN) as usize;
+        self.set_cursor(x, y);
+        print!("{}", self.fit_text(text, max_width.min(available)));
         io::stdout().flush().unwrap();
     }
     
@@ -127,16 +238,16 @@ impl UI {
 
     pub fn draw_button(&self, x: u16, y: u16, text: &str, selected: bool) {
         self.set_cursor(x, y);
-        let max_width = (self.terminal.width().saturating_sub(x).saturating_sub(10)) as usize;
-        let display_text = if text.chars().count() > max_width {
-            format!("{}...", text.chars().take(max_width.saturating_sub(3)).collect::<String>())
-        } else {
-            text.to_string()
+        let margin = match self.layout_mode() {
+            LayoutMode::Full => 10,
+            LayoutMode::Compact => 6,
         };
-        
+        let max_width = (self.terminal.width().saturating_sub(x).saturating_sub(margin)) as usize;
+        let display_text = self.fit_text(text, max_width);
+
         if selected {
             self.set_color(Color::Selection);
-            print!("▶ {} ◀", display_text);
+            print!("{} {} {}", self.glyph("selected"), display_text, self.glyph("selected_end"));
         } else {
             self.set_color(Color::Fg);
             print!("  {}  ", display_text);
@@ -155,10 +266,22 @@ impl UI {
         let (box_x, box_y, box_w, _) = self.get_box_dimensions();
         let title_x = box_x;
         let title_y = box_y;
-        
         let fill_width = box_w as usize;
+        // Translittérer et tronquer avant de centrer : l'ASCII peut être plus large que le glyphe
+        // d'origine, et l'ellipse doit elle aussi rester en ASCII
+        let title = self.fit_text(title, fill_width.saturating_sub(2));
+        let title = title.as_str();
+
+        if self.layout_mode() == LayoutMode::Compact {
+            let title_text = center_in_width(&format!(" {} ", title), fill_width, '═');
+            self.set_color(Color::Accent);
+            self.draw_text(title_x, title_y, &title_text);
+            self.set_color(Color::Reset);
+            return;
+        }
+
         let title_line = format!("╔{}╗", "═".repeat(fill_width.saturating_sub(2)));
-        let title_text = format!("║{:^width$}║", title, width = fill_width.saturating_sub(2));
+        let title_text = format!("║{}║", center_in_width(title, fill_width.saturating_sub(2), ' '));
         let title_bottom = format!("╚{}╝", "═".repeat(fill_width.saturating_sub(2)));
         
         self.set_color(Color::Accent);
@@ -168,14 +291,30 @@ impl UI {
         self.set_color(Color::Reset);
     }
 
+    /// En-tête avec une étiquette discrète à droite de la ligne de titre (ex: distribution de l'hôte)
+    ///
+    /// L'étiquette est omise si elle chevaucherait le titre centré (terminal étroit).
+    pub fn draw_header_tagged(&self, title: &str, tag: &str) {
+        self.draw_header(title);
+        let (box_x, box_y, box_w, _) = self.get_box_dimensions();
+        let tag = format!(" {} ", self.render_text(tag));
+        let title_width = display_width(&self.render_text(title)) + 2;
+        let Some(offset) = header_tag_offset(box_w as usize, title_width, display_width(&tag)) else {
+            return;
+        };
+        let y = match self.layout_mode() {
+            LayoutMode::Full => box_y + 1,
+            LayoutMode::Compact => box_y,
+        };
+        self.set_color(Color::Info);
+        self.draw_text(box_x + offset as u16, y, &tag);
+        self.set_color(Color::Reset);
+    }
+
     pub fn draw_status_bar(&self, y: u16, message: &str) {
         let max_width = (self.terminal.width().saturating_sub(10)) as usize;
-        let display_msg = if message.chars().count() > max_width {
-            format!("{}...", message.chars().take(max_width.saturating_sub(3)).collect::<String>())
-        } else {
-            message.to_string()
-        };
-        
+        let display_msg = self.fit_text(message, max_width);
+
         self.set_cursor(5, y);
         self.set_color(Color::Fg);
         print!("{}", display_msg);
@@ -183,6 +322,28 @@ impl UI {
         io::stdout().flush().unwrap();
     }
 
+    /// Affiche les `height` dernières lignes d'un journal d'opération ; les lignes de la zone
+    /// sont effacées à chaque appel pour que le panneau défile en place.
+    pub fn draw_log_panel(&self, x: u16, y: u16, height: u16, lines: &[(String, Color)]) {
+        let visible = log_tail(lines, height as usize);
+        for row in 0..height {
+            self.clear_line(y + row);
+            if let Some((text, color)) = visible.get(row as usize) {
+                self.set_color(*color);
+                self.draw_text_clipped(x, y + row, text, usize::MAX);
+            }
+        }
+        self.set_color(Color::Reset);
+    }
+
+    /// Barre de progression de `width` colonnes, pourcentage compris (`fraction` entre 0.0 et 1.0)
+    pub fn draw_progress_bar(&self, x: u16, y: u16, width: u16, fraction: f64) {
+        let bar = progress_bar_text(width as usize, fraction, self.glyph("thumb"), self.glyph("progress_empty"));
+        self.set_color(Color::Accent);
+        self.draw_text(x, y, &bar);
+        self.set_color(Color::Reset);
+    }
+
     pub fn draw_scrollbar(&self, x: u16, y: u16, height: u16, total_items: usize, visible_items: usize, offset: usize) {
         if total_items <= visible_items {
             return;
@@ -201,14 +362,14 @@ impl UI {
         
         for i in 0..scrollbar_height {
             self.set_cursor(x, y + 1 + i);
-            print!("│");
+            print!("{}", self.glyph("vertical"));
         }
         
         self.set_color(Color::Selection);
         for i in 0..thumb_height {
             if thumb_pos + i < scrollbar_height {
                 self.set_cursor(x, y + 1 + thumb_pos + i);
-                print!("█");
+                print!("{}", self.glyph("thumb"));
             }
         }
         
@@ -217,7 +378,28 @@ impl UI {
     }
 }
 
-#[derive(Copy, Clone)]
+/// Texte d'une barre de progression : cases pleines, cases vides puis pourcentage sur 5 colonnes
+fn progress_bar_text(width: usize, fraction: f64, filled: &str, empty: &str) -> String {
+    let fraction = fraction.clamp(0.0, 1.0);
+    let cells = width.saturating_sub(5);
+    let done = ((cells as f64) * fraction).round() as usize;
+    format!("{}{} {:>3}%", filled.repeat(done), empty.repeat(cells - done), (fraction * 100.0).round() as u32)
+}
+
+/// Colonne de l'étiquette d'en-tête, calée à deux colonnes du bord droit ; `None` si elle
+/// toucherait le titre centré sur `width` colonnes
+fn header_tag_offset(width: usize, title_width: usize, tag_width: usize) -> Option<usize> {
+    let start = width.checked_sub(tag_width + 2)?;
+    let title_end = (width + title_width).div_ceil(2);
+    (start > title_end).then_some(start)
+}
+
+/// Dernières lignes d'un journal qui tiennent sur `height` lignes
+fn log_tail<T>(lines: &[T], height: usize) -> &[T] {
+    &lines[lines.len().saturating_sub(height)..]
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Color {
     Fg,           // Texte principal (#e0e0e0)
     Accent,       // Bordure et titre (#443a86)
@@ -230,3 +412,62 @@ pub enum Color {
     Reset,        // Réinitialisation
 }
 
+impl Color {
+    /// Couleurs de texte de l'interface (sans `Reset`)
+    pub const ALL: [Color; 8] = [
+        Color::Fg,
+        Color::Accent,
+        Color::Success,
+        Color::Error,
+        Color::Warning,
+        Color::Info,
+        Color::Selection,
+        Color::Yellow,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Color::Fg => "Fg",
+            Color::Accent => "Accent",
+            Color::Success => "Success",
+            Color::Error => "Error",
+            Color::Warning => "Warning",
+            Color::Info => "Info",
+            Color::Selection => "Selection",
+            Color::Yellow => "Yellow",
+            Color::Reset => "Reset",
+        }
+    }
+
+    /// Couleur également disponible en fond (`set_bg_color`)
+    pub fn has_background(&self) -> bool {
+        matches!(self, Color::Accent | Color::Selection | Color::Error | Color::Warning)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_tail() {
+        let lines = ["a", "b", "c", "d"];
+        assert_eq!(log_tail(&lines, 2), &["c", "d"]);
+        assert_eq!(log_tail(&lines, 10), &lines);
+        assert!(log_tail(&lines, 0).is_empty());
+    }
+
+    #[test]
+    fn test_progress_bar_text() {
+        assert_eq!(progress_bar_text(15, 0.5, "#", "."), "#####.....  50%");
+        assert_eq!(progress_bar_text(9, 1.2, "#", "."), "#### 100%");
+        assert_eq!(progress_bar_text(3, 0.3, "#", "."), "  30%");
+    }
+
+    #[test]
+    fn test_header_tag_offset() {
+        assert_eq!(header_tag_offset(80, 32, 11), Some(67));
+        assert_eq!(header_tag_offset(60, 32, 20), None);
+        assert_eq!(header_tag_offset(8, 2, 10), None);
+    }
+}
@@ -1,12 +1,201 @@
 pub fn truncate_string(s: &str, max_chars: usize) -> String {
-    if s.chars().count() > max_chars {
-        format!("{}...", s.chars().take(max_chars.saturating_sub(3)).collect::<String>())
-    } else {
-        s.to_string()
-    }
+    truncate_to_width(s, max_chars)
 }
 
 pub fn truncate_string_exact(s: &str, max_chars: usize) -> String {
     s.chars().take(max_chars).collect::<String>()
 }
 
+
+/// Formate une taille en octets de façon lisible (Ko, Mo, Go)
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["o", "Ko", "Mo", "Go"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} {}", bytes, UNITS[0])
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+/// Formate une durée en secondes sous la forme `1 min 05 s`
+pub fn format_duration(secs: u64) -> String {
+    if secs < 60 {
+        format!("{} s", secs)
+    } else {
+        format!("{} min {:02} s", secs / 60, secs % 60)
+    }
+}
+
+/// Largeur d'affichage d'un caractère dans le terminal (0, 1 ou 2 colonnes)
+///
+/// Approximation de wcwidth : marques combinantes et sélecteurs de variante à 0,
+/// CJK et emoji à 2, le reste (dont les caractères de dessin de boîte) à 1.
+pub fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x0300..=0x036F | 0x200B..=0x200F | 0x20D0..=0x20FF | 0xFE00..=0xFE0F => 0,
+        0x1100..=0x115F
+        | 0x231A..=0x231B
+        | 0x23E9..=0x23EC
+        | 0x23F0
+        | 0x23F3
+        | 0x25FD..=0x25FE
+        | 0x2614..=0x2615
+        | 0x2648..=0x2653
+        | 0x267F
+        | 0x2693
+        | 0x26A1
+        | 0x26AA..=0x26AB
+        | 0x26BD..=0x26BE
+        | 0x26C4..=0x26C5
+        | 0x26CE
+        | 0x26D4
+        | 0x26EA
+        | 0x26F2..=0x26F3
+        | 0x26F5
+        | 0x26FA
+        | 0x26FD
+        | 0x2705
+        | 0x270A..=0x270B
+        | 0x2728
+        | 0x274C
+        | 0x274E
+        | 0x2753..=0x2755
+        | 0x2757
+        | 0x2795..=0x2797
+        | 0x27B0
+        | 0x27BF
+        | 0x2B1B..=0x2B1C
+        | 0x2B50
+        | 0x2B55
+        | 0x2E80..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x1F000..=0x1FAFF
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
+
+/// Largeur d'affichage d'une chaîne dans le terminal
+pub fn display_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+/// Tronque une chaîne à `max_width` colonnes, en terminant par `…` si elle dépasse
+pub fn truncate_to_width(s: &str, max_width: usize) -> String {
+    truncate_with_ellipsis(s, max_width, "…")
+}
+
+/// Tronque une chaîne à `max_width` colonnes, en terminant par `ellipsis` si elle dépasse
+/// (`...` pour les terminaux sans UTF-8) ; l'ellipse elle-même est coupée si la place manque
+pub fn truncate_with_ellipsis(s: &str, max_width: usize, ellipsis: &str) -> String {
+    if display_width(s) <= max_width {
+        return s.to_string();
+    }
+    if max_width <= display_width(ellipsis) {
+        return ellipsis.chars().take(max_width).collect();
+    }
+
+    let budget = max_width - display_width(ellipsis);
+    let mut result = String::new();
+    let mut width = 0;
+    for c in s.chars() {
+        let w = char_width(c);
+        if width + w > budget {
+            break;
+        }
+        result.push(c);
+        width += w;
+    }
+    result.push_str(ellipsis);
+    result
+}
+
+/// Tronque (avec `…`) ou complète par des espaces pour occuper exactement `width` colonnes
+pub fn pad_to_width(s: &str, width: usize) -> String {
+    let text = truncate_to_width(s, width);
+    let padding = width.saturating_sub(display_width(&text));
+    format!("{}{}", text, " ".repeat(padding))
+}
+
+/// Largeur d'une colonne de tableau : la plus longue valeur (ou l'en-tête), au plus `max_width`
+pub fn column_width<'s>(header: &str, values: impl IntoIterator<Item = &'s str>, max_width: usize) -> usize {
+    let widest = values.into_iter().map(display_width).max().unwrap_or(0);
+    widest.max(display_width(header)).min(max_width.max(display_width(header)))
+}
+
+/// Encodage base64 standard (avec `=`), pour les séquences OSC 52
+pub fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 0x3F) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+/// Centre un texte sur `width` colonnes en complétant avec `fill` selon sa largeur d'affichage
+pub fn center_in_width(text: &str, width: usize, fill: char) -> String {
+    let text = truncate_to_width(text, width);
+    let padding = width.saturating_sub(display_width(&text));
+    let left = padding / 2;
+    let right = padding - left;
+    format!("{}{}{}", fill.to_string().repeat(left), text, fill.to_string().repeat(right))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_to_width() {
+        assert_eq!(truncate_to_width("rmdb", 10), "rmdb");
+        assert_eq!(truncate_to_width("container-tres-long", 10), "container…");
+        assert_eq!(display_width("┌──┐"), 4);
+        assert_eq!(display_width("✅ ok"), 5);
+        // Un emoji ne doit pas être coupé en deux colonnes
+        assert_eq!(truncate_to_width("ab✅cd", 4), "ab…");
+        assert_eq!(display_width(&truncate_to_width("ab✅cd", 4)), 3);
+        assert_eq!(truncate_to_width("abc", 0), "");
+        assert_eq!(truncate_with_ellipsis("container-tres-long", 10, "..."), "contain...");
+        assert_eq!(truncate_with_ellipsis("container", 2, "..."), "..");
+        assert_eq!(center_in_width("Déploiement ✅", 18, ' '), "  Déploiement ✅  ");
+        assert_eq!(center_in_width("→", 4, '═'), "═→══");
+    }
+
+    #[test]
+    fn test_base64_encode() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode("Diagnostic é\n".as_bytes()), "RGlhZ25vc3RpYyDDqQo=");
+    }
+
+    #[test]
+    fn test_column_layout() {
+        let names = ["rmdb", "rmdb-labo-salle-informatique-batiment-b", "é"];
+        assert_eq!(column_width("Nom", names, 20), 20);
+        assert_eq!(column_width("Nom", ["a"], 20), 3);
+        assert_eq!(column_width("Nom", names[..1].iter().copied(), 2), 3);
+        assert_eq!(pad_to_width("rmdb", 6), "rmdb  ");
+        assert_eq!(pad_to_width(names[1], 10), "rmdb-labo…");
+        assert_eq!(display_width(&pad_to_width("✅ ok", 8)), 8);
+    }
+}